@@ -0,0 +1,131 @@
+// WS-Addressing 1.0 message addressing properties.
+use std::io::Write;
+
+use rand::RngCore;
+use xml::{writer::XmlEvent, EventWriter};
+
+use crate::{envelope::Version, error::Error, header::start_header_block, header::HeaderBlock};
+
+pub const WSA_NAMESPACE: &str = "http://www.w3.org/2005/08/addressing";
+pub const ANONYMOUS: &str = "http://www.w3.org/2005/08/addressing/anonymous";
+
+// Header blocks we process when they come back in a response.
+const HEADERS: &[&str] = &["To", "Action", "MessageID", "RelatesTo", "ReplyTo", "From", "FaultTo"];
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Addressing {
+    pub to: Option<String>,
+    pub action: Option<String>,
+    pub message_id: Option<String>,
+    pub relates_to: Option<String>,
+    pub reply_to: Option<String>,
+}
+
+impl Addressing {
+    // Properties for a new request: a fresh MessageID and anonymous ReplyTo.
+    pub fn request(to: Option<String>, action: Option<String>) -> Self {
+        Addressing {
+            to,
+            action,
+            message_id: Some(new_message_id()),
+            relates_to: None,
+            reply_to: Some(ANONYMOUS.to_string()),
+        }
+    }
+
+    pub fn understands(namespace: Option<&str>, name: &str) -> bool {
+        namespace == Some(WSA_NAMESPACE) && HEADERS.contains(&name)
+    }
+
+    pub fn write<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        version: Version,
+    ) -> Result<(), Error> {
+        let mut property = |name: &str, value: &Option<String>| -> Result<(), Error> {
+            if let Some(value) = value {
+                start_header_block(writer, version, ("wsa", WSA_NAMESPACE), name, false)?;
+                writer.write(XmlEvent::characters(value))?;
+                writer.write(XmlEvent::end_element())?;
+            }
+            Ok(())
+        };
+
+        property("Action", &self.action)?;
+        property("MessageID", &self.message_id)?;
+        property("RelatesTo", &self.relates_to)?;
+        property("To", &self.to)?;
+
+        if let Some(address) = &self.reply_to {
+            start_header_block(writer, version, ("wsa", WSA_NAMESPACE), "ReplyTo", false)?;
+            writer.write(XmlEvent::start_element("wsa:Address"))?;
+            writer.write(XmlEvent::characters(address))?;
+            writer.write(XmlEvent::end_element())?;
+            writer.write(XmlEvent::end_element())?;
+        }
+        Ok(())
+    }
+
+    // None when the message carries no WS-Addressing headers.
+    pub fn read(blocks: &[HeaderBlock]) -> Result<Option<Self>, Error> {
+        let mut addressing = Addressing::default();
+        let mut found = false;
+
+        for block in blocks.iter().filter(|b| b.namespace.as_deref() == Some(WSA_NAMESPACE)) {
+            let doc = roxmltree::Document::parse(&block.xml)?;
+            let root = doc.root_element();
+            let text = || root.text().map(|t| t.trim().to_string());
+
+            match block.name.as_str() {
+                "To" => addressing.to = text(),
+                "Action" => addressing.action = text(),
+                "MessageID" => addressing.message_id = text(),
+                "RelatesTo" => addressing.relates_to = text(),
+                "ReplyTo" => {
+                    addressing.reply_to = root
+                        .children()
+                        .find(|n| n.has_tag_name((WSA_NAMESPACE, "Address")))
+                        .and_then(|n| n.text())
+                        .map(|t| t.trim().to_string())
+                }
+                _ => continue,
+            }
+            found = true;
+        }
+
+        Ok(if found { Some(addressing) } else { None })
+    }
+}
+
+// urn:uuid: with a random (version 4) UUID.
+pub fn new_message_id() -> String {
+    let mut bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex = bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+    format!(
+        "urn:uuid:{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_new_message_id() {
+        let id = new_message_id();
+
+        assert_eq!(id.len(), 45);
+        assert!(id.starts_with("urn:uuid:"));
+        assert_eq!(&id[23..24], "4");
+        assert_ne!(id, new_message_id());
+    }
+}
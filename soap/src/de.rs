@@ -0,0 +1,122 @@
+use popper::{XmlDeserialize, XmlPopper};
+use roxmltree::Node;
+
+use crate::error::Error;
+
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+// Returns the source of `node` as a standalone document. Namespaces declared on
+// ancestors (usually the Envelope) are re-declared on the element itself so that
+// prefixed names and QName values inside the fragment still resolve.
+pub fn element_source(text: &str, node: &Node) -> String {
+    let source = &text[node.range()];
+    let start_tag = &source[..start_tag_len(source)];
+    let name_end = start_tag
+        .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .unwrap_or(start_tag.len());
+
+    let declarations = node
+        .namespaces()
+        .filter(|ns| ns.uri() != XML_NAMESPACE)
+        .filter(|ns| {
+            let attr = match ns.name() {
+                Some(prefix) => format!("xmlns:{}", prefix),
+                None => "xmlns".to_string(),
+            };
+            !declares(start_tag, &attr)
+        })
+        .map(|ns| match ns.name() {
+            Some(prefix) => format!(" xmlns:{}=\"{}\"", prefix, escape_attribute(ns.uri())),
+            None => format!(" xmlns=\"{}\"", escape_attribute(ns.uri())),
+        })
+        .collect::<String>();
+
+    format!("{}{}{}", &source[..name_end], declarations, &source[name_end..])
+}
+
+// Deserializes `node` with popper, the same way generated code pops a child element.
+pub fn from_element<T: XmlDeserialize>(text: &str, node: &Node) -> Result<T, Error> {
+    let source = element_source(text, node);
+    let mut popper = XmlPopper::new(&source)?;
+    Ok(popper.pop_child(node.tag_name().name())?)
+}
+
+// Content of the Body of a received message: the element in it for the types of elements,
+// all of its children for `Parts`.
+pub trait FromBody: Sized {
+    fn from_body(text: &str, body: &Node) -> Result<Self, Error>;
+}
+
+impl<T: XmlDeserialize> FromBody for T {
+    fn from_body(text: &str, body: &Node) -> Result<Self, Error> {
+        let content = body
+            .children()
+            .find(|n| n.is_element())
+            .ok_or_else(|| Error::Xml("empty Body".to_string()))?;
+        from_element(text, &content)
+    }
+}
+
+// A document style message with several parts, which are the children of the Body. The
+// struct generated for the message reads each of them as a field.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Parts<T>(pub T);
+
+impl<T: XmlDeserialize> FromBody for Parts<T> {
+    fn from_body(text: &str, body: &Node) -> Result<Self, Error> {
+        Ok(Parts(from_element(text, body)?))
+    }
+}
+
+fn start_tag_len(source: &str) -> usize {
+    let mut quote = None;
+    for (i, c) in source.char_indices() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, '>') => return i + 1,
+            _ => {}
+        }
+    }
+    source.len()
+}
+
+fn declares(start_tag: &str, attr: &str) -> bool {
+    start_tag.match_indices(attr).any(|(i, _)| {
+        let before = start_tag[..i].chars().last();
+        let after = start_tag[i + attr.len()..].trim_start().chars().next();
+        before.is_some_and(char::is_whitespace) && after == Some('=')
+    })
+}
+
+fn escape_attribute(value: &str) -> String {
+    value.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_element_source_redeclares_namespaces() {
+        let text = r#"<s:Envelope xmlns:s="http://www.w3.org/2003/05/soap-envelope" xmlns:tt="http://www.onvif.org/ver10/schema"><s:Body><tds:GetServicesResponse xmlns:tds="http://www.onvif.org/ver10/device/wsdl"><tds:Service/></tds:GetServicesResponse></s:Body></s:Envelope>"#;
+        let doc = roxmltree::Document::parse(text).unwrap();
+        let node =
+            doc.descendants().find(|n| n.tag_name().name() == "GetServicesResponse").unwrap();
+
+        let source = element_source(text, &node);
+        let fragment = roxmltree::Document::parse(&source).unwrap();
+        let root = fragment.root_element();
+
+        assert_eq!(root.tag_name().name(), "GetServicesResponse");
+        assert_eq!(
+            root.lookup_namespace_uri(Some("tt")),
+            Some("http://www.onvif.org/ver10/schema")
+        );
+        assert_eq!(
+            root.lookup_namespace_uri(Some("tds")),
+            Some("http://www.onvif.org/ver10/device/wsdl")
+        );
+        assert_eq!(source.matches("xmlns:tds").count(), 1);
+    }
+}
@@ -0,0 +1,424 @@
+use roxmltree::Node;
+use xml::writer::XmlEvent;
+
+use crate::{
+    de::{self, FromBody},
+    error::Error,
+    fault::Fault,
+    header::{HeaderBlock, Headers},
+    ser::{self, XmlSerialize},
+};
+
+pub const SOAP11_NAMESPACE: &str = "http://schemas.xmlsoap.org/soap/envelope/";
+pub const SOAP12_NAMESPACE: &str = "http://www.w3.org/2003/05/soap-envelope";
+
+// Prefix bound to the envelope namespace in every message we write.
+pub const ENVELOPE_PREFIX: &str = "s";
+
+const SOAP11_ACTOR_NEXT: &str = "http://schemas.xmlsoap.org/soap/actor/next";
+const SOAP12_ROLE_NEXT: &str = "http://www.w3.org/2003/05/soap-envelope/role/next";
+const SOAP12_ROLE_NONE: &str = "http://www.w3.org/2003/05/soap-envelope/role/none";
+const SOAP12_ROLE_ULTIMATE_RECEIVER: &str =
+    "http://www.w3.org/2003/05/soap-envelope/role/ultimateReceiver";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Version {
+    Soap11,
+    #[default]
+    Soap12,
+}
+
+impl Version {
+    pub fn namespace(self) -> &'static str {
+        match self {
+            Version::Soap11 => SOAP11_NAMESPACE,
+            Version::Soap12 => SOAP12_NAMESPACE,
+        }
+    }
+
+    pub fn from_namespace(namespace: &str) -> Option<Self> {
+        match namespace {
+            SOAP11_NAMESPACE => Some(Version::Soap11),
+            SOAP12_NAMESPACE => Some(Version::Soap12),
+            _ => None,
+        }
+    }
+
+    // Value of the Content-Type header for HTTP bindings.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Version::Soap11 => "text/xml; charset=utf-8",
+            Version::Soap12 => "application/soap+xml; charset=utf-8",
+        }
+    }
+
+    // HTTP headers of a request for an operation with the given action: SOAP 1.1 sends it
+    // as SOAPAction, SOAP 1.2 as the action parameter of the media type.
+    pub fn http_headers(self, action: Option<&str>) -> Vec<(&'static str, String)> {
+        match (self, action) {
+            (Version::Soap11, _) => vec![
+                ("Content-Type", self.content_type().to_string()),
+                ("SOAPAction", format!("\"{}\"", action.unwrap_or(""))),
+            ],
+            (Version::Soap12, Some(action)) => {
+                vec![("Content-Type", format!("{}; action=\"{}\"", self.content_type(), action))]
+            }
+            (Version::Soap12, None) => vec![("Content-Type", self.content_type().to_string())],
+        }
+    }
+
+    pub fn must_understand_value(self) -> &'static str {
+        match self {
+            Version::Soap11 => "1",
+            Version::Soap12 => "true",
+        }
+    }
+
+    // soap:actor was renamed to soap:role in 1.2.
+    fn role_attribute(self) -> &'static str {
+        match self {
+            Version::Soap11 => "actor",
+            Version::Soap12 => "role",
+        }
+    }
+
+    // Whether a header block with this role is addressed to the ultimate receiver (us).
+    fn targets_us(self, role: Option<&str>) -> bool {
+        match (self, role) {
+            (_, None) => true,
+            (Version::Soap11, Some(role)) => role == SOAP11_ACTOR_NEXT,
+            (Version::Soap12, Some(role)) => {
+                role == SOAP12_ROLE_NEXT || role == SOAP12_ROLE_ULTIMATE_RECEIVER
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Envelope<H, B> {
+    pub version: Version,
+    pub header: H,
+    pub body: B,
+}
+
+impl<H, B> Envelope<H, B> {
+    pub fn new(version: Version, header: H, body: B) -> Self {
+        Envelope { version, header, body }
+    }
+}
+
+impl<H: Headers, B: XmlSerialize> Envelope<H, B> {
+    pub fn to_xml(&self) -> Result<String, Error> {
+        let mut buffer = Vec::new();
+        let mut writer = ser::event_writer(&mut buffer);
+        let name = |local: &str| format!("{}:{}", ENVELOPE_PREFIX, local);
+
+        writer.write(
+            XmlEvent::start_element(name("Envelope").as_str())
+                .ns(ENVELOPE_PREFIX, self.version.namespace()),
+        )?;
+
+        if !self.header.is_empty() {
+            writer.write(XmlEvent::start_element(name("Header").as_str()))?;
+            self.header.write_headers(&mut writer, self.version)?;
+            writer.write(XmlEvent::end_element())?;
+        }
+
+        writer.write(XmlEvent::start_element(name("Body").as_str()))?;
+        self.body.xml_serialize(&mut writer)?;
+        writer.write(XmlEvent::end_element())?;
+
+        writer.write(XmlEvent::end_element())?;
+
+        String::from_utf8(buffer).map_err(|e| Error::Serialize(e.to_string()))
+    }
+}
+
+impl<H: Headers, B: FromBody> Envelope<H, B> {
+    // Parses a received message. A Fault in the body is returned as Error::Fault,
+    // header blocks we must but can't understand as Error::MustUnderstand.
+    pub fn from_xml(text: &str) -> Result<Self, Error> {
+        parse(text, |body| B::from_body(text, body))
+    }
+}
+
+impl<H: Headers> Envelope<H, ()> {
+    // Like `from_xml` for messages that carry no data, such as the response to a one-way
+    // operation: the body may be empty and its content is ignored unless it is a Fault.
+    pub fn from_xml_without_body(text: &str) -> Result<Self, Error> {
+        parse(text, |_| Ok(()))
+    }
+}
+
+fn parse<H: Headers, B>(
+    text: &str,
+    body: impl FnOnce(&Node) -> Result<B, Error>,
+) -> Result<Envelope<H, B>, Error> {
+    let doc = roxmltree::Document::parse(text)?;
+    let root = doc.root_element();
+
+    let namespace = root.tag_name().namespace().unwrap_or_default();
+    let version = Version::from_namespace(namespace)
+        .ok_or_else(|| Error::VersionMismatch(namespace.to_string()))?;
+    if root.tag_name().name() != "Envelope" {
+        return Err(Error::Xml(format!("expected Envelope, found {}", root.tag_name().name())));
+    }
+
+    let child =
+        |name: &str| root.children().find(|n| n.is_element() && n.has_tag_name((namespace, name)));
+
+    let blocks = match child("Header") {
+        Some(header) => header_blocks(text, &header, version),
+        None => vec![],
+    };
+    check_must_understand::<H>(&blocks, version)?;
+
+    let body_node = child("Body").ok_or_else(|| Error::Xml("missing Body".to_string()))?;
+    let content = body_node.children().find(|n| n.is_element());
+    if let Some(fault) = content.filter(|n| n.has_tag_name((namespace, "Fault"))) {
+        return Err(Error::Fault(Box::new(Fault::parse(text, &fault, version))));
+    }
+
+    Ok(Envelope { version, header: H::read_headers(&blocks)?, body: body(&body_node)? })
+}
+
+fn header_blocks(text: &str, header: &Node, version: Version) -> Vec<HeaderBlock> {
+    let ns = version.namespace();
+    header
+        .children()
+        .filter(|n| n.is_element())
+        .map(|n| HeaderBlock {
+            namespace: n.tag_name().namespace().map(str::to_string),
+            name: n.tag_name().name().to_string(),
+            must_understand: n
+                .attribute((ns, "mustUnderstand"))
+                .is_some_and(|v| matches!(v.trim(), "1" | "true")),
+            role: n.attribute((ns, version.role_attribute())).map(str::to_string),
+            xml: de::element_source(text, &n),
+        })
+        .collect()
+}
+
+fn check_must_understand<H: Headers>(
+    blocks: &[HeaderBlock],
+    version: Version,
+) -> Result<(), Error> {
+    let missed = blocks.iter().find(|b| {
+        b.must_understand
+            && b.role.as_deref() != Some(SOAP12_ROLE_NONE)
+            && version.targets_us(b.role.as_deref())
+            && !H::understands(b.namespace.as_deref(), &b.name)
+    });
+
+    match missed {
+        Some(block) => Err(Error::MustUnderstand {
+            namespace: block.namespace.clone(),
+            name: block.name.clone(),
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use xml::EventWriter;
+
+    use super::*;
+    use crate::{
+        fault::FaultCode,
+        header::{start_header_block, NoHeaders, RawHeaders},
+    };
+
+    const TT: &str = "http://www.onvif.org/ver10/schema";
+
+    struct GetDeviceInformation;
+
+    impl XmlSerialize for GetDeviceInformation {
+        fn xml_serialize<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+            writer.write(
+                XmlEvent::start_element("tds:GetDeviceInformation")
+                    .ns("tds", "http://www.onvif.org/ver10/device/wsdl"),
+            )?;
+            writer.write(XmlEvent::end_element())?;
+            Ok(())
+        }
+    }
+
+    struct SessionHeaders {
+        session: String,
+    }
+
+    impl Headers for SessionHeaders {
+        fn understands(namespace: Option<&str>, name: &str) -> bool {
+            namespace == Some(TT) && name == "Session"
+        }
+
+        fn write_headers<W: Write>(
+            &self,
+            writer: &mut EventWriter<W>,
+            version: Version,
+        ) -> Result<(), Error> {
+            start_header_block(writer, version, ("tt", TT), "Session", true)?;
+            writer.write(XmlEvent::characters(&self.session))?;
+            writer.write(XmlEvent::end_element())?;
+            Ok(())
+        }
+
+        fn read_headers(blocks: &[HeaderBlock]) -> Result<Self, Error> {
+            let block = blocks
+                .iter()
+                .find(|b| b.is(TT, "Session"))
+                .ok_or_else(|| Error::Deserialize("missing Session".to_string()))?;
+            let doc = roxmltree::Document::parse(&block.xml)?;
+            Ok(SessionHeaders {
+                session: doc.root_element().text().unwrap_or_default().to_string(),
+            })
+        }
+    }
+
+    fn response(header: &str, body: &str) -> String {
+        format!(
+            r#"<s:Envelope xmlns:s="{}" xmlns:tt="{}"><s:Header>{}</s:Header><s:Body>{}</s:Body></s:Envelope>"#,
+            SOAP12_NAMESPACE, TT, header, body
+        )
+    }
+
+    #[test]
+    fn test_serialize_without_headers() {
+        let envelope = Envelope::new(Version::Soap12, NoHeaders, GetDeviceInformation);
+
+        assert_eq!(
+            envelope.to_xml().unwrap(),
+            r#"<?xml version="1.0" encoding="UTF-8"?><s:Envelope xmlns:s="http://www.w3.org/2003/05/soap-envelope"><s:Body><tds:GetDeviceInformation xmlns:tds="http://www.onvif.org/ver10/device/wsdl" /></s:Body></s:Envelope>"#
+        );
+    }
+
+    #[test]
+    fn test_serialize_custom_headers() {
+        let headers = SessionHeaders { session: "42".to_string() };
+        let xml = Envelope::new(Version::Soap11, headers, GetDeviceInformation).to_xml().unwrap();
+
+        assert!(xml.contains(r#"<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/">"#));
+        assert!(xml.contains(
+            r#"<s:Header><tt:Session xmlns:tt="http://www.onvif.org/ver10/schema" s:mustUnderstand="1">42</tt:Session></s:Header>"#
+        ));
+    }
+
+    #[test]
+    fn test_raw_headers_round_trip() {
+        let text =
+            response(r#"<tt:Session s:mustUnderstand="false" a="b">42<tt:Id/></tt:Session>"#, "");
+        let doc = roxmltree::Document::parse(&text).unwrap();
+        let header = doc.descendants().find(|n| n.has_tag_name("Header")).unwrap();
+        let headers = RawHeaders(header_blocks(&text, &header, Version::Soap12));
+
+        let xml = Envelope::new(Version::Soap12, headers, GetDeviceInformation).to_xml().unwrap();
+
+        assert!(xml.contains(
+            r#"<s:Header><tt:Session xmlns:tt="http://www.onvif.org/ver10/schema" s:mustUnderstand="false" a="b">42<tt:Id /></tt:Session></s:Header>"#
+        ));
+    }
+
+    #[test]
+    fn test_header_blocks() {
+        let text = response(
+            r#"<tt:Session s:mustUnderstand="true">42</tt:Session><Trace s:role="http://www.w3.org/2003/05/soap-envelope/role/next"/>"#,
+            "",
+        );
+        let doc = roxmltree::Document::parse(&text).unwrap();
+        let header = doc.descendants().find(|n| n.has_tag_name("Header")).unwrap();
+        let blocks = header_blocks(&text, &header, Version::Soap12);
+
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks[0].is(TT, "Session"));
+        assert!(blocks[0].must_understand);
+        assert_eq!(blocks[0].role, None);
+        assert_eq!(blocks[1].namespace, None);
+        assert!(!blocks[1].must_understand);
+        assert_eq!(blocks[1].role.as_deref(), Some(SOAP12_ROLE_NEXT));
+    }
+
+    #[test]
+    fn test_must_understand() {
+        let blocks = |header: &str| {
+            let text = response(header, "");
+            let doc = roxmltree::Document::parse(&text).unwrap();
+            let node = doc.descendants().find(|n| n.has_tag_name("Header")).unwrap();
+            header_blocks(&text, &node, Version::Soap12)
+        };
+
+        let session = blocks(r#"<tt:Session s:mustUnderstand="true">42</tt:Session>"#);
+        assert!(check_must_understand::<SessionHeaders>(&session, Version::Soap12).is_ok());
+        match check_must_understand::<NoHeaders>(&session, Version::Soap12) {
+            Err(Error::MustUnderstand { namespace, name }) => {
+                assert_eq!(namespace.as_deref(), Some(TT));
+                assert_eq!(name, "Session");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        // Blocks for other roles, or not for any role, are not our business.
+        let other_roles = blocks(
+            r#"<tt:A s:mustUnderstand="1" s:role="http://www.w3.org/2003/05/soap-envelope/role/none"/><tt:B s:mustUnderstand="1" s:role="http://example.org/cache"/><tt:C s:mustUnderstand="false"/>"#,
+        );
+        assert!(check_must_understand::<RawHeaders>(&other_roles, Version::Soap12).is_ok());
+    }
+
+    #[test]
+    fn test_fault_is_error() {
+        let text = response(
+            "",
+            r#"<s:Fault><s:Code><s:Value>s:Receiver</s:Value></s:Code><s:Reason><s:Text xml:lang="en">Busy</s:Text></s:Reason></s:Fault>"#,
+        );
+
+        match Envelope::<NoHeaders, String>::from_xml(&text) {
+            Err(Error::Fault(fault)) => {
+                assert_eq!(fault.code, FaultCode::Receiver);
+                assert_eq!(fault.reason, "Busy");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(
+            Envelope::<NoHeaders, ()>::from_xml_without_body(&text),
+            Err(Error::Fault(_))
+        ));
+    }
+
+    #[test]
+    fn test_without_body() {
+        let empty = Envelope::<NoHeaders, ()>::from_xml_without_body(&response("", "")).unwrap();
+        assert_eq!(empty.version, Version::Soap12);
+        assert!(Envelope::<NoHeaders, ()>::from_xml_without_body(&response("", "<Ack/>")).is_ok());
+    }
+
+    #[test]
+    fn test_version_mismatch() {
+        let text = r#"<s:Envelope xmlns:s="http://example.org/envelope"><s:Body/></s:Envelope>"#;
+
+        assert!(matches!(
+            Envelope::<NoHeaders, String>::from_xml(text),
+            Err(Error::VersionMismatch(ns)) if ns == "http://example.org/envelope"
+        ));
+    }
+
+    #[test]
+    fn test_http_headers() {
+        assert_eq!(
+            Version::Soap11.http_headers(Some("urn:Add")),
+            vec![
+                ("Content-Type", "text/xml; charset=utf-8".to_string()),
+                ("SOAPAction", "\"urn:Add\"".to_string())
+            ]
+        );
+        assert_eq!(Version::Soap11.http_headers(None)[1], ("SOAPAction", "\"\"".to_string()));
+        assert_eq!(
+            Version::Soap12.http_headers(Some("urn:Add")),
+            vec![(
+                "Content-Type",
+                "application/soap+xml; charset=utf-8; action=\"urn:Add\"".to_string()
+            )]
+        );
+    }
+}
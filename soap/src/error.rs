@@ -0,0 +1,57 @@
+use std::fmt;
+
+use crate::fault::Fault;
+
+#[derive(Debug)]
+pub enum Error {
+    // The message is not well-formed XML or is not a SOAP envelope.
+    Xml(String),
+    // The envelope namespace does not match the expected SOAP version.
+    VersionMismatch(String),
+    // A header block targeted at us has mustUnderstand set, but nothing processes it.
+    MustUnderstand { namespace: Option<String>, name: String },
+    // The body contains a SOAP Fault.
+    Fault(Box<Fault>),
+    Serialize(String),
+    Deserialize(String),
+    Transport(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Xml(e) => write!(f, "Malformed SOAP message: {}", e),
+            Error::VersionMismatch(ns) => write!(f, "Unexpected envelope namespace: {}", ns),
+            Error::MustUnderstand { namespace: Some(ns), name } => {
+                write!(f, "Header block {{{}}}{} must be understood", ns, name)
+            }
+            Error::MustUnderstand { namespace: None, name } => {
+                write!(f, "Header block {} must be understood", name)
+            }
+            Error::Fault(fault) => write!(f, "SOAP fault: {}", fault),
+            Error::Serialize(e) => write!(f, "Serialization error: {}", e),
+            Error::Deserialize(e) => write!(f, "Deserialization error: {}", e),
+            Error::Transport(e) => write!(f, "Transport error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<roxmltree::Error> for Error {
+    fn from(e: roxmltree::Error) -> Self {
+        Error::Xml(e.to_string())
+    }
+}
+
+impl From<xml::writer::Error> for Error {
+    fn from(e: xml::writer::Error) -> Self {
+        Error::Serialize(e.to_string())
+    }
+}
+
+impl From<popper::DeError> for Error {
+    fn from(e: popper::DeError) -> Self {
+        Error::Deserialize(format!("{:?}", e))
+    }
+}
@@ -0,0 +1,212 @@
+use std::fmt;
+
+use roxmltree::Node;
+
+use crate::{de, envelope::Version};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FaultCode {
+    VersionMismatch,
+    MustUnderstand,
+    DataEncodingUnknown,
+    // Client in SOAP 1.1
+    Sender,
+    // Server in SOAP 1.1
+    Receiver,
+    Other(String),
+}
+
+impl FaultCode {
+    fn parse(value: &str) -> Self {
+        let local = value.rsplit(':').next().unwrap_or(value);
+        // SOAP 1.1 allows dotted refinements such as Client.Authentication.
+        match local.split('.').next().unwrap_or(local) {
+            "VersionMismatch" => FaultCode::VersionMismatch,
+            "MustUnderstand" => FaultCode::MustUnderstand,
+            "DataEncodingUnknown" => FaultCode::DataEncodingUnknown,
+            "Sender" | "Client" => FaultCode::Sender,
+            "Receiver" | "Server" => FaultCode::Receiver,
+            _ => FaultCode::Other(value.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for FaultCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FaultCode::VersionMismatch => write!(f, "VersionMismatch"),
+            FaultCode::MustUnderstand => write!(f, "MustUnderstand"),
+            FaultCode::DataEncodingUnknown => write!(f, "DataEncodingUnknown"),
+            FaultCode::Sender => write!(f, "Sender"),
+            FaultCode::Receiver => write!(f, "Receiver"),
+            FaultCode::Other(code) => write!(f, "{}", code),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fault {
+    pub code: FaultCode,
+    // Subcode values from the outermost to the innermost one, as written (prefix:local).
+    // In SOAP 1.1 a dotted faultcode refinement is reported here.
+    pub subcodes: Vec<String>,
+    pub reason: String,
+    pub node: Option<String>,
+    // soap:Role (1.2) or faultactor (1.1)
+    pub role: Option<String>,
+    // Standalone source of the Detail element.
+    pub detail: Option<String>,
+}
+
+impl Fault {
+    pub fn parse(text: &str, node: &Node, version: Version) -> Self {
+        match version {
+            Version::Soap11 => parse_soap11(text, node),
+            Version::Soap12 => parse_soap12(text, node, version.namespace()),
+        }
+    }
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code)?;
+        for subcode in &self.subcodes {
+            write!(f, "/{}", subcode)?;
+        }
+        write!(f, ": {}", self.reason)
+    }
+}
+
+// SOAP 1.1 fault children are unqualified.
+fn parse_soap11(text: &str, fault: &Node) -> Fault {
+    let child = |name: &str| fault.children().find(|n| n.is_element() && n.has_tag_name(name));
+    let code = child("faultcode").and_then(|n| n.text()).map(str::trim).unwrap_or_default();
+    let local = code.rsplit(':').next().unwrap_or(code);
+
+    Fault {
+        code: FaultCode::parse(code),
+        subcodes: local.split('.').skip(1).map(str::to_string).collect(),
+        reason: child("faultstring").and_then(|n| n.text()).unwrap_or_default().trim().to_string(),
+        node: None,
+        role: child("faultactor").and_then(|n| n.text()).map(|s| s.trim().to_string()),
+        detail: child("detail").map(|n| de::element_source(text, &n)),
+    }
+}
+
+fn parse_soap12(text: &str, fault: &Node, ns: &str) -> Fault {
+    fn child<'a, 'input>(
+        node: &Node<'a, 'input>,
+        ns: &str,
+        name: &str,
+    ) -> Option<Node<'a, 'input>> {
+        node.children().find(|n| n.is_element() && n.has_tag_name((ns, name)))
+    }
+    let value = |node: &Node| {
+        child(node, ns, "Value").and_then(|n| n.text()).unwrap_or_default().trim().to_string()
+    };
+
+    let code = child(fault, ns, "Code");
+    let mut subcodes = vec![];
+    let mut subcode = code.and_then(|c| child(&c, ns, "Subcode"));
+    while let Some(node) = subcode {
+        subcodes.push(value(&node));
+        subcode = child(&node, ns, "Subcode");
+    }
+
+    // Reason may carry several translations, prefer the English one.
+    let texts = child(fault, ns, "Reason")
+        .map(|r| {
+            r.children()
+                .filter(|n| n.is_element() && n.has_tag_name((ns, "Text")))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let reason = texts
+        .iter()
+        .find(|n| {
+            n.attribute(("http://www.w3.org/XML/1998/namespace", "lang"))
+                .is_some_and(|lang| lang.starts_with("en"))
+        })
+        .or_else(|| texts.first())
+        .and_then(|n| n.text())
+        .unwrap_or_default();
+
+    Fault {
+        code: FaultCode::parse(&code.map(|c| value(&c)).unwrap_or_default()),
+        subcodes,
+        reason: reason.trim().to_string(),
+        node: child(fault, ns, "Node").and_then(|n| n.text()).map(|s| s.trim().to_string()),
+        role: child(fault, ns, "Role").and_then(|n| n.text()).map(|s| s.trim().to_string()),
+        detail: child(fault, ns, "Detail").map(|n| de::element_source(text, &n)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(text: &str, version: Version) -> Fault {
+        let doc = roxmltree::Document::parse(text).unwrap();
+        let node = doc.descendants().find(|n| n.has_tag_name("Fault")).unwrap();
+        Fault::parse(text, &node, version)
+    }
+
+    #[test]
+    fn test_parse_soap12_fault() {
+        let text = r#"
+<env:Envelope xmlns:env="http://www.w3.org/2003/05/soap-envelope" xmlns:ter="http://www.onvif.org/ver10/error">
+    <env:Body>
+        <env:Fault>
+            <env:Code>
+                <env:Value>env:Sender</env:Value>
+                <env:Subcode>
+                    <env:Value>ter:InvalidArgVal</env:Value>
+                    <env:Subcode><env:Value>ter:NoProfile</env:Value></env:Subcode>
+                </env:Subcode>
+            </env:Code>
+            <env:Reason>
+                <env:Text xml:lang="de">Profil existiert nicht</env:Text>
+                <env:Text xml:lang="en">Profile does not exist</env:Text>
+            </env:Reason>
+            <env:Node>http://www.example.org/device</env:Node>
+            <env:Detail><ter:Token>42</ter:Token></env:Detail>
+        </env:Fault>
+    </env:Body>
+</env:Envelope>"#;
+
+        let fault = parse(text, Version::Soap12);
+
+        assert_eq!(fault.code, FaultCode::Sender);
+        assert_eq!(fault.subcodes, vec!["ter:InvalidArgVal", "ter:NoProfile"]);
+        assert_eq!(fault.reason, "Profile does not exist");
+        assert_eq!(fault.node.as_deref(), Some("http://www.example.org/device"));
+        assert_eq!(fault.role, None);
+        assert!(fault.detail.unwrap().contains("xmlns:ter=\"http://www.onvif.org/ver10/error\""));
+        assert_eq!(
+            parse(text, Version::Soap12).to_string(),
+            "Sender/ter:InvalidArgVal/ter:NoProfile: Profile does not exist"
+        );
+    }
+
+    #[test]
+    fn test_parse_soap11_fault() {
+        let text = r#"
+<SOAP-ENV:Envelope xmlns:SOAP-ENV="http://schemas.xmlsoap.org/soap/envelope/">
+    <SOAP-ENV:Body>
+        <SOAP-ENV:Fault>
+            <faultcode>SOAP-ENV:Client.Authentication</faultcode>
+            <faultstring>Not authorized</faultstring>
+            <faultactor>http://www.example.org/gateway</faultactor>
+        </SOAP-ENV:Fault>
+    </SOAP-ENV:Body>
+</SOAP-ENV:Envelope>"#;
+
+        let fault = parse(text, Version::Soap11);
+
+        assert_eq!(fault.code, FaultCode::Sender);
+        assert_eq!(fault.subcodes, vec!["Authentication"]);
+        assert_eq!(fault.reason, "Not authorized");
+        assert_eq!(fault.role.as_deref(), Some("http://www.example.org/gateway"));
+        assert_eq!(fault.detail, None);
+    }
+}
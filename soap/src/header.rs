@@ -0,0 +1,124 @@
+use std::io::Write;
+
+use popper::{XmlDeserialize, XmlPopper};
+use xml::{writer::XmlEvent, EventWriter};
+
+use crate::{
+    envelope::{Version, ENVELOPE_PREFIX},
+    error::Error,
+    ser,
+};
+
+// A single child of soap:Header as it was received.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeaderBlock {
+    pub namespace: Option<String>,
+    pub name: String,
+    pub must_understand: bool,
+    // soap:role (1.2) or soap:actor (1.1), None means the ultimate receiver.
+    pub role: Option<String>,
+    // Standalone source of the block with all in-scope namespaces declared on it.
+    pub xml: String,
+}
+
+impl HeaderBlock {
+    pub fn is(&self, namespace: &str, name: &str) -> bool {
+        self.namespace.as_deref() == Some(namespace) && self.name == name
+    }
+
+    pub fn parse<T: XmlDeserialize>(&self) -> Result<T, Error> {
+        let mut popper = XmlPopper::new(&self.xml)?;
+        Ok(popper.pop_child(&self.name)?)
+    }
+}
+
+// Implemented by the header part of an Envelope. Custom header sets are plain structs
+// implementing this trait; `understands` lists the blocks they process, which makes
+// mustUnderstand blocks with those names acceptable in a received message.
+pub trait Headers: Sized {
+    fn understands(_namespace: Option<&str>, _name: &str) -> bool {
+        false
+    }
+
+    fn write_headers<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        version: Version,
+    ) -> Result<(), Error>;
+
+    fn read_headers(blocks: &[HeaderBlock]) -> Result<Self, Error>;
+
+    // soap:Header is omitted entirely when this returns true.
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NoHeaders;
+
+impl Headers for NoHeaders {
+    fn write_headers<W: Write>(&self, _: &mut EventWriter<W>, _: Version) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn read_headers(_: &[HeaderBlock]) -> Result<Self, Error> {
+        Ok(NoHeaders)
+    }
+
+    fn is_empty(&self) -> bool {
+        true
+    }
+}
+
+// Keeps every received block untouched. It doesn't claim to understand anything,
+// so mustUnderstand checks still apply.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RawHeaders(pub Vec<HeaderBlock>);
+
+impl RawHeaders {
+    pub fn find(&self, namespace: &str, name: &str) -> Option<&HeaderBlock> {
+        self.0.iter().find(|block| block.is(namespace, name))
+    }
+}
+
+impl Headers for RawHeaders {
+    fn write_headers<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        _: Version,
+    ) -> Result<(), Error> {
+        for block in &self.0 {
+            let doc = roxmltree::Document::parse(&block.xml)?;
+            ser::write_node(writer, &doc.root_element())?;
+        }
+        Ok(())
+    }
+
+    fn read_headers(blocks: &[HeaderBlock]) -> Result<Self, Error> {
+        Ok(RawHeaders(blocks.to_vec()))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+// Opens a header block element `prefix:name` in `namespace`, marking it with
+// soap:mustUnderstand when requested. The caller writes the content and the end tag.
+pub fn start_header_block<W: Write>(
+    writer: &mut EventWriter<W>,
+    version: Version,
+    (prefix, namespace): (&str, &str),
+    name: &str,
+    must_understand: bool,
+) -> Result<(), Error> {
+    let qname = format!("{}:{}", prefix, name);
+    let attr = format!("{}:mustUnderstand", ENVELOPE_PREFIX);
+    let mut start = XmlEvent::start_element(qname.as_str()).ns(prefix, namespace);
+    if must_understand {
+        start = start.attr(attr.as_str(), version.must_understand_value());
+    }
+    writer.write(start)?;
+    Ok(())
+}
@@ -0,0 +1,18 @@
+pub mod addressing;
+pub mod de;
+pub mod envelope;
+pub mod error;
+pub mod fault;
+pub mod header;
+pub mod security;
+pub mod ser;
+pub mod transport;
+
+pub use addressing::Addressing;
+pub use envelope::{Envelope, Version};
+pub use error::Error;
+pub use fault::{Fault, FaultCode};
+pub use header::{HeaderBlock, Headers, NoHeaders, RawHeaders};
+pub use security::UsernameToken;
+pub use ser::XmlSerialize;
+pub use transport::{HeaderLayer, StandardHeaders};
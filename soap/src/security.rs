@@ -0,0 +1,155 @@
+// WS-Security UsernameToken profile 1.0 with PasswordDigest, as required by ONVIF.
+use std::{io::Write, time::SystemTime};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rand::RngCore;
+use sha1::{Digest, Sha1};
+use xml::{writer::XmlEvent, EventWriter};
+
+use crate::{envelope::Version, error::Error, header::start_header_block, ser};
+
+pub const WSSE_NAMESPACE: &str =
+    "http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-wssecurity-secext-1.0.xsd";
+pub const WSU_NAMESPACE: &str =
+    "http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-wssecurity-utility-1.0.xsd";
+
+const PASSWORD_DIGEST: &str = "http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-username-token-profile-1.0#PasswordDigest";
+const BASE64_BINARY: &str = "http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-soap-message-security-1.0#Base64Binary";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UsernameToken {
+    pub username: String,
+    pub nonce: Vec<u8>,
+    // Lexical value of wsu:Created, the digest is computed over exactly this string.
+    pub created: String,
+    pub digest: String,
+}
+
+impl UsernameToken {
+    // `created` should be the clock of the receiver: devices reject tokens that are too
+    // far from their own time, see HeaderLayer::sync_clock. chrono's DateTime converts
+    // into SystemTime.
+    pub fn new(username: &str, password: &str, created: impl Into<SystemTime>) -> Self {
+        let mut nonce = vec![0; 16];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let created = format_created(unix_millis(created.into()));
+        Self::with_nonce(username, password, nonce, created)
+    }
+
+    pub fn with_nonce(username: &str, password: &str, nonce: Vec<u8>, created: String) -> Self {
+        let digest = password_digest(&nonce, &created, password);
+        UsernameToken { username: username.to_string(), nonce, created, digest }
+    }
+
+    pub fn write<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        version: Version,
+    ) -> Result<(), Error> {
+        start_header_block(writer, version, ("wsse", WSSE_NAMESPACE), "Security", true)?;
+        writer.write(XmlEvent::start_element("wsse:UsernameToken").ns("wsu", WSU_NAMESPACE))?;
+
+        ser::write_text_element(writer, "wsse:Username", &self.username)?;
+
+        writer.write(XmlEvent::start_element("wsse:Password").attr("Type", PASSWORD_DIGEST))?;
+        writer.write(XmlEvent::characters(&self.digest))?;
+        writer.write(XmlEvent::end_element())?;
+
+        writer.write(XmlEvent::start_element("wsse:Nonce").attr("EncodingType", BASE64_BINARY))?;
+        writer.write(XmlEvent::characters(&STANDARD.encode(&self.nonce)))?;
+        writer.write(XmlEvent::end_element())?;
+
+        ser::write_text_element(writer, "wsu:Created", &self.created)?;
+
+        writer.write(XmlEvent::end_element())?;
+        writer.write(XmlEvent::end_element())?;
+        Ok(())
+    }
+}
+
+// Milliseconds since 1970-01-01T00:00:00Z, negative before.
+pub(crate) fn unix_millis(time: SystemTime) -> i64 {
+    match time.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(since) => since.as_millis() as i64,
+        Err(err) => -(err.duration().as_millis() as i64),
+    }
+}
+
+// UTC time in the lexical form of xs:dateTime with milliseconds, e.g. 2020-01-02T03:04:05.678Z.
+// Dates follow civil_from_days of https://howardhinnant.github.io/date_algorithms.html
+fn format_created(millis: i64) -> String {
+    let days = millis.div_euclid(86_400_000);
+    let millis = millis.rem_euclid(86_400_000);
+
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+// Base64(SHA1(nonce + created + password))
+pub fn password_digest(nonce: &[u8], created: &str, password: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(nonce);
+    hasher.update(created.as_bytes());
+    hasher.update(password.as_bytes());
+    STANDARD.encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_password_digest() {
+        let nonce = STANDARD.decode("LKqI6G/AikKCQrN0zqZFlg==").unwrap();
+
+        assert_eq!(
+            password_digest(&nonce, "2010-09-16T07:50:45Z", "userpassword"),
+            "tuOSpGlFlIXsozq4HFNeeGeFLEI="
+        );
+    }
+
+    #[test]
+    fn test_format_created() {
+        assert_eq!(format_created(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(format_created(951_782_400_001), "2000-02-29T00:00:00.001Z");
+        assert_eq!(format_created(-1), "1969-12-31T23:59:59.999Z");
+    }
+
+    #[test]
+    fn test_new_token() {
+        let created = SystemTime::UNIX_EPOCH + std::time::Duration::from_millis(1_577_934_245_678);
+        let token = UsernameToken::new("admin", "secret", created);
+
+        assert_eq!(token.created, "2020-01-02T03:04:05.678Z");
+        assert_eq!(token.nonce.len(), 16);
+        assert_eq!(token.digest, password_digest(&token.nonce, &token.created, "secret"));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_new_token_chrono() {
+        let created = chrono::DateTime::parse_from_rfc3339("2020-01-02T03:04:05.678Z").unwrap();
+        let token = UsernameToken::new("admin", "secret", created);
+
+        assert_eq!(token.created, "2020-01-02T03:04:05.678Z");
+    }
+}
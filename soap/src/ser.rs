@@ -0,0 +1,322 @@
+use std::io::Write;
+
+use roxmltree::Node;
+use xml::writer::{EmitterConfig, XmlEvent};
+pub use xml::EventWriter;
+
+pub use crate::error::Error;
+
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+// Counterpart of popper's XmlDeserialize: writes the attributes and the content of the
+// element holding `self`. The element itself is written by the caller, see `write_child`
+// and `Element`. A message body is written as the content of soap:Body.
+pub trait XmlSerialize {
+    fn xml_attributes(&self) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    // Namespaces the content refers to, declared on the element holding `self` as
+    // (prefix, namespace). An empty prefix declares the default namespace. Used by values
+    // such as QNames, whose text is only meaningful with its prefix in scope.
+    fn xml_namespaces(&self) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    fn xml_serialize<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error>;
+}
+
+// An empty body, e.g. for WSDL 2.0 messages declared with element="#none".
+impl XmlSerialize for () {
+    fn xml_serialize<W: Write>(&self, _writer: &mut EventWriter<W>) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<T: XmlSerialize + ?Sized> XmlSerialize for &T {
+    fn xml_attributes(&self) -> Vec<(String, String)> {
+        (*self).xml_attributes()
+    }
+
+    fn xml_namespaces(&self) -> Vec<(String, String)> {
+        (*self).xml_namespaces()
+    }
+
+    fn xml_serialize<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+        (*self).xml_serialize(writer)
+    }
+}
+
+impl<T: XmlSerialize> XmlSerialize for Option<T> {
+    fn xml_attributes(&self) -> Vec<(String, String)> {
+        self.as_ref().map(T::xml_attributes).unwrap_or_default()
+    }
+
+    fn xml_namespaces(&self) -> Vec<(String, String)> {
+        self.as_ref().map(T::xml_namespaces).unwrap_or_default()
+    }
+
+    fn xml_serialize<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+        match self {
+            Some(value) => value.xml_serialize(writer),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: XmlSerialize> XmlSerialize for Vec<T> {
+    fn xml_namespaces(&self) -> Vec<(String, String)> {
+        self.iter().flat_map(T::xml_namespaces).collect()
+    }
+
+    fn xml_serialize<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+        self.iter().try_for_each(|value| value.xml_serialize(writer))
+    }
+}
+
+impl XmlSerialize for str {
+    fn xml_serialize<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+        writer.write(XmlEvent::characters(self))?;
+        Ok(())
+    }
+}
+
+// Simple values are written as text in their Display form.
+macro_rules! impl_text {
+    ($($type:ty),*) => {
+        $(impl XmlSerialize for $type {
+            fn xml_serialize<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+                writer.write(XmlEvent::characters(&self.to_string()))?;
+                Ok(())
+            }
+        })*
+    };
+}
+
+impl_text!(String, bool, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+// Infinities are INF and -INF in XSD, Rust writes inf and -inf.
+macro_rules! impl_float {
+    ($($type:ty),*) => {
+        $(impl XmlSerialize for $type {
+            fn xml_serialize<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+                let text = match *self {
+                    v if v == <$type>::INFINITY => "INF".to_string(),
+                    v if v == <$type>::NEG_INFINITY => "-INF".to_string(),
+                    v => v.to_string(),
+                };
+                writer.write(XmlEvent::characters(&text))?;
+                Ok(())
+            }
+        })*
+    };
+}
+
+impl_float!(f32, f64);
+
+// `content` wrapped into the element it is declared as, e.g. the request element of an
+// operation. Its namespace is declared as the default one, so that the child elements of
+// schemas with elementFormDefault="qualified" need no prefix. With a prefix, the namespace
+// is bound to it instead and the children stay unqualified, as the part accessors of rpc
+// wrappers must be.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Element<'a, T> {
+    pub prefix: Option<&'a str>,
+    pub namespace: Option<&'a str>,
+    pub name: &'a str,
+    pub content: T,
+}
+
+impl<'a, T> Element<'a, T> {
+    pub fn new(namespace: Option<&'a str>, name: &'a str, content: T) -> Self {
+        Element { prefix: None, namespace, name, content }
+    }
+
+    pub fn prefixed(prefix: &'a str, namespace: &'a str, name: &'a str, content: T) -> Self {
+        Element { prefix: Some(prefix), namespace: Some(namespace), name, content }
+    }
+}
+
+impl<T: XmlSerialize> XmlSerialize for Element<'_, T> {
+    fn xml_serialize<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+        match (self.prefix, self.namespace) {
+            (Some(prefix), Some(namespace)) => {
+                let name = format!("{}:{}", prefix, self.name);
+                write_element(writer, &name, Some((Some(prefix), namespace)), &self.content)
+            }
+            (_, namespace) => {
+                write_element(writer, self.name, namespace.map(|ns| (None, ns)), &self.content)
+            }
+        }
+    }
+}
+
+// Writes `<name attributes>content</name>` for a field of generated code.
+pub fn write_child<W: Write, T: XmlSerialize + ?Sized>(
+    writer: &mut EventWriter<W>,
+    name: &str,
+    value: &T,
+) -> Result<(), Error> {
+    write_element(writer, name, None, value)
+}
+
+// `namespace` is declared on the element, bound to the prefix or as the default namespace.
+// The namespaces `value` refers to are declared next to it.
+fn write_element<W: Write, T: XmlSerialize + ?Sized>(
+    writer: &mut EventWriter<W>,
+    name: &str,
+    namespace: Option<(Option<&str>, &str)>,
+    value: &T,
+) -> Result<(), Error> {
+    let attributes = value.xml_attributes();
+    let mut start = XmlEvent::start_element(name);
+    match namespace {
+        Some((Some(prefix), namespace)) => start = start.ns(prefix, namespace),
+        Some((None, namespace)) => start = start.default_ns(namespace),
+        None => {}
+    }
+    for (prefix, namespace) in value.xml_namespaces() {
+        start = start.ns(prefix, namespace);
+    }
+    for (name, value) in &attributes {
+        start = start.attr(name.as_str(), value);
+    }
+    writer.write(start)?;
+    value.xml_serialize(writer)?;
+    writer.write(XmlEvent::end_element())?;
+    Ok(())
+}
+
+pub fn event_writer<W: Write>(sink: W) -> EventWriter<W> {
+    EmitterConfig::new().write_document_declaration(true).perform_indent(false).create_writer(sink)
+}
+
+// Writes `<name>value</name>` where `name` may carry a prefix declared by an ancestor.
+pub fn write_text_element<W: Write>(
+    writer: &mut EventWriter<W>,
+    name: &str,
+    value: &str,
+) -> Result<(), Error> {
+    writer.write(XmlEvent::start_element(name))?;
+    writer.write(XmlEvent::characters(value))?;
+    writer.write(XmlEvent::end_element())?;
+    Ok(())
+}
+
+// Re-emits a parsed element (and its subtree) through the writer. Namespaces are
+// declared where the source declared them, the document root declares all in scope.
+pub fn write_node<W: Write>(writer: &mut EventWriter<W>, node: &Node) -> Result<(), Error> {
+    if node.is_text() {
+        writer.write(XmlEvent::characters(node.text().unwrap_or_default()))?;
+        return Ok(());
+    }
+    if !node.is_element() {
+        return Ok(());
+    }
+
+    let name = qualified_name(node, node.tag_name().namespace(), node.tag_name().name());
+    let attributes = node
+        .attributes()
+        .map(|a| (qualified_name(node, a.namespace(), a.name()), a.value()))
+        .collect::<Vec<_>>();
+    let inherited =
+        node.parent_element().map(|p| p.namespaces().collect::<Vec<_>>()).unwrap_or_default();
+
+    let mut start = XmlEvent::start_element(name.as_str());
+    for ns in node.namespaces().filter(|ns| ns.uri() != XML_NAMESPACE && !inherited.contains(ns)) {
+        start = start.ns(ns.name().unwrap_or(""), ns.uri());
+    }
+    for (name, value) in &attributes {
+        start = start.attr(name.as_str(), value);
+    }
+    writer.write(start)?;
+
+    for child in node.children() {
+        write_node(writer, &child)?;
+    }
+
+    writer.write(XmlEvent::end_element())?;
+    Ok(())
+}
+
+fn qualified_name(node: &Node, namespace: Option<&str>, name: &str) -> String {
+    match namespace.and_then(|ns| node.lookup_prefix(ns)) {
+        Some(prefix) if !prefix.is_empty() => format!("{}:{}", prefix, name),
+        _ => name.to_string(),
+    }
+}
+
+pub fn to_string<T: XmlSerialize>(value: &T) -> Result<String, Error> {
+    let mut buffer = Vec::new();
+    value.xml_serialize(&mut event_writer(&mut buffer))?;
+    String::from_utf8(buffer).map_err(|e| Error::Serialize(e.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Point {
+        x: f64,
+        label: Option<String>,
+    }
+
+    impl XmlSerialize for Point {
+        fn xml_attributes(&self) -> Vec<(String, String)> {
+            self.label.iter().map(|l| ("label".to_string(), l.clone())).collect()
+        }
+
+        fn xml_serialize<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+            write_child(writer, "X", &self.x)
+        }
+    }
+
+    // Text that is only meaningful with the prefix p in scope.
+    struct Prefixed;
+
+    impl XmlSerialize for Prefixed {
+        fn xml_namespaces(&self) -> Vec<(String, String)> {
+            vec![("p".to_string(), "urn:p".to_string())]
+        }
+
+        fn xml_serialize<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+            "p:value".xml_serialize(writer)
+        }
+    }
+
+    fn serialize<T: XmlSerialize>(value: &T) -> String {
+        let mut buffer = Vec::new();
+        let mut writer =
+            EmitterConfig::new().write_document_declaration(false).create_writer(&mut buffer);
+        value.xml_serialize(&mut writer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_serialize_element() {
+        let point = Point { x: f64::NEG_INFINITY, label: Some("a&b".to_string()) };
+        assert_eq!(
+            serialize(&Element::new(Some("http://example.com"), "Point", &point)),
+            r#"<Point xmlns="http://example.com" label="a&amp;b"><X>-INF</X></Point>"#
+        );
+
+        let points = vec![Point { x: 1.5, label: None }, Point { x: f64::NAN, label: None }];
+        assert_eq!(
+            serialize(&Element::new(None, "Points", &points)),
+            "<Points><X>1.5</X><X>NaN</X></Points>"
+        );
+
+        assert_eq!(
+            serialize(&Element::prefixed("m", "http://example.com", "Point", &points[0])),
+            r#"<m:Point xmlns:m="http://example.com"><X>1.5</X></m:Point>"#
+        );
+    }
+
+    #[test]
+    fn test_serialize_namespaces() {
+        assert_eq!(
+            serialize(&Element::new(None, "Code", Some(Prefixed))),
+            r#"<Code xmlns:p="urn:p">p:value</Code>"#
+        );
+    }
+}
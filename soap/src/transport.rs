@@ -0,0 +1,368 @@
+// Entry points called by the functions wsdl-parser generates for port type operations:
+//
+//     pub async fn get_device_information<T: transport::Transport>(
+//         transport: &T,
+//         request: &GetDeviceInformation,
+//     ) -> Result<GetDeviceInformationResponse, transport::Error> {
+//         transport::request(
+//             transport,
+//             Some("http://www.onvif.org/ver10/device/wsdl/GetDeviceInformation"),
+//             &ser::Element::new(Some(TDS), "GetDeviceInformation", request),
+//         )
+//         .await
+//     }
+use std::{
+    io::Write,
+    sync::atomic::{AtomicI64, Ordering},
+    time::{Duration, SystemTime},
+};
+
+use async_trait::async_trait;
+use xml::EventWriter;
+
+use crate::{
+    addressing::Addressing,
+    de::FromBody,
+    envelope::{Envelope, Version},
+    header::{HeaderBlock, Headers},
+    security::{unix_millis, UsernameToken},
+    ser::XmlSerialize,
+};
+pub use crate::{de::Parts, error::Error};
+
+// Moves a serialized envelope to the service and returns the response envelope.
+//
+// `action` is the action of the operation (soapAction or wsam:Action in the description),
+// None if it has none. HTTP transports send it with the headers of `Version::http_headers`.
+#[async_trait]
+pub trait Transport: Sync {
+    async fn request(&self, message: &str, action: Option<&str>) -> Result<String, Error>;
+
+    // Waits for a message initiated by the service (notification and solicit-response
+    // operations). Only transports that can accept incoming messages implement it.
+    async fn receive(&self) -> Result<String, Error> {
+        Err(Error::Transport("receiving messages is not supported".to_string()))
+    }
+
+    // Answers the message returned by the last `receive`.
+    async fn respond(&self, _message: &str) -> Result<(), Error> {
+        Err(Error::Transport("responding to messages is not supported".to_string()))
+    }
+
+    fn version(&self) -> Version {
+        Version::default()
+    }
+
+    // Header blocks added to the messages we send for an operation with the given action.
+    fn headers(&self, _action: Option<&str>) -> StandardHeaders {
+        StandardHeaders::default()
+    }
+}
+
+pub async fn request<T: Transport, R: XmlSerialize, S: FromBody>(
+    transport: &T,
+    action: Option<&str>,
+    request: &R,
+) -> Result<S, Error> {
+    let envelope = Envelope::new(transport.version(), transport.headers(action), request);
+    let response: Envelope<StandardHeaders, S> = call(transport, action, &envelope).await?;
+    Ok(response.body)
+}
+
+// One-way operation: the response, if any, carries no data. It may still be a Fault, e.g.
+// for the robust-in-only pattern of WSDL 2.0.
+pub async fn send<T: Transport, R: XmlSerialize>(
+    transport: &T,
+    action: Option<&str>,
+    request: &R,
+) -> Result<(), Error> {
+    let envelope = Envelope::new(transport.version(), transport.headers(action), request);
+    let response = transport.request(&envelope.to_xml()?, action).await?;
+    // HTTP transports return an empty message for 202 Accepted.
+    if !response.trim().is_empty() {
+        Envelope::<StandardHeaders, ()>::from_xml_without_body(&response)?;
+    }
+    Ok(())
+}
+
+// Notification operation.
+pub async fn receive<T: Transport, S: FromBody>(transport: &T) -> Result<S, Error> {
+    let message = transport.receive().await?;
+    let envelope: Envelope<StandardHeaders, S> = Envelope::from_xml(&message)?;
+    Ok(envelope.body)
+}
+
+// Solicit-response operation: `respond` builds the answer to the message of the service,
+// `action` is the action of the answer.
+pub async fn solicit<T, S, R, F>(
+    transport: &T,
+    action: Option<&str>,
+    respond: F,
+) -> Result<(), Error>
+where
+    T: Transport,
+    S: FromBody,
+    R: XmlSerialize,
+    F: FnOnce(S) -> R,
+{
+    let request = receive(transport).await?;
+    let envelope = Envelope::new(transport.version(), transport.headers(action), respond(request));
+    transport.respond(&envelope.to_xml()?).await
+}
+
+// Like `request`, but with control over the header blocks on both sides.
+pub async fn call<T, HR, R, HS, S>(
+    transport: &T,
+    action: Option<&str>,
+    envelope: &Envelope<HR, R>,
+) -> Result<Envelope<HS, S>, Error>
+where
+    T: Transport,
+    HR: Headers,
+    R: XmlSerialize,
+    HS: Headers,
+    S: FromBody,
+{
+    let response = transport.request(&envelope.to_xml()?, action).await?;
+    Envelope::from_xml(&response)
+}
+
+// WS-Security and WS-Addressing headers. Security is write-only, responses
+// only fill `addressing`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StandardHeaders {
+    pub security: Option<UsernameToken>,
+    pub addressing: Option<Addressing>,
+}
+
+impl Headers for StandardHeaders {
+    fn understands(namespace: Option<&str>, name: &str) -> bool {
+        Addressing::understands(namespace, name)
+    }
+
+    fn write_headers<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+        version: Version,
+    ) -> Result<(), Error> {
+        if let Some(security) = &self.security {
+            security.write(writer, version)?;
+        }
+        if let Some(addressing) = &self.addressing {
+            addressing.write(writer, version)?;
+        }
+        Ok(())
+    }
+
+    fn read_headers(blocks: &[HeaderBlock]) -> Result<Self, Error> {
+        Ok(StandardHeaders { security: None, addressing: Addressing::read(blocks)? })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.security.is_none() && self.addressing.is_none()
+    }
+}
+
+// Wraps a transport and injects a UsernameToken and/or WS-Addressing headers into
+// every request.
+//
+// Cheap devices often have their clock off by hours, and reject tokens whose Created
+// time is too far from theirs. Call `sync_clock` with the device time (ONVIF
+// GetSystemDateAndTime doesn't require authentication) to create tokens in device time.
+pub struct HeaderLayer<T> {
+    inner: T,
+    credentials: Option<(String, String)>,
+    to: Option<String>,
+    addressing: bool,
+    // Device time minus local time, in milliseconds.
+    clock_offset: AtomicI64,
+}
+
+impl<T> HeaderLayer<T> {
+    pub fn new(inner: T) -> Self {
+        HeaderLayer {
+            inner,
+            credentials: None,
+            to: None,
+            addressing: false,
+            clock_offset: AtomicI64::new(0),
+        }
+    }
+
+    pub fn with_credentials(mut self, username: &str, password: &str) -> Self {
+        self.credentials = Some((username.to_string(), password.to_string()));
+        self
+    }
+
+    // Enables WS-Addressing with the given wsa:To. wsa:Action is the action of the operation.
+    pub fn with_addressing(mut self, to: Option<&str>) -> Self {
+        self.addressing = true;
+        self.to = to.map(str::to_string);
+        self
+    }
+
+    // chrono's DateTime converts into SystemTime.
+    pub fn sync_clock(&self, remote: impl Into<SystemTime>) {
+        let offset = unix_millis(remote.into()) - unix_millis(SystemTime::now());
+        self.set_clock_offset_millis(offset);
+    }
+
+    pub fn set_clock_offset_millis(&self, offset: i64) {
+        self.clock_offset.store(offset, Ordering::Relaxed);
+    }
+
+    pub fn clock_offset_millis(&self) -> i64 {
+        self.clock_offset.load(Ordering::Relaxed)
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn set_clock_offset(&self, offset: chrono::Duration) {
+        self.set_clock_offset_millis(offset.num_milliseconds());
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn clock_offset(&self) -> chrono::Duration {
+        chrono::Duration::milliseconds(self.clock_offset_millis())
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<T: Transport + Sync + Send> Transport for HeaderLayer<T> {
+    async fn request(&self, message: &str, action: Option<&str>) -> Result<String, Error> {
+        self.inner.request(message, action).await
+    }
+
+    async fn receive(&self) -> Result<String, Error> {
+        self.inner.receive().await
+    }
+
+    async fn respond(&self, message: &str) -> Result<(), Error> {
+        self.inner.respond(message).await
+    }
+
+    fn version(&self) -> Version {
+        self.inner.version()
+    }
+
+    fn headers(&self, action: Option<&str>) -> StandardHeaders {
+        let mut headers = self.inner.headers(action);
+        if let Some((username, password)) = &self.credentials {
+            let offset = self.clock_offset_millis();
+            let now = SystemTime::now();
+            let created = if offset < 0 {
+                now - Duration::from_millis(offset.unsigned_abs())
+            } else {
+                now + Duration::from_millis(offset as u64)
+            };
+            headers.security = Some(UsernameToken::new(username, password, created));
+        }
+        if self.addressing {
+            headers.addressing =
+                Some(Addressing::request(self.to.clone(), action.map(str::to_string)));
+        }
+        headers
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Dummy;
+
+    #[async_trait]
+    impl Transport for Dummy {
+        async fn request(&self, _: &str, _: Option<&str>) -> Result<String, Error> {
+            Err(Error::Transport("not connected".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_header_layer_headers() {
+        let layer = HeaderLayer::new(Dummy)
+            .with_credentials("admin", "secret")
+            .with_addressing(Some("http://192.168.0.2/onvif/device_service"));
+        layer.sync_clock(SystemTime::now() - Duration::from_secs(3 * 3600));
+        assert!((layer.clock_offset_millis() + 3 * 3600 * 1000).abs() < 5000);
+
+        let headers = layer.headers(Some("urn:GetDeviceInformation"));
+        let token = headers.security.unwrap();
+        let expected = unix_millis(SystemTime::now()) - 3 * 3600 * 1000;
+        let hour = |millis: i64| format!("T{:02}:", millis.rem_euclid(86_400_000) / 3_600_000);
+        assert!(
+            token.created.contains(&hour(expected))
+                || token.created.contains(&hour(expected - 5000))
+        );
+
+        let addressing = headers.addressing.unwrap();
+        assert_eq!(addressing.to.as_deref(), Some("http://192.168.0.2/onvif/device_service"));
+        assert_eq!(addressing.action.as_deref(), Some("urn:GetDeviceInformation"));
+        assert!(addressing.message_id.unwrap().starts_with("urn:uuid:"));
+
+        assert_eq!(layer.headers(None).addressing.unwrap().action, None);
+        assert!(Dummy.headers(None).is_empty());
+    }
+
+    #[test]
+    fn test_serialize_standard_headers() {
+        let headers = StandardHeaders {
+            security: Some(UsernameToken::with_nonce(
+                "admin",
+                "secret",
+                vec![1, 2, 3],
+                "2020-01-02T03:04:05Z".to_string(),
+            )),
+            addressing: Some(Addressing {
+                action: Some("http://www.onvif.org/ver10/device/wsdl/GetDeviceInformation".into()),
+                ..Addressing::default()
+            }),
+        };
+        let xml = Envelope::new(Version::Soap12, headers, None::<Empty>).to_xml().unwrap();
+
+        assert!(xml.contains(
+            r#"<wsse:Security xmlns:wsse="http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-wssecurity-secext-1.0.xsd" s:mustUnderstand="true">"#
+        ));
+        assert!(xml.contains(r#"<wsse:Username>admin</wsse:Username>"#));
+        assert!(xml.contains(r#"<wsse:Nonce EncodingType="http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-soap-message-security-1.0#Base64Binary">AQID</wsse:Nonce>"#));
+        assert!(xml.contains(r#"<wsu:Created>2020-01-02T03:04:05Z</wsu:Created>"#));
+        assert!(xml.contains(
+            r#"<wsa:Action xmlns:wsa="http://www.w3.org/2005/08/addressing">http://www.onvif.org/ver10/device/wsdl/GetDeviceInformation</wsa:Action>"#
+        ));
+    }
+
+    #[test]
+    fn test_read_addressing() {
+        let text = r#"<s:Envelope xmlns:s="http://www.w3.org/2003/05/soap-envelope" xmlns:wsa="http://www.w3.org/2005/08/addressing"><s:Header><wsa:Action s:mustUnderstand="true">urn:response</wsa:Action><wsa:RelatesTo>urn:uuid:1</wsa:RelatesTo></s:Header><s:Body><Response/></s:Body></s:Envelope>"#;
+        let doc = roxmltree::Document::parse(text).unwrap();
+        let header = doc.descendants().find(|n| n.has_tag_name("Header")).unwrap();
+        let blocks = header
+            .children()
+            .filter(|n| n.is_element())
+            .map(|n| HeaderBlock {
+                namespace: n.tag_name().namespace().map(str::to_string),
+                name: n.tag_name().name().to_string(),
+                must_understand: true,
+                role: None,
+                xml: crate::de::element_source(text, &n),
+            })
+            .collect::<Vec<_>>();
+
+        assert!(StandardHeaders::understands(Some(crate::addressing::WSA_NAMESPACE), "Action"));
+        let headers = StandardHeaders::read_headers(&blocks).unwrap();
+        let addressing = headers.addressing.unwrap();
+        assert_eq!(addressing.action.as_deref(), Some("urn:response"));
+        assert_eq!(addressing.relates_to.as_deref(), Some("urn:uuid:1"));
+    }
+
+    struct Empty;
+
+    impl XmlSerialize for Empty {
+        fn xml_serialize<W: Write>(&self, _: &mut EventWriter<W>) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+}
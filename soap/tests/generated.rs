@@ -0,0 +1,163 @@
+// Calls the clients wsdl-parser generates for its test documents through this crate. The
+// generator tests check that the included files match the generated code.
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Mutex,
+    task::{Context, Poll, Waker},
+};
+
+use async_trait::async_trait;
+use soap::{ser, transport::Transport, Error};
+
+#[allow(clippy::all, dead_code, unused)]
+mod calculator {
+    use popper::{DeError, XmlDeserialize, XmlPopper};
+    use soap::{ser, transport};
+
+    pub trait Validate {}
+
+    include!("../../wsdl-parser/tests/rpc_and_multi_part/expected.rs");
+}
+
+#[allow(clippy::all, dead_code, unused)]
+mod calculator_wsdl2 {
+    use popper::{DeError, XmlDeserialize, XmlPopper};
+    use soap::{ser, transport};
+
+    pub trait Validate {}
+
+    include!("../../wsdl-parser/tests/wsdl2/expected.rs");
+}
+
+// Records the requests with their action and answers each with the same response.
+struct Recorder {
+    requests: Mutex<Vec<(String, Option<String>)>>,
+    response: String,
+}
+
+impl Recorder {
+    fn new(body: &str) -> Self {
+        Recorder {
+            requests: Mutex::new(vec![]),
+            response: format!(
+                r#"<s:Envelope xmlns:s="http://www.w3.org/2003/05/soap-envelope"><s:Body>{}</s:Body></s:Envelope>"#,
+                body
+            ),
+        }
+    }
+
+    fn request(&self) -> String {
+        self.requests.lock().unwrap().last().cloned().unwrap().0
+    }
+
+    fn action(&self) -> Option<String> {
+        self.requests.lock().unwrap().last().cloned().unwrap().1
+    }
+}
+
+#[async_trait]
+impl Transport for Recorder {
+    async fn request(&self, message: &str, action: Option<&str>) -> Result<String, Error> {
+        self.requests.lock().unwrap().push((message.to_string(), action.map(str::to_string)));
+        Ok(self.response.clone())
+    }
+}
+
+// The futures of Recorder complete without waiting.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+#[test]
+fn test_rpc_request() {
+    let transport = Recorder::new(
+        r#"<m:AddResponse xmlns:m="urn:calculator"><sum>3</sum><overflow>false</overflow></m:AddResponse>"#,
+    );
+    let request = calculator::Add { a: 1, b: 2 };
+
+    let response = block_on(calculator::add(&transport, &request)).unwrap();
+
+    assert_eq!(response, calculator::AddResponse { sum: 3, overflow: false });
+    assert!(transport
+        .request()
+        .contains(r#"<ns:Add xmlns:ns="urn:calculator"><a>1</a><b>2</b></ns:Add>"#));
+    assert_eq!(transport.action().as_deref(), Some("http://example.com/calculator/Add"));
+}
+
+#[test]
+fn test_multi_part_request() {
+    let transport = Recorder::new("<Ack/>");
+    let request = calculator::UploadRequest { header: "h".to_string(), payload: "<p>".to_string() };
+
+    block_on(calculator::upload(&transport, &request)).unwrap();
+
+    assert!(transport.request().contains(concat!(
+        r#"<s:Body><tns:Header xmlns:tns="http://example.com/calculator">h</tns:Header>"#,
+        r#"<tns:Payload xmlns:tns="http://example.com/calculator">&lt;p&gt;</tns:Payload></s:Body>"#
+    )));
+}
+
+#[test]
+fn test_multi_part_response() {
+    let transport = Recorder::new(
+        r#"<tns:Header xmlns:tns="http://example.com/calculator">h</tns:Header><tns:Payload xmlns:tns="http://example.com/calculator">p</tns:Payload>"#,
+    );
+
+    let response = block_on(calculator::download(&transport, &"h".to_string())).unwrap();
+
+    assert_eq!(
+        response,
+        calculator::DownloadResponse { header: "h".to_string(), payload: "p".to_string() }
+    );
+    assert!(transport
+        .request()
+        .contains(r#"<s:Body><Header xmlns="http://example.com/calculator">h</Header></s:Body>"#));
+}
+
+#[test]
+fn test_one_way_fault() {
+    let transport = Recorder::new(
+        r#"<s:Fault><s:Code><s:Value>s:Sender</s:Value></s:Code><s:Reason><s:Text xml:lang="en">Too large</s:Text></s:Reason></s:Fault>"#,
+    );
+    let request = calculator::UploadRequest { header: "h".to_string(), payload: "p".to_string() };
+
+    match block_on(calculator::upload(&transport, &request)) {
+        Err(Error::Fault(fault)) => assert_eq!(fault.reason, "Too large"),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn test_simple_type_request() {
+    let transport =
+        Recorder::new(r#"<AddResponse xmlns="http://example.com/calculator">3</AddResponse>"#);
+
+    let sum =
+        block_on(calculator_wsdl2::add(&transport, &calculator_wsdl2::Add { a: 1, b: 2 })).unwrap();
+    assert_eq!(sum, 3);
+
+    block_on(calculator_wsdl2::reset(&transport, &"now".to_string())).unwrap();
+    assert!(transport
+        .request()
+        .contains(r#"<Reset xmlns="http://example.com/calculator">now</Reset>"#));
+    assert_eq!(transport.action().as_deref(), Some("http://example.com/calculator/Reset"));
+}
+
+#[test]
+fn test_any_element() {
+    let transport = Recorder::new("<Pong>5</Pong>");
+
+    let pong: i32 =
+        block_on(calculator_wsdl2::echo(&transport, &ser::Element::new(None, "Ping", "hi")))
+            .unwrap();
+
+    assert_eq!(pong, 5);
+    assert!(transport.request().contains("<s:Body><Ping>hi</Ping></s:Body>"));
+}
@@ -1,87 +1,172 @@
 use std::{
     fs,
-    io::{prelude::*, Read},
+    io::prelude::*,
     path::{Path, PathBuf},
 };
 
-use anyhow::Context;
-use clap::Parser;
-use roxmltree::{Document, Node};
-use wsdl_parser::{generator::generate, parser::definitions::Definitions};
-use xsd_parser::{generator::builder::GeneratorBuilder, parser::schema::parse_schema};
+use anyhow::{bail, Context};
+use clap::{Parser, ValueEnum};
+use roxmltree::Document;
+use wsdl_parser::{
+    generator::{
+        description_operation_docs, description_operation_types, generate_documents_operations,
+        merge_documents, operation_docs, operation_types,
+    },
+    loader,
+};
+use xsd_parser::{
+    check::check_file,
+    config::Config,
+    doc::{self, DocFormat},
+    generator::builder::GeneratorBuilder,
+    loader::walk_dir,
+    model::SchemaSet,
+    parser::{parse_schemas, reachable::retain_reachable},
+};
 
 #[derive(Parser)]
 #[clap(name = env!("CARGO_PKG_NAME"))]
 #[clap(version = env!("CARGO_PKG_VERSION"))]
 #[clap(about = env!("CARGO_PKG_DESCRIPTION"))]
 struct Opt {
-    /// Input .wsdl file
+    /// Input .wsdl file (WSDL 1.1 or 2.0)
     #[clap(long, short)]
     input: Option<PathBuf>,
 
     /// Output file
     #[clap(long, short)]
     output: Option<PathBuf>,
+
+    /// Generator config file (TOML)
+    #[clap(long, short)]
+    config: Option<PathBuf>,
+
+    /// Generate only this operation and the types it depends on (can be repeated)
+    #[clap(long = "root", value_name = "NAME")]
+    roots: Vec<String>,
+
+    /// What to write
+    #[clap(long, value_enum, default_value = "rust")]
+    emit: Emit,
+
+    /// Compare with the existing output instead of writing it, fail if it is out of date
+    #[clap(long)]
+    check: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Emit {
+    /// Generated Rust code
+    Rust,
+    /// Documentation of the operations and schema components as Markdown
+    Markdown,
+    /// Documentation of the operations and schema components as a static HTML page
+    Html,
 }
 
 fn main() -> anyhow::Result<()> {
     let opt: Opt = Opt::parse();
 
+    let mut config = match &opt.config {
+        Some(path) => Config::from_file(path)?,
+        None => Config::default(),
+    };
+    config.roots.extend(opt.roots);
+
+    let emit = opt.emit;
     let input_path = opt.input.unwrap_or_else(|| PathBuf::from("input/wsdl"));
     let md = fs::metadata(&input_path).unwrap();
-    if md.is_dir() {
+    let files = if md.is_dir() {
         let output_path = opt.output.unwrap_or_else(|| PathBuf::from("output/wsdl-rs"));
-        process_dir(&input_path, &output_path)?;
+        walk_dir(&input_path, &output_path)?
+            .into_iter()
+            .map(|(input, output)| match emit {
+                Emit::Rust => (input, Some(output)),
+                Emit::Markdown => (input, Some(output.with_extension("md"))),
+                Emit::Html => (input, Some(output.with_extension("html"))),
+            })
+            .collect()
     } else {
-        process_single_file(&input_path, opt.output.as_deref())?;
+        vec![(input_path, opt.output)]
+    };
+
+    let mut stale = 0;
+    for (input, output) in files {
+        let code = process_single_file(&input, &config, emit)?;
+        match output {
+            Some(output) if opt.check => {
+                if let Some(diff) = check_file(&output, &code)? {
+                    print!("{}", diff);
+                    stale += 1;
+                }
+            }
+            Some(output) => {
+                if let Some(parent) = output.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                write_to_file(&output, &code).context("Error writing file")?;
+            }
+            None if opt.check => bail!("--check needs the --output to compare with"),
+            None => println!("{}", code),
+        }
+    }
+    if stale > 0 {
+        bail!("{} generated file(s) are out of date", stale);
     }
 
     Ok(())
 }
 
-//TODO: Add a common mechanism for working with files
-fn process_dir(input_path: &Path, output_path: &Path) -> anyhow::Result<()> {
-    if !output_path.exists() {
-        fs::create_dir_all(output_path)?;
+fn process_single_file(input_path: &Path, config: &Config, emit: Emit) -> anyhow::Result<String> {
+    // The input and every wsdl and xsd document it imports, directly or through wsdl:types.
+    let files = loader::load(input_path)?;
+    let docs = files
+        .iter()
+        .map(|f| {
+            Document::parse(&f.text)
+                .with_context(|| format!("Failed to parse {}", f.path.display()))
+        })
+        .collect::<anyhow::Result<Vec<Document>>>()?;
+
+    let (definitions, description) = merge_documents(&docs);
+
+    if emit != Emit::Rust {
+        let schemas = docs.iter().flat_map(loader::schemas).collect::<Vec<_>>();
+        let mut operations = vec![];
+        if let Some(definitions) = &definitions {
+            operations.extend(operation_docs(definitions));
+        }
+        if let Some(description) = &description {
+            operations.extend(description_operation_docs(description));
+        }
+        let format = if emit == Emit::Html { DocFormat::Html } else { DocFormat::Markdown };
+        let title = input_path.file_stem().unwrap_or_default().to_string_lossy();
+        return Ok(doc::render(&SchemaSet::parse(&schemas), &operations, &title, format));
     }
-    for entry in fs::read_dir(input_path)? {
-        let path = entry?.path();
-        if path.is_dir() {
-            process_dir(&path, &output_path.join(path.file_name().unwrap()))?;
-        } else {
-            let output_file_path = PathBuf::from(path.file_name().unwrap()).with_extension("rs");
-            let output_file_path = output_path.join(output_file_path);
-            process_single_file(&path, Some(&output_file_path))?;
+
+    // Roots may name operations as well as schema elements and types. The elements and
+    // types the selected operations send and receive are kept with their dependencies.
+    let operations = Some(config.roots.as_slice()).filter(|roots| !roots.is_empty());
+    let mut roots = config.roots.clone();
+    if let Some(operations) = operations {
+        if let Some(definitions) = &definitions {
+            roots.extend(operation_types(definitions, operations));
+        }
+        if let Some(description) = &description {
+            roots.extend(description_operation_types(description, operations));
         }
     }
-    Ok(())
-}
 
-fn process_single_file(input_path: &Path, output_path: Option<&Path>) -> anyhow::Result<()> {
-    let text = load_file(input_path)?;
-    let doc = Document::parse(text.as_str()).context("Failed to parse input document")?;
-    let definitions = Definitions::new(&doc.root_element());
-    let gen = GeneratorBuilder::default().build();
-    let schemas =
-        definitions.types().iter().flat_map(|t| t.schemas()).collect::<Vec<Node<'_, '_>>>();
-    let mut code =
-        schemas.iter().map(|f| gen.generate_rs_file(&parse_schema(f))).collect::<Vec<String>>();
-
-    code.push(generate(&definitions));
-    let code = code.join("");
-    if let Some(output_filename) = output_path {
-        write_to_file(output_filename, &code).context("Error writing file")?;
-    } else {
-        println!("{}", code);
+    let schemas = docs.iter().flat_map(loader::schemas).collect::<Vec<_>>();
+    let mut rs_files = parse_schemas(&schemas);
+    if operations.is_some() {
+        retain_reachable(&mut rs_files, &roots);
     }
-    Ok(())
-}
+    let gen = GeneratorBuilder::default().with_config(config.clone()).build();
+    let mut code = rs_files.iter().map(|f| gen.generate_rs_file(f)).collect::<Vec<String>>();
 
-fn load_file(path: &Path) -> std::io::Result<String> {
-    let mut file = fs::File::open(path)?;
-    let mut text = String::new();
-    file.read_to_string(&mut text)?;
-    Ok(text)
+    code.push(generate_documents_operations(&docs, operations));
+    Ok(code.join(""))
 }
 
 fn write_to_file(path: &Path, text: &str) -> std::io::Result<()> {
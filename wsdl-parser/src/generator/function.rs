@@ -1,79 +1,312 @@
+use std::{borrow::Cow, cell::RefCell};
+
+use roxmltree::Node;
+
+use xsd_parser::parser::types::{Struct, StructField, StructFieldSource};
+
 use crate::parser::{
+    binding::{self, Style},
     definitions::Definitions,
     message::{Message, Part},
     port_type::{
         Operation,
-        OperationType::{OneWay, RequestResponse},
+        OperationType::{Notification, OneWay, RequestResponse, SolicitResponse},
     },
+    wsdl2::interface::{self, Pattern},
 };
 
-const GENERIC_TRANSPORT: Param<'static> = Param { name: "T", typename: "transport::Transport" };
-const ARGUMENT_TRANSPORT: Param<'static> = Param { name: "transport", typename: "T" };
+// Message exchange pattern of the operation, seen from the client.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FunctionKind {
+    // We send the input and wait for the output.
+    RequestResponse,
+    // We send the input, there is no response.
+    OneWay,
+    // The service sends the output and expects the input as an answer.
+    SolicitResponse,
+    // The service sends the output.
+    Notification,
+}
+
+// Element a message body is sent as.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BodyElement {
+    pub namespace: Option<String>,
+    pub name: String,
+    // Set for rpc wrappers: the wrapper is qualified with this prefix, its part accessors
+    // are unqualified.
+    pub prefix: Option<String>,
+}
+
+// Struct generated for an rpc wrapper element or a multi-part message.
+#[derive(Debug, Clone)]
+pub struct Wrapper {
+    pub entity: Struct,
+    // Elements of the element parts of document style messages by field name. They are
+    // children of the Body, so they are written qualified.
+    pub elements: Vec<(String, BodyElement)>,
+}
 
 #[derive(Debug)]
 pub struct Function<'a> {
-    pub generic_params: Vec<Param<'a>>,
-    pub arguments: Vec<Param<'a>>,
-    pub return_type: &'a str,
+    pub kind: FunctionKind,
+    // Type of the wsdl:input message body (sent by the client).
+    pub input: Option<Cow<'a, str>>,
+    // Type of the wsdl:output message body (sent by the service).
+    pub output: Option<Cow<'a, str>>,
+    // Element the input is sent as. None if the input is not an element, e.g. the parts of
+    // a document style message with several parts.
+    pub input_element: Option<BodyElement>,
+    // The output is a document style message with several parts, read from all children
+    // of the Body.
+    pub output_parts: bool,
+    // Action of the input: its wsam:Action, or the soapAction (wsoap:action) of the binding.
+    pub action: Option<&'a str>,
+    // The input or output may be any element (#any and #other in WSDL 2.0), its type is a
+    // generic parameter of the function.
+    pub any_input: bool,
+    pub any_output: bool,
     pub documentation: Option<&'a str>,
     pub name: &'a str,
+    // Structs to generate for rpc wrapper elements and multi-part messages.
+    pub wrappers: Vec<Wrapper>,
+    // Elements and types the function sends and receives, directly or in wrappers, as
+    // {namespace}name resolved where the message refers to them.
+    pub types: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
-pub struct Param<'a> {
-    pub name: &'a str,
-    pub typename: &'a str,
-}
+impl<'a> Function<'a> {
+    pub fn new(
+        operation: &'a Operation<'_>,
+        definitions: &'a Definitions<'_>,
+        style: Style,
+        binding: Option<&'a binding::Operation<'_>>,
+    ) -> Self {
+        let mut wrappers = vec![];
+        let mut types = vec![];
+        let mut body_type = |message: Option<&'a Message<'_>>, wrapper: BodyElement| {
+            message.map(|m| {
+                types.extend(m.parts().iter().filter_map(|p| {
+                    p.element().or_else(|| p.type_()).map(|t| expanded_name(p.node(), t))
+                }));
+                message_type(m, style, wrapper, &mut wrappers)
+            })
+        };
+        // Rpc wrappers are in the namespace of soap:body, qualified with the prefix the wsdl
+        // document uses for it.
+        let wrapper = |name: String, param: Option<&binding::Param<'_>>| {
+            let namespace = param.and_then(|p| p.body_namespace());
+            BodyElement {
+                namespace: namespace.map(str::to_string),
+                name,
+                prefix: namespace
+                    .map(|ns| definitions.node().lookup_prefix(ns).unwrap_or("ns").to_string()),
+            }
+        };
+        let binding_input = binding.and_then(|b| b.input());
+        let binding_output = binding.and_then(|b| b.output());
+        let request_wrapper = operation.name().to_string();
+        let response_wrapper = format!("{}Response", operation.name());
+
+        let (kind, input, output, input_param) = match operation.operation_type() {
+            RequestResponse { input, output, .. } => (
+                FunctionKind::RequestResponse,
+                body_type(
+                    definitions.get_message_by_param(input),
+                    wrapper(request_wrapper, binding_input),
+                ),
+                body_type(
+                    definitions.get_message_by_param(output),
+                    wrapper(response_wrapper, binding_output),
+                ),
+                Some(input),
+            ),
+            OneWay { input } => (
+                FunctionKind::OneWay,
+                body_type(
+                    definitions.get_message_by_param(input),
+                    wrapper(request_wrapper, binding_input),
+                ),
+                None,
+                Some(input),
+            ),
+            SolicitResponse { output, input, .. } => (
+                FunctionKind::SolicitResponse,
+                body_type(
+                    definitions.get_message_by_param(input),
+                    wrapper(response_wrapper, binding_input),
+                ),
+                body_type(
+                    definitions.get_message_by_param(output),
+                    wrapper(request_wrapper, binding_output),
+                ),
+                Some(input),
+            ),
+            Notification { output } => (
+                FunctionKind::Notification,
+                None,
+                body_type(
+                    definitions.get_message_by_param(output),
+                    wrapper(request_wrapper, binding_output),
+                ),
+                None,
+            ),
+        };
 
-impl<'a> Param<'a> {
-    pub fn new(part: &'a Part<'_>) -> Self {
-        Param {
-            name: part.name(),
-            typename: part
-                .element()
-                .or_else(|| part.type_())
-                .expect("Element or type must be presented in wsdl:part"),
+        Function {
+            kind,
+            input_element: input.as_ref().and_then(|(_, element)| element.clone()),
+            output_parts: output.as_ref().is_some_and(|(_, element)| element.is_none()),
+            input: input.map(|(type_name, _)| type_name),
+            output: output.map(|(type_name, _)| type_name),
+            action: input_param
+                .and_then(|p| p.action().or_else(|| binding.and_then(|b| b.soap_action()))),
+            any_input: false,
+            any_output: false,
+            documentation: operation.documentation(),
+            name: operation.name(),
+            wrappers,
+            types,
         }
     }
-}
 
-impl<'a> Function<'a> {
-    pub fn new(operation: &'a Operation<'_>, definitions: &'a Definitions<'_>) -> Self {
-        let input_message;
-        let mut output_message = None;
-        match operation.operation_type() {
-            RequestResponse { input, output, .. } => {
-                input_message = definitions.get_message_by_param(input);
-                output_message = definitions.get_message_by_param(output);
-            }
-            OneWay { input } => input_message = definitions.get_message_by_param(input),
-            _ => {
-                unreachable!("No present in ONVIF");
-            }
+    // WSDL 2.0 messages are always a single element, so there is nothing to wrap. Robust
+    // patterns are generated like their plain counterparts: transport::send reads the
+    // response of robust-in-only operations and returns a fault as Error::Fault, and we
+    // never answer the message of robust-out-only operations with a fault. The optional
+    // message of in-opt-out and out-opt-in is always expected and sent. Other patterns are
+    // taken by the messages they have.
+    pub fn from_interface_operation(
+        operation: &'a interface::Operation<'_>,
+        binding_action: Option<&'a str>,
+    ) -> Self {
+        let kind = match operation.pattern() {
+            Pattern::InOut | Pattern::InOptionalOut => FunctionKind::RequestResponse,
+            Pattern::InOnly | Pattern::RobustInOnly => FunctionKind::OneWay,
+            Pattern::OutIn | Pattern::OutOptionalIn => FunctionKind::SolicitResponse,
+            Pattern::OutOnly | Pattern::RobustOutOnly => FunctionKind::Notification,
+            Pattern::Other(_) => match (operation.input(), operation.output()) {
+                (Some(_), Some(_)) => FunctionKind::RequestResponse,
+                (None, Some(_)) => FunctionKind::Notification,
+                (_, None) => FunctionKind::OneWay,
+            },
         };
 
+        let types = operation
+            .input()
+            .into_iter()
+            .chain(operation.output())
+            .filter_map(|m| m.element().map(|e| expanded_name(m.node(), e)))
+            .collect();
+
         Function {
-            generic_params: vec![GENERIC_TRANSPORT],
-            arguments: get_input_params(input_message),
-            return_type: output_message.and_then(|m| m.parts()[0].element()).unwrap_or(""),
+            kind,
+            input: operation.input().and_then(|m| m.element()).map(Cow::Borrowed),
+            output: operation.output().and_then(|m| m.element()).map(Cow::Borrowed),
+            input_element: operation
+                .input()
+                .and_then(|m| m.element().map(|e| body_element(m.node(), e))),
+            output_parts: false,
+            action: operation.input().and_then(|m| m.action().or(binding_action)),
+            any_input: operation.input().is_some_and(|m| m.is_any()),
+            any_output: operation.output().is_some_and(|m| m.is_any()),
             documentation: operation.documentation(),
             name: operation.name(),
+            wrappers: vec![],
+            types,
         }
     }
 }
 
-fn get_input_params<'a>(input: Option<&'a Message<'_>>) -> Vec<Param<'a>> {
-    let mut result = vec![ARGUMENT_TRANSPORT];
-    if input.is_none() {
-        return result;
+// The QName as {namespace}name, so it can be matched with schemas that use another prefix.
+fn expanded_name(node: &Node<'_, '_>, qname: &str) -> String {
+    match resolve(node, qname) {
+        (Some(uri), name) => format!("{{{}}}{}", uri, name),
+        (None, _) => qname.to_string(),
     }
-    let input = input.unwrap();
+}
+
+// The global element named by the QName.
+fn body_element(node: &Node<'_, '_>, qname: &str) -> BodyElement {
+    let (namespace, name) = resolve(node, qname);
+    BodyElement { namespace: namespace.map(str::to_string), name: name.to_string(), prefix: None }
+}
 
-    if input.parts().len() == 1 {
-        // ONVIF case
-        result.push(Param { name: "request", typename: input.parts()[0].element().unwrap() })
-    } else {
-        result.append(&mut input.parts().iter().map(Param::new).collect());
+// The element of an element part, qualified with the prefix of its QName.
+fn part_element(node: &Node<'_, '_>, qname: &str) -> BodyElement {
+    let prefix = match qname.split_once(':') {
+        Some((prefix, _)) => prefix,
+        None => "ns",
     };
-    result
+    BodyElement { prefix: Some(prefix.to_string()), ..body_element(node, qname) }
+}
+
+fn resolve<'a>(node: &Node<'a, '_>, qname: &'a str) -> (Option<&'a str>, &'a str) {
+    let (prefix, name) = match qname.split_once(':') {
+        Some((prefix, name)) => (Some(prefix), name),
+        None => (None, qname),
+    };
+    (node.lookup_namespace_uri(prefix), name)
+}
+
+// Document style messages with a single element part are sent as that element.
+// Rpc style messages are wrapped into an element named after the operation (with
+// "Response" appended for the answer), which holds one accessor per part. Other
+// document style messages get a struct named after the message with a field per part.
+// Returns the type of the body and the element it is sent as, if any.
+fn message_type<'a>(
+    message: &'a Message<'_>,
+    style: Style,
+    rpc_wrapper: BodyElement,
+    wrappers: &mut Vec<Wrapper>,
+) -> (Cow<'a, str>, Option<BodyElement>) {
+    let parts = message.parts();
+    if style == Style::Document && parts.len() == 1 {
+        if let Some(element) = parts[0].element() {
+            return (Cow::Borrowed(element), Some(body_element(parts[0].node(), element)));
+        }
+    }
+
+    let (name, element) = match style {
+        Style::Rpc => (rpc_wrapper.name.clone(), Some(rpc_wrapper)),
+        Style::Document => (message.name().to_string(), None),
+    };
+
+    let elements = match style {
+        Style::Rpc => vec![],
+        Style::Document => parts
+            .iter()
+            .filter_map(|part| {
+                let element = part.element()?;
+                Some((part_field(part).name, part_element(part.node(), element)))
+            })
+            .collect(),
+    };
+    wrappers.push(Wrapper {
+        entity: Struct {
+            name: name.clone(),
+            fields: RefCell::new(parts.iter().map(part_field).collect()),
+            ..Default::default()
+        },
+        elements,
+    });
+
+    (Cow::Owned(name), element)
+}
+
+fn part_field(part: &Part<'_>) -> StructField {
+    // An element part is represented by the element itself, a type part by an accessor
+    // element named after the part.
+    let (name, type_name) = match (part.element(), part.type_()) {
+        (Some(element), _) => (element.split(':').next_back().unwrap(), element),
+        (None, Some(type_name)) => (part.name(), type_name),
+        (None, None) => panic!("Element or type must be presented in wsdl:part {}", part.name()),
+    };
+
+    StructField {
+        name: name.to_string(),
+        type_name: type_name.to_string(),
+        source: StructFieldSource::Element,
+        ..Default::default()
+    }
 }
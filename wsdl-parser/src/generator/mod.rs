@@ -1,28 +1,371 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashSet};
 
 use inflector::cases::{pascalcase::to_pascal_case, snakecase::to_snake_case};
-use roxmltree::Namespace;
+use roxmltree::{Document, Namespace};
+use xsd_parser::{
+    doc,
+    generator::{
+        builder::GeneratorBuilder,
+        struct_field::{wrap_modifiers, DefaultStructFieldGen, StructFieldGenerator},
+        Generator,
+    },
+    parser::types::{RsEntity, StructField},
+};
 
-use crate::{generator::function::Function, parser::definitions::Definitions};
+use crate::{
+    generator::function::{BodyElement, Function, FunctionKind},
+    loader::is_schema,
+    parser::{
+        definitions::Definitions,
+        port_type::OperationType::{Notification, OneWay, RequestResponse, SolicitResponse},
+        wsdl2::{description::Description, interface::MessageReference, is_wsdl2},
+    },
+};
 
 pub mod function;
 
 pub fn generate(definitions: &Definitions) -> String {
+    generate_operations(definitions, None)
+}
+
+// Generates the functions of the named operations, or of all operations for None.
+pub fn generate_operations(definitions: &Definitions, operations: Option<&[String]>) -> String {
+    let mut generated_wrappers = HashSet::new();
     let mut res = vec![];
 
-    for port_type in definitions.port_types().values() {
-        for op in port_type.operations() {
-            let func = Function::new(op, definitions);
-            res.push(generate_function(&func, definitions.target_namespace()));
+    for func in functions(definitions, operations) {
+        for wrapper in &func.wrappers {
+            // Document style messages may be shared between operations.
+            if generated_wrappers.insert(wrapper.entity.name.clone()) {
+                let gen = wrapper_generator(definitions, wrapper.elements.clone());
+                res.push(gen.generate(&RsEntity::Struct(wrapper.entity.clone())));
+            }
         }
+        res.push(generate_function(&func, definitions.target_namespace()));
     }
     res.join("")
 }
 
-const REQUEST_FUNC_BODY: &str = "transport::request(transport, request).await";
+pub fn generate_description(description: &Description) -> String {
+    generate_description_operations(description, None)
+}
+
+pub fn generate_description_operations(
+    description: &Description,
+    operations: Option<&[String]>,
+) -> String {
+    description_functions(description, operations)
+        .iter()
+        .map(|func| generate_function(func, description.target_namespace()))
+        .collect()
+}
+
+// The wsdl documents of `docs`, as loaded by crate::loader::load, merged into the WSDL 1.1
+// definitions and the WSDL 2.0 description of the first document of each version. Schema
+// documents are skipped.
+pub fn merge_documents<'a, 'input: 'a>(
+    docs: impl IntoIterator<Item = &'a Document<'input>>,
+) -> (Option<Definitions<'a>>, Option<Description<'a>>) {
+    let roots = docs.into_iter().map(|doc| doc.root_element()).filter(|root| !is_schema(root));
+    let (wsdl2, wsdl11): (Vec<_>, Vec<_>) = roots.partition(is_wsdl2);
+
+    let mut definitions = wsdl11.iter().map(Definitions::new);
+    let definitions = definitions.next().map(|mut root| {
+        definitions.for_each(|imported| root.merge(imported));
+        root
+    });
+    let mut descriptions = wsdl2.iter().map(Description::new);
+    let description = descriptions.next().map(|mut root| {
+        descriptions.for_each(|imported| root.merge(imported));
+        root
+    });
+    (definitions, description)
+}
+
+// Generates the functions of the wsdl documents of `docs`, see merge_documents.
+pub fn generate_documents<'a, 'input: 'a>(
+    docs: impl IntoIterator<Item = &'a Document<'input>>,
+) -> String {
+    generate_documents_operations(docs, None)
+}
+
+pub fn generate_documents_operations<'a, 'input: 'a>(
+    docs: impl IntoIterator<Item = &'a Document<'input>>,
+    operations: Option<&[String]>,
+) -> String {
+    let (definitions, description) = merge_documents(docs);
+    let mut code = String::new();
+    if let Some(definitions) = &definitions {
+        code.push_str(&generate_operations(definitions, operations));
+    }
+    if let Some(description) = &description {
+        code.push_str(&generate_description_operations(description, operations));
+    }
+    code
+}
+
+// Elements and types the named operations send and receive as {namespace}name, the roots to
+// keep the schema types they need with xsd_parser::parser::reachable::retain_reachable.
+pub fn operation_types(definitions: &Definitions, operations: &[String]) -> Vec<String> {
+    functions(definitions, Some(operations)).into_iter().flat_map(|f| f.types).collect()
+}
+
+pub fn description_operation_types(
+    description: &Description,
+    operations: &[String],
+) -> Vec<String> {
+    description_functions(description, Some(operations)).into_iter().flat_map(|f| f.types).collect()
+}
+
+// Operations of all port types to document with the schemas by xsd_parser::doc, messages
+// are given by the elements or types of their parts.
+pub fn operation_docs(definitions: &Definitions) -> Vec<doc::Operation> {
+    let parts = |message: &str| {
+        let message = definitions.messages().get(message.split(':').next_back().unwrap());
+        message
+            .map(|m| m.parts().iter().filter_map(|p| p.element().or_else(|| p.type_())))
+            .into_iter()
+            .flatten()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    let mut port_types = definitions.port_types().values().collect::<Vec<_>>();
+    port_types.sort_by_key(|port_type| port_type.name());
+    let mut res = vec![];
+    for port_type in port_types {
+        for op in port_type.operations() {
+            let (input, output, faults) = match op.operation_type() {
+                RequestResponse { input, output, faults }
+                | SolicitResponse { input, output, faults } => {
+                    (Some(input), Some(output), faults.as_slice())
+                }
+                OneWay { input } => (Some(input), None, &[][..]),
+                Notification { output } => (None, Some(output), &[][..]),
+            };
+            res.push(doc::Operation {
+                interface: port_type.name().to_string(),
+                name: op.name().to_string(),
+                documentation: op.documentation().map(str::to_string),
+                input: input.map(|p| parts(p.message())).unwrap_or_default(),
+                output: output.map(|p| parts(p.message())).unwrap_or_default(),
+                faults: faults.iter().flat_map(|f| parts(f.message())).collect(),
+            });
+        }
+    }
+    res
+}
+
+pub fn description_operation_docs(description: &Description) -> Vec<doc::Operation> {
+    let mut interfaces = description.interfaces().values().collect::<Vec<_>>();
+    interfaces.sort_by_key(|interface| interface.name());
+    let mut res = vec![];
+    for interface in interfaces {
+        // Fault references name an interface fault, which gives the element.
+        let fault_element = |name: &str| {
+            let name = name.rsplit(':').next().unwrap();
+            interface.faults().iter().find(|f| f.name() == name).and_then(|f| f.element())
+        };
+        for op in interface.operations() {
+            let element = |m: Option<&MessageReference>| {
+                m.and_then(|m| m.element()).map(str::to_string).into_iter().collect()
+            };
+            res.push(doc::Operation {
+                interface: interface.name().to_string(),
+                name: op.name().to_string(),
+                documentation: op.documentation().map(str::to_string),
+                input: element(op.input()),
+                output: element(op.output()),
+                faults: op
+                    .faults()
+                    .iter()
+                    .filter_map(|f| fault_element(f.ref_()))
+                    .map(str::to_string)
+                    .collect(),
+            });
+        }
+    }
+    res
+}
+
+fn functions<'a>(
+    definitions: &'a Definitions<'_>,
+    operations: Option<&[String]>,
+) -> Vec<Function<'a>> {
+    // Sorted by name, the port types are kept in a HashMap and the generated code must not
+    // change between runs.
+    let mut port_types = definitions.port_types().values().collect::<Vec<_>>();
+    port_types.sort_by_key(|port_type| port_type.name());
+    let mut res = vec![];
+    for port_type in port_types {
+        for op in port_type.operations() {
+            if operations.is_none_or(|names| names.iter().any(|n| n == op.name())) {
+                let style = definitions.operation_style(port_type, op.name());
+                let binding = definitions.binding_operation(port_type, op.name());
+                res.push(Function::new(op, definitions, style, binding));
+            }
+        }
+    }
+    res
+}
+
+fn description_functions<'a>(
+    description: &'a Description<'_>,
+    operations: Option<&[String]>,
+) -> Vec<Function<'a>> {
+    let mut interfaces = description.interfaces().values().collect::<Vec<_>>();
+    interfaces.sort_by_key(|interface| interface.name());
+    let mut res = vec![];
+    for interface in interfaces {
+        let binding = description
+            .bindings()
+            .values()
+            .filter(|b| b.interface().and_then(|i| i.rsplit(':').next()) == Some(interface.name()))
+            .min_by_key(|b| b.name());
+        for op in interface.operations() {
+            if operations.is_none_or(|names| names.iter().any(|n| n == op.name())) {
+                let action = binding.and_then(|b| b.operation_action(op.name()));
+                res.push(Function::from_interface_operation(op, action));
+            }
+        }
+    }
+    res
+}
+
+// Wrapper structs reference part types by the prefixes declared in the wsdl document.
+fn wrapper_generator<'a>(
+    definitions: &'a Definitions<'_>,
+    elements: Vec<(String, BodyElement)>,
+) -> Generator<'a> {
+    let gen = GeneratorBuilder::default()
+        .with_struct_field_gen(Box::new(PartFieldGen { elements }))
+        .build();
+    *gen.target_ns.borrow_mut() = definitions.target_namespace().cloned();
+    *gen.xsd_ns.borrow_mut() = definitions
+        .node()
+        .namespaces()
+        .filter(|ns| ns.uri() == XSD_NAMESPACE)
+        .min_by_key(|ns| ns.name().is_none())
+        .cloned();
+    gen
+}
+
+// Writes the fields of element parts as their qualified element, other fields as usual.
+struct PartFieldGen {
+    elements: Vec<(String, BodyElement)>,
+}
+
+impl StructFieldGenerator for PartFieldGen {
+    fn serialize(&self, field: &StructField, gen: &Generator) -> String {
+        match self.elements.iter().find(|(name, _)| *name == field.name) {
+            Some((_, element)) => format!(
+                "let value = &self.{};\n{}\n",
+                self.get_name(field, gen),
+                wrap_modifiers(
+                    &format!(
+                        "ser::XmlSerialize::xml_serialize(&{}, writer)?;",
+                        element_expression(element, "value")
+                    ),
+                    &field.type_modifiers,
+                )
+            ),
+            None => DefaultStructFieldGen.serialize(field, gen),
+        }
+    }
+}
+
+// `content` wrapped into the element.
+fn element_expression(element: &BodyElement, content: &str) -> String {
+    match (&element.prefix, &element.namespace) {
+        (Some(prefix), Some(namespace)) => format!(
+            "ser::Element::prefixed({:?}, {:?}, {:?}, {})",
+            prefix, namespace, element.name, content
+        ),
+        _ => format!(
+            "ser::Element::new({:?}, {:?}, {})",
+            element.namespace.as_deref(),
+            element.name,
+            content
+        ),
+    }
+}
 
 fn generate_function(func: &Function<'_>, target_ns: Option<&Namespace>) -> String {
-    let ftype = |t| default_format_type(t, target_ns);
+    let ftype = |t: Option<&str>| match t {
+        Some(t) => default_format_type(t, target_ns),
+        None => Cow::Borrowed("()"),
+    };
+    let mut transport = format!("{}: {}", ARGUMENT_TRANSPORT, GENERIC_TRANSPORT);
+    // Any element is sent as R and received as S, the caller picks the types.
+    let input = if func.any_input {
+        transport.push_str(", R: ser::XmlSerialize");
+        Cow::Borrowed("R")
+    } else {
+        ftype(func.input.as_deref())
+    };
+    let output = if func.any_output {
+        transport.push_str(", S: XmlDeserialize");
+        Cow::Borrowed("S")
+    } else {
+        ftype(func.output.as_deref())
+    };
+
+    // The input is sent wrapped into the element it is declared as.
+    let element = func
+        .input_element
+        .as_ref()
+        .map(|element| move |content: &str| element_expression(element, content));
+    let request = match &element {
+        Some(element) => format!("&{}", element("request")),
+        None => "request".to_string(),
+    };
+    // The parts of a multi-part output are read from the Body into its struct.
+    let received = if func.output_parts { "transport::Parts(request)" } else { "request" };
+    let respond = match &element {
+        Some(element) => format!("|{}| {}", received, element("respond(request)")),
+        None if func.output_parts => format!("|{}| respond(request)", received),
+        None => "respond".to_string(),
+    };
+    let parts = |call: String| {
+        if func.output_parts {
+            format!("let transport::Parts(response) = {}?;\n    Ok(response)", call)
+        } else {
+            call
+        }
+    };
+
+    let action = format!("{:?}", func.action);
+
+    let (generics, arguments, return_type, body) = match func.kind {
+        FunctionKind::RequestResponse => (
+            transport,
+            format!("transport: &T,\n    request: &{}", input),
+            output,
+            parts(format!("transport::request(transport, {}, {}).await", action, request)),
+        ),
+        FunctionKind::OneWay => (
+            transport,
+            format!("transport: &T,\n    request: &{}", input),
+            Cow::Borrowed("()"),
+            format!("transport::send(transport, {}, {}).await", action, request),
+        ),
+        FunctionKind::Notification => (
+            transport,
+            "transport: &T".to_string(),
+            output,
+            parts("transport::receive(transport).await".to_string()),
+        ),
+        FunctionKind::SolicitResponse => (
+            match input.as_ref() {
+                "()" => format!("{}, F: FnOnce({})", transport, output),
+                _ => format!("{}, F: FnOnce({}) -> {}", transport, output, input),
+            },
+            "transport: &T,\n    respond: F".to_string(),
+            Cow::Borrowed("()"),
+            format!("transport::solicit(transport, {}, {}).await", action, respond),
+        ),
+    };
+
     format!(
         r#"
 {comment}pub async fn {name}<{generics}>(
@@ -33,23 +376,17 @@ fn generate_function(func: &Function<'_>, target_ns: Option<&Namespace>) -> Stri
 "#,
         comment = default_format_comment(func.documentation, 80, 0),
         name = default_format_name(func.name),
-        generics = func
-            .generic_params
-            .iter()
-            .map(|p| format!("{}: {}", p.name, ftype(p.typename)))
-            .collect::<Vec<String>>()
-            .join(", "),
-        arguments = func
-            .arguments
-            .iter()
-            .map(|p| format!("{}: &{}", p.name, ftype(p.typename)))
-            .collect::<Vec<String>>()
-            .join(",\n    "),
-        return_type = ftype(func.return_type),
-        body = REQUEST_FUNC_BODY
+        generics = generics,
+        arguments = arguments,
+        return_type = return_type,
+        body = body
     )
 }
 
+const ARGUMENT_TRANSPORT: &str = "T";
+const GENERIC_TRANSPORT: &str = "transport::Transport";
+const XSD_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema";
+
 fn split_comment_line(s: &str, max_len: usize, indent: usize) -> String {
     let indent_str = " ".repeat(indent);
 
@@ -101,7 +438,7 @@ fn default_format_type(type_name: &str, target_ns: Option<&Namespace>) -> Cow<'s
 }
 
 pub fn default_format_name(name: &str) -> String {
-    sanitize(to_snake_case(name.split(':').last().unwrap()))
+    sanitize(to_snake_case(name.split(':').next_back().unwrap()))
 }
 
 fn split_name(name: &str) -> (Option<&str>, &str) {
@@ -1,2 +1,3 @@
 pub mod generator;
+pub mod loader;
 pub mod parser;
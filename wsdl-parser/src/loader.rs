@@ -0,0 +1,56 @@
+use std::path::Path;
+
+use roxmltree::{Document, Node};
+use xsd_parser::loader::{schema_locations, LoadError, Loader, SourceFile};
+
+use crate::parser::{constants::attribute, types::Types, ElementType, WsdlElement};
+
+// Loads a wsdl document with everything it references: imported (and for WSDL 2.0
+// included) wsdl documents and xsd files imported by wsdl:import or by the schemas in
+// wsdl:types. The root document comes first.
+pub fn load(path: &Path) -> Result<Vec<SourceFile>, LoadError> {
+    let mut loader = Loader::default();
+    loader.load_with(path, &|doc| references(&doc.root_element()))?;
+    Ok(loader.into_files())
+}
+
+fn references(root: &Node) -> Vec<String> {
+    if is_schema(root) {
+        return schema_locations(root);
+    }
+
+    root.children()
+        .filter(|n| n.is_element())
+        .flat_map(|n| match n.wsdl_type() {
+            ElementType::Import | ElementType::Include => {
+                n.attribute(attribute::LOCATION).map(str::to_string).into_iter().collect()
+            }
+            ElementType::Types => {
+                Types::new(&n).schemas().iter().flat_map(schema_locations).collect()
+            }
+            _ => vec![],
+        })
+        .collect()
+}
+
+pub fn is_schema(root: &Node) -> bool {
+    root.tag_name().name() == "schema"
+}
+
+// Top level schemas of a loaded document: the document itself for xsd files, the
+// content of wsdl:types for wsdl documents.
+pub fn schemas<'a, 'input>(doc: &'a Document<'input>) -> Vec<Node<'a, 'input>> {
+    let root = doc.root_element();
+    if is_schema(&root) {
+        return vec![root];
+    }
+
+    root.children()
+        .filter(|n| n.is_element() && n.wsdl_type() == ElementType::Types)
+        .flat_map(|n| {
+            n.children()
+                .filter(|n| n.is_element() && n.tag_name().name() == "schema")
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
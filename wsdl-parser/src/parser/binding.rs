@@ -17,6 +17,24 @@ impl<'a> Binding<'a> {
         self.node.attribute(attribute::TYPE).expect("Location required for wsdl:binding")
     }
 
+    // Namespace and local name of the port type the binding is for.
+    pub fn port_type(&self) -> (Option<&'a str>, &'a str) {
+        let (prefix, name) = match self.type_().split_once(':') {
+            Some((prefix, name)) => (Some(prefix), name),
+            None => (None, self.type_()),
+        };
+        (self.node.lookup_namespace_uri(prefix), name)
+    }
+
+    // soap:binding/@style, the default for all operations of the binding.
+    pub fn style(&self) -> Option<Style> {
+        soap_style(&self.node, "binding")
+    }
+
+    pub fn operations(&self) -> &[Operation<'a>] {
+        self.operations.as_ref()
+    }
+
     pub fn new(node: &Node<'a, '_>) -> Self {
         Self {
             node: *node,
@@ -47,6 +65,28 @@ impl<'a> Operation<'a> {
         self.node.attribute(attribute::NAME).expect("Namespace required for wsdl:binding")
     }
 
+    // soap:operation/@style, overrides the style of the binding.
+    pub fn style(&self) -> Option<Style> {
+        soap_style(&self.node, "operation")
+    }
+
+    pub fn input(&self) -> Option<&Param<'a>> {
+        self.input.as_ref()
+    }
+
+    pub fn output(&self) -> Option<&Param<'a>> {
+        self.output.as_ref()
+    }
+
+    // soap:operation/@soapAction, None if it is missing or empty.
+    pub fn soap_action(&self) -> Option<&'a str> {
+        self.node
+            .children()
+            .filter(|n| n.is_element() && n.tag_name().name() == "operation")
+            .find_map(|n| n.attribute(attribute::SOAP_ACTION))
+            .filter(|action| !action.is_empty())
+    }
+
     pub fn new(node: &Node<'a, '_>) -> Self {
         let mut input = None;
         let mut output = None;
@@ -76,4 +116,28 @@ impl<'a> Param<'a> {
     pub fn name(&self) -> Option<&'a str> {
         self.node.attribute(attribute::NAME)
     }
+
+    // soap:body/@namespace, the namespace of rpc wrapper elements.
+    pub fn body_namespace(&self) -> Option<&'a str> {
+        self.node
+            .children()
+            .filter(|n| n.is_element() && n.tag_name().name() == "body")
+            .find_map(|n| n.attribute(attribute::NAMESPACE))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Style {
+    #[default]
+    Document,
+    Rpc,
+}
+
+// Looks for the style attribute on a soap:binding or soap:operation extension element.
+// Both SOAP 1.1 and 1.2 bindings use the same local names.
+fn soap_style(node: &Node, name: &str) -> Option<Style> {
+    node.children()
+        .filter(|n| n.is_element() && n.tag_name().name() == name)
+        .find_map(|n| n.attribute(attribute::STYLE))
+        .map(|style| if style == "rpc" { Style::Rpc } else { Style::Document })
 }
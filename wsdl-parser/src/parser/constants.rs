@@ -7,4 +7,42 @@ pub mod attribute {
     pub const ELEMENT: &str = "element";
     pub const PARAMETER_ORDER: &str = "parameterOrder";
     pub const MESSAGE: &str = "message";
+    pub const STYLE: &str = "style";
+    pub const INTERFACE: &str = "interface";
+    pub const PATTERN: &str = "pattern";
+    pub const EXTENDS: &str = "extends";
+    pub const BINDING: &str = "binding";
+    pub const ADDRESS: &str = "address";
+    pub const REF: &str = "ref";
+    pub const MESSAGE_LABEL: &str = "messageLabel";
+    pub const SOAP_ACTION: &str = "soapAction";
+}
+
+pub const WSDL2_NAMESPACE: &str = "http://www.w3.org/ns/wsdl";
+pub const WSOAP_NAMESPACE: &str = "http://www.w3.org/ns/wsdl/soap";
+
+// Namespaces of the Action attribute of wsdl:input and wsdl:output: WS-Addressing Metadata
+// and the older WS-Addressing WSDL Binding.
+pub const ACTION_NAMESPACES: &[&str] =
+    &["http://www.w3.org/2007/05/addressing/metadata", "http://www.w3.org/2006/05/addressing/wsdl"];
+
+// The wsam:Action attribute of a message reference.
+pub fn action<'a>(node: &roxmltree::Node<'a, '_>) -> Option<&'a str> {
+    node.attributes()
+        .find(|a| {
+            a.name() == "Action" && a.namespace().is_some_and(|ns| ACTION_NAMESPACES.contains(&ns))
+        })
+        .map(|a| a.value())
+}
+
+// Message exchange patterns of WSDL 2.0 (Part 2: Adjuncts, section 2).
+pub mod pattern {
+    pub const IN_ONLY: &str = "http://www.w3.org/ns/wsdl/in-only";
+    pub const ROBUST_IN_ONLY: &str = "http://www.w3.org/ns/wsdl/robust-in-only";
+    pub const IN_OUT: &str = "http://www.w3.org/ns/wsdl/in-out";
+    pub const IN_OPTIONAL_OUT: &str = "http://www.w3.org/ns/wsdl/in-opt-out";
+    pub const OUT_ONLY: &str = "http://www.w3.org/ns/wsdl/out-only";
+    pub const ROBUST_OUT_ONLY: &str = "http://www.w3.org/ns/wsdl/robust-out-only";
+    pub const OUT_IN: &str = "http://www.w3.org/ns/wsdl/out-in";
+    pub const OUT_OPTIONAL_IN: &str = "http://www.w3.org/ns/wsdl/out-opt-in";
 }
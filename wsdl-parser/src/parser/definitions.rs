@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use roxmltree::{Namespace, Node};
 
 use crate::parser::{
-    binding::Binding,
+    binding::{Binding, Operation as BindingOperation, Style},
     constants::attribute,
     message::Message,
     port_type::{Param, PortType},
@@ -77,8 +77,41 @@ impl<'a> Definitions<'a> {
         &self.messages
     }
 
+    pub fn bindings(&self) -> &HashMap<&'a str, Binding<'a>> {
+        &self.bindings
+    }
+
+    // The binding of the port type, the first by name if there are several (e.g. for SOAP 1.1
+    // and 1.2). Its type is matched with the port type by namespace and local name.
+    pub fn binding(&self, port_type: &PortType<'_>) -> Option<&Binding<'a>> {
+        self.bindings
+            .values()
+            .filter(|b| b.port_type() == (port_type.target_namespace(), port_type.name()))
+            .min_by_key(|b| b.name())
+    }
+
+    // The operation in the binding of the port type.
+    pub fn binding_operation(
+        &self,
+        port_type: &PortType<'_>,
+        operation: &str,
+    ) -> Option<&BindingOperation<'a>> {
+        self.binding(port_type)?.operations().iter().find(|op| op.name() == operation)
+    }
+
+    // Style of the operation in the binding of the port type, document if there is none.
+    pub fn operation_style(&self, port_type: &PortType<'_>, operation: &str) -> Style {
+        self.binding(port_type)
+            .and_then(|b| {
+                self.binding_operation(port_type, operation)
+                    .and_then(|op| op.style())
+                    .or_else(|| b.style())
+            })
+            .unwrap_or_default()
+    }
+
     pub fn get_message_by_param(&self, param: &Param<'_>) -> Option<&Message> {
-        self.messages.get(param.message().split(':').last().unwrap())
+        self.messages.get(param.message().split(':').next_back().unwrap())
     }
 
     pub fn new(definitions: &Node<'a, '_>) -> Self {
@@ -103,6 +136,23 @@ impl<'a> Definitions<'a> {
         res
     }
 
+    // Adds the content of an imported wsdl document. Entries of `self` win on name clashes.
+    pub fn merge(&mut self, imported: Definitions<'a>) {
+        for (name, import) in imported.imports {
+            self.imports.entry(name).or_insert(import);
+        }
+        for (name, message) in imported.messages {
+            self.messages.entry(name).or_insert(message);
+        }
+        for (name, port_type) in imported.port_types {
+            self.port_types.entry(name).or_insert(port_type);
+        }
+        for (name, binding) in imported.bindings {
+            self.bindings.entry(name).or_insert(binding);
+        }
+        self.types.extend(imported.types);
+    }
+
     fn add_import(&mut self, node: &Node<'a, '_>) {
         let import = Import::new(node);
         assert!(
@@ -93,4 +93,8 @@ impl<'a> Part<'a> {
     pub fn new(node: &Node<'a, '_>) -> Self {
         Self { node: *node }
     }
+
+    pub fn node(&self) -> &Node<'a, 'a> {
+        &self.node
+    }
 }
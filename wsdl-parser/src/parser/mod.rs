@@ -1,6 +1,6 @@
 #[allow(dead_code)]
 pub mod binding;
-mod constants;
+pub(crate) mod constants;
 #[allow(dead_code)]
 pub mod definitions;
 #[allow(dead_code)]
@@ -9,6 +9,7 @@ pub mod message;
 pub mod port_type;
 #[allow(dead_code)]
 pub mod types;
+pub mod wsdl2;
 
 pub trait WsdlElement {
     fn wsdl_type(&self) -> ElementType;
@@ -18,15 +19,22 @@ pub trait WsdlElement {
 pub enum ElementType {
     Binding,
     Definitions,
+    Description,
     Documentation,
+    Endpoint,
     Import,
+    Include,
+    InFault,
     Input,
+    Interface,
     Fault,
     Message,
     Operation,
+    OutFault,
     Output,
     Part,
     PortType,
+    Service,
     Types,
     UnknownElement(String),
 }
@@ -38,15 +46,22 @@ impl<'a> WsdlElement for roxmltree::Node<'a, '_> {
         match self.tag_name().name() {
             "binding" => Binding,
             "definitions" => Definitions,
+            "description" => Description,
             "documentation" => Documentation,
+            "endpoint" => Endpoint,
             "import" => Import,
+            "include" => Include,
+            "infault" => InFault,
             "input" => Input,
+            "interface" => Interface,
             "fault" => Fault,
             "message" => Message,
             "operation" => Operation,
+            "outfault" => OutFault,
             "output" => Output,
             "part" => Part,
             "portType" => PortType,
+            "service" => Service,
             "types" => Types,
             _ => UnknownElement(self.tag_name().name().to_string()),
         }
@@ -57,7 +72,7 @@ impl<'a> WsdlElement for roxmltree::Node<'a, '_> {
 mod test {
     use roxmltree::Document;
 
-    use crate::parser::definitions::Definitions;
+    use crate::parser::{binding::Style, definitions::Definitions};
 
     const TEXT: &str = r#"
 <wsdl:definitions
@@ -126,6 +141,10 @@ mod test {
 			</wsdl:output>
 		</wsdl:operation>
 	</wsdl:binding>
+
+	<wsdl:binding name="AnotherDeviceBinding" type="other:Device" xmlns:other="urn:other">
+		<soap:binding style="rpc" transport="http://schemas.xmlsoap.org/soap/http"/>
+	</wsdl:binding>
 </wsdl:definitions>
     "#;
 
@@ -148,4 +167,19 @@ mod test {
         assert_eq!(def.imports().len(), 0);
         assert_eq!(def.port_types().len(), 1);
     }
+
+    #[test]
+    fn test_binding_of_port_type() {
+        let doc = Document::parse(TEXT).unwrap();
+        let def = Definitions::new(&doc.root_element());
+        let port_type = def.port_types().get("Device").unwrap();
+
+        // AnotherDeviceBinding comes first by name, but is for a port type of another namespace.
+        assert_eq!(def.binding(port_type).unwrap().name(), "DeviceBinding");
+        assert_eq!(def.operation_style(port_type, "GetServices"), Style::Document);
+        assert_eq!(
+            def.binding_operation(port_type, "GetServices").unwrap().soap_action(),
+            Some("http://www.onvif.org/ver10/device/wsdl/GetServices")
+        );
+    }
 }
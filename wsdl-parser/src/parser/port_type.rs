@@ -1,6 +1,9 @@
 use roxmltree::Node;
 
-use crate::parser::{constants::attribute, ElementType, WsdlElement};
+use crate::parser::{
+    constants::{self, attribute},
+    ElementType, WsdlElement,
+};
 
 // Content: Sequence [1..1]
 // wsdl:documentation   [0..1] from type wsdl:tDocumented
@@ -40,6 +43,11 @@ impl<'a> PortType<'a> {
         self.node.attribute(attribute::NAME).expect("Name required for wsdl:portType")
     }
 
+    // targetNamespace of the document declaring the port type, which may be an import.
+    pub fn target_namespace(&self) -> Option<&'a str> {
+        self.node.document().root_element().attribute(attribute::TARGET_NAMESPACE)
+    }
+
     pub fn operations(&self) -> &[Operation] {
         self.operations.as_ref()
     }
@@ -191,6 +199,11 @@ impl<'a> Param<'a> {
             .attribute(attribute::MESSAGE)
             .expect("Message required for wsdl:input and wsdl:output")
     }
+
+    // wsam:Action of the message.
+    pub fn action(&self) -> Option<&'a str> {
+        constants::action(&self.node)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -0,0 +1,53 @@
+use roxmltree::Node;
+
+use crate::parser::constants::{attribute, WSOAP_NAMESPACE};
+
+// Element information
+// Namespace: http://www.w3.org/ns/wsdl
+// Type: wsdl:BindingType
+//
+// Attributes:
+// name	        [1..1]	xsd:NCName
+// type	        [1..1]	xsd:anyURI, e.g. http://www.w3.org/ns/wsdl/soap
+// interface	[0..1]	xsd:QName
+//
+// Operations of the binding only carry protocol details, the message content is taken
+// from the interface.
+#[derive(Clone, Debug)]
+pub struct Binding<'a> {
+    node: Node<'a, 'a>,
+}
+
+impl<'a> Binding<'a> {
+    pub fn new(node: &Node<'a, '_>) -> Self {
+        Self { node: *node }
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.node.attribute(attribute::NAME).expect("Name required for wsdl:binding")
+    }
+
+    pub fn type_(&self) -> &'a str {
+        self.node.attribute(attribute::TYPE).expect("Type required for wsdl:binding")
+    }
+
+    pub fn interface(&self) -> Option<&'a str> {
+        self.node.attribute(attribute::INTERFACE)
+    }
+
+    // wsoap:action of the binding operation referring to the interface operation by name.
+    pub fn operation_action(&self, operation: &str) -> Option<&'a str> {
+        self.node
+            .children()
+            .filter(|n| n.is_element() && n.tag_name().name() == "operation")
+            .filter(|n| {
+                n.attribute(attribute::REF).is_some_and(|r| r.rsplit(':').next() == Some(operation))
+            })
+            .find_map(|n| n.attribute((WSOAP_NAMESPACE, "action")))
+    }
+
+    // wsoap:version, "1.2" if omitted.
+    pub fn soap_version(&self) -> &'a str {
+        self.node.attributes().find(|a| a.name() == "version").map_or("1.2", |a| a.value())
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::{HashMap, HashSet};
+
+use roxmltree::{Namespace, Node};
+
+use crate::parser::{
+    constants::attribute,
+    types::Types,
+    wsdl2::{
+        binding::Binding,
+        interface::{Interface, Operation},
+        service::Service,
+    },
+    ElementType, WsdlElement,
+};
+
+// Element information
+// Namespace: http://www.w3.org/ns/wsdl
+// Schema document: wsdl20.xsd
+// Type: wsdl:DescriptionType
+//
+// Content: Sequence [1..1]
+//      wsdl:documentation [0..*]
+//      Choice [0..*]
+//          wsdl:import
+//          wsdl:include
+//      wsdl:types [0..1]
+//      Choice [0..*]
+//          wsdl:interface
+//          wsdl:binding
+//          wsdl:service
+//
+// Attributes:
+// targetNamespace	[1..1]	xsd:anyURI
+#[derive(Debug)]
+pub struct Description<'a> {
+    node: Node<'a, 'a>,
+    imports: Vec<Import<'a>>,
+    types: Vec<Types<'a>>,
+    interfaces: HashMap<&'a str, Interface<'a>>,
+    bindings: HashMap<&'a str, Binding<'a>>,
+    services: HashMap<&'a str, Service<'a>>,
+}
+
+impl<'a> Description<'a> {
+    pub fn new(description: &Node<'a, '_>) -> Self {
+        let mut res = Self {
+            node: *description,
+            imports: vec![],
+            types: vec![],
+            interfaces: HashMap::new(),
+            bindings: HashMap::new(),
+            services: HashMap::new(),
+        };
+        for ch in description.children().filter(|n| n.is_element()) {
+            match ch.wsdl_type() {
+                ElementType::Import => res.imports.push(Import::new(&ch)),
+                ElementType::Types => res.types.push(Types::new(&ch)),
+                ElementType::Interface => res.add_interface(&ch),
+                ElementType::Binding => res.add_binding(&ch),
+                ElementType::Service => res.add_service(&ch),
+                _ => {}
+            }
+        }
+        res
+    }
+
+    pub fn target_namespace(&self) -> Option<&'a Namespace<'_>> {
+        match self.node().attribute(attribute::TARGET_NAMESPACE) {
+            Some(tn) => self.node().namespaces().find(|a| a.uri() == tn),
+            None => None,
+        }
+    }
+
+    pub fn node(&self) -> &Node<'_, '_> {
+        &self.node
+    }
+
+    pub fn imports(&self) -> &[Import<'a>] {
+        self.imports.as_ref()
+    }
+
+    pub fn types(&self) -> &[Types<'a>] {
+        self.types.as_ref()
+    }
+
+    pub fn interfaces(&self) -> &HashMap<&'a str, Interface<'a>> {
+        &self.interfaces
+    }
+
+    pub fn bindings(&self) -> &HashMap<&'a str, Binding<'a>> {
+        &self.bindings
+    }
+
+    pub fn services(&self) -> &HashMap<&'a str, Service<'a>> {
+        &self.services
+    }
+
+    // Looks up an interface by its QName, the prefix is ignored.
+    pub fn interface(&self, name: &str) -> Option<&Interface<'a>> {
+        self.interfaces.get(name.rsplit(':').next().unwrap())
+    }
+
+    // Operations of the interface followed by the operations of the interfaces it
+    // extends, directly or indirectly. Each interface is visited once.
+    pub fn all_operations(&self, interface: &'a Interface<'a>) -> Vec<&Operation<'a>> {
+        let mut visited = HashSet::new();
+        let mut pending = vec![interface];
+        let mut res = vec![];
+        while let Some(interface) = pending.pop() {
+            if !visited.insert(interface.name()) {
+                continue;
+            }
+            res.extend(interface.operations());
+            pending.extend(interface.extends().iter().rev().filter_map(|n| self.interface(n)));
+        }
+        res
+    }
+
+    // Adds the content of an imported or included description. Entries of `self` win
+    // on name clashes.
+    pub fn merge(&mut self, imported: Description<'a>) {
+        for (name, interface) in imported.interfaces {
+            self.interfaces.entry(name).or_insert(interface);
+        }
+        for (name, binding) in imported.bindings {
+            self.bindings.entry(name).or_insert(binding);
+        }
+        for (name, service) in imported.services {
+            self.services.entry(name).or_insert(service);
+        }
+        self.imports.extend(imported.imports);
+        self.types.extend(imported.types);
+    }
+
+    fn add_interface(&mut self, node: &Node<'a, '_>) {
+        let interface = Interface::new(node);
+        assert!(
+            self.interfaces.insert(interface.name(), interface).is_none(),
+            "interface name must be unique"
+        );
+    }
+
+    fn add_binding(&mut self, node: &Node<'a, '_>) {
+        let binding = Binding::new(node);
+        assert!(
+            self.bindings.insert(binding.name(), binding).is_none(),
+            "binding name must be unique"
+        );
+    }
+
+    fn add_service(&mut self, node: &Node<'a, '_>) {
+        let service = Service::new(node);
+        assert!(
+            self.services.insert(service.name(), service).is_none(),
+            "service name must be unique"
+        );
+    }
+}
+
+// Unlike WSDL 1.1, the location of a WSDL 2.0 import is only a hint and may be omitted.
+#[derive(Clone, Debug)]
+pub struct Import<'a> {
+    node: Node<'a, 'a>,
+}
+
+impl<'a> Import<'a> {
+    pub fn new(node: &Node<'a, '_>) -> Self {
+        Self { node: *node }
+    }
+
+    pub fn namespace(&self) -> &'a str {
+        self.node.attribute(attribute::NAMESPACE).expect("Namespace required for wsdl:import")
+    }
+
+    pub fn location(&self) -> Option<&'a str> {
+        self.node.attribute(attribute::LOCATION)
+    }
+}
@@ -0,0 +1,252 @@
+use roxmltree::Node;
+
+use crate::parser::{
+    constants::{self, attribute, pattern},
+    ElementType, WsdlElement,
+};
+
+// Element information
+// Namespace: http://www.w3.org/ns/wsdl
+// Schema document: wsdl20.xsd
+// Type: wsdl:InterfaceType
+//
+// Content: Choice [0..*]
+//      wsdl:documentation
+//      wsdl:operation
+//      wsdl:fault
+//
+// Attributes:
+// name	            [1..1]	xsd:NCName
+// extends	        [0..1]	list of xsd:QName
+// styleDefault	    [0..1]	list of xsd:anyURI
+#[derive(Clone, Debug)]
+pub struct Interface<'a> {
+    node: Node<'a, 'a>,
+    operations: Vec<Operation<'a>>,
+    faults: Vec<Fault<'a>>,
+}
+
+impl<'a> Interface<'a> {
+    pub fn new(node: &Node<'a, '_>) -> Self {
+        let mut operations = vec![];
+        let mut faults = vec![];
+        for ch in node.children().filter(|n| n.is_element()) {
+            match ch.wsdl_type() {
+                ElementType::Operation => operations.push(Operation::new(&ch)),
+                ElementType::Fault => faults.push(Fault::new(&ch)),
+                _ => {}
+            }
+        }
+        Self { node: *node, operations, faults }
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.node.attribute(attribute::NAME).expect("Name required for wsdl:interface")
+    }
+
+    pub fn extends(&self) -> Vec<&'a str> {
+        self.node.attribute(attribute::EXTENDS).unwrap_or("").split_whitespace().collect()
+    }
+
+    pub fn operations(&self) -> &[Operation<'a>] {
+        self.operations.as_ref()
+    }
+
+    pub fn faults(&self) -> &[Fault<'a>] {
+        self.faults.as_ref()
+    }
+}
+
+// Element information
+// Namespace: http://www.w3.org/ns/wsdl
+// Type: wsdl:InterfaceOperationType
+//
+// Content: Choice [0..*]
+//      wsdl:documentation
+//      wsdl:input
+//      wsdl:output
+//      wsdl:infault
+//      wsdl:outfault
+//
+// Attributes:
+// name	    [1..1]	xsd:NCName
+// pattern	[0..1]	xsd:anyURI, in-out if omitted
+// safe	    [0..1]	xsd:boolean
+// style	[0..1]	list of xsd:anyURI
+#[derive(Clone, Debug)]
+pub struct Operation<'a> {
+    node: Node<'a, 'a>,
+    pattern: Pattern<'a>,
+    input: Option<MessageReference<'a>>,
+    output: Option<MessageReference<'a>>,
+    faults: Vec<FaultReference<'a>>,
+}
+
+impl<'a> Operation<'a> {
+    pub fn new(node: &Node<'a, '_>) -> Self {
+        let mut input = None;
+        let mut output = None;
+        let mut faults = vec![];
+        for ch in node.children().filter(|n| n.is_element()) {
+            match ch.wsdl_type() {
+                ElementType::Input => input = Some(MessageReference::new(&ch)),
+                ElementType::Output => output = Some(MessageReference::new(&ch)),
+                ElementType::InFault | ElementType::OutFault => {
+                    faults.push(FaultReference::new(&ch))
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            node: *node,
+            pattern: Pattern::new(node.attribute(attribute::PATTERN)),
+            input,
+            output,
+            faults,
+        }
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.node.attribute(attribute::NAME).expect("Name required for wsdl:operation")
+    }
+
+    pub fn pattern(&self) -> Pattern<'a> {
+        self.pattern
+    }
+
+    // The message sent to the service.
+    pub fn input(&self) -> Option<&MessageReference<'a>> {
+        self.input.as_ref()
+    }
+
+    // The message sent by the service.
+    pub fn output(&self) -> Option<&MessageReference<'a>> {
+        self.output.as_ref()
+    }
+
+    pub fn faults(&self) -> &[FaultReference<'a>] {
+        self.faults.as_ref()
+    }
+
+    pub fn documentation(&self) -> Option<&'a str> {
+        self.node.children().find_map(|n| {
+            if n.wsdl_type() == ElementType::Documentation {
+                n.text()
+            } else {
+                None
+            }
+        })
+    }
+}
+
+// Message exchange patterns predefined by WSDL 2.0 Part 2, or the URI of another one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pattern<'a> {
+    InOnly,
+    RobustInOnly,
+    InOut,
+    InOptionalOut,
+    OutOnly,
+    RobustOutOnly,
+    OutIn,
+    OutOptionalIn,
+    Other(&'a str),
+}
+
+impl<'a> Pattern<'a> {
+    pub fn new(uri: Option<&'a str>) -> Self {
+        use Pattern::*;
+        match uri.unwrap_or(pattern::IN_OUT) {
+            pattern::IN_ONLY => InOnly,
+            pattern::ROBUST_IN_ONLY => RobustInOnly,
+            pattern::IN_OUT => InOut,
+            pattern::IN_OPTIONAL_OUT => InOptionalOut,
+            pattern::OUT_ONLY => OutOnly,
+            pattern::ROBUST_OUT_ONLY => RobustOutOnly,
+            pattern::OUT_IN => OutIn,
+            pattern::OUT_OPTIONAL_IN => OutOptionalIn,
+            uri => Other(uri),
+        }
+    }
+}
+
+// wsdl:input or wsdl:output of an interface operation. The message is described by a
+// global element, there are no wsdl:message components in WSDL 2.0.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MessageReference<'a> {
+    node: Node<'a, 'a>,
+}
+
+impl<'a> MessageReference<'a> {
+    pub fn new(node: &Node<'a, '_>) -> Self {
+        Self { node: *node }
+    }
+
+    pub fn message_label(&self) -> Option<&'a str> {
+        self.node.attribute(attribute::MESSAGE_LABEL)
+    }
+
+    // QName of the element. None for the #any, #none and #other tokens, which don't
+    // describe the content (omitting the attribute means #other).
+    pub fn element(&self) -> Option<&'a str> {
+        self.node.attribute(attribute::ELEMENT).filter(|e| !e.starts_with('#'))
+    }
+
+    // Whether the message may be any element: #any, or #other for content described by
+    // something else than a schema.
+    pub fn is_any(&self) -> bool {
+        matches!(self.node.attribute(attribute::ELEMENT), None | Some("#any") | Some("#other"))
+    }
+
+    // wsam:Action of the message.
+    pub fn action(&self) -> Option<&'a str> {
+        constants::action(&self.node)
+    }
+
+    pub fn node(&self) -> &Node<'a, 'a> {
+        &self.node
+    }
+}
+
+// Interface level fault declaration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fault<'a> {
+    node: Node<'a, 'a>,
+}
+
+impl<'a> Fault<'a> {
+    pub fn new(node: &Node<'a, '_>) -> Self {
+        Self { node: *node }
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.node.attribute(attribute::NAME).expect("Name required for wsdl:fault")
+    }
+
+    pub fn element(&self) -> Option<&'a str> {
+        self.node.attribute(attribute::ELEMENT).filter(|e| !e.starts_with('#'))
+    }
+}
+
+// wsdl:infault or wsdl:outfault of an interface operation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FaultReference<'a> {
+    node: Node<'a, 'a>,
+}
+
+impl<'a> FaultReference<'a> {
+    pub fn new(node: &Node<'a, '_>) -> Self {
+        Self { node: *node }
+    }
+
+    pub fn ref_(&self) -> &'a str {
+        self.node
+            .attribute(attribute::REF)
+            .expect("Ref required for wsdl:infault and wsdl:outfault")
+    }
+
+    pub fn is_infault(&self) -> bool {
+        self.node.wsdl_type() == ElementType::InFault
+    }
+}
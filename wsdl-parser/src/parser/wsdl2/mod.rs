@@ -0,0 +1,79 @@
+// WSDL 2.0 front end. Descriptions are read into their own component model (interfaces
+// instead of port types and messages) and generated by the same function generator as
+// WSDL 1.1 definitions, see generator::generate_description.
+use roxmltree::Node;
+
+use crate::parser::constants::WSDL2_NAMESPACE;
+
+#[allow(dead_code)]
+pub mod binding;
+#[allow(dead_code)]
+pub mod description;
+#[allow(dead_code)]
+pub mod interface;
+#[allow(dead_code)]
+pub mod service;
+
+pub fn is_wsdl2(root: &Node) -> bool {
+    root.tag_name().namespace() == Some(WSDL2_NAMESPACE)
+}
+
+#[cfg(test)]
+mod test {
+    use roxmltree::Document;
+
+    use super::{description::Description, interface::Pattern, is_wsdl2};
+
+    const TEXT: &str = r##"
+<description xmlns="http://www.w3.org/ns/wsdl"
+    xmlns:tns="http://example.com/store"
+    targetNamespace="http://example.com/store">
+    <interface name="Base">
+        <operation name="Ping">
+            <input element="#none"/>
+            <output element="tns:Pong"/>
+        </operation>
+    </interface>
+    <interface name="Store" extends="tns:Base">
+        <operation name="Order" pattern="http://www.w3.org/ns/wsdl/in-only">
+            <input element="tns:Order"/>
+        </operation>
+    </interface>
+    <binding name="StoreSoap" interface="tns:Store" type="http://www.w3.org/ns/wsdl/soap"/>
+    <service name="StoreService" interface="tns:Store">
+        <endpoint name="Primary" binding="tns:StoreSoap" address="http://example.com/store"/>
+        <endpoint name="Backup" binding="tns:StoreSoap"/>
+    </service>
+</description>
+    "##;
+
+    #[test]
+    fn test_parse() {
+        let doc = Document::parse(TEXT).unwrap();
+        assert!(is_wsdl2(&doc.root_element()));
+        let desc = Description::new(&doc.root_element());
+
+        assert_eq!(desc.target_namespace().unwrap().name(), Some("tns"));
+        assert_eq!(desc.interfaces().len(), 2);
+
+        let store = desc.interface("tns:Store").unwrap();
+        assert_eq!(store.extends(), vec!["tns:Base"]);
+        let operations = desc.all_operations(store);
+        assert_eq!(
+            operations.iter().map(|op| op.name()).collect::<Vec<_>>(),
+            vec!["Order", "Ping"]
+        );
+        assert_eq!(operations[0].pattern(), Pattern::InOnly);
+        assert_eq!(operations[1].pattern(), Pattern::InOut);
+        assert_eq!(Pattern::new(Some("urn:mep")), Pattern::Other("urn:mep"));
+        assert_eq!(operations[1].input().unwrap().element(), None);
+        assert_eq!(operations[1].output().unwrap().element(), Some("tns:Pong"));
+
+        assert_eq!(desc.bindings()["StoreSoap"].interface(), Some("tns:Store"));
+        let endpoints = desc.services()["StoreService"].endpoints();
+        assert_eq!(endpoints.len(), 2);
+        assert_eq!(endpoints[0].binding(), "tns:StoreSoap");
+        assert_eq!(endpoints[0].address(), Some("http://example.com/store"));
+        assert_eq!(endpoints[1].address(), None);
+    }
+}
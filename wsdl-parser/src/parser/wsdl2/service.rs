@@ -0,0 +1,72 @@
+use roxmltree::Node;
+
+use crate::parser::{constants::attribute, ElementType, WsdlElement};
+
+// Element information
+// Namespace: http://www.w3.org/ns/wsdl
+// Type: wsdl:ServiceType
+//
+// Content: Choice [1..*]
+//      wsdl:documentation
+//      wsdl:endpoint
+//
+// Attributes:
+// name	        [1..1]	xsd:NCName
+// interface	[1..1]	xsd:QName
+#[derive(Clone, Debug)]
+pub struct Service<'a> {
+    node: Node<'a, 'a>,
+    endpoints: Vec<Endpoint<'a>>,
+}
+
+impl<'a> Service<'a> {
+    pub fn new(node: &Node<'a, '_>) -> Self {
+        Self {
+            node: *node,
+            endpoints: node
+                .children()
+                .filter(|n| n.is_element() && n.wsdl_type() == ElementType::Endpoint)
+                .map(|n| Endpoint::new(&n))
+                .collect(),
+        }
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.node.attribute(attribute::NAME).expect("Name required for wsdl:service")
+    }
+
+    pub fn interface(&self) -> &'a str {
+        self.node.attribute(attribute::INTERFACE).expect("Interface required for wsdl:service")
+    }
+
+    pub fn endpoints(&self) -> &[Endpoint<'a>] {
+        self.endpoints.as_ref()
+    }
+}
+
+// Attributes:
+// name	    [1..1]	xsd:NCName
+// binding	[1..1]	xsd:QName
+// address	[0..1]	xsd:anyURI
+#[derive(Clone, Debug)]
+pub struct Endpoint<'a> {
+    node: Node<'a, 'a>,
+}
+
+impl<'a> Endpoint<'a> {
+    pub fn new(node: &Node<'a, '_>) -> Self {
+        Self { node: *node }
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.node.attribute(attribute::NAME).expect("Name required for wsdl:endpoint")
+    }
+
+    pub fn binding(&self) -> &'a str {
+        self.node.attribute(attribute::BINDING).expect("Binding required for wsdl:endpoint")
+    }
+
+    pub fn address(&self) -> Option<&'a str> {
+        self.node.attribute(attribute::ADDRESS)
+    }
+}
@@ -1,19 +1,33 @@
-use roxmltree::{Document, Node};
-use wsdl_parser::{generator::generate, parser::definitions::Definitions};
+use roxmltree::Document;
+use wsdl_parser::{
+    generator::{generate, generate_description},
+    loader::schemas,
+    parser::{
+        definitions::Definitions,
+        wsdl2::{description::Description, is_wsdl2},
+    },
+};
 use xsd_parser::{generator::builder::GeneratorBuilder, parser::schema::parse_schema};
 
+mod multiple_port_types;
 mod port_type_to_function;
+mod rpc_and_multi_part;
+mod wsdl2;
 
 pub fn generate_wsdl(input: &str) -> String {
     let doc = Document::parse(input).unwrap();
-    let definitions = Definitions::new(&doc.root_element());
+    let root = doc.root_element();
     let gen = GeneratorBuilder::default().build();
-    let schemas =
-        definitions.types().iter().flat_map(|t| t.schemas()).collect::<Vec<Node<'_, '_>>>();
-    let mut code =
-        schemas.iter().map(|f| gen.generate_rs_file(&parse_schema(f))).collect::<Vec<String>>();
-
-    code.push(generate(&definitions));
+    let mut code = schemas(&doc)
+        .iter()
+        .map(|f| gen.generate_rs_file(&parse_schema(f)))
+        .collect::<Vec<String>>();
+
+    if is_wsdl2(&root) {
+        code.push(generate_description(&Description::new(&root)));
+    } else {
+        code.push(generate(&Definitions::new(&root)));
+    }
     code.join("")
 }
 
@@ -0,0 +1,24 @@
+use std::{env, fs};
+
+use xsd_parser::check::check_file;
+
+use super::generate_wsdl;
+
+#[test]
+fn generated_code_is_stable() {
+    let input = include_str!("input.wsdl");
+    let path = env::temp_dir().join(format!("wsdl-parser-check-{}.rs", std::process::id()));
+    fs::write(&path, generate_wsdl(input)).unwrap();
+
+    // Every run parses into new HashMaps, which iterate in a different order.
+    for _ in 0..16 {
+        assert_eq!(check_file(&path, &generate_wsdl(input)).unwrap(), None);
+    }
+    fs::remove_file(&path).unwrap();
+
+    let code = generate_wsdl(input);
+    let position = |name: &str| code.find(&format!("pub async fn {}", name)).unwrap();
+    assert!(position("find_customer") < position("find_invoice"));
+    assert!(position("find_invoice") < position("find_order"));
+    assert!(position("find_order") < position("find_product"));
+}
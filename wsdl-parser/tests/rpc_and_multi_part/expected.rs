@@ -0,0 +1,371 @@
+pub type Header = std::string::String;
+pub type Payload = std::string::String;
+#[derive(PartialEq, Debug)]
+pub struct Add {
+    pub a: i32,
+
+    pub b: i32,
+}
+
+impl Validate for Add {}
+
+impl XmlDeserialize for Add {
+    fn xml_deserialize(outer_popper: &mut XmlPopper) -> Result<Self, DeError> {
+        let mut popper = outer_popper.recursive_clone();
+
+        let output = Self {
+            a: {
+                let inter = popper.pop_child("a")?;
+
+                let field = inter;
+                field
+            },
+            b: {
+                let inter = popper.pop_child("b")?;
+
+                let field = inter;
+                field
+            },
+        };
+
+        *outer_popper = popper;
+
+        Ok(output)
+    }
+}
+
+impl ser::XmlSerialize for Add {
+    fn xml_serialize<W: std::io::Write>(
+        &self,
+        writer: &mut ser::EventWriter<W>
+    ) -> Result<(), ser::Error> {
+        let value = &self.a;
+        ser::write_child(writer, "a", value)?;
+        let value = &self.b;
+        ser::write_child(writer, "b", value)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct AddResponse {
+    pub sum: i32,
+
+    pub overflow: bool,
+}
+
+impl Validate for AddResponse {}
+
+impl XmlDeserialize for AddResponse {
+    fn xml_deserialize(outer_popper: &mut XmlPopper) -> Result<Self, DeError> {
+        let mut popper = outer_popper.recursive_clone();
+
+        let output = Self {
+            sum: {
+                let inter = popper.pop_child("sum")?;
+
+                let field = inter;
+                field
+            },
+            overflow: {
+                let inter = popper.pop_child("overflow")?;
+
+                let field = inter;
+                field
+            },
+        };
+
+        *outer_popper = popper;
+
+        Ok(output)
+    }
+}
+
+impl ser::XmlSerialize for AddResponse {
+    fn xml_serialize<W: std::io::Write>(
+        &self,
+        writer: &mut ser::EventWriter<W>
+    ) -> Result<(), ser::Error> {
+        let value = &self.sum;
+        ser::write_child(writer, "sum", value)?;
+        let value = &self.overflow;
+        ser::write_child(writer, "overflow", value)?;
+
+        Ok(())
+    }
+}
+
+// Adds two numbers.
+pub async fn add<T: transport::Transport>(
+    transport: &T,
+    request: &Add
+) -> Result<AddResponse, transport::Error> {
+    transport::request(
+        transport,
+        Some("http://example.com/calculator/Add"),
+        &ser::Element::prefixed("ns", "urn:calculator", "Add", request)
+    )
+    .await
+}
+#[derive(PartialEq, Debug)]
+pub struct UploadRequest {
+    pub header: Header,
+
+    pub payload: Payload,
+}
+
+impl Validate for UploadRequest {}
+
+impl XmlDeserialize for UploadRequest {
+    fn xml_deserialize(outer_popper: &mut XmlPopper) -> Result<Self, DeError> {
+        let mut popper = outer_popper.recursive_clone();
+
+        let output = Self {
+            header: {
+                let inter = popper.pop_child("Header")?;
+
+                let field = inter;
+                field
+            },
+            payload: {
+                let inter = popper.pop_child("Payload")?;
+
+                let field = inter;
+                field
+            },
+        };
+
+        *outer_popper = popper;
+
+        Ok(output)
+    }
+}
+
+impl ser::XmlSerialize for UploadRequest {
+    fn xml_serialize<W: std::io::Write>(
+        &self,
+        writer: &mut ser::EventWriter<W>
+    ) -> Result<(), ser::Error> {
+        let value = &self.header;
+        ser::XmlSerialize::xml_serialize(
+            &ser::Element::prefixed("tns", "http://example.com/calculator", "Header", value),
+            writer
+        )?;
+        let value = &self.payload;
+        ser::XmlSerialize::xml_serialize(
+            &ser::Element::prefixed("tns", "http://example.com/calculator", "Payload", value),
+            writer
+        )?;
+
+        Ok(())
+    }
+}
+
+pub async fn upload<T: transport::Transport>(
+    transport: &T,
+    request: &UploadRequest
+) -> Result<(), transport::Error> {
+    transport::send(transport, Some("http://example.com/calculator/Upload"), request).await
+}
+#[derive(PartialEq, Debug)]
+pub struct DownloadResponse {
+    pub header: Header,
+
+    pub payload: Payload,
+}
+
+impl Validate for DownloadResponse {}
+
+impl XmlDeserialize for DownloadResponse {
+    fn xml_deserialize(outer_popper: &mut XmlPopper) -> Result<Self, DeError> {
+        let mut popper = outer_popper.recursive_clone();
+
+        let output = Self {
+            header: {
+                let inter = popper.pop_child("Header")?;
+
+                let field = inter;
+                field
+            },
+            payload: {
+                let inter = popper.pop_child("Payload")?;
+
+                let field = inter;
+                field
+            },
+        };
+
+        *outer_popper = popper;
+
+        Ok(output)
+    }
+}
+
+impl ser::XmlSerialize for DownloadResponse {
+    fn xml_serialize<W: std::io::Write>(
+        &self,
+        writer: &mut ser::EventWriter<W>
+    ) -> Result<(), ser::Error> {
+        let value = &self.header;
+        ser::XmlSerialize::xml_serialize(
+            &ser::Element::prefixed("tns", "http://example.com/calculator", "Header", value),
+            writer
+        )?;
+        let value = &self.payload;
+        ser::XmlSerialize::xml_serialize(
+            &ser::Element::prefixed("tns", "http://example.com/calculator", "Payload", value),
+            writer
+        )?;
+
+        Ok(())
+    }
+}
+
+pub async fn download<T: transport::Transport>(
+    transport: &T,
+    request: &Header
+) -> Result<DownloadResponse, transport::Error> {
+    let transport::Parts(response) = transport::request(
+        transport,
+        Some("http://example.com/calculator/Download"),
+        &ser::Element::new(Some("http://example.com/calculator"), "Header", request)
+    )
+    .await?;
+    Ok(response)
+}
+#[derive(PartialEq, Debug)]
+pub struct StatusChanged {
+    pub status: std::string::String,
+}
+
+impl Validate for StatusChanged {}
+
+impl XmlDeserialize for StatusChanged {
+    fn xml_deserialize(outer_popper: &mut XmlPopper) -> Result<Self, DeError> {
+        let mut popper = outer_popper.recursive_clone();
+
+        let output = Self {
+            status: {
+                let inter = popper.pop_child("status")?;
+
+                let field = inter;
+                field
+            },
+        };
+
+        *outer_popper = popper;
+
+        Ok(output)
+    }
+}
+
+impl ser::XmlSerialize for StatusChanged {
+    fn xml_serialize<W: std::io::Write>(
+        &self,
+        writer: &mut ser::EventWriter<W>
+    ) -> Result<(), ser::Error> {
+        let value = &self.status;
+        ser::write_child(writer, "status", value)?;
+
+        Ok(())
+    }
+}
+
+pub async fn status_changed<T: transport::Transport>(
+    transport: &T
+) -> Result<StatusChanged, transport::Error> {
+    transport::receive(transport).await
+}
+#[derive(PartialEq, Debug)]
+pub struct ConfirmResponse {
+    pub accepted: bool,
+}
+
+impl Validate for ConfirmResponse {}
+
+impl XmlDeserialize for ConfirmResponse {
+    fn xml_deserialize(outer_popper: &mut XmlPopper) -> Result<Self, DeError> {
+        let mut popper = outer_popper.recursive_clone();
+
+        let output = Self {
+            accepted: {
+                let inter = popper.pop_child("accepted")?;
+
+                let field = inter;
+                field
+            },
+        };
+
+        *outer_popper = popper;
+
+        Ok(output)
+    }
+}
+
+impl ser::XmlSerialize for ConfirmResponse {
+    fn xml_serialize<W: std::io::Write>(
+        &self,
+        writer: &mut ser::EventWriter<W>
+    ) -> Result<(), ser::Error> {
+        let value = &self.accepted;
+        ser::write_child(writer, "accepted", value)?;
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Confirm {
+    pub token: std::string::String,
+}
+
+impl Validate for Confirm {}
+
+impl XmlDeserialize for Confirm {
+    fn xml_deserialize(outer_popper: &mut XmlPopper) -> Result<Self, DeError> {
+        let mut popper = outer_popper.recursive_clone();
+
+        let output = Self {
+            token: {
+                let inter = popper.pop_child("token")?;
+
+                let field = inter;
+                field
+            },
+        };
+
+        *outer_popper = popper;
+
+        Ok(output)
+    }
+}
+
+impl ser::XmlSerialize for Confirm {
+    fn xml_serialize<W: std::io::Write>(
+        &self,
+        writer: &mut ser::EventWriter<W>
+    ) -> Result<(), ser::Error> {
+        let value = &self.token;
+        ser::write_child(writer, "token", value)?;
+
+        Ok(())
+    }
+}
+
+pub async fn confirm<T: transport::Transport, F: FnOnce(Confirm) -> ConfirmResponse>(
+    transport: &T,
+    respond: F
+) -> Result<(), transport::Error> {
+    transport::solicit(
+        transport,
+        None,
+        |request| ser::Element::prefixed(
+            "tns",
+            "http://example.com/calculator",
+            "ConfirmResponse",
+            respond(request)
+        )
+    )
+    .await
+}
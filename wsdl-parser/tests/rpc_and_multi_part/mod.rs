@@ -0,0 +1,31 @@
+use wsdl_parser::{
+    generator::{generate_operations, operation_types},
+    parser::definitions::Definitions,
+};
+
+use super::{ast_test, generate_wsdl};
+
+#[test]
+fn generator_does_not_panic() {
+    println!("{}", generate_wsdl(include_str!("input.wsdl")))
+}
+
+#[test]
+fn generator_output_has_correct_ast() {
+    ast_test(include_str!("input.wsdl"), include_str!("expected.rs"));
+}
+
+#[test]
+fn operation_types_include_wrapped_parts() {
+    let doc = roxmltree::Document::parse(include_str!("input.wsdl")).unwrap();
+    let definitions = Definitions::new(&doc.root_element());
+
+    let types = operation_types(&definitions, &["Upload".to_string()]);
+    assert!(types.contains(&"{http://example.com/calculator}Header".to_string()));
+    assert!(types.contains(&"{http://example.com/calculator}Payload".to_string()));
+    assert!(!types.iter().any(|t| t.ends_with("}int")));
+
+    let code = generate_operations(&definitions, Some(&["Upload".to_string()]));
+    assert!(code.contains("pub async fn upload"));
+    assert!(!code.contains("pub async fn add"));
+}
@@ -0,0 +1,96 @@
+#[derive(PartialEq, Debug)]
+pub struct Add {
+    pub a: i32,
+
+    pub b: i32,
+}
+
+impl Validate for Add {}
+
+impl XmlDeserialize for Add {
+    fn xml_deserialize(outer_popper: &mut XmlPopper) -> Result<Self, DeError> {
+        let mut popper = outer_popper.recursive_clone();
+
+        let output = Self {
+            a: {
+                let inter = popper.pop_child("A")?;
+
+                let field = inter;
+                field
+            },
+            b: {
+                let inter = popper.pop_child("B")?;
+
+                let field = inter;
+                field
+            },
+        };
+
+        *outer_popper = popper;
+
+        Ok(output)
+    }
+}
+
+impl ser::XmlSerialize for Add {
+    fn xml_serialize<W: std::io::Write>(
+        &self,
+        writer: &mut ser::EventWriter<W>
+    ) -> Result<(), ser::Error> {
+        let value = &self.a;
+        ser::write_child(writer, "A", value)?;
+        let value = &self.b;
+        ser::write_child(writer, "B", value)?;
+
+        Ok(())
+    }
+}
+
+pub type AddResponse = i32;
+pub type Reset = std::string::String;
+pub type Overflow = std::string::String;
+
+// Adds two numbers.
+pub async fn add<T: transport::Transport>(
+    transport: &T,
+    request: &Add
+) -> Result<AddResponse, transport::Error> {
+    transport::request(
+        transport,
+        Some("http://example.com/calculator/Add"),
+        &ser::Element::new(Some("http://example.com/calculator"), "Add", request)
+    )
+    .await
+}
+
+pub async fn reset<T: transport::Transport>(
+    transport: &T,
+    request: &Reset
+) -> Result<(), transport::Error> {
+    transport::send(
+        transport,
+        Some("http://example.com/calculator/Reset"),
+        &ser::Element::new(Some("http://example.com/calculator"), "Reset", request)
+    )
+    .await
+}
+
+pub async fn on_overflow<T: transport::Transport>(
+    transport: &T
+) -> Result<Overflow, transport::Error> {
+    transport::receive(transport).await
+}
+
+pub async fn confirm<T: transport::Transport, F: FnOnce(Reset)>(
+    transport: &T,
+    respond: F
+) -> Result<(), transport::Error> {
+    transport::solicit(transport, None, respond).await
+}
+
+pub async fn echo<T: transport::Transport, R: ser::XmlSerialize, S: XmlDeserialize>(
+    transport: &T,
+    request: &R
+) -> Result<S, transport::Error> {
+    transport::request(transport, None, request).await
+}
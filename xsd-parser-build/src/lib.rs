@@ -0,0 +1,421 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use roxmltree::{Document, Namespace};
+use wsdl_parser::{generator::generate_documents, loader as wsdl_loader};
+use xsd_parser::{
+    config::{Config as GeneratorConfig, ConfigError},
+    generator::builder::GeneratorBuilder,
+    loader::{LoadError, Loader, SourceFile},
+    parser::parse_schemas,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    Load(LoadError),
+    Io(PathBuf, io::Error),
+    Config(ConfigError),
+    MissingOutDir,
+    UnsupportedRoots,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Load(e) => write!(f, "{}", e),
+            Error::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            Error::Config(e) => write!(f, "{}", e),
+            Error::MissingOutDir => {
+                write!(f, "OUT_DIR is not set, use Config::out_dir outside of build scripts")
+            }
+            Error::UnsupportedRoots => {
+                write!(f, "roots are not supported by build scripts, use elements instead")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<LoadError> for Error {
+    fn from(e: LoadError) -> Self {
+        Error::Load(e)
+    }
+}
+
+impl From<ConfigError> for Error {
+    fn from(e: ConfigError) -> Self {
+        Error::Config(e)
+    }
+}
+
+// Generates code for xsd and wsdl files from a build script:
+//
+//     xsd_parser_build::Config::new()
+//         .schema("schemas/onvif.xsd")
+//         .namespace_module("http://www.onvif.org/ver10/schema", "onvif")
+//         .compile()?;
+//
+// Every target namespace is written to its own `<module>.rs` in OUT_DIR, to be included
+// with `include!(concat!(env!("OUT_DIR"), "/onvif.rs"))`. The module defaults to the
+// prefix the schema declares for its target namespace, since that is the path other
+// schemas use to refer to its types, or to the file name without a prefix.
+#[derive(Debug, Default)]
+pub struct Config {
+    schemas: Vec<PathBuf>,
+    wsdls: Vec<PathBuf>,
+    namespace_modules: HashMap<String, String>,
+    out_dir: Option<PathBuf>,
+    bounded_integers: bool,
+    config_file: Option<PathBuf>,
+}
+
+impl Config {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Adds an xsd file. Imported and included schemas are loaded with it.
+    pub fn schema(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        self.schemas.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    // Adds a WSDL 1.1 or 2.0 file. Imported documents and schemas are loaded with it.
+    pub fn wsdl(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        self.wsdls.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn namespace_module(&mut self, namespace: &str, module: &str) -> &mut Self {
+        self.namespace_modules.insert(namespace.to_string(), module.to_string());
+        self
+    }
+
+    // Defaults to the OUT_DIR of the build script.
+    pub fn out_dir(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        self.out_dir = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn bounded_integers(&mut self, enabled: bool) -> &mut Self {
+        self.bounded_integers = enabled;
+        self
+    }
+
+    // Reads the generator options from the TOML file the CLIs take with --config, so the
+    // same code is generated. Namespace modules and bounded integers set here are added to
+    // those of the file. Roots are not supported.
+    pub fn config_file(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        self.config_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    // Writes the generated modules and tells cargo to run the build script again when one
+    // of the loaded files changes.
+    pub fn compile(&self) -> Result<(), Error> {
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => out_dir.clone(),
+            None => env::var_os("OUT_DIR").map(PathBuf::from).ok_or(Error::MissingOutDir)?,
+        };
+
+        // The inputs are watched before they are loaded, so that a build that failed to load
+        // them runs again once they are fixed.
+        for path in self.schemas.iter().chain(&self.wsdls).chain(&self.config_file) {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+
+        let (files, modules) = self.generate()?;
+        for file in &files {
+            println!("cargo:rerun-if-changed={}", file.path.display());
+        }
+
+        fs::create_dir_all(&out_dir).map_err(|e| Error::Io(out_dir.clone(), e))?;
+        for (module, code) in modules {
+            let path = out_dir.join(format!("{}.rs", module));
+            fs::write(&path, code).map_err(|e| Error::Io(path, e))?;
+        }
+        Ok(())
+    }
+
+    // Loads all inputs and returns them with the code of each module.
+    fn generate(&self) -> Result<(Vec<SourceFile>, BTreeMap<String, String>), Error> {
+        let mut config = match &self.config_file {
+            Some(path) => GeneratorConfig::from_file(path)?,
+            None => GeneratorConfig::default(),
+        };
+        if !config.roots.is_empty() {
+            return Err(Error::UnsupportedRoots);
+        }
+        config.modules.extend(self.namespace_modules.clone());
+        config.bounded_integers |= self.bounded_integers;
+
+        let mut loader = Loader::default();
+        for path in &self.schemas {
+            loader.load(path)?;
+        }
+
+        // Every wsdl input is merged with the wsdl documents it imports, as the CLI does,
+        // while the schemas of all inputs are parsed together.
+        let mut services = vec![];
+        for path in &self.wsdls {
+            let files = wsdl_loader::load(path)?;
+            for file in &files {
+                loader.load_with(&file.path, &|_| vec![])?;
+            }
+            services.push(files.into_iter().map(|f| f.path).collect::<Vec<_>>());
+        }
+
+        let files = loader.into_files();
+        let docs = files
+            .iter()
+            .map(|f| Document::parse(&f.text).map_err(|e| LoadError::Xml(f.path.clone(), e)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (schemas, schema_paths): (Vec<_>, Vec<_>) = docs
+            .iter()
+            .zip(&files)
+            .flat_map(|(doc, file)| {
+                wsdl_loader::schemas(doc).into_iter().map(move |schema| (schema, &file.path))
+            })
+            .unzip();
+        let rs_files = parse_schemas(&schemas);
+        let schema_modules = rs_files
+            .iter()
+            .zip(&schema_paths)
+            .map(|(rs_file, path)| module_name(&config.modules, rs_file.target_ns.as_ref(), path))
+            .collect::<Vec<_>>();
+
+        // Types of other namespaces are referred to through the modules they are written to.
+        config.modules.extend(rs_files.iter().zip(&schema_modules).filter_map(
+            |(rs_file, module)| {
+                Some((rs_file.target_ns.as_ref()?.uri().to_string(), module.clone()))
+            },
+        ));
+        let gen = GeneratorBuilder::default().with_config(config.clone()).build();
+        let mut modules = BTreeMap::<String, String>::new();
+        for (rs_file, module) in rs_files.iter().zip(schema_modules) {
+            modules.entry(module).or_default().push_str(&gen.generate_rs_file(rs_file));
+        }
+
+        // The functions of a wsdl input go to the module of its target namespace.
+        let doc = |path: &PathBuf| files.iter().position(|f| &f.path == path).map(|i| &docs[i]);
+        for paths in &services {
+            let root = doc(&paths[0]).map(|doc| doc.root_element());
+            let target_ns = root.as_ref().and_then(|root| {
+                let uri = root.attribute("targetNamespace")?;
+                root.namespaces().find(|ns| ns.uri() == uri)
+            });
+            let module = module_name(&config.modules, target_ns, &paths[0]);
+            let code = generate_documents(paths.iter().filter_map(doc));
+            modules.entry(module).or_default().push_str(&code);
+        }
+
+        Ok((files, modules))
+    }
+}
+
+fn module_name(
+    modules: &HashMap<String, String>,
+    target_ns: Option<&Namespace>,
+    path: &Path,
+) -> String {
+    if let Some(module) = target_ns.and_then(|ns| modules.get(ns.uri())) {
+        return module.clone();
+    }
+
+    match target_ns.and_then(|ns| ns.name()) {
+        Some(prefix) => prefix.to_string(),
+        None => path.file_stem().unwrap_or_default().to_string_lossy().into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("xsd-parser-build-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_compile_schemas() {
+        let dir = temp_dir("schemas");
+        fs::write(
+            dir.join("root.xsd"),
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tns="http://example.com/root" xmlns:cmn="http://example.com/common" targetNamespace="http://example.com/root">
+    <xs:import namespace="http://example.com/common" schemaLocation="common.xsd"/>
+    <xs:complexType name="Foo">
+        <xs:sequence>
+            <xs:element name="Bar" type="cmn:Bar"/>
+        </xs:sequence>
+    </xs:complexType>
+</xs:schema>"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("common.xsd"),
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:cmn="http://example.com/common" targetNamespace="http://example.com/common">
+    <xs:simpleType name="Bar">
+        <xs:restriction base="xs:string"/>
+    </xs:simpleType>
+</xs:schema>"#,
+        )
+        .unwrap();
+
+        let mut config = Config::new();
+        config
+            .schema(dir.join("root.xsd"))
+            .namespace_module("http://example.com/root", "root")
+            .out_dir(dir.join("out"));
+        let (files, modules) = config.generate().unwrap();
+        config.compile().unwrap();
+
+        let root = fs::read_to_string(dir.join("out/root.rs")).unwrap();
+        let common = fs::read_to_string(dir.join("out/cmn.rs")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(modules.keys().collect::<Vec<_>>(), vec!["cmn", "root"]);
+        assert!(root.contains("pub struct Foo"));
+        assert!(root.contains("pub bar: cmn::Bar"));
+        assert!(common.contains("pub struct Bar"));
+    }
+
+    #[test]
+    fn test_compile_namespace_modules() {
+        let dir = temp_dir("modules");
+        fs::write(
+            dir.join("tns.xsd"),
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tns="http://example.com/tns" xmlns:cmn="http://example.com/common" targetNamespace="http://example.com/tns">
+    <xs:import namespace="http://example.com/common" schemaLocation="common.xsd"/>
+    <xs:complexType name="Foo">
+        <xs:sequence>
+            <xs:element name="Bar" type="cmn:Bar"/>
+            <xs:element name="Baz" type="tns:Baz"/>
+        </xs:sequence>
+    </xs:complexType>
+    <xs:simpleType name="Baz">
+        <xs:restriction base="xs:int"/>
+    </xs:simpleType>
+</xs:schema>"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("common.xsd"),
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:c="http://example.com/common" targetNamespace="http://example.com/common">
+    <xs:simpleType name="Bar">
+        <xs:restriction base="xs:string"/>
+    </xs:simpleType>
+</xs:schema>"#,
+        )
+        .unwrap();
+
+        let mut config = Config::new();
+        config.schema(dir.join("tns.xsd")).namespace_module("http://example.com/common", "common");
+        let (_, modules) = config.generate().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(modules.keys().collect::<Vec<_>>(), vec!["common", "tns"]);
+        assert!(modules["tns"].contains("pub bar: common::Bar"));
+        assert!(modules["tns"].contains("pub baz: Baz"));
+        assert!(modules["common"].contains("pub struct Bar"));
+    }
+
+    #[test]
+    fn test_config_file() {
+        let dir = temp_dir("config");
+        fs::write(
+            dir.join("tns.xsd"),
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tns="http://example.com/tns" xmlns:cmn="http://example.com/common" targetNamespace="http://example.com/tns">
+    <xs:import namespace="http://example.com/common" schemaLocation="common.xsd"/>
+    <xs:complexType name="Foo">
+        <xs:sequence>
+            <xs:element name="Bar" type="cmn:Bar"/>
+        </xs:sequence>
+    </xs:complexType>
+</xs:schema>"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("common.xsd"),
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" targetNamespace="http://example.com/common">
+    <xs:simpleType name="Bar">
+        <xs:restriction base="xs:string"/>
+    </xs:simpleType>
+</xs:schema>"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("config.toml"),
+            r#"
+[modules]
+"http://example.com/common" = "common"
+
+[renames]
+"tns:Foo" = "Renamed"
+"#,
+        )
+        .unwrap();
+        fs::write(dir.join("roots.toml"), "roots = [\"tns:Foo\"]\n").unwrap();
+
+        let mut config = Config::new();
+        config.schema(dir.join("tns.xsd")).config_file(dir.join("config.toml"));
+        let generated = config.generate();
+        let rooted = config.config_file(dir.join("roots.toml")).generate();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let (_, modules) = generated.unwrap();
+        assert_eq!(modules.keys().collect::<Vec<_>>(), vec!["common", "tns"]);
+        assert!(modules["tns"].contains("pub struct Renamed"));
+        assert!(modules["tns"].contains("pub bar: common::Bar"));
+        assert!(matches!(rooted, Err(Error::UnsupportedRoots)));
+    }
+
+    #[test]
+    fn test_compile_wsdl() {
+        let dir = temp_dir("wsdl");
+        fs::write(
+            dir.join("service.wsdl"),
+            r#"<wsdl:definitions xmlns:wsdl="http://schemas.xmlsoap.org/wsdl/" xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tds="http://example.com/device" targetNamespace="http://example.com/device">
+    <wsdl:types>
+        <xs:schema targetNamespace="http://example.com/device" elementFormDefault="qualified">
+            <xs:element name="GetTime" type="xs:string"/>
+            <xs:element name="GetTimeResponse" type="xs:string"/>
+        </xs:schema>
+    </wsdl:types>
+    <wsdl:message name="GetTimeRequest">
+        <wsdl:part name="parameters" element="tds:GetTime"/>
+    </wsdl:message>
+    <wsdl:message name="GetTimeResponse">
+        <wsdl:part name="parameters" element="tds:GetTimeResponse"/>
+    </wsdl:message>
+    <wsdl:portType name="Device">
+        <wsdl:operation name="GetTime">
+            <wsdl:input message="tds:GetTimeRequest"/>
+            <wsdl:output message="tds:GetTimeResponse"/>
+        </wsdl:operation>
+    </wsdl:portType>
+</wsdl:definitions>"#,
+        )
+        .unwrap();
+
+        let (files, modules) = Config::new().wsdl(dir.join("service.wsdl")).generate().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(modules.keys().collect::<Vec<_>>(), vec!["tds"]);
+        assert!(modules["tds"].contains("pub async fn get_time"));
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let result = Config::new().schema("missing.xsd").out_dir(env::temp_dir()).compile();
+        assert!(matches!(result, Err(Error::Load(LoadError::Io(_, _)))));
+    }
+}
@@ -1,78 +1,337 @@
 use std::{
     fs,
     fs::OpenOptions,
-    io::{prelude::*, Read},
+    io::prelude::*,
     path::{Path, PathBuf},
 };
 
-use anyhow::Context;
-use clap::Parser;
-use xsd_parser::{generator::builder::GeneratorBuilder, parser::parse};
+use anyhow::{bail, Context};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use roxmltree::Document;
+use xsd_parser::{
+    check::check_file,
+    config::Config,
+    diff::{diff, report, Severity},
+    doc::{self, DocFormat},
+    generator::builder::GeneratorBuilder,
+    ir::IrFile,
+    loader::{walk_dir, Loader, SourceFile},
+    model::SchemaSet,
+    parser::{parse_schemas, reachable::retain_reachable, types::RsFile},
+    sample::{sample, SampleOptions},
+    validate::validate,
+};
 
 #[derive(Parser)]
 #[clap(name = env!("CARGO_PKG_NAME"))]
 #[clap(version = env!("CARGO_PKG_VERSION"))]
 #[clap(about = env!("CARGO_PKG_DESCRIPTION"))]
 struct Opt {
-    /// Input .xsd file
+    #[clap(subcommand)]
+    command: Option<Command>,
+
+    /// Input .xsd file, or a .json file written with `--emit ir-json`
     #[clap(long, short)]
     input: Option<PathBuf>,
 
     /// Output file
     #[clap(long, short)]
     output: Option<PathBuf>,
+
+    /// Generator config file (TOML)
+    #[clap(long, short)]
+    config: Option<PathBuf>,
+
+    /// Map integer types whose facets bound them to i64/u64 instead of big integers
+    #[clap(long)]
+    bounded_integers: bool,
+
+    /// Generate only the types this element or type depends on (can be repeated)
+    #[clap(long = "root", value_name = "NAME")]
+    roots: Vec<String>,
+
+    /// What to write
+    #[clap(long, value_enum, default_value = "rust")]
+    emit: Emit,
+
+    /// Compare with the existing output instead of writing it, fail if it is out of date
+    #[clap(long)]
+    check: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Write an example instance document of an element
+    Sample(SampleOpt),
+    /// Check an instance document against schemas
+    Validate(ValidateOpt),
+    /// Report the changes between two versions of schemas
+    Diff(DiffOpt),
+}
+
+#[derive(Args)]
+struct SampleOpt {
+    /// Schema file, the schemas it imports and includes are loaded with it
+    #[clap(long, short)]
+    schema: PathBuf,
+
+    /// Root element, `prefix:Name` or `Name`
+    #[clap(long, short)]
+    element: String,
+
+    /// Write optional elements and attributes too
+    #[clap(long)]
+    optional: bool,
+
+    /// Element or group to take in choices (can be repeated)
+    #[clap(long = "choice", value_name = "NAME")]
+    choices: Vec<String>,
+
+    /// Output file
+    #[clap(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct ValidateOpt {
+    /// Schema file, the schemas it imports and includes are loaded with it (can be repeated)
+    #[clap(long = "schema", short, required = true)]
+    schemas: Vec<PathBuf>,
+
+    /// Document to validate
+    document: PathBuf,
+}
+
+#[derive(Args)]
+struct DiffOpt {
+    /// Old schema file, or a directory whose .xsd files are all loaded
+    old: PathBuf,
+
+    /// New schema file or directory
+    new: PathBuf,
+
+    /// Output file for the report
+    #[clap(long, short)]
+    output: Option<PathBuf>,
+
+    /// Fail if there are breaking changes
+    #[clap(long)]
+    fail_on_breaking: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Emit {
+    /// Generated Rust code
+    Rust,
+    /// The parsed schema model as JSON, see xsd_parser::ir
+    IrJson,
+    /// Documentation of the schema components as Markdown
+    Markdown,
+    /// Documentation of the schema components as a static HTML page
+    Html,
 }
 
 fn main() -> anyhow::Result<()> {
     let opt: Opt = Opt::parse();
+    if let Some(command) = opt.command {
+        return match command {
+            Command::Sample(sample_opt) => run_sample(sample_opt),
+            Command::Validate(validate_opt) => run_validate(validate_opt),
+            Command::Diff(diff_opt) => run_diff(diff_opt),
+        };
+    }
 
+    let mut config = match &opt.config {
+        Some(path) => Config::from_file(path)?,
+        None => Config::default(),
+    };
+    config.bounded_integers |= opt.bounded_integers;
+    config.roots.extend(opt.roots);
+
+    let emit = opt.emit;
     let input_path = opt.input.unwrap_or_else(|| PathBuf::from("input/xsd"));
     let md = fs::metadata(&input_path).unwrap();
-    if md.is_dir() {
+    let files = if md.is_dir() {
         let output_path = opt.output.unwrap_or_else(|| PathBuf::from("output/rs"));
-        process_dir(&input_path, &output_path)?;
+        walk_dir(&input_path, &output_path)?
+            .into_iter()
+            .map(|(input, output)| match emit {
+                Emit::Rust => (input, Some(output)),
+                Emit::IrJson => (input, Some(output.with_extension("json"))),
+                Emit::Markdown => (input, Some(output.with_extension("md"))),
+                Emit::Html => (input, Some(output.with_extension("html"))),
+            })
+            .collect()
     } else {
-        process_single_file(&input_path, opt.output.as_deref())?;
+        vec![(input_path, opt.output)]
+    };
+
+    let mut stale = 0;
+    for (input, output) in files {
+        let text = process_single_file(&input, &config, emit)?;
+        match output {
+            Some(output) if opt.check => {
+                if let Some(diff) = check_file(&output, &text)? {
+                    print!("{}", diff);
+                    stale += 1;
+                }
+            }
+            Some(output) => {
+                if let Some(parent) = output.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                write_to_file(&output, &text).context("Error writing file")?;
+            }
+            None if opt.check => bail!("--check needs the --output to compare with"),
+            None => println!("{}", text),
+        }
+    }
+    if stale > 0 {
+        bail!("{} generated file(s) are out of date", stale);
     }
 
     Ok(())
 }
 
-fn process_dir(input_path: &Path, output_path: &Path) -> anyhow::Result<()> {
-    if !output_path.exists() {
-        fs::create_dir_all(output_path)?;
-    }
-    for entry in fs::read_dir(input_path)? {
-        let path = entry?.path();
-        if path.is_dir() {
-            process_dir(&path, &output_path.join(path.file_name().unwrap()))?;
-        } else {
-            let output_file_path = PathBuf::from(path.file_name().unwrap()).with_extension("rs");
-            let output_file_path = output_path.join(output_file_path);
-            process_single_file(&path, Some(&output_file_path))?;
+fn process_single_file(input_path: &Path, config: &Config, emit: Emit) -> anyhow::Result<String> {
+    let is_doc = emit == Emit::Markdown || emit == Emit::Html;
+    if input_path.extension().is_some_and(|ext| ext == "json") {
+        if is_doc {
+            bail!("Documentation is rendered from schemas, not from {}", input_path.display());
         }
+        let text = fs::read_to_string(input_path)
+            .with_context(|| format!("Failed to read {}", input_path.display()))?;
+        let ir = IrFile::from_json(&text)
+            .with_context(|| format!("Failed to parse {}", input_path.display()))?;
+        let xml = ir.namespace_document();
+        let namespaces = Document::parse(&xml)?;
+        let rs_file = ir
+            .to_rs_file(&namespaces)
+            .with_context(|| format!("Failed to read {}", input_path.display()))?;
+        return Ok(render(&rs_file, config, emit));
+    }
+
+    // Imported schemas are parsed with the input to resolve the types it refers to, but only
+    // the types of the input are generated.
+    let files = load(input_path)?;
+    let docs = parse_documents(&files)?;
+
+    let schemas = docs.iter().map(|doc| doc.root_element()).collect::<Vec<_>>();
+    if is_doc {
+        let format = if emit == Emit::Html { DocFormat::Html } else { DocFormat::Markdown };
+        let title = input_path.file_stem().unwrap_or_default().to_string_lossy();
+        return Ok(doc::render(&SchemaSet::parse(&schemas), &[], &title, format));
+    }
+    let mut rs_files = parse_schemas(&schemas);
+    if !config.roots.is_empty() {
+        retain_reachable(&mut rs_files, &config.roots);
+    }
+    Ok(render(&rs_files[0], config, emit))
+}
+
+fn run_sample(opt: SampleOpt) -> anyhow::Result<()> {
+    let files = load(&opt.schema)?;
+    let docs = parse_documents(&files)?;
+    let schemas = docs.iter().map(|doc| doc.root_element()).collect::<Vec<_>>();
+    let set = SchemaSet::parse(&schemas);
+
+    let element = match set.find_element(&opt.element) {
+        Some(element) => element,
+        None => bail!("No global element {} in {}", opt.element, opt.schema.display()),
+    };
+    let options = SampleOptions { optional: opt.optional, choices: opt.choices };
+    let xml = sample(&set, element, &options);
+    match opt.output {
+        Some(output) => write_to_file(&output, &xml).context("Error writing file")?,
+        None => print!("{}", xml),
     }
     Ok(())
 }
 
-fn process_single_file(input_path: &Path, output_path: Option<&Path>) -> anyhow::Result<()> {
-    let text = load_file(input_path)?;
-    let rs_file = parse(text.as_str()).map_err(|_| anyhow::anyhow!("Error parsing file"))?;
-    let gen = GeneratorBuilder::default().build();
-    let code = gen.generate_rs_file(&rs_file);
-    if let Some(output_filename) = output_path {
-        write_to_file(output_filename, &code).context("Error writing file")?;
-    } else {
-        println!("{}", code);
+fn run_validate(opt: ValidateOpt) -> anyhow::Result<()> {
+    let mut loader = Loader::default();
+    for schema in &opt.schemas {
+        loader.load(schema)?;
+    }
+    let files = loader.into_files();
+    let docs = parse_documents(&files)?;
+    let schemas = docs.iter().map(|doc| doc.root_element()).collect::<Vec<_>>();
+    let set = SchemaSet::parse(&schemas);
+
+    let text = fs::read_to_string(&opt.document)
+        .with_context(|| format!("Failed to read {}", opt.document.display()))?;
+    let doc = Document::parse(&text)
+        .with_context(|| format!("Failed to parse {}", opt.document.display()))?;
+    let violations = validate(&set, &doc);
+    for violation in &violations {
+        println!("{}:{}", opt.document.display(), violation);
+    }
+    if !violations.is_empty() {
+        bail!("{} violation(s) in {}", violations.len(), opt.document.display());
     }
     Ok(())
 }
 
-fn load_file(path: &Path) -> std::io::Result<String> {
-    let mut file = fs::File::open(path)?;
-    let mut text = String::new();
-    file.read_to_string(&mut text)?;
-    Ok(text)
+fn run_diff(opt: DiffOpt) -> anyhow::Result<()> {
+    let old_files = load_tree(&opt.old)?;
+    let new_files = load_tree(&opt.new)?;
+    let old_docs = parse_documents(&old_files)?;
+    let new_docs = parse_documents(&new_files)?;
+    let old = SchemaSet::parse(&old_docs.iter().map(|doc| doc.root_element()).collect::<Vec<_>>());
+    let new = SchemaSet::parse(&new_docs.iter().map(|doc| doc.root_element()).collect::<Vec<_>>());
+
+    let changes = diff(&old, &new);
+    let text = report(&changes);
+    match opt.output {
+        Some(output) => write_to_file(&output, &text).context("Error writing file")?,
+        None => print!("{}", text),
+    }
+    let breaking = changes.iter().filter(|c| c.severity == Severity::Breaking).count();
+    if opt.fail_on_breaking && breaking > 0 {
+        bail!("{} breaking change(s)", breaking);
+    }
+    Ok(())
+}
+
+fn load(path: &Path) -> anyhow::Result<Vec<SourceFile>> {
+    let mut loader = Loader::default();
+    loader.load(path)?;
+    Ok(loader.into_files())
+}
+
+// A schema with its imports, or every schema of a directory tree.
+fn load_tree(path: &Path) -> anyhow::Result<Vec<SourceFile>> {
+    if !path.is_dir() {
+        return load(path);
+    }
+    let mut loader = Loader::default();
+    for (file, _) in walk_dir(path, Path::new(""))? {
+        if file.extension().is_some_and(|ext| ext == "xsd") {
+            loader.load(&file)?;
+        }
+    }
+    Ok(loader.into_files())
+}
+
+fn parse_documents(files: &[SourceFile]) -> anyhow::Result<Vec<Document<'_>>> {
+    files
+        .iter()
+        .map(|f| {
+            Document::parse(&f.text)
+                .with_context(|| format!("Failed to parse {}", f.path.display()))
+        })
+        .collect()
+}
+
+fn render(rs_file: &RsFile, config: &Config, emit: Emit) -> String {
+    match emit {
+        Emit::Rust => GeneratorBuilder::default()
+            .with_config(config.clone())
+            .build()
+            .generate_rs_file(rs_file),
+        Emit::IrJson => IrFile::from(rs_file).to_json(),
+        Emit::Markdown | Emit::Html => unreachable!("documentation is rendered from schemas"),
+    }
 }
 
 fn write_to_file(path: &Path, text: &str) -> std::io::Result<()> {
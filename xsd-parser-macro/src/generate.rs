@@ -0,0 +1,162 @@
+use std::path::{Path, PathBuf};
+
+use roxmltree::Document;
+use wsdl_parser::{
+    generator::generate_documents,
+    loader::{self as wsdl_loader, is_schema},
+};
+use xsd_parser::{
+    config::Config,
+    generator::builder::GeneratorBuilder,
+    loader::{LoadError, Loader},
+    parser::parse_schemas,
+};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Input {
+    Xsd,
+    Wsdl,
+}
+
+#[derive(Default, Debug)]
+pub struct Options {
+    pub bounded_integers: bool,
+    // Generator config file, as taken by the CLIs with --config.
+    pub config: Option<PathBuf>,
+}
+
+// Returns the generated code and the paths of all files it was generated from, the config file
+// included.
+pub fn generate(
+    input: Input,
+    path: &Path,
+    options: &Options,
+) -> Result<(String, Vec<PathBuf>), String> {
+    let mut config = match &options.config {
+        Some(path) => Config::from_file(path).map_err(|e| e.to_string())?,
+        None => Config::default(),
+    };
+    // Roots select wsdl operations as well as types, which is up to the CLIs.
+    if !config.roots.is_empty() {
+        return Err("roots are not supported by the macros, use elements instead".to_string());
+    }
+    config.bounded_integers |= options.bounded_integers;
+
+    let files = match input {
+        Input::Xsd => {
+            let mut loader = Loader::default();
+            loader.load(path).map_err(error_message)?;
+            loader.into_files()
+        }
+        Input::Wsdl => wsdl_loader::load(path).map_err(error_message)?,
+    };
+    let docs = files
+        .iter()
+        .map(|f| {
+            Document::parse(&f.text).map_err(|e| error_message(LoadError::Xml(f.path.clone(), e)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let root = docs[0].root_element();
+    match input {
+        Input::Xsd if !is_schema(&root) => {
+            return Err(format!("{} is not an xsd schema", path.display()))
+        }
+        Input::Wsdl if is_schema(&root) => {
+            return Err(format!("{} is not a wsdl document, use include_xsd!", path.display()))
+        }
+        _ => {}
+    }
+
+    // The schemas of the root document come first, the others are only needed to resolve
+    // the types it refers to.
+    let root_schemas = wsdl_loader::schemas(&docs[0]).len();
+    let schemas = docs.iter().flat_map(wsdl_loader::schemas).collect::<Vec<_>>();
+    let gen = GeneratorBuilder::default().with_config(config).build();
+    let mut code = parse_schemas(&schemas)
+        .iter()
+        .take(root_schemas)
+        .map(|rs_file| gen.generate_rs_file(rs_file))
+        .collect::<String>();
+
+    if input == Input::Wsdl {
+        code.push_str(&generate_documents(&docs));
+    }
+
+    Ok((code, files.into_iter().map(|f| f.path).chain(options.config.clone()).collect()))
+}
+
+// Points at the line of the file that failed to parse, like rustc does for source files.
+fn error_message(e: LoadError) -> String {
+    match e {
+        LoadError::Xml(path, e) => {
+            let pos = e.pos();
+            format!("{}:{}:{}: {}", path.display(), pos.row, pos.col, e)
+        }
+        e => e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, fs};
+
+    use super::*;
+
+    #[test]
+    fn test_generate_xsd() {
+        let dir = env::temp_dir().join(format!("xsd-parser-macro-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("root.xsd"),
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:cmn="http://example.com/common">
+    <xs:import namespace="http://example.com/common" schemaLocation="common.xsd"/>
+    <xs:complexType name="Foo">
+        <xs:sequence>
+            <xs:element name="Bar" type="cmn:Bar"/>
+        </xs:sequence>
+    </xs:complexType>
+</xs:schema>"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("common.xsd"),
+            r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" targetNamespace="http://example.com/common">
+    <xs:simpleType name="Bar">
+        <xs:restriction base="xs:string"/>
+    </xs:simpleType>
+</xs:schema>"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("broken.xsd"),
+            "<xs:schema xmlns:xs=\"http://www.w3.org/2001/XMLSchema\">\n    <xs:element>\n</xs:schema>",
+        )
+        .unwrap();
+        fs::write(dir.join("config.toml"), "[types]\n\"cmn:Bar\" = \"crate::MyBar\"\n").unwrap();
+        fs::write(dir.join("roots.toml"), "roots = [\"Foo\"]\n").unwrap();
+
+        let generated = generate(Input::Xsd, &dir.join("root.xsd"), &Options::default());
+        let config = |name: &str| Options { config: Some(dir.join(name)), ..Options::default() };
+        let configured = generate(Input::Xsd, &dir.join("root.xsd"), &config("config.toml"));
+        let rooted = generate(Input::Xsd, &dir.join("root.xsd"), &config("roots.toml"));
+        let broken = generate(Input::Xsd, &dir.join("broken.xsd"), &Options::default());
+        let not_wsdl = generate(Input::Wsdl, &dir.join("root.xsd"), &Options::default());
+        let dir = dir.canonicalize().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let (code, files) = generated.unwrap();
+        assert!(code.contains("pub struct Foo"));
+        assert!(!code.contains("pub struct Bar"));
+        assert_eq!(files, vec![dir.join("root.xsd"), dir.join("common.xsd")]);
+
+        let (code, files) = configured.unwrap();
+        assert!(code.contains("crate::MyBar"));
+        assert_eq!(files.last(), Some(&dir.join("config.toml")));
+        assert!(rooted.unwrap_err().starts_with("roots are not supported"));
+
+        let message = broken.unwrap_err();
+        assert!(message.starts_with(&format!("{}:3:1: ", dir.join("broken.xsd").display())));
+        assert!(not_wsdl.unwrap_err().ends_with("is not a wsdl document, use include_xsd!"));
+    }
+}
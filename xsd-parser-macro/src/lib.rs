@@ -0,0 +1,95 @@
+use std::{env, path::PathBuf};
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Ident, LitBool, LitStr, Token,
+};
+
+use crate::generate::{Input, Options};
+
+mod generate;
+
+// Expands to the code generated for an xsd file:
+//
+//     include_xsd!("schemas/common.xsd", bounded_integers = true, config = "xsd-parser.toml");
+//
+// The paths are relative to the crate root. The config file is the one the CLIs take with
+// --config, without roots. Imported and included schemas are loaded to resolve types, but
+// only the types of the given schema are generated.
+#[proc_macro]
+pub fn include_xsd(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as MacroArgs);
+    expand(Input::Xsd, args).into()
+}
+
+// Expands to the code generated for a WSDL 1.1 or 2.0 file: the types of its inline
+// schemas and a function per operation.
+#[proc_macro]
+pub fn include_wsdl(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as MacroArgs);
+    expand(Input::Wsdl, args).into()
+}
+
+struct MacroArgs {
+    path: LitStr,
+    options: Options,
+}
+
+impl Parse for MacroArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        let mut options = Options::default();
+
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let name: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match name.to_string().as_str() {
+                "bounded_integers" => options.bounded_integers = input.parse::<LitBool>()?.value,
+                "config" => options.config = Some(PathBuf::from(input.parse::<LitStr>()?.value())),
+                _ => {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!(
+                            "unknown option `{}`, expected `bounded_integers` or `config`",
+                            name
+                        ),
+                    ))
+                }
+            }
+        }
+
+        if !input.is_empty() {
+            return Err(input.error("expected `,`"));
+        }
+        Ok(MacroArgs { path, options })
+    }
+}
+
+fn expand(input: Input, mut args: MacroArgs) -> proc_macro2::TokenStream {
+    let root = env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from).unwrap_or_default();
+    let path = root.join(args.path.value());
+    args.options.config = args.options.config.map(|config| root.join(config));
+
+    let (code, files) = match generate::generate(input, &path, &args.options) {
+        Ok(generated) => generated,
+        Err(message) => return syn::Error::new(args.path.span(), message).to_compile_error(),
+    };
+    let items = match code.parse::<proc_macro2::TokenStream>() {
+        Ok(items) => items,
+        Err(e) => {
+            let message =
+                format!("failed to tokenize the code generated for {}: {}", path.display(), e);
+            return syn::Error::new(args.path.span(), message).to_compile_error();
+        }
+    };
+
+    // Reading the files with include_bytes! makes the compiler expand the macro again when
+    // one of them changes.
+    let files = files.iter().map(|f| f.to_string_lossy().into_owned());
+    quote! {
+        #(const _: &[u8] = include_bytes!(#files);)*
+        #items
+    }
+}
@@ -0,0 +1,101 @@
+use std::{fs, io, path::Path};
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use similar::TextDiff;
+
+// Compares generated code with the file it was written to before, for CI jobs that keep
+// the generated code in the repository. Rust sources are compared by their tokens, so
+// formatting, trailing commas and `//` comments don't matter; other files are compared as
+// text. Returns a unified diff from the file to the generated code if they differ, a
+// missing file is compared as an empty one.
+pub fn check_file(path: &Path, generated: &str) -> io::Result<Option<String>> {
+    let existing = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+
+    if is_same(&existing, generated) {
+        return Ok(None);
+    }
+    let name = path.display().to_string();
+    let diff = TextDiff::from_lines(existing.as_str(), generated)
+        .unified_diff()
+        .header(&name, &format!("{} (generated)", name))
+        .to_string();
+    Ok(Some(diff))
+}
+
+fn is_same(existing: &str, generated: &str) -> bool {
+    if syn::parse_file(existing).is_err() || syn::parse_file(generated).is_err() {
+        return existing == generated;
+    }
+    match (existing.parse::<TokenStream>(), generated.parse::<TokenStream>()) {
+        (Ok(existing), Ok(generated)) => normalize(existing) == normalize(generated),
+        _ => false,
+    }
+}
+
+// Tokens as text with the trailing commas rustfmt adds removed. A trailing comma in
+// parentheses only goes if there is another one, `(a,)` is a tuple and `(a)` is not.
+fn normalize(tokens: TokenStream) -> Vec<String> {
+    let mut res = vec![];
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) => {
+                let commas = group
+                    .stream()
+                    .into_iter()
+                    .filter(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ','))
+                    .count();
+                let mut inner = normalize(group.stream());
+                let removable = group.delimiter() != Delimiter::Parenthesis || commas > 1;
+                if removable && inner.last().map(String::as_str) == Some(",") {
+                    inner.pop();
+                }
+                // Delimiters are never tokens on their own, so they can't be confused with
+                // the tokens of the group.
+                res.push(format!("{:?}(", group.delimiter()));
+                res.extend(inner);
+                res.push(")".to_string());
+            }
+            token => res.push(token.to_string()),
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn test_check_file() {
+        let path = env::temp_dir().join(format!("xsd-parser-check-{}.rs", std::process::id()));
+        fs::write(&path, "pub struct Foo {\n    pub a: i32,\n}\n").unwrap();
+
+        // Formatting, trailing commas and line comments are not compared.
+        let reformatted = "// Generated.\npub struct Foo { pub a: i32 }";
+        assert_eq!(check_file(&path, reformatted).unwrap(), None);
+
+        let diff = check_file(&path, "pub struct Foo {\n    pub a: i64,\n}\n").unwrap().unwrap();
+        assert!(diff.contains("-    pub a: i32,\n+    pub a: i64,\n"));
+        assert!(diff.starts_with(&format!("--- {}\n", path.display())));
+
+        // A one element tuple is not the parenthesized type.
+        fs::write(&path, "pub type Foo = (i32,);\npub fn foo(a: i32, b: i32,) {}\n").unwrap();
+        assert_eq!(
+            check_file(&path, "pub type Foo = (i32,);\npub fn foo(a: i32, b: i32) {}").unwrap(),
+            None
+        );
+        assert!(check_file(&path, "pub type Foo = (i32);\npub fn foo(a: i32, b: i32) {}")
+            .unwrap()
+            .is_some());
+
+        fs::remove_file(&path).unwrap();
+        let diff = check_file(&path, "pub struct Foo;\n").unwrap().unwrap();
+        assert!(diff.contains("+pub struct Foo;\n"));
+    }
+}
@@ -0,0 +1,131 @@
+use std::{
+    collections::HashMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+// Generator options read from a TOML file:
+//
+//     backend = "yaserde"
+//     derives = ["Clone"]
+//     doc_comments = "rustdoc"
+//     elements = ["tds:GetDeviceInformation"]
+//     roots = ["GetProfiles"]
+//
+//     [modules]
+//     "http://www.onvif.org/ver10/schema" = "onvif"
+//
+//     [types]
+//     "tt:ReferenceToken" = "crate::ReferenceToken"
+//
+//     [renames]
+//     "tt:Capabilities" = "DeviceCapabilities"
+//
+// Types and elements are named by the prefix their schema declares for them, names in a
+// target namespace without a prefix are written without one.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub backend: Backend,
+    // Derived in addition to the derives of the backend.
+    pub derives: Vec<String>,
+    pub doc_comments: DocComments,
+    pub bounded_integers: bool,
+    // Global elements to generate, all of them if not set. Types are always generated.
+    pub elements: Option<Vec<String>>,
+    // Root elements, types or wsdl operations. If set, only the types they depend on are
+    // generated.
+    pub roots: Vec<String>,
+    // Rust modules of the types of foreign namespaces, by namespace URI. The prefix of the
+    // namespace is used otherwise.
+    pub modules: HashMap<String, String>,
+    // Rust types used instead of generating a type.
+    pub types: HashMap<String, String>,
+    pub renames: HashMap<String, String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    // XmlDeserialize implementations for popper.
+    #[default]
+    Popper,
+    // YaSerialize and YaDeserialize derives.
+    Yaserde,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocComments {
+    // Schema documentation as `//` comments.
+    #[default]
+    Line,
+    // Schema documentation as `///` doc comments.
+    Rustdoc,
+    None,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Toml(PathBuf, toml::de::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            ConfigError::Toml(path, e) => write!(f, "{}: {}", path.display(), e),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let text = fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_path_buf(), e))?;
+        toml::from_str(&text).map_err(|e| ConfigError::Toml(path.to_path_buf(), e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config: Config = toml::from_str(
+            r#"
+backend = "yaserde"
+derives = ["Clone"]
+doc_comments = "rustdoc"
+elements = ["tds:GetDeviceInformation"]
+roots = ["GetProfiles"]
+
+[modules]
+"http://www.onvif.org/ver10/schema" = "onvif"
+
+[types]
+"tt:ReferenceToken" = "crate::ReferenceToken"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.backend, Backend::Yaserde);
+        assert_eq!(config.derives, vec!["Clone"]);
+        assert_eq!(config.doc_comments, DocComments::Rustdoc);
+        assert!(!config.bounded_integers);
+        assert_eq!(config.elements, Some(vec!["tds:GetDeviceInformation".to_string()]));
+        assert_eq!(config.roots, vec!["GetProfiles"]);
+        assert_eq!(config.modules["http://www.onvif.org/ver10/schema"], "onvif");
+        assert_eq!(config.types["tt:ReferenceToken"], "crate::ReferenceToken");
+        assert!(config.renames.is_empty());
+
+        assert_eq!(toml::from_str::<Config>("").unwrap(), Config::default());
+        assert!(toml::from_str::<Config>("backend = \"serde\"").is_err());
+        assert!(toml::from_str::<Config>("derive = [\"Clone\"]").is_err());
+    }
+}
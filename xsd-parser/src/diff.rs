@@ -0,0 +1,741 @@
+use std::fmt;
+
+use crate::model::{
+    AttributeUse, ComplexType, Element, Facets, Particle, QName, SchemaSet, SimpleType, Term, Type,
+    TypeRef, Use, Variety,
+};
+
+// Nesting of anonymous types and groups that is compared.
+const MAX_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    // Documents valid against the old schemas may be invalid against the new ones, or an
+    // element or type they use was removed or changed.
+    Breaking,
+    Compatible,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub severity: Severity,
+    // "element" or "type".
+    pub kind: &'static str,
+    // `prefix:Name` of the global component, followed by the names of the local elements and
+    // attributes leading to the change.
+    pub path: String,
+    pub description: String,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}: {}", self.kind, self.path, self.description)
+    }
+}
+
+// Changes of the global elements and types from `old` to `new`, matched by QName. Content
+// models are compared by the elements they contain, with groups and the content of base
+// types resolved, so a change of a group or base type is reported for every type using it.
+pub fn diff(old: &SchemaSet, new: &SchemaSet) -> Vec<Change> {
+    let mut differ = Differ { old, new, kind: "element", changes: vec![] };
+    for (name, element) in &old.elements {
+        let path = old.display_name(name);
+        match new.elements.get(name) {
+            Some(new_element) => differ.element(&path, element, new_element, 0),
+            None => differ.push(Severity::Breaking, &path, "removed".to_string()),
+        }
+    }
+    for name in new.elements.keys().filter(|name| !old.elements.contains_key(name)) {
+        differ.push(Severity::Compatible, &new.display_name(name), "added".to_string());
+    }
+
+    differ.kind = "type";
+    for (name, ty) in &old.types {
+        let path = old.display_name(name);
+        match new.types.get(name) {
+            Some(new_ty) => differ.type_(&path, ty, new_ty, 0),
+            None => differ.push(Severity::Breaking, &path, "removed".to_string()),
+        }
+    }
+    for name in new.types.keys().filter(|name| !old.types.contains_key(name)) {
+        differ.push(Severity::Compatible, &new.display_name(name), "added".to_string());
+    }
+    differ.changes
+}
+
+// Release notes of the changes, breaking ones first.
+pub fn report(changes: &[Change]) -> String {
+    if changes.is_empty() {
+        return "No changes.\n".to_string();
+    }
+    let breaking = changes.iter().filter(|c| c.severity == Severity::Breaking).count();
+    let mut res =
+        format!("{} breaking and {} compatible change(s).\n", breaking, changes.len() - breaking);
+    let sections =
+        [(Severity::Breaking, "Breaking changes"), (Severity::Compatible, "Compatible changes")];
+    for (severity, heading) in sections.iter() {
+        let changes = changes.iter().filter(|c| c.severity == *severity).collect::<Vec<_>>();
+        if changes.is_empty() {
+            continue;
+        }
+        res.push_str(&format!("\n## {}\n\n", heading));
+        for change in changes {
+            res.push_str(&format!("- {} `{}`: {}\n", change.kind, change.path, change.description));
+        }
+    }
+    res
+}
+
+struct Differ<'a> {
+    old: &'a SchemaSet,
+    new: &'a SchemaSet,
+    // Kind of the global components being compared.
+    kind: &'static str,
+    changes: Vec<Change>,
+}
+
+// An element or wildcard of a content model, with its occurrences in the whole model.
+struct Slot<'a> {
+    // None for xs:any.
+    name: Option<&'a QName>,
+    // The declaration, None for wildcards and references to undeclared elements.
+    element: Option<&'a Element>,
+    is_ref: bool,
+    // Namespace constraint of a wildcard.
+    any: &'a str,
+    min: usize,
+    // None if unbounded.
+    max: Option<usize>,
+}
+
+impl<'a> Slot<'a> {
+    fn matches(&self, other: &Slot) -> bool {
+        self.name == other.name && self.any == other.any
+    }
+}
+
+impl<'a> Differ<'a> {
+    fn push(&mut self, severity: Severity, path: &str, description: String) {
+        let kind = self.kind;
+        self.changes.push(Change { severity, kind, path: path.to_string(), description });
+    }
+
+    fn element(&mut self, path: &str, old: &Element, new: &Element, depth: usize) {
+        self.type_ref(path, "type", &old.ty, &new.ty, depth);
+        match (old.nillable, new.nillable) {
+            (true, false) => self.push(Severity::Breaking, path, "no longer nillable".to_string()),
+            (false, true) => self.push(Severity::Compatible, path, "now nillable".to_string()),
+            _ => {}
+        }
+        match (old.is_abstract, new.is_abstract) {
+            (false, true) => self.push(Severity::Breaking, path, "now abstract".to_string()),
+            (true, false) => {
+                self.push(Severity::Compatible, path, "no longer abstract".to_string())
+            }
+            _ => {}
+        }
+        self.value(path, "fixed value", &old.fixed, &new.fixed, true);
+        self.value(path, "default value", &old.default, &new.default, false);
+
+        let old_head = old.substitution_group.as_ref().map(|head| self.old.display_name(head));
+        let new_head = new.substitution_group.as_ref().map(|head| self.new.display_name(head));
+        if old_head != new_head {
+            let description = match (old_head, new_head) {
+                (Some(old), Some(new)) => {
+                    format!("substitution group changed from `{}` to `{}`", old, new)
+                }
+                (Some(old), None) => format!("no longer in substitution group `{}`", old),
+                (None, Some(new)) => format!("added to substitution group `{}`", new),
+                (None, None) => unreachable!(),
+            };
+            self.push(Severity::Breaking, path, description);
+        }
+    }
+
+    // Fixed and default values of elements and attributes. Removing a fixed value allows
+    // more documents, any other change alters what documents mean.
+    fn value(
+        &mut self,
+        path: &str,
+        what: &str,
+        old: &Option<String>,
+        new: &Option<String>,
+        fixed: bool,
+    ) {
+        let (severity, description) = match (old, new) {
+            (Some(old), Some(new)) if old != new => {
+                (Severity::Breaking, format!("{} changed from `{}` to `{}`", what, old, new))
+            }
+            (Some(old), None) if fixed => {
+                (Severity::Compatible, format!("{} `{}` removed", what, old))
+            }
+            (Some(old), None) => (Severity::Breaking, format!("{} `{}` removed", what, old)),
+            (None, Some(new)) => (Severity::Breaking, format!("{} `{}` added", what, new)),
+            _ => return,
+        };
+        self.push(severity, path, description);
+    }
+
+    // Named types are compared by name only, their definitions are compared on their own.
+    fn type_ref(&mut self, path: &str, what: &str, old: &TypeRef, new: &TypeRef, depth: usize) {
+        match (old, new) {
+            (TypeRef::Named(old), TypeRef::Named(new)) if old == new => {}
+            (TypeRef::Anonymous(old), TypeRef::Anonymous(new)) => {
+                if depth < MAX_DEPTH {
+                    self.type_(path, old, new, depth + 1);
+                }
+            }
+            _ => {
+                let old = type_name(self.old, old);
+                let new = type_name(self.new, new);
+                self.push(
+                    Severity::Breaking,
+                    path,
+                    format!("{} changed from {} to {}", what, old, new),
+                );
+            }
+        }
+    }
+
+    fn type_(&mut self, path: &str, old: &Type, new: &Type, depth: usize) {
+        match (old, new) {
+            (Type::Complex(old), Type::Complex(new)) => self.complex_type(path, old, new, depth),
+            (Type::Simple(old), Type::Simple(new)) => self.simple_type(path, old, new, depth),
+            (Type::Simple(_), Type::Complex(_)) => self.push(
+                Severity::Breaking,
+                path,
+                "changed from a simple to a complex type".to_string(),
+            ),
+            (Type::Complex(_), Type::Simple(_)) => self.push(
+                Severity::Breaking,
+                path,
+                "changed from a complex to a simple type".to_string(),
+            ),
+        }
+    }
+
+    fn complex_type(&mut self, path: &str, old: &ComplexType, new: &ComplexType, depth: usize) {
+        let old_base =
+            old.base.as_ref().map(|(base, derivation)| (self.old.display_name(base), derivation));
+        let new_base =
+            new.base.as_ref().map(|(base, derivation)| (self.new.display_name(base), derivation));
+        if old_base != new_base {
+            let name = |base: Option<(String, _)>| match base {
+                Some((base, _)) => format!("`{}`", base),
+                None => "none".to_string(),
+            };
+            let description =
+                format!("base type changed from {} to {}", name(old_base), name(new_base));
+            self.push(Severity::Breaking, path, description);
+        }
+        match (old.is_abstract, new.is_abstract) {
+            (false, true) => self.push(Severity::Breaking, path, "now abstract".to_string()),
+            (true, false) => {
+                self.push(Severity::Compatible, path, "no longer abstract".to_string())
+            }
+            _ => {}
+        }
+        match (old.mixed, new.mixed) {
+            (true, false) => self.push(Severity::Breaking, path, "no longer mixed".to_string()),
+            (false, true) => self.push(Severity::Compatible, path, "now mixed".to_string()),
+            _ => {}
+        }
+
+        match (self.old.simple_content(old), self.new.simple_content(new)) {
+            (Some(old), Some(new)) => self.type_ref(path, "content type", old, new, depth),
+            (Some(_), None) => self.push(
+                Severity::Breaking,
+                path,
+                "changed from simple to element content".to_string(),
+            ),
+            (None, Some(_)) => self.push(
+                Severity::Breaking,
+                path,
+                "changed from element to simple content".to_string(),
+            ),
+            (None, None) => self.content(path, old, new, depth),
+        }
+
+        self.attributes(path, old, new, depth);
+        match (self.old.any_attribute(old), self.new.any_attribute(new)) {
+            (true, false) => {
+                self.push(Severity::Breaking, path, "no longer allows any attribute".to_string())
+            }
+            (false, true) => {
+                self.push(Severity::Compatible, path, "now allows any attribute".to_string())
+            }
+            _ => {}
+        }
+    }
+
+    fn content(&mut self, path: &str, old: &ComplexType, new: &ComplexType, depth: usize) {
+        let (old_slots, old_ordered) = slots(self.old, &self.old.particles(old));
+        let (new_slots, new_ordered) = slots(self.new, &self.new.particles(new));
+
+        for old_slot in &old_slots {
+            let name = slot_name(self.old, old_slot);
+            let new_slot = match new_slots.iter().find(|s| s.matches(old_slot)) {
+                Some(new_slot) => new_slot,
+                None => {
+                    self.push(Severity::Breaking, path, format!("{} removed", name));
+                    continue;
+                }
+            };
+            let local_path = match old_slot.name {
+                Some(element) => format!("{}/{}", path, self.new.display_name(element)),
+                None => path.to_string(),
+            };
+            self.occurs(&local_path, old_slot, new_slot);
+            // Global elements are compared on their own.
+            if let (Some(old), Some(new)) = (old_slot.element, new_slot.element) {
+                if !(old_slot.is_ref && new_slot.is_ref) {
+                    self.element(&local_path, old, new, depth);
+                }
+            }
+        }
+        for new_slot in new_slots.iter().filter(|s| !old_slots.iter().any(|o| o.matches(s))) {
+            let name = slot_name(self.new, new_slot);
+            match new_slot.min {
+                0 => self.push(Severity::Compatible, path, format!("optional {} added", name)),
+                _ => self.push(Severity::Breaking, path, format!("required {} added", name)),
+            }
+        }
+
+        // Elements of sequences must keep their relative order.
+        if old_ordered
+            && new_ordered
+            && common(&old_slots, &new_slots) != common(&new_slots, &old_slots)
+        {
+            self.push(Severity::Breaking, path, "order of elements changed".to_string());
+        }
+    }
+
+    fn occurs(&mut self, path: &str, old: &Slot, new: &Slot) {
+        if new.min > old.min {
+            let description = match old.min {
+                0 => "now required".to_string(),
+                _ => format!("minOccurs raised from {} to {}", old.min, new.min),
+            };
+            self.push(Severity::Breaking, path, description);
+        } else if new.min < old.min {
+            let description = match new.min {
+                0 => "now optional".to_string(),
+                _ => format!("minOccurs lowered from {} to {}", old.min, new.min),
+            };
+            self.push(Severity::Compatible, path, description);
+        }
+
+        // Going between one and several occurrences changes how the element is represented.
+        let max =
+            |max: Option<usize>| max.map_or_else(|| "unbounded".to_string(), |m| m.to_string());
+        let changed = |direction| {
+            format!("maxOccurs {} from {} to {}", direction, max(old.max), max(new.max))
+        };
+        let (severity, description) = match (old.max, new.max) {
+            (old, new) if old == new => return,
+            (Some(1), _) => (Severity::Breaking, "now repeated".to_string()),
+            (_, Some(1)) => (Severity::Breaking, "no longer repeated".to_string()),
+            (Some(old), Some(new)) if new > old => (Severity::Compatible, changed("raised")),
+            (Some(_), None) => (Severity::Compatible, changed("raised")),
+            _ => (Severity::Breaking, changed("lowered")),
+        };
+        self.push(severity, path, description);
+    }
+
+    fn attributes(&mut self, path: &str, old: &ComplexType, new: &ComplexType, depth: usize) {
+        // Prohibited attributes are the same as undeclared ones.
+        let declared = |uses: Vec<AttributeUse>| {
+            uses.into_iter().filter(|u| u.use_ != Use::Prohibited).collect::<Vec<_>>()
+        };
+        let old_uses = declared(self.old.attribute_uses(old));
+        let new_uses = declared(self.new.attribute_uses(new));
+
+        for old_use in &old_uses {
+            let name = self.old.display_name(&old_use.name);
+            let new_use = match new_uses.iter().find(|u| u.name == old_use.name) {
+                Some(new_use) => new_use,
+                None => {
+                    self.push(Severity::Breaking, path, format!("attribute `{}` removed", name));
+                    continue;
+                }
+            };
+            let attribute_path = format!("{}/@{}", path, name);
+            match (old_use.use_, new_use.use_) {
+                (Use::Optional, Use::Required) => {
+                    self.push(Severity::Breaking, &attribute_path, "now required".to_string())
+                }
+                (Use::Required, Use::Optional) => {
+                    self.push(Severity::Compatible, &attribute_path, "now optional".to_string())
+                }
+                _ => {}
+            }
+            self.type_ref(&attribute_path, "type", &old_use.ty, &new_use.ty, depth);
+            self.value(&attribute_path, "fixed value", &old_use.fixed, &new_use.fixed, true);
+            self.value(&attribute_path, "default value", &old_use.default, &new_use.default, false);
+        }
+        for new_use in new_uses.iter().filter(|u| !old_uses.iter().any(|o| o.name == u.name)) {
+            let name = self.new.display_name(&new_use.name);
+            match new_use.use_ {
+                Use::Required => self.push(
+                    Severity::Breaking,
+                    path,
+                    format!("required attribute `{}` added", name),
+                ),
+                _ => self.push(
+                    Severity::Compatible,
+                    path,
+                    format!("optional attribute `{}` added", name),
+                ),
+            }
+        }
+    }
+
+    fn simple_type(&mut self, path: &str, old: &SimpleType, new: &SimpleType, depth: usize) {
+        match (&old.variety, &new.variety) {
+            (
+                Variety::Restriction { base: old_base, facets: old_facets },
+                Variety::Restriction { base: new_base, facets: new_facets },
+            ) => {
+                self.type_ref(path, "base type", old_base, new_base, depth);
+                self.facets(path, old_facets, new_facets);
+            }
+            (Variety::List { item: old }, Variety::List { item: new }) => {
+                self.type_ref(path, "item type", old, new, depth)
+            }
+            (Variety::Union { members: old }, Variety::Union { members: new }) => {
+                self.union(path, old, new, depth)
+            }
+            (old, new) => {
+                let description =
+                    format!("changed from a {} to a {}", variety_name(old), variety_name(new));
+                self.push(Severity::Breaking, path, description);
+            }
+        }
+    }
+
+    // Named members are matched by name, anonymous ones by position.
+    fn union(&mut self, path: &str, old: &[TypeRef], new: &[TypeRef], depth: usize) {
+        let (old_named, new_named) = (named(old), named(new));
+        for member in old_named.iter().filter(|m| !new_named.contains(m)) {
+            let description = format!("union member `{}` removed", self.old.display_name(member));
+            self.push(Severity::Breaking, path, description);
+        }
+        for member in new_named.iter().filter(|m| !old_named.contains(m)) {
+            let description = format!("union member `{}` added", self.new.display_name(member));
+            self.push(Severity::Compatible, path, description);
+        }
+
+        let (old_anonymous, new_anonymous) = (anonymous(old), anonymous(new));
+        for (old, new) in old_anonymous.iter().zip(&new_anonymous) {
+            if depth < MAX_DEPTH {
+                self.type_(path, old, new, depth + 1);
+            }
+        }
+        if old_anonymous.len() > new_anonymous.len() {
+            self.push(Severity::Breaking, path, "anonymous union member removed".to_string());
+        } else if old_anonymous.len() < new_anonymous.len() {
+            self.push(Severity::Compatible, path, "anonymous union member added".to_string());
+        }
+    }
+
+    fn facets(&mut self, path: &str, old: &Facets, new: &Facets) {
+        if old.enumeration.is_empty() && !new.enumeration.is_empty() {
+            self.push(Severity::Breaking, path, "restricted to an enumeration".to_string());
+        } else if !old.enumeration.is_empty() && new.enumeration.is_empty() {
+            self.push(
+                Severity::Compatible,
+                path,
+                "no longer restricted to an enumeration".to_string(),
+            );
+        } else {
+            for value in old.enumeration.iter().filter(|v| !new.enumeration.contains(v)) {
+                self.push(
+                    Severity::Breaking,
+                    path,
+                    format!("enumeration value `{}` removed", value),
+                );
+            }
+            for value in new.enumeration.iter().filter(|v| !old.enumeration.contains(v)) {
+                self.push(
+                    Severity::Compatible,
+                    path,
+                    format!("enumeration value `{}` added", value),
+                );
+            }
+        }
+
+        if old.patterns != new.patterns {
+            let patterns = |patterns: &[String]| {
+                patterns.iter().map(|p| format!("`{}`", p)).collect::<Vec<_>>().join(", ")
+            };
+            let (severity, description) = match (old.patterns.is_empty(), new.patterns.is_empty()) {
+                (false, true) => {
+                    (Severity::Compatible, format!("pattern {} removed", patterns(&old.patterns)))
+                }
+                (true, false) => {
+                    (Severity::Breaking, format!("pattern {} added", patterns(&new.patterns)))
+                }
+                _ => (
+                    Severity::Breaking,
+                    format!(
+                        "pattern changed from {} to {}",
+                        patterns(&old.patterns),
+                        patterns(&new.patterns)
+                    ),
+                ),
+            };
+            self.push(severity, path, description);
+        }
+
+        let number = |value: Option<usize>| value.map(|v| v.to_string());
+        let bounds = [
+            ("minInclusive", old.min_inclusive.clone(), new.min_inclusive.clone(), Some(false)),
+            ("minExclusive", old.min_exclusive.clone(), new.min_exclusive.clone(), Some(false)),
+            ("maxInclusive", old.max_inclusive.clone(), new.max_inclusive.clone(), Some(true)),
+            ("maxExclusive", old.max_exclusive.clone(), new.max_exclusive.clone(), Some(true)),
+            ("length", number(old.length), number(new.length), None),
+            ("minLength", number(old.min_length), number(new.min_length), Some(false)),
+            ("maxLength", number(old.max_length), number(new.max_length), Some(true)),
+            ("totalDigits", number(old.total_digits), number(new.total_digits), Some(true)),
+            (
+                "fractionDigits",
+                number(old.fraction_digits),
+                number(new.fraction_digits),
+                Some(true),
+            ),
+        ];
+        for (facet, old, new, upper) in bounds.iter() {
+            self.bound(path, facet, old, new, *upper);
+        }
+    }
+
+    // A facet that limits values from above (`upper`), from below, or to exactly its value
+    // (None). Bounds that can't be compared as numbers, e.g. dates, are breaking when changed.
+    fn bound(
+        &mut self,
+        path: &str,
+        facet: &str,
+        old: &Option<String>,
+        new: &Option<String>,
+        upper: Option<bool>,
+    ) {
+        let (severity, description) = match (old, new) {
+            (Some(old), Some(new)) if old != new => {
+                let relaxed = match (old.parse::<f64>(), new.parse::<f64>(), upper) {
+                    (Ok(old), Ok(new), Some(true)) => new > old,
+                    (Ok(old), Ok(new), Some(false)) => new < old,
+                    _ => false,
+                };
+                let severity = if relaxed { Severity::Compatible } else { Severity::Breaking };
+                (severity, format!("{} changed from {} to {}", facet, old, new))
+            }
+            (Some(old), None) => (Severity::Compatible, format!("{} {} removed", facet, old)),
+            (None, Some(new)) => (Severity::Breaking, format!("{} {} added", facet, new)),
+            _ => return,
+        };
+        self.push(severity, path, description);
+    }
+}
+
+// Elements and wildcards of a content model, and whether their order matters. Occurrences
+// are multiplied through the enclosing groups, and the alternatives of a choice are optional.
+fn slots<'a>(set: &'a SchemaSet, particles: &[&'a Particle]) -> (Vec<Slot<'a>>, bool) {
+    let mut slots = vec![];
+    let mut ordered = true;
+    for particle in particles {
+        collect_slots(set, particle, 1, Some(1), 0, &mut slots, &mut ordered);
+    }
+    (slots, ordered)
+}
+
+fn collect_slots<'a>(
+    set: &'a SchemaSet,
+    particle: &'a Particle,
+    min: usize,
+    max: Option<usize>,
+    depth: usize,
+    slots: &mut Vec<Slot<'a>>,
+    ordered: &mut bool,
+) {
+    let min = min.saturating_mul(particle.min_occurs);
+    let max = match (max, particle.max_occurs) {
+        (Some(max), Some(occurs)) => Some(max.saturating_mul(occurs)),
+        _ => None,
+    };
+    let slot = match &particle.term {
+        Term::Element(element) => Slot {
+            name: Some(&element.name),
+            element: Some(element),
+            is_ref: false,
+            any: "",
+            min,
+            max,
+        },
+        Term::ElementRef(name) => Slot {
+            name: Some(name),
+            element: set.elements.get(name),
+            is_ref: true,
+            any: "",
+            min,
+            max,
+        },
+        Term::Any { namespace, .. } => {
+            Slot { name: None, element: None, is_ref: false, any: namespace, min, max }
+        }
+        Term::Group(name) => {
+            if let (Some(group), true) = (set.groups.get(name), depth < MAX_DEPTH) {
+                collect_slots(set, group, min, max, depth + 1, slots, ordered);
+            }
+            return;
+        }
+        Term::Sequence(particles) | Term::Choice(particles) | Term::All(particles) => {
+            let min = match &particle.term {
+                Term::Choice(_) if particles.len() > 1 => 0,
+                _ => min,
+            };
+            if let Term::All(_) = particle.term {
+                *ordered = false;
+            }
+            for particle in particles {
+                collect_slots(set, particle, min, max, depth, slots, ordered);
+            }
+            return;
+        }
+    };
+
+    // An element appearing several times in the model, e.g. in a repeated sequence.
+    match slots.iter_mut().find(|s| s.matches(&slot)) {
+        Some(existing) => {
+            existing.min = existing.min.saturating_add(slot.min);
+            existing.max = match (existing.max, slot.max) {
+                (Some(a), Some(b)) => Some(a.saturating_add(b)),
+                _ => None,
+            };
+        }
+        None => slots.push(slot),
+    }
+}
+
+// Names of the elements of `slots` that are in `others` too, in order.
+fn common<'a>(slots: &[Slot<'a>], others: &[Slot<'_>]) -> Vec<&'a QName> {
+    slots.iter().filter(|s| others.iter().any(|o| o.matches(s))).filter_map(|s| s.name).collect()
+}
+
+fn named(members: &[TypeRef]) -> Vec<&QName> {
+    members
+        .iter()
+        .filter_map(|m| match m {
+            TypeRef::Named(name) => Some(name),
+            TypeRef::Anonymous(_) => None,
+        })
+        .collect()
+}
+
+fn anonymous(members: &[TypeRef]) -> Vec<&Type> {
+    members
+        .iter()
+        .filter_map(|m| match m {
+            TypeRef::Anonymous(ty) => Some(&**ty),
+            TypeRef::Named(_) => None,
+        })
+        .collect()
+}
+
+fn slot_name(set: &SchemaSet, slot: &Slot) -> String {
+    match slot.name {
+        Some(name) => format!("element `{}`", set.display_name(name)),
+        None => format!("wildcard `{}`", slot.any),
+    }
+}
+
+fn type_name(set: &SchemaSet, ty: &TypeRef) -> String {
+    match ty {
+        TypeRef::Named(name) => format!("`{}`", set.display_name(name)),
+        TypeRef::Anonymous(_) => "an anonymous type".to_string(),
+    }
+}
+
+fn variety_name(variety: &Variety) -> &'static str {
+    match variety {
+        Variety::Restriction { .. } => "restriction",
+        Variety::List { .. } => "list",
+        Variety::Union { .. } => "union",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn schema_set(text: &str) -> SchemaSet {
+        let doc = roxmltree::Document::parse(text).unwrap();
+        SchemaSet::parse(&[doc.root_element()])
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = schema_set(
+            r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tt="http://example.com/tt" targetNamespace="http://example.com/tt" elementFormDefault="qualified">
+    <xs:simpleType name="Mode">
+        <xs:restriction base="xs:string">
+            <xs:enumeration value="Auto"/>
+            <xs:enumeration value="Manual"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:complexType name="Settings">
+        <xs:sequence>
+            <xs:element name="Mode" type="tt:Mode"/>
+            <xs:element name="Level" type="xs:int"/>
+        </xs:sequence>
+        <xs:attribute name="token" type="xs:string"/>
+    </xs:complexType>
+    <xs:element name="Settings" type="tt:Settings"/>
+    <xs:element name="Legacy" type="xs:string"/>
+</xs:schema>"#,
+        );
+        let new = schema_set(
+            r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tt="http://example.com/tt" targetNamespace="http://example.com/tt" elementFormDefault="qualified">
+    <xs:simpleType name="Mode">
+        <xs:restriction base="xs:string">
+            <xs:enumeration value="Auto"/>
+            <xs:enumeration value="Off"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:complexType name="Settings">
+        <xs:sequence>
+            <xs:element name="Mode" type="tt:Mode" maxOccurs="unbounded"/>
+            <xs:element name="Level" type="xs:float"/>
+            <xs:element name="Gain" type="xs:int" minOccurs="0"/>
+            <xs:element name="Range" type="xs:int"/>
+        </xs:sequence>
+        <xs:attribute name="token" type="xs:string" use="required"/>
+    </xs:complexType>
+    <xs:element name="Settings" type="tt:Settings"/>
+</xs:schema>"#,
+        );
+
+        let changes = diff(&old, &new);
+        assert_eq!(diff(&old, &old), vec![]);
+        assert_eq!(
+            report(&changes),
+            r#"6 breaking and 2 compatible change(s).
+
+## Breaking changes
+
+- element `tt:Legacy`: removed
+- type `tt:Mode`: enumeration value `Manual` removed
+- type `tt:Settings/tt:Mode`: now repeated
+- type `tt:Settings/tt:Level`: type changed from `xs:int` to `xs:float`
+- type `tt:Settings`: required element `tt:Range` added
+- type `tt:Settings/@token`: now required
+
+## Compatible changes
+
+- type `tt:Mode`: enumeration value `Off` added
+- type `tt:Settings`: optional element `tt:Gain` added
+"#
+        );
+    }
+}
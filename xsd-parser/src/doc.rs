@@ -0,0 +1,749 @@
+use crate::model::{
+    AttributeUse, ComplexType, Derivation, Element, Facets, IdentityKind, Particle, QName,
+    SchemaSet, SimpleType, Term, Type, TypeRef, Use, Variety,
+};
+
+// Nesting of anonymous types shown inside content models.
+const MAX_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocFormat {
+    Markdown,
+    // A single static page.
+    Html,
+}
+
+// A WSDL operation to document with the schema components, its messages given as the
+// `prefix:Name` of the elements (or, for rpc style, the types) of their parts.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Operation {
+    // Port type or interface.
+    pub interface: String,
+    pub name: String,
+    pub documentation: Option<String>,
+    pub input: Vec<String>,
+    pub output: Vec<String>,
+    pub faults: Vec<String>,
+}
+
+// Documentation of all components of the schemas and the given operations on one page: an
+// index, then a section per element, complex type, simple type, group, attribute group and
+// operation with its annotation, content model, attributes, facets and inheritance chain.
+// Names of components are links to their sections.
+pub fn render(set: &SchemaSet, operations: &[Operation], title: &str, format: DocFormat) -> String {
+    let blocks = Builder { set, blocks: vec![] }.build(operations, title);
+    match format {
+        DocFormat::Markdown => markdown(&blocks),
+        DocFormat::Html => html(&blocks, title),
+    }
+}
+
+enum Block {
+    Heading(usize, Option<String>, Vec<Span>),
+    Paragraph(Vec<Span>),
+    // Items with their nesting level.
+    List(Vec<(usize, Vec<Span>)>),
+    Table(Vec<&'static str>, Vec<Vec<Vec<Span>>>),
+}
+
+enum Span {
+    Text(String),
+    Strong(String),
+    Code(String),
+    // Text and the anchor it links to.
+    Link(String, String),
+}
+
+fn text(value: &str) -> Span {
+    Span::Text(value.to_string())
+}
+
+struct Builder<'a> {
+    set: &'a SchemaSet,
+    blocks: Vec<Block>,
+}
+
+impl<'a> Builder<'a> {
+    fn build(mut self, operations: &[Operation], title: &str) -> Vec<Block> {
+        let set = self.set;
+        let complex = set.types.iter().filter(|(_, ty)| matches!(ty, Type::Complex(_)));
+        let simple = set.types.iter().filter(|(_, ty)| matches!(ty, Type::Simple(_)));
+        let sections: Vec<(&str, &str, Vec<&QName>)> = vec![
+            ("Elements", "element", set.elements.keys().collect()),
+            ("Complex types", "type", complex.map(|(name, _)| name).collect()),
+            ("Simple types", "type", simple.map(|(name, _)| name).collect()),
+            ("Groups", "group", set.groups.keys().collect()),
+            ("Attribute groups", "attribute-group", set.attribute_groups.keys().collect()),
+        ];
+
+        self.blocks.push(Block::Heading(1, None, vec![text(title)]));
+        self.blocks.push(Block::Heading(2, None, vec![text("Contents")]));
+        let mut index = vec![];
+        for (heading, kind, names) in sections.iter().filter(|(_, _, names)| !names.is_empty()) {
+            index.push((0, vec![Span::Link(heading.to_string(), anchor("section", heading))]));
+            for name in names {
+                index.push((1, vec![self.link(kind, name)]));
+            }
+        }
+        if !operations.is_empty() {
+            index.push((
+                0,
+                vec![Span::Link("Operations".to_string(), anchor("section", "Operations"))],
+            ));
+            for op in operations {
+                let name = format!("{}.{}", op.interface, op.name);
+                index.push((1, vec![Span::Link(name.clone(), anchor("operation", &name))]));
+            }
+        }
+        self.blocks.push(Block::List(index));
+
+        for (heading, kind, names) in sections {
+            if names.is_empty() {
+                continue;
+            }
+            let id = anchor("section", heading);
+            self.blocks.push(Block::Heading(2, Some(id), vec![text(heading)]));
+            for name in names {
+                let display = set.display_name(name);
+                let title = match kind {
+                    "element" => "Element",
+                    "group" => "Group",
+                    "attribute-group" => "Attribute group",
+                    _ => match set.types[name] {
+                        Type::Complex(_) => "Complex type",
+                        Type::Simple(_) => "Simple type",
+                    },
+                };
+                let heading = vec![text(&format!("{} ", title)), Span::Code(display.clone())];
+                self.blocks.push(Block::Heading(3, Some(anchor(kind, &display)), heading));
+                match kind {
+                    "element" => self.element(&set.elements[name]),
+                    "group" => {
+                        let mut items = vec![];
+                        self.particle(&set.groups[name], 0, &mut items);
+                        self.blocks.push(Block::List(items));
+                    }
+                    "attribute-group" => {
+                        let uses = set.attribute_group_uses(&set.attribute_groups[name]);
+                        self.attribute_table(&uses);
+                    }
+                    _ => self.type_(name, &set.types[name]),
+                }
+            }
+        }
+
+        if !operations.is_empty() {
+            let id = anchor("section", "Operations");
+            self.blocks.push(Block::Heading(2, Some(id), vec![text("Operations")]));
+            for op in operations {
+                self.operation(op);
+            }
+        }
+        self.blocks
+    }
+
+    fn link(&self, kind: &str, name: &QName) -> Span {
+        let display = self.set.display_name(name);
+        Span::Link(display.clone(), anchor(kind, &display))
+    }
+
+    // Link to a type, built-in and undeclared types are shown as code.
+    fn type_link(&self, ty: &TypeRef) -> Span {
+        match ty {
+            TypeRef::Named(name) if self.set.types.contains_key(name) => self.link("type", name),
+            TypeRef::Named(name) => Span::Code(self.set.display_name(name)),
+            TypeRef::Anonymous(ty) => match **ty {
+                Type::Complex(_) => text("anonymous complex type"),
+                Type::Simple(_) => text("anonymous simple type"),
+            },
+        }
+    }
+
+    fn documentation(&mut self, documentation: &Option<String>) {
+        if let Some(doc) = documentation.as_deref().map(collapse).filter(|d| !d.is_empty()) {
+            self.blocks.push(Block::Paragraph(vec![Span::Text(doc)]));
+        }
+    }
+
+    fn property(&mut self, name: &str, mut value: Vec<Span>) {
+        if !value.is_empty() {
+            value.insert(0, text(" "));
+        }
+        value.insert(0, Span::Strong(format!("{}:", name)));
+        self.blocks.push(Block::Paragraph(value));
+    }
+
+    fn element(&mut self, element: &Element) {
+        self.documentation(&element.documentation);
+        self.property("Type", vec![self.type_link(&element.ty)]);
+        if let Some(head) = &element.substitution_group {
+            self.property("Substitution group", vec![self.link("element", head)]);
+        }
+        let substitutes = self
+            .set
+            .elements
+            .values()
+            .filter(|e| e.substitution_group.as_ref() == Some(&element.name))
+            .map(|e| self.link("element", &e.name))
+            .collect::<Vec<_>>();
+        if !substitutes.is_empty() {
+            self.property("Substitution group members", separated(substitutes, ", "));
+        }
+        let flags = [(element.is_abstract, "abstract"), (element.nillable, "nillable")];
+        let flags = flags.iter().filter(|(set, _)| *set).map(|(_, f)| *f).collect::<Vec<_>>();
+        if !flags.is_empty() {
+            self.property("Properties", vec![text(&flags.join(", "))]);
+        }
+        if let Some(default) = &element.default {
+            self.property("Default", vec![Span::Code(default.clone())]);
+        }
+        if let Some(fixed) = &element.fixed {
+            self.property("Fixed", vec![Span::Code(fixed.clone())]);
+        }
+
+        if !element.identity_constraints.is_empty() {
+            let items = element
+                .identity_constraints
+                .iter()
+                .map(|c| {
+                    let mut item = match &c.kind {
+                        IdentityKind::Unique => vec![text("unique ")],
+                        IdentityKind::Key => vec![text("key ")],
+                        IdentityKind::KeyRef(_) => vec![text("keyref ")],
+                    };
+                    item.push(Span::Code(self.set.display_name(&c.name)));
+                    item.push(text(": selector "));
+                    item.push(Span::Code(c.selector.clone()));
+                    item.push(text(", fields "));
+                    item.push(Span::Code(c.fields.join(", ")));
+                    if let IdentityKind::KeyRef(refer) = &c.kind {
+                        item.push(text(", refers to "));
+                        item.push(Span::Code(self.set.display_name(refer)));
+                    }
+                    (0, item)
+                })
+                .collect();
+            self.property("Identity constraints", vec![]);
+            self.blocks.push(Block::List(items));
+        }
+
+        if let TypeRef::Anonymous(ty) = &element.ty {
+            match &**ty {
+                Type::Complex(ct) => self.complex_type(None, ct),
+                Type::Simple(st) => self.simple_type(st),
+            }
+        }
+    }
+
+    fn type_(&mut self, name: &QName, ty: &Type) {
+        match ty {
+            Type::Complex(ct) => {
+                self.documentation(&ct.documentation);
+                self.complex_type(Some(name), ct);
+            }
+            Type::Simple(st) => {
+                self.documentation(&st.documentation);
+                self.inheritance(name);
+                self.simple_type(st);
+            }
+        }
+    }
+
+    // The chain of bases of a type and the types derived from it.
+    fn inheritance(&mut self, name: &QName) {
+        let chain = self.set.base_chain(name);
+        if !chain.is_empty() {
+            let mut spans = vec![Span::Code(self.set.display_name(name))];
+            for base in &chain {
+                spans.push(text(" → "));
+                spans.push(self.type_link(&TypeRef::Named(base.clone())));
+            }
+            self.property("Inheritance", spans);
+        }
+        let derived = self
+            .set
+            .derived_types(name)
+            .into_iter()
+            .map(|d| self.link("type", d))
+            .collect::<Vec<_>>();
+        if !derived.is_empty() {
+            self.property("Derived types", separated(derived, ", "));
+        }
+    }
+
+    fn complex_type(&mut self, name: Option<&QName>, ct: &ComplexType) {
+        if let Some(name) = name {
+            self.inheritance(name);
+        }
+        if let Some((base, derivation)) = &ct.base {
+            let derivation = match derivation {
+                Derivation::Extension => "Extends",
+                Derivation::Restriction => "Restricts",
+            };
+            self.property(derivation, vec![self.type_link(&TypeRef::Named(base.clone()))]);
+        }
+        let flags = [(ct.is_abstract, "abstract"), (ct.mixed, "mixed")];
+        let flags = flags.iter().filter(|(set, _)| *set).map(|(_, f)| *f).collect::<Vec<_>>();
+        if !flags.is_empty() {
+            self.property("Properties", vec![text(&flags.join(", "))]);
+        }
+
+        if let Some(content) = self.set.simple_content(ct) {
+            let mut spans = vec![self.type_link(content)];
+            spans.extend(self.inline_facets(content));
+            self.property("Content", spans);
+        } else {
+            let mut items = vec![];
+            for particle in self.set.particles(ct) {
+                self.particle(particle, 0, &mut items);
+            }
+            if items.is_empty() {
+                self.property("Content", vec![text("empty")]);
+            } else {
+                self.property("Content", vec![]);
+                self.blocks.push(Block::List(items));
+            }
+        }
+
+        let uses = self.set.attribute_uses(ct);
+        if !uses.is_empty() {
+            self.property("Attributes", vec![]);
+            self.attribute_table(&uses);
+        }
+        if self.set.any_attribute(ct) {
+            self.blocks.push(Block::Paragraph(vec![text("Any other attribute is allowed.")]));
+        }
+    }
+
+    fn simple_type(&mut self, st: &SimpleType) {
+        match &st.variety {
+            Variety::Restriction { base, facets } => {
+                self.property("Restricts", vec![self.type_link(base)]);
+                let rows = facet_rows(facets)
+                    .into_iter()
+                    .map(|(facet, value)| vec![vec![text(facet)], vec![Span::Code(value)]])
+                    .collect::<Vec<_>>();
+                if !rows.is_empty() {
+                    self.blocks.push(Block::Table(vec!["Facet", "Value"], rows));
+                }
+            }
+            Variety::List { item } => self.property("List of", vec![self.type_link(item)]),
+            Variety::Union { members } => {
+                let members = members.iter().map(|m| self.type_link(m)).collect();
+                self.property("Union of", separated(members, ", "));
+            }
+        }
+    }
+
+    // Facets of an anonymous simple type, for content and attribute types shown in one line.
+    fn inline_facets(&self, ty: &TypeRef) -> Vec<Span> {
+        match ty {
+            TypeRef::Anonymous(ty) => match &**ty {
+                Type::Simple(SimpleType {
+                    variety: Variety::Restriction { base, facets }, ..
+                }) => {
+                    let facets = facet_rows(facets)
+                        .into_iter()
+                        .map(|(facet, value)| format!("{} {}", facet, value))
+                        .collect::<Vec<_>>();
+                    let mut spans = vec![text(" restricting "), self.type_link(base)];
+                    if !facets.is_empty() {
+                        spans.push(text(&format!(" ({})", facets.join(", "))));
+                    }
+                    spans
+                }
+                _ => vec![],
+            },
+            TypeRef::Named(_) => vec![],
+        }
+    }
+
+    fn attribute_table(&mut self, uses: &[AttributeUse]) {
+        let rows = uses
+            .iter()
+            .map(|u| {
+                let mut ty = vec![self.type_link(&u.ty)];
+                ty.extend(self.inline_facets(&u.ty));
+                let use_ = match u.use_ {
+                    Use::Optional => "optional",
+                    Use::Required => "required",
+                    Use::Prohibited => "prohibited",
+                };
+                let value = match (&u.fixed, &u.default) {
+                    (Some(fixed), _) => vec![text("fixed "), Span::Code(fixed.clone())],
+                    (_, Some(default)) => vec![text("default "), Span::Code(default.clone())],
+                    _ => vec![],
+                };
+                let doc = u.documentation.as_deref().map(collapse).unwrap_or_default();
+                vec![
+                    vec![Span::Code(self.set.display_name(&u.name))],
+                    ty,
+                    vec![text(use_)],
+                    value,
+                    vec![Span::Text(doc)],
+                ]
+            })
+            .collect();
+        self.blocks.push(Block::Table(vec!["Name", "Type", "Use", "Value", "Description"], rows));
+    }
+
+    // Content model as a nested list, anonymous types of local elements are expanded.
+    fn particle(&self, particle: &Particle, depth: usize, items: &mut Vec<(usize, Vec<Span>)>) {
+        let occurs = occurs(particle);
+        match &particle.term {
+            Term::Element(element) => {
+                let mut item = vec![Span::Code(self.set.display_name(&element.name))];
+                item.push(text(" : "));
+                item.push(self.type_link(&element.ty));
+                item.extend(self.inline_facets(&element.ty));
+                item.push(text(&occurs));
+                if let Some(fixed) = &element.fixed {
+                    item.push(text(" fixed "));
+                    item.push(Span::Code(fixed.clone()));
+                }
+                if let Some(doc) = element.documentation.as_deref().map(collapse) {
+                    item.push(text(&format!(" – {}", doc)));
+                }
+                items.push((depth, item));
+                if let TypeRef::Anonymous(ty) = &element.ty {
+                    if let (Type::Complex(ct), true) = (&**ty, depth < MAX_DEPTH) {
+                        for attribute in self.set.attribute_uses(ct) {
+                            let item = vec![
+                                Span::Code(format!("@{}", self.set.display_name(&attribute.name))),
+                                text(" : "),
+                                self.type_link(&attribute.ty),
+                            ];
+                            items.push((depth + 1, item));
+                        }
+                        for particle in self.set.particles(ct) {
+                            self.particle(particle, depth + 1, items);
+                        }
+                    }
+                }
+            }
+            Term::ElementRef(name) => {
+                items.push((depth, vec![self.link("element", name), text(&occurs)]))
+            }
+            Term::Group(name) => {
+                items.push((depth, vec![text("group "), self.link("group", name), text(&occurs)]))
+            }
+            // Empty groups, as in types without content, are left out.
+            Term::Sequence(particles) | Term::Choice(particles) | Term::All(particles)
+                if particles.is_empty() => {}
+            Term::Sequence(particles) | Term::Choice(particles) | Term::All(particles) => {
+                let kind = match &particle.term {
+                    Term::Sequence(_) => "sequence",
+                    Term::Choice(_) => "choice of",
+                    _ => "all of, in any order",
+                };
+                items.push((depth, vec![Span::Text(format!("{}{}", kind, occurs))]));
+                for particle in particles {
+                    self.particle(particle, depth + 1, items);
+                }
+            }
+            Term::Any { namespace, .. } => {
+                let any = format!("any element from {}{}", namespace, occurs);
+                items.push((depth, vec![Span::Text(any)]));
+            }
+        }
+    }
+
+    fn operation(&mut self, op: &Operation) {
+        let name = format!("{}.{}", op.interface, op.name);
+        let heading = vec![text("Operation "), Span::Code(name.clone())];
+        self.blocks.push(Block::Heading(3, Some(anchor("operation", &name)), heading));
+        self.documentation(&op.documentation);
+        for (property, parts) in
+            [("Input", &op.input), ("Output", &op.output), ("Faults", &op.faults)].iter()
+        {
+            if !parts.is_empty() {
+                let links = parts.iter().map(|p| self.component_link(p)).collect();
+                self.property(property, separated(links, ", "));
+            }
+        }
+    }
+
+    // Link to the element or type named in a WSDL message part.
+    fn component_link(&self, name: &str) -> Span {
+        let local = name.rsplit(':').next().unwrap_or(name);
+        for name in [name, local].iter() {
+            if let Some(element) = self.set.find_element(name) {
+                return self.link("element", &element.name);
+            }
+            let ty = self.set.find(&self.set.types, name).and_then(|ty| match ty {
+                Type::Complex(ct) => ct.name.as_ref(),
+                Type::Simple(st) => st.name.as_ref(),
+            });
+            if let Some(ty) = ty {
+                return self.link("type", ty);
+            }
+        }
+        Span::Code(name.to_string())
+    }
+}
+
+fn occurs(particle: &Particle) -> String {
+    match (particle.min_occurs, particle.max_occurs) {
+        (1, Some(1)) => String::new(),
+        (min, Some(max)) => format!(" [{}..{}]", min, max),
+        (min, None) => format!(" [{}..*]", min),
+    }
+}
+
+fn facet_rows(facets: &Facets) -> Vec<(&'static str, String)> {
+    let mut rows =
+        facets.enumeration.iter().map(|e| ("enumeration", e.clone())).collect::<Vec<_>>();
+    rows.extend(facets.patterns.iter().map(|p| ("pattern", p.clone())));
+    let bounds = [
+        ("minInclusive", &facets.min_inclusive),
+        ("maxInclusive", &facets.max_inclusive),
+        ("minExclusive", &facets.min_exclusive),
+        ("maxExclusive", &facets.max_exclusive),
+    ];
+    rows.extend(bounds.iter().filter_map(|(name, value)| Some((*name, (*value).clone()?))));
+    let lengths = [
+        ("length", facets.length),
+        ("minLength", facets.min_length),
+        ("maxLength", facets.max_length),
+        ("totalDigits", facets.total_digits),
+        ("fractionDigits", facets.fraction_digits),
+    ];
+    rows.extend(lengths.iter().filter_map(|(name, value)| Some((*name, (*value)?.to_string()))));
+    rows
+}
+
+fn separated(spans: Vec<Span>, separator: &str) -> Vec<Span> {
+    let mut res = vec![];
+    for (i, span) in spans.into_iter().enumerate() {
+        if i > 0 {
+            res.push(text(separator));
+        }
+        res.push(span);
+    }
+    res
+}
+
+fn anchor(kind: &str, name: &str) -> String {
+    let name = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '.' { c } else { '-' })
+        .collect::<String>();
+    format!("{}-{}", kind, name.trim_matches('-'))
+}
+
+fn collapse(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn markdown(blocks: &[Block]) -> String {
+    let spans = |spans: &[Span], table: bool| {
+        spans
+            .iter()
+            .map(|span| {
+                let escaped = |value: &str| {
+                    let value = escape_markdown(value);
+                    if table {
+                        value.replace('|', "\\|")
+                    } else {
+                        value
+                    }
+                };
+                match span {
+                    Span::Text(value) => escaped(value),
+                    Span::Strong(value) => format!("**{}**", escaped(value)),
+                    Span::Code(value) if table => format!("`{}`", value.replace('|', "\\|")),
+                    Span::Code(value) => format!("`{}`", value),
+                    Span::Link(value, anchor) => format!("[{}](#{})", escaped(value), anchor),
+                }
+            })
+            .collect::<String>()
+    };
+
+    let mut md = String::new();
+    for block in blocks {
+        match block {
+            Block::Heading(level, anchor, text) => {
+                if let Some(anchor) = anchor {
+                    md.push_str(&format!("<a id=\"{}\"></a>\n\n", anchor));
+                }
+                md.push_str(&format!("{} {}\n\n", "#".repeat(*level), spans(text, false)));
+            }
+            Block::Paragraph(text) => md.push_str(&format!("{}\n\n", spans(text, false))),
+            Block::List(items) => {
+                for (depth, text) in items {
+                    md.push_str(&format!("{}- {}\n", "  ".repeat(*depth), spans(text, false)));
+                }
+                md.push('\n');
+            }
+            Block::Table(header, rows) => {
+                md.push_str(&format!("| {} |\n", header.join(" | ")));
+                md.push_str(&format!("|{}\n", "---|".repeat(header.len())));
+                for row in rows {
+                    let cells = row.iter().map(|cell| spans(cell, true)).collect::<Vec<_>>();
+                    md.push_str(&format!("| {} |\n", cells.join(" | ")));
+                }
+                md.push('\n');
+            }
+        }
+    }
+    md
+}
+
+fn escape_markdown(value: &str) -> String {
+    let mut res = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#') {
+            res.push('\\');
+        }
+        res.push(c);
+    }
+    res
+}
+
+fn html(blocks: &[Block], title: &str) -> String {
+    let spans = |spans: &[Span]| {
+        spans
+            .iter()
+            .map(|span| match span {
+                Span::Text(value) => escape_html(value),
+                Span::Strong(value) => format!("<strong>{}</strong>", escape_html(value)),
+                Span::Code(value) => format!("<code>{}</code>", escape_html(value)),
+                Span::Link(value, anchor) => {
+                    format!("<a href=\"#{}\">{}</a>", anchor, escape_html(value))
+                }
+            })
+            .collect::<String>()
+    };
+
+    let mut page = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n\
+         body {{ font-family: sans-serif; max-width: 60em; margin: auto; }}\n\
+         table {{ border-collapse: collapse; }}\n\
+         th, td {{ border: 1px solid #ccc; padding: 0.2em 0.5em; text-align: left; }}\n\
+         </style>\n</head>\n<body>\n",
+        escape_html(title)
+    );
+    for block in blocks {
+        match block {
+            Block::Heading(level, anchor, text) => {
+                let id = anchor.as_ref().map(|a| format!(" id=\"{}\"", a)).unwrap_or_default();
+                page.push_str(&format!("<h{0}{1}>{2}</h{0}>\n", level, id, spans(text)));
+            }
+            Block::Paragraph(text) => page.push_str(&format!("<p>{}</p>\n", spans(text))),
+            Block::List(items) => {
+                // Nested lists from the levels of the items.
+                let mut open = 0;
+                for (depth, text) in items {
+                    while open <= *depth {
+                        page.push_str("<ul>\n");
+                        open += 1;
+                    }
+                    while open > depth + 1 {
+                        page.push_str("</ul>\n");
+                        open -= 1;
+                    }
+                    page.push_str(&format!("<li>{}</li>\n", spans(text)));
+                }
+                page.push_str(&"</ul>\n".repeat(open));
+            }
+            Block::Table(header, rows) => {
+                page.push_str("<table>\n<tr>");
+                for cell in header {
+                    page.push_str(&format!("<th>{}</th>", cell));
+                }
+                page.push_str("</tr>\n");
+                for row in rows {
+                    page.push_str("<tr>");
+                    for cell in row {
+                        page.push_str(&format!("<td>{}</td>", spans(cell)));
+                    }
+                    page.push_str("</tr>\n");
+                }
+                page.push_str("</table>\n");
+            }
+        }
+    }
+    page.push_str("</body>\n</html>\n");
+    page
+}
+
+fn escape_html(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let doc = roxmltree::Document::parse(
+            r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tt="http://example.com/tt" targetNamespace="http://example.com/tt" elementFormDefault="qualified">
+    <xs:simpleType name="Port">
+        <xs:restriction base="xs:int">
+            <xs:minInclusive value="1"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:complexType name="Base">
+        <xs:attribute name="token" type="xs:string" use="required"/>
+    </xs:complexType>
+    <xs:complexType name="Endpoint">
+        <xs:annotation>
+            <xs:documentation>Where a service
+                listens.</xs:documentation>
+        </xs:annotation>
+        <xs:complexContent>
+            <xs:extension base="tt:Base">
+                <xs:sequence>
+                    <xs:element name="Port" type="tt:Port" minOccurs="0"/>
+                </xs:sequence>
+            </xs:extension>
+        </xs:complexContent>
+    </xs:complexType>
+    <xs:element name="Endpoint" type="tt:Endpoint"/>
+</xs:schema>"#,
+        )
+        .unwrap();
+        let set = SchemaSet::parse(&[doc.root_element()]);
+        let operations = vec![Operation {
+            interface: "Device".to_string(),
+            name: "GetEndpoint".to_string(),
+            output: vec!["tt:Endpoint".to_string()],
+            ..Operation::default()
+        }];
+
+        let md = render(&set, &operations, "Example", DocFormat::Markdown);
+        assert!(md.contains(
+            r#"<a id="type-tt-Endpoint"></a>
+
+### Complex type `tt:Endpoint`
+
+Where a service listens.
+
+**Inheritance:** `tt:Endpoint` → [tt:Base](#type-tt-Base)
+
+**Extends:** [tt:Base](#type-tt-Base)
+
+**Content:**
+
+- sequence
+  - `tt:Port` : [tt:Port](#type-tt-Port) \[0..1\]
+
+**Attributes:**
+
+| Name | Type | Use | Value | Description |
+|---|---|---|---|---|
+| `token` | `xs:string` | required |  |  |
+"#
+        ));
+        assert!(md.contains("| minInclusive | `1` |\n"));
+        assert!(md.contains("**Output:** [tt:Endpoint](#element-tt-Endpoint)\n"));
+
+        let html = render(&set, &operations, "Example", DocFormat::Html);
+        assert!(html.contains(
+            "<ul>\n<li>sequence</li>\n<ul>\n<li><code>tt:Port</code> : \
+             <a href=\"#type-tt-Port\">tt:Port</a> [0..1]</li>\n</ul>\n</ul>\n"
+        ));
+    }
+}
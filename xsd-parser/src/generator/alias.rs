@@ -11,7 +11,7 @@ pub trait AliasGenerator {
     }
 
     fn format_comment(&self, comment: Option<&str>, gen: &Generator) -> String {
-        gen.base().format_comment(comment, 0)
+        gen.format_comment(comment, 0)
     }
 
     fn format_name(&self, name: &str, gen: &Generator) -> String {
@@ -21,10 +21,13 @@ pub trait BaseGenerator {
     }
 
     fn format_type_name(&self, type_name: &str, gen: &Generator) -> Cow<'_, str> {
+        if let Some(t) = gen.config.types.get(&gen.qualified_name(type_name)) {
+            return t.clone().into();
+        }
         if let Some(t) = match_built_in_type(type_name, &gen.xsd_ns.borrow()) {
             return t.into();
         }
-        default_format_type(type_name, &gen.target_ns.borrow())
+        default_format_type(&gen.resolve_type_name(type_name), &gen.target_ns.borrow())
     }
 
     fn format_name(&self, name: &str) -> Cow<'_, str> {
@@ -1,13 +1,16 @@
-use crate::generator::{
-    alias::{AliasGenerator, DefaultAliasGen},
-    base::{BaseGenerator, DefaultBaseGenerator},
-    enum_case::{DefaultEnumCaseGen, EnumCaseGenerator},
-    import::{DefaultImportGen, ImportGenerator},
-    r#enum::{DefaultEnumGen, EnumGenerator},
-    r#struct::{DefaultStructGen, StructGenerator},
-    struct_field::{DefaultStructFieldGen, StructFieldGenerator},
-    tuple_struct::{DefaultTupleStructGen, TupleStructGenerator},
-    Generator,
+use crate::{
+    config::Config,
+    generator::{
+        alias::{AliasGenerator, DefaultAliasGen},
+        base::{BaseGenerator, DefaultBaseGenerator},
+        enum_case::{DefaultEnumCaseGen, EnumCaseGenerator},
+        import::{DefaultImportGen, ImportGenerator},
+        r#enum::{DefaultEnumGen, EnumGenerator},
+        r#struct::{DefaultStructGen, StructGenerator},
+        struct_field::{DefaultStructFieldGen, StructFieldGenerator},
+        tuple_struct::{DefaultTupleStructGen, TupleStructGenerator},
+        Generator,
+    },
 };
 
 #[derive(Default)]
@@ -57,6 +60,16 @@ impl<'input> GeneratorBuilder<'input> {
         self
     }
 
+    pub fn with_bounded_integers(mut self, enabled: bool) -> Self {
+        self.gen.config.bounded_integers = enabled;
+        self
+    }
+
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.gen.config = config;
+        self
+    }
+
     pub fn build(self) -> Generator<'input> {
         let mut gen = self.gen;
         gen.base.get_or_insert_with(|| Box::new(DefaultBaseGenerator {})); //.set_target_ns(&gen.target_ns);
@@ -102,6 +115,7 @@ mod test {
         let gen = GeneratorBuilder::default().build();
         test_generator_state(&gen);
         assert!(gen.target_ns.borrow().is_none());
+        assert!(!gen.config.bounded_integers);
     }
 
     #[test]
@@ -63,6 +63,7 @@ pub fn default_modify_type(type_name: &str, modifiers: &[TypeModifier]) -> Cow<'
         match modifier {
             TypeModifier::Array => result = format!("Vec<{}>", result),
             TypeModifier::Option => result = format!("Option<{}>", result),
+            TypeModifier::List => result = format!("xs::XsdList<{}>", result),
             _ => (),
         }
     }
@@ -107,6 +108,20 @@ pub fn yaserde_for_element(
     }
 }
 
+pub fn yaserde_for_namespace(target_namespace: Option<&Namespace>) -> String {
+    match target_namespace {
+        Some(ns) => match ns.name() {
+            Some(prefix) => format!(
+                "#[yaserde(prefix = \"{prefix}\", namespace = \"{prefix}: {uri}\")]\n",
+                prefix = prefix,
+                uri = ns.uri()
+            ),
+            None => format!("#[yaserde(namespace = \"{}\")]\n", ns.uri()),
+        },
+        None => String::new(),
+    }
+}
+
 pub fn yaserde_for_flatten_element(indent: &str, extra: &str) -> String {
     format!("{}#[yaserde(flatten{extra})]\n", indent)
 }
@@ -200,6 +215,8 @@ mod test {
 
         assert_eq!(default_modify_type("Type", &[Recursive, Option]), "Vec<Type>");
         assert_eq!(default_modify_type("Type", &[Recursive, Array, Option]), "Vec<Type>");
+        assert_eq!(default_modify_type("Type", &[List]), "xs::XsdList<Type>");
+        assert_eq!(default_modify_type("Type", &[List, Option]), "Option<xs::XsdList<Type>>");
         assert_eq!(default_modify_type("Type", &[Recursive, Array, Empty]), "()");
     }
 }
@@ -1,8 +1,17 @@
 use std::borrow::Cow;
 
 use crate::{
-    generator::{validator::gen_validate_impl, Generator},
-    parser::types::{Enum, EnumSource},
+    config::Backend,
+    generator::{
+        default::yaserde_for_namespace,
+        utils::{resolve_whitespace, whitespace_facet},
+        validator::gen_validate_impl,
+        Generator,
+    },
+    parser::{
+        types::{Enum, EnumSource},
+        xsd_elements::WhiteSpace,
+    },
 };
 
 pub trait EnumGenerator {
@@ -98,6 +107,7 @@ type Err = std::convert::Infallible;
             {parse_enum}\n\n\
             {validation}\n\n\
             {deserialize}\n\n\
+            {serialize}\n\n\
             {subtypes}\n\n",
             comment = self.format_comment(entity, gen),
             macros = self.macros(entity, gen),
@@ -107,12 +117,20 @@ type Err = std::convert::Infallible;
             // default = default_case,
             subtypes = self.subtypes(entity, gen),
             validation = self.validation(entity, gen),
-            deserialize = self.deserialize(entity, gen)
+            deserialize = match gen.config.backend {
+                Backend::Popper => self.deserialize(entity, gen),
+                Backend::Yaserde => String::new(),
+            },
+            serialize = match gen.config.backend {
+                Backend::Popper => self.serialize(entity, gen),
+                Backend::Yaserde => String::new(),
+            }
         )
     }
 
     fn deserialize(&self, entity: &Enum, gen: &Generator) -> String {
         let mod_name = self.mod_name(entity, gen);
+        let whitespace_type = self.whitespace_type(entity, gen);
 
         let mut cases = String::new();
         let mut case_gens = String::new();
@@ -123,7 +141,8 @@ type Err = std::convert::Infallible;
                 }
             }
 
-            let (case_de, case_assign) = gen.enum_case_gen().deserialize(&case, gen);
+            let (case_de, case_assign) =
+                gen.enum_case_gen().deserialize(&case, gen, whitespace_type);
 
             cases.push_str(&case_de);
 
@@ -176,6 +195,26 @@ type Err = std::convert::Infallible;
         )
     }
 
+    fn serialize(&self, entity: &Enum, gen: &Generator) -> String {
+        let cases = entity
+            .cases
+            .iter()
+            .map(|case| gen.enum_case_gen().serialize(case, gen))
+            .collect::<String>();
+
+        format!(
+            r#"
+            impl ser::XmlSerialize for {} {{
+            fn xml_serialize<W: std::io::Write>(&self, writer: &mut ser::EventWriter<W>) -> Result<(), ser::Error> {{
+                match self {{
+                    {cases}
+                }}
+                Ok(())
+        }}}}"#,
+            self.get_name(entity, gen)
+        )
+    }
+
     fn cases(&self, entity: &Enum, gen: &Generator) -> String {
         let mod_name = self.mod_name(entity, gen);
 
@@ -227,6 +266,28 @@ type Err = std::convert::Infallible;
         }
     }
 
+    // The type normalizing values of an enumeration whose whiteSpace, of its own or of its
+    // base, doesn't preserve them, so that they match the cases.
+    fn whitespace_type(&self, entity: &Enum, gen: &Generator) -> Option<&'static str> {
+        if entity.source != EnumSource::Restriction {
+            return None;
+        }
+
+        let whitespace = match whitespace_facet(&entity.facets) {
+            Some(whitespace) => whitespace.clone(),
+            None => resolve_whitespace(
+                &entity.type_name,
+                &gen.restrictions.borrow(),
+                &gen.xsd_ns.borrow(),
+            )?,
+        };
+        match whitespace {
+            WhiteSpace::Preserve => None,
+            WhiteSpace::Replace => Some("xs::NormalizedString"),
+            WhiteSpace::Collapse => Some("xs::Token"),
+        }
+    }
+
     fn mod_name(&self, entity: &Enum, gen: &Generator) -> String {
         gen.base().mod_name(entity.name.as_str())
     }
@@ -246,35 +307,21 @@ type Err = std::convert::Infallible;
         gen.base().format_type_name(entity.name.as_str(), gen).into()
     }
 
-    fn macros(&self, entity: &Enum, _gen: &Generator) -> Cow<'static, str> {
-        if entity.source == EnumSource::Union {
-            return "#[derive(PartialEq, Debug)]".into();
-        }
-
-        let derives = "#[derive(PartialEq, Debug)]";
-        // let tns = gen.target_ns.borrow();
-        // match tns.as_ref() {
-        //     Some(tn) => match tn.name() {
-        //         Some(name) => format!(
-        //             "{derives}\n#[yaserde(prefix = \"{prefix}\", namespace = \"{prefix}: {uri}\")]",
-        //             derives = derives,
-        //             prefix = name,
-        //             uri = tn.uri()
-        //         ),
-        //         None => format!(
-        //             "{derives}\n#[yaserde(namespace = \"{uri}\")]",
-        //             derives = derives,
-        //             uri = tn.uri()
-        //         ),
-        //     },
-        //     None => format!("{derives}", derives = derives),
-        // }
-        // .into()
-        derives.into()
+    fn macros(&self, entity: &Enum, gen: &Generator) -> Cow<'static, str> {
+        let macros = match (gen.config.backend, entity.source == EnumSource::Union) {
+            (Backend::Popper, _) => gen.derive(&["PartialEq", "Debug"]),
+            (Backend::Yaserde, true) => gen.derive(&["PartialEq", "Debug", "UtilsUnionSerDe"]),
+            (Backend::Yaserde, false) => format!(
+                "{}{}",
+                gen.derive(&["PartialEq", "Debug", "YaSerialize", "YaDeserialize"]),
+                yaserde_for_namespace(gen.target_ns.borrow().as_ref())
+            ),
+        };
+        macros.trim_end().to_string().into()
     }
 
     fn format_comment(&self, entity: &Enum, gen: &Generator) -> String {
-        gen.base().format_comment(entity.comment.as_deref(), 0)
+        gen.format_comment(entity.comment.as_deref(), 0)
     }
 
     fn validation(&self, entity: &Enum, gen: &Generator) -> Cow<'static, str> {
@@ -1,6 +1,9 @@
 use crate::{
-    generator::{default::default_format_type, Generator},
-    parser::types::{EnumCase, EnumSource},
+    config::Backend,
+    generator::{
+        default::default_format_type, struct_field::wrap_modifiers, utils::split_name, Generator,
+    },
+    parser::types::{EnumCase, EnumSource, TypeModifier},
 };
 
 pub trait EnumCaseGenerator {
@@ -43,61 +46,61 @@ pub trait EnumCaseGenerator {
     }
 
     fn format_comment(&self, entity: &EnumCase, gen: &Generator) -> String {
-        gen.base().format_comment(entity.comment.as_deref(), gen.base().indent_size())
+        gen.format_comment(entity.comment.as_deref(), gen.base().indent_size())
     }
 
-    fn macros(&self, entity: &EnumCase, _gen: &Generator, _extra: &str) -> String {
-        if entity.source == EnumSource::Union {
+    fn macros(&self, entity: &EnumCase, gen: &Generator, extra: &str) -> String {
+        if entity.source == EnumSource::Union || gen.config.backend != Backend::Yaserde {
             return "".into();
         }
 
-        /*
-        let (prefix, field_name) = split_name(entity.name.as_str());
-        match prefix {
-            Some(p) => format!(
-                "{indent}#[yaserde(prefix = \"{prefix}\", rename = \"{rename}\"{extra})]\n",
-                indent = gen.base().indent(),
-                prefix = p,
-                rename = field_name
+        let indent = gen.base().indent();
+        match split_name(entity.name.as_str()) {
+            (Some(prefix), field_name) => format!(
+                "{}#[yaserde(prefix = \"{}\", rename = \"{}\"{})]\n",
+                indent, prefix, field_name, extra
             ),
-            None => {
-                if field_name == self.get_name(entity, gen) {
-                    if extra.len() > 0 {
-                        format!("{indent}#[yaserde({extra})]", indent = gen.base().indent())
-                    } else {
-                        "".into()
-                    }
+            (None, field_name) if field_name == self.get_name(entity, gen) => {
+                if extra.is_empty() {
+                    "".into()
                 } else {
-                    format!(
-                        "{indent}#[yaserde(rename = \"{rename}\"{extra})]\n",
-                        indent = gen.base().indent(),
-                        rename = field_name
-                    )
+                    format!("{}#[yaserde({})]\n", indent, extra)
                 }
             }
+            (None, field_name) => {
+                format!("{}#[yaserde(rename = \"{}\"{})]\n", indent, field_name, extra)
+            }
         }
-        */
-
-        String::new()
     }
 
-    fn deserialize(&self, case: &EnumCase, gen: &Generator) -> (String, String) {
+    // `whitespace_type` normalizes the value of an enumeration before it is compared with the
+    // case, see EnumGenerator::whitespace_type.
+    fn deserialize(
+        &self,
+        case: &EnumCase,
+        gen: &Generator,
+        whitespace_type: Option<&str>,
+    ) -> (String, String) {
         let case_getter = if case.source == EnumSource::Union || case.type_name.is_none() {
             // special case: we are just parsing the value
 
             if case.type_name.is_some() {
                 format!("{}::xml_deserialize(popper)", self.get_type_name(case, gen))
             } else {
+                let normalize = whitespace_type
+                    .map(|ty| format!("let value = value.parse::<{}>().unwrap().0;", ty))
+                    .unwrap_or_default();
                 format!(
                     r#"
                 let value = popper.pop_value()?;
+                {}
                 if value == "{}" {{
                     core::result::Result::Ok(value)
                 }} else {{
                     core::result::Result::Err(DeError::EnumMismatch)
                 }}
                 "#,
-                    case.name
+                    normalize, case.name
                 )
             }
         } else {
@@ -113,6 +116,7 @@ pub trait EnumCaseGenerator {
                     crate::parser::types::TypeModifier::Option => Some("maybe_pop_child"),
                     crate::parser::types::TypeModifier::Recursive => Some("pop_child"),
                     crate::parser::types::TypeModifier::Empty => None,
+                    crate::parser::types::TypeModifier::List => None,
                     crate::parser::types::TypeModifier::Flatten => {
                         flatten = true;
                         None
@@ -173,6 +177,28 @@ pub trait EnumCaseGenerator {
             assign,
         )
     }
+
+    // The match arm of xml_serialize writing the case: the value of an enumeration or a
+    // union, the child element of a choice.
+    fn serialize(&self, case: &EnumCase, gen: &Generator) -> String {
+        let name = self.get_name(case, gen);
+        let write = if case.type_name.is_none() {
+            return format!(
+                "Self::{} => ser::XmlSerialize::xml_serialize(\"{}\", writer)?,\n",
+                name, case.name
+            );
+        } else if case.source == EnumSource::Union
+            || case.type_modifiers.contains(&TypeModifier::Flatten)
+        {
+            "ser::XmlSerialize::xml_serialize(value, writer)?;".to_string()
+        } else {
+            wrap_modifiers(
+                &format!("ser::write_child(writer, \"{}\", value)?;", case.name),
+                &case.type_modifiers,
+            )
+        };
+        format!("Self::{}(value) => {{ {} }}\n", name, write)
+    }
 }
 
 pub struct DefaultEnumCaseGen;
@@ -11,23 +11,27 @@ pub mod tuple_struct;
 mod utils;
 pub mod validator;
 
-use std::{borrow::Borrow, cell::RefCell};
+use std::{borrow::Borrow, cell::RefCell, collections::HashMap};
 
 use roxmltree::Namespace;
 
 use crate::{
+    config::{Config, DocComments},
     generator::{
         alias::AliasGenerator, base::BaseGenerator, enum_case::EnumCaseGenerator,
         import::ImportGenerator, r#enum::EnumGenerator, r#struct::StructGenerator,
-        struct_field::StructFieldGenerator, tuple_struct::TupleStructGenerator,
+        struct_field::StructFieldGenerator, tuple_struct::TupleStructGenerator, utils::split_name,
     },
-    parser::types::{RsEntity, RsFile},
+    parser::types::{EnumSource, Facet, RsEntity, RsFile},
 };
 
 #[derive(Default)]
 pub struct Generator<'input> {
     pub target_ns: RefCell<Option<Namespace<'input>>>,
     pub xsd_ns: RefCell<Option<Namespace<'input>>>,
+    pub namespaces: RefCell<Vec<Namespace<'input>>>,
+    // Base and facets of the simple type restrictions of the schema by name, lists aside.
+    pub restrictions: RefCell<HashMap<String, (String, Vec<Facet>)>>,
 
     pub tuple_struct_gen: Option<Box<dyn TupleStructGenerator>>,
     pub struct_gen: Option<Box<dyn StructGenerator>>,
@@ -37,13 +41,100 @@ pub struct Generator<'input> {
     pub enum_gen: Option<Box<dyn EnumGenerator>>,
     pub alias_gen: Option<Box<dyn AliasGenerator>>,
     pub import_gen: Option<Box<dyn ImportGenerator>>,
+
+    pub config: Config,
 }
 
 impl<'input> Generator<'input> {
     pub fn generate_rs_file(&self, schema: &RsFile<'input>) -> String {
         *self.target_ns.borrow_mut() = schema.target_ns.clone();
         *self.xsd_ns.borrow_mut() = schema.xsd_ns.clone();
-        schema.types.iter().map(|entity| self.generate(entity)).collect()
+        *self.namespaces.borrow_mut() = schema.namespaces.clone();
+        *self.restrictions.borrow_mut() = schema
+            .types
+            .iter()
+            .filter_map(|entity| match entity {
+                RsEntity::TupleStruct(ts) if ts.type_modifiers.is_empty() => {
+                    Some((&ts.name, &ts.type_name, &ts.facets))
+                }
+                RsEntity::Enum(en) if en.source == EnumSource::Restriction => {
+                    Some((&en.name, &en.type_name, &en.facets))
+                }
+                _ => None,
+            })
+            .map(|(name, base, facets)| (name.clone(), (base.clone(), facets.clone())))
+            .collect();
+        schema
+            .types
+            .iter()
+            .enumerate()
+            .filter(|(index, entity)| self.is_generated(*index, entity, schema))
+            .map(|(_, entity)| self.generate(entity))
+            .collect()
+    }
+
+    // Types replaced by the config and global elements that are not selected are skipped.
+    fn is_generated(&self, index: usize, entity: &RsEntity, schema: &RsFile) -> bool {
+        let name = self.qualified_name(entity.name());
+        if self.config.types.contains_key(&name) {
+            return false;
+        }
+
+        match &self.config.elements {
+            Some(elements) if schema.elements.contains(&index) => elements.contains(&name),
+            _ => true,
+        }
+    }
+
+    // The name with the prefix of the target namespace if it has none, as names are
+    // written in the config.
+    pub fn qualified_name(&self, name: &str) -> String {
+        match (split_name(name), self.target_ns.borrow().as_ref().and_then(|ns| ns.name())) {
+            ((None, local), Some(prefix)) => format!("{}:{}", prefix, local),
+            _ => name.to_string(),
+        }
+    }
+
+    // Applies the renames and namespace modules of the config. Prefixes of foreign
+    // namespaces are replaced by their modules, `default_format_type` turns them into paths.
+    pub fn resolve_type_name(&self, type_name: &str) -> String {
+        if type_name.contains("::") {
+            return type_name.to_string();
+        }
+
+        let qualified = self.qualified_name(type_name);
+        let (prefix, name) = split_name(type_name);
+        let name = self.config.renames.get(&qualified).map(String::as_str).unwrap_or(name);
+
+        let target_uri = self.target_ns.borrow().as_ref().map(|ns| ns.uri().to_string());
+        let module = prefix
+            .and_then(|p| self.namespaces.borrow().iter().find(|ns| ns.name() == Some(p)).cloned())
+            .filter(|ns| Some(ns.uri()) != target_uri.as_deref())
+            .and_then(|ns| self.config.modules.get(ns.uri()).cloned());
+
+        match module.as_deref().or(prefix) {
+            Some(prefix) => format!("{}:{}", prefix, name),
+            None => name.to_string(),
+        }
+    }
+
+    pub fn format_comment(&self, comment: Option<&str>, indent: usize) -> String {
+        let comment = self.base().format_comment(comment, indent);
+        match self.config.doc_comments {
+            DocComments::Line => comment,
+            DocComments::Rustdoc => {
+                comment.lines().map(|line| format!("{}\n", line.replacen("//", "///", 1))).collect()
+            }
+            DocComments::None => String::new(),
+        }
+    }
+
+    // The derive attribute for the derives of the backend and the ones of the config.
+    pub fn derive(&self, derives: &[&str]) -> String {
+        let extra = self.config.derives.iter().filter(|d| !derives.contains(&d.as_str()));
+        let derives =
+            derives.iter().map(|d| d.to_string()).chain(extra.cloned()).collect::<Vec<_>>();
+        format!("#[derive({})]\n", derives.join(", "))
     }
 
     pub fn generate(&self, entity: &RsEntity) -> String {
@@ -75,8 +166,12 @@ impl<'input> Generator<'input> {
 #[cfg(test)]
 mod test {
     use crate::{
+        config::{Backend, Config, DocComments},
         generator::builder::GeneratorBuilder,
-        parser::types::{RsEntity, RsFile, TupleStruct},
+        parser::{
+            schema::parse_schema,
+            types::{RsEntity, RsFile, TupleStruct},
+        },
     };
 
     #[test]
@@ -98,4 +193,131 @@ mod test {
             format!("{}{}pub struct Name (pub Type);\n\n{}", comment, macros, validation);
         assert_eq!(gen.generate_rs_file(&rs_file), expected);
     }
+
+    #[test]
+    fn test_generate_rs_file_yaserde() {
+        let config = Config { backend: Backend::Yaserde, ..Default::default() };
+        let gen = GeneratorBuilder::default().with_config(config).build();
+        let mut rs_file = RsFile::default();
+        assert!(gen.generate_rs_file(&rs_file).is_empty());
+
+        rs_file.types.push(RsEntity::TupleStruct(TupleStruct {
+            name: "name".to_string(),
+            comment: Some("comment".into()),
+            type_name: "type".to_string(),
+            ..Default::default()
+        }));
+        let comment = "// comment\n";
+        let macros = "#[derive(Default, PartialEq, Debug, UtilsTupleIo, UtilsDefaultSerde)]\n";
+        let validation = "impl Validate for Name {}\n";
+        let expected =
+            format!("{}{}pub struct Name (pub Type);\n\n{}", comment, macros, validation);
+        assert_eq!(gen.generate_rs_file(&rs_file), expected);
+    }
+
+    #[test]
+    fn test_generate_with_config() {
+        let doc = roxmltree::Document::parse(
+            r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tt="http://example.com/tt" xmlns:cmn="http://example.com/common" targetNamespace="http://example.com/tt">
+    <xs:simpleType name="ReferenceToken">
+        <xs:restriction base="xs:string"/>
+    </xs:simpleType>
+    <xs:complexType name="Capabilities">
+        <xs:annotation><xs:documentation>Device capabilities.</xs:documentation></xs:annotation>
+        <xs:sequence>
+            <xs:element name="Token" type="tt:ReferenceToken"/>
+            <xs:element name="Common" type="cmn:Common"/>
+        </xs:sequence>
+    </xs:complexType>
+    <xs:element name="GetCapabilities" type="tt:Capabilities"/>
+    <xs:element name="GetServices" type="tt:Capabilities"/>
+    <xs:complexType name="Status">
+        <xs:sequence>
+            <xs:element name="Code" type="xs:int"/>
+        </xs:sequence>
+    </xs:complexType>
+    <xs:element name="Status" type="tt:Status"/>
+</xs:schema>"#,
+        )
+        .unwrap();
+        let rs_file = parse_schema(&doc.root_element());
+
+        let config = Config {
+            derives: vec!["Clone".to_string(), "Debug".to_string()],
+            doc_comments: DocComments::Rustdoc,
+            elements: Some(vec!["tt:GetServices".to_string()]),
+            modules: vec![("http://example.com/common".to_string(), "common".to_string())]
+                .into_iter()
+                .collect(),
+            types: vec![("tt:ReferenceToken".to_string(), "crate::Token".to_string())]
+                .into_iter()
+                .collect(),
+            renames: vec![("tt:Capabilities".to_string(), "DeviceCapabilities".to_string())]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        let code =
+            GeneratorBuilder::default().with_config(config).build().generate_rs_file(&rs_file);
+
+        assert!(!code.contains("pub struct ReferenceToken"));
+        assert!(code.contains("/// Device capabilities.\n#[derive(PartialEq, Debug, Clone)]\n"));
+        assert!(code.contains("pub struct DeviceCapabilities {"));
+        assert!(code.contains("pub token: crate::Token,"));
+        assert!(code.contains("pub common: common::Common,"));
+        assert!(!code.contains("pub type GetCapabilities"));
+        assert!(code.contains("pub type GetServices = DeviceCapabilities;"));
+        // Only the element of the name is skipped, not the type.
+        assert!(code.contains("pub struct Status {"));
+        assert!(!code.contains("pub type Status"));
+    }
+
+    #[test]
+    fn test_generate_whitespace_of_restriction_chains() {
+        let doc = roxmltree::Document::parse(
+            r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tt="http://example.com/tt" targetNamespace="http://example.com/tt">
+    <xs:simpleType name="Name">
+        <xs:restriction base="xs:string"/>
+    </xs:simpleType>
+    <xs:simpleType name="CollapsedName">
+        <xs:restriction base="tt:Name">
+            <xs:whiteSpace value="collapse"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:simpleType name="Mode">
+        <xs:restriction base="tt:Name">
+            <xs:enumeration value="On"/>
+            <xs:enumeration value="Off"/>
+            <xs:whiteSpace value="collapse"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:simpleType name="Level">
+        <xs:restriction base="xs:int">
+            <xs:enumeration value="1"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:simpleType name="Color">
+        <xs:restriction base="tt:Name">
+            <xs:enumeration value="Red"/>
+        </xs:restriction>
+    </xs:simpleType>
+</xs:schema>"#,
+        )
+        .unwrap();
+        let rs_file = parse_schema(&doc.root_element());
+        let code = GeneratorBuilder::default().build().generate_rs_file(&rs_file);
+        let normalize = |ty| format!("let value = value.parse::<{}>().unwrap().0;", ty);
+        let enum_code = |name| {
+            let start = code.find(&format!("impl XmlDeserialize for {} ", name)).unwrap();
+            code[start..].split("impl ser::XmlSerialize").next().unwrap().to_string()
+        };
+
+        assert!(code.contains("pub struct Name (pub std::string::String);"));
+        assert!(code.contains("pub struct CollapsedName (pub xs::Token);"));
+        assert!(enum_code("Mode").contains(&normalize("xs::Token")));
+        assert!(enum_code("Level").contains(&normalize("xs::Token")));
+        assert!(!enum_code("Color").contains("parse::<"));
+    }
 }
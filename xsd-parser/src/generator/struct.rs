@@ -1,21 +1,29 @@
 use std::borrow::Cow;
 
 use crate::{
-    generator::{validator::gen_validate_impl, Generator},
+    config::Backend,
+    generator::{default::yaserde_for_namespace, validator::gen_validate_impl, Generator},
     parser::types::Struct,
 };
 
 pub trait StructGenerator {
     fn generate(&self, entity: &Struct, gen: &Generator) -> String {
         format!(
-            "{comment}{macros}pub struct {name} {{{fields}}}\n\n{validation}\n{deserialize}\n{subtypes}\n",
+            "{comment}{macros}pub struct {name} {{{fields}}}\n\n{validation}\n{deserialize}\n{serialize}\n{subtypes}\n",
             comment = self.format_comment(entity, gen),
             macros = self.macros(entity, gen),
             name = self.get_type_name(entity, gen),
             fields = self.fields(entity, gen),
             subtypes = self.subtypes(entity, gen),
             validation = self.validation(entity, gen),
-            deserialize = self.deserialize(entity, gen),
+            deserialize = match gen.config.backend {
+                Backend::Popper => self.deserialize(entity, gen),
+                Backend::Yaserde => String::new(),
+            },
+            serialize = match gen.config.backend {
+                Backend::Popper => self.serialize(entity, gen),
+                Backend::Yaserde => String::new(),
+            },
         )
     }
 
@@ -49,6 +57,43 @@ pub trait StructGenerator {
         )
     }
 
+    fn serialize(&self, entity: &Struct, gen: &Generator) -> String {
+        let fields = entity.fields.borrow();
+        let attributes = fields
+            .iter()
+            .map(|f| gen.struct_field_gen().serialize_attribute(f, gen))
+            .collect::<String>();
+        let content =
+            fields.iter().map(|f| gen.struct_field_gen().serialize(f, gen)).collect::<String>();
+
+        let attributes = if attributes.is_empty() {
+            attributes
+        } else {
+            format!(
+                r#"
+            fn xml_attributes(&self) -> Vec<(String, String)> {{
+                let mut attributes = vec![];
+                {attributes}
+                attributes
+            }}
+            "#
+            )
+        };
+
+        format!(
+            r#"
+            impl ser::XmlSerialize for {} {{
+            {attributes}
+            fn xml_serialize<W: std::io::Write>(&self, writer: &mut ser::EventWriter<W>) -> Result<(), ser::Error> {{
+                {content}
+                Ok(())
+        }}
+    }}
+        "#,
+            self.get_type_name(entity, gen)
+        )
+    }
+
     fn fields(&self, entity: &Struct, gen: &Generator) -> String {
         let mod_name = self.mod_name(entity, gen);
 
@@ -113,31 +158,20 @@ pub trait StructGenerator {
         gen.base().format_type_name(entity.name.as_str(), gen).into()
     }
 
-    fn macros(&self, _entity: &Struct, _gen: &Generator) -> Cow<'static, str> {
-        let derives = "#[derive(PartialEq, Debug)]\n";
-        // let tns = gen.target_ns.borrow();
-        // match tns.as_ref() {
-        //     Some(tn) => match tn.name() {
-        //         Some(name) => format!(
-        //             "{derives}#[yaserde(prefix = \"{prefix}\", namespace = \"{prefix}: {uri}\")]\n",
-        //             derives = derives,
-        //             prefix = name,
-        //             uri = tn.uri()
-        //         ),
-        //         None => format!(
-        //             "{derives}#[yaserde(namespace = \"{uri}\")]\n",
-        //             derives = derives,
-        //             uri = tn.uri()
-        //         ),
-        //     },
-        //     None => format!("{derives}", derives = derives),
-        // }
-        // .into()
-        derives.into()
+    fn macros(&self, _entity: &Struct, gen: &Generator) -> Cow<'static, str> {
+        match gen.config.backend {
+            Backend::Popper => gen.derive(&["PartialEq", "Debug"]).into(),
+            Backend::Yaserde => format!(
+                "{}{}",
+                gen.derive(&["Default", "PartialEq", "Debug", "YaSerialize", "YaDeserialize"]),
+                yaserde_for_namespace(gen.target_ns.borrow().as_ref())
+            )
+            .into(),
+        }
     }
 
     fn format_comment(&self, entity: &Struct, gen: &Generator) -> String {
-        gen.base().format_comment(entity.comment.as_deref(), 0)
+        gen.format_comment(entity.comment.as_deref(), 0)
     }
 
     fn mod_name(&self, entity: &Struct, gen: &Generator) -> String {
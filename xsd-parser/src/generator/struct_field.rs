@@ -1,5 +1,9 @@
 use crate::{
-    generator::Generator,
+    config::Backend,
+    generator::{
+        default::{yaserde_for_attribute, yaserde_for_element, yaserde_for_flatten_element},
+        Generator,
+    },
     parser::types::{StructField, StructFieldSource, TypeModifier},
 };
 
@@ -38,39 +42,35 @@ pub trait StructFieldGenerator {
     }
 
     fn format_comment(&self, entity: &StructField, gen: &Generator) -> String {
-        gen.base().format_comment(entity.comment.as_deref(), gen.base().indent_size())
+        gen.format_comment(entity.comment.as_deref(), gen.base().indent_size())
     }
 
-    fn macros(&self, _entity: &StructField, _gen: &Generator, _extra: &str) -> String {
-        /*
+    fn macros(&self, entity: &StructField, gen: &Generator, extra: &str) -> String {
+        if gen.config.backend != Backend::Yaserde {
+            return String::new();
+        }
+
         let indent = gen.base().indent();
         if entity.type_modifiers.contains(&TypeModifier::Flatten) {
-            yaserde_for_flatten_element(indent.as_str(), extra)
-        } else {
-            match entity.source {
-                StructFieldSource::Choice | StructFieldSource::Sequence => {
-                    yaserde_for_flatten_element(indent.as_str(), extra)
-                }
-                StructFieldSource::Attribute => {
-                    yaserde_for_attribute(entity.name.as_str(), indent.as_str(), extra)
-                }
-                StructFieldSource::Element => yaserde_for_element(
-                    entity.name.as_str(),
-                    gen.target_ns.borrow().as_ref(),
-                    indent.as_str(),
-                    extra,
-                ),
-                _ => {
-                    if extra.len() > 0 {
-                        format!("{indent}#[yaserde({extra})]", indent = gen.base().indent())
-                    } else {
-                        "".into()
-                    }
-                }
+            return yaserde_for_flatten_element(indent.as_str(), extra);
+        }
+
+        match entity.source {
+            StructFieldSource::Choice | StructFieldSource::Sequence => {
+                yaserde_for_flatten_element(indent.as_str(), extra)
+            }
+            StructFieldSource::Attribute => {
+                yaserde_for_attribute(entity.name.as_str(), indent.as_str(), extra)
             }
+            StructFieldSource::Element => yaserde_for_element(
+                entity.name.as_str(),
+                gen.target_ns.borrow().as_ref(),
+                indent.as_str(),
+                extra,
+            ),
+            _ if !extra.is_empty() => format!("{}#[yaserde({})]\n", indent, extra),
+            _ => String::new(),
         }
-        */
-        String::new()
     }
 
     fn deserialize(&self, field: &StructField, gen: &Generator) -> String {
@@ -101,6 +101,7 @@ pub trait StructFieldGenerator {
                     None
                 }
                 crate::parser::types::TypeModifier::Empty => None,
+                crate::parser::types::TypeModifier::List => None,
                 crate::parser::types::TypeModifier::Flatten => {
                     flatten = true;
                     None
@@ -148,6 +149,71 @@ pub trait StructFieldGenerator {
             format!("{field_getter}\nlet field = inter;")
         }
     }
+
+    // Statements of xml_attributes pushing the attribute to `attributes`.
+    fn serialize_attribute(&self, field: &StructField, gen: &Generator) -> String {
+        let name = self.get_name(field, gen);
+        match field.source {
+            StructFieldSource::Attribute => format!(
+                "let value = &self.{};\n{}\n",
+                name,
+                wrap_modifiers(
+                    &format!(
+                        "attributes.push((\"{}\".to_string(), value.to_string()));",
+                        field.name
+                    ),
+                    &field.type_modifiers,
+                )
+            ),
+            StructFieldSource::Base => {
+                format!("attributes.extend(ser::XmlSerialize::xml_attributes(&self.{}));\n", name)
+            }
+            _ => String::new(),
+        }
+    }
+
+    // Statements of xml_serialize writing the field: elements as child elements, the base
+    // and flattened groups as part of the content of this element.
+    fn serialize(&self, field: &StructField, gen: &Generator) -> String {
+        if field.type_modifiers.contains(&TypeModifier::Empty) {
+            return String::new();
+        }
+
+        let name = self.get_name(field, gen);
+        let inline = field.type_modifiers.contains(&TypeModifier::Flatten)
+            || matches!(
+                field.source,
+                StructFieldSource::Base | StructFieldSource::Choice | StructFieldSource::Sequence
+            );
+        match field.source {
+            StructFieldSource::Attribute => String::new(),
+            _ if inline => format!("ser::XmlSerialize::xml_serialize(&self.{}, writer)?;\n", name),
+            _ => format!(
+                "let value = &self.{};\n{}\n",
+                name,
+                wrap_modifiers(
+                    &format!("ser::write_child(writer, \"{}\", value)?;", field.name),
+                    &field.type_modifiers,
+                )
+            ),
+        }
+    }
+}
+
+// Runs `statement` for each `value` held by `value`: once, if it is set, or for each item.
+pub fn wrap_modifiers(statement: &str, modifiers: &[TypeModifier]) -> String {
+    // Recursive types are always stored in a Vec, see default_modify_type.
+    let modifiers = if modifiers.contains(&TypeModifier::Recursive) {
+        &[TypeModifier::Array][..]
+    } else {
+        modifiers
+    };
+
+    modifiers.iter().fold(statement.to_string(), |code, modifier| match modifier {
+        TypeModifier::Array => format!("for value in value.iter() {{ {} }}", code),
+        TypeModifier::Option => format!("if let Some(value) = value {{ {} }}", code),
+        _ => code,
+    })
 }
 
 pub struct DefaultStructFieldGen;
@@ -1,15 +1,34 @@
 use std::borrow::Cow;
 
 use crate::{
+    config::Backend,
     generator::{
+        utils::{match_bounded_integer_type, match_whitespace_type},
         validator::{gen_facet_validation, gen_validate_impl},
         Generator,
     },
-    parser::{types::TupleStruct, xsd_elements::FacetType},
+    parser::{
+        types::{TupleStruct, TypeModifier},
+        xsd_elements::FacetType,
+    },
 };
 
 pub trait TupleStructGenerator {
     fn generate(&self, entity: &TupleStruct, gen: &Generator) -> String {
+        // UtilsTupleIo and UtilsDefaultSerde implement the conversions for yaserde.
+        if gen.config.backend == Backend::Yaserde {
+            return format!(
+                "{comment}{macros}pub struct {name} (pub {typename});\n{list}\n{validation}\n{subtypes}",
+                comment = self.format_comment(entity, gen),
+                name = self.get_name(entity, gen),
+                macros = self.macros(entity, gen),
+                typename = self.get_type_name(entity, gen),
+                subtypes = self.subtypes(entity, gen),
+                validation = self.validation(entity, gen),
+                list = self.list(entity, gen)
+            );
+        }
+
         let display_gen = format!(
             r#"impl std::fmt::Display for {name} {{
 {indent}fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {{
@@ -33,14 +52,38 @@ type Err = std::convert::Infallible;
         );
 
         format!(
-            "{comment}{macros}pub struct {name} (pub {typename});\n{display_gen}\n{parse_gen}\n{validation}\n{deserialize}\n{subtypes}\n",
+            "{comment}{macros}pub struct {name} (pub {typename});\n{list}{display_gen}\n{parse_gen}\n{validation}\n{deserialize}\n{serialize}\n{subtypes}\n",
             comment = self.format_comment(entity, gen),
             name = self.get_name(entity, gen),
             macros = self.macros(entity, gen),
             typename = self.get_type_name(entity, gen),
             subtypes = self.subtypes(entity, gen),
             validation = self.validation(entity, gen),
-            deserialize = self.deserialize(entity, gen)
+            deserialize = self.deserialize(entity, gen),
+            serialize = self.serialize(entity, gen),
+            list = self.list(entity, gen)
+        )
+    }
+
+    // Length facets of restrictions of this type call len() through the wrapper.
+    fn list(&self, entity: &TupleStruct, gen: &Generator) -> String {
+        if !entity.type_modifiers.contains(&TypeModifier::List) {
+            return String::new();
+        }
+
+        format!(
+            r#"impl {name} {{
+{indent}pub fn len(&self) -> usize {{
+{indent}{indent}self.0.len()
+{indent}}}
+
+{indent}pub fn is_empty(&self) -> bool {{
+{indent}{indent}self.0.is_empty()
+{indent}}}
+}}
+"#,
+            name = self.get_name(entity, gen),
+            indent = gen.base().indent()
         )
     }
 
@@ -56,25 +99,54 @@ type Err = std::convert::Infallible;
         )
     }
 
+    fn serialize(&self, entity: &TupleStruct, gen: &Generator) -> String {
+        format!(
+            r#"impl ser::XmlSerialize for {typename} {{
+    fn xml_attributes(&self) -> Vec<(String, String)> {{
+        ser::XmlSerialize::xml_attributes(&self.0)
+    }}
+
+    fn xml_serialize<W: std::io::Write>(&self, writer: &mut ser::EventWriter<W>) -> Result<(), ser::Error> {{
+        ser::XmlSerialize::xml_serialize(&self.0, writer)
+    }}
+}}"#,
+            typename = self.get_name(entity, gen)
+        )
+    }
+
     fn subtypes(&self, entity: &TupleStruct, gen: &Generator) -> String {
         gen.base().join_subtypes(entity.subtypes.as_ref(), gen)
     }
 
     fn get_type_name(&self, entity: &TupleStruct, gen: &Generator) -> String {
-        gen.base()
-            .modify_type(
-                gen.base().format_type_name(entity.type_name.as_str(), gen).as_ref(),
-                &entity.type_modifiers,
-            )
-            .into()
+        let xsd_ns = gen.xsd_ns.borrow();
+        let bounded = if gen.config.bounded_integers {
+            match_bounded_integer_type(&entity.type_name, &entity.facets, &xsd_ns)
+        } else {
+            None
+        };
+        let restrictions = gen.restrictions.borrow();
+        let whitespace =
+            || match_whitespace_type(&entity.type_name, &entity.facets, &restrictions, &xsd_ns);
+        let type_name = match bounded.or_else(whitespace) {
+            Some(t) => t.into(),
+            None => gen.base().format_type_name(entity.type_name.as_str(), gen),
+        };
+        gen.base().modify_type(type_name.as_ref(), &entity.type_modifiers).into()
     }
 
     fn get_name(&self, entity: &TupleStruct, gen: &Generator) -> String {
         gen.base().format_type_name(entity.name.as_str(), gen).into()
     }
 
-    fn macros(&self, entity: &TupleStruct, _gen: &Generator) -> Cow<'static, str> {
-        let extra = if entity.facets.iter().any(|f| {
+    fn macros(&self, entity: &TupleStruct, gen: &Generator) -> Cow<'static, str> {
+        let mut derives = match gen.config.backend {
+            Backend::Popper => vec!["Default", "PartialEq", "Debug"],
+            Backend::Yaserde => {
+                vec!["Default", "PartialEq", "Debug", "UtilsTupleIo", "UtilsDefaultSerde"]
+            }
+        };
+        let ordered = entity.facets.iter().any(|f| {
             matches!(
                 f.facet_type,
                 FacetType::MinExclusive(_)
@@ -82,20 +154,18 @@ type Err = std::convert::Infallible;
                     | FacetType::MinInclusive(_)
                     | FacetType::MaxInclusive(_)
             )
-        }) {
-            ", PartialOrd"
-        } else {
-            ""
-        };
+        });
 
         // HACK(drosen): Just to get validation working
-        let extra = if entity.type_name == "xs:decimal" { ", PartialOrd" } else { extra };
+        if ordered || entity.type_name == "xs:decimal" {
+            derives.push("PartialOrd");
+        }
 
-        format!("#[derive(Default, PartialEq, Debug{extra})]\n").into()
+        gen.derive(&derives).into()
     }
 
     fn format_comment(&self, entity: &TupleStruct, gen: &Generator) -> String {
-        gen.base().format_comment(entity.comment.as_deref(), 0)
+        gen.format_comment(entity.comment.as_deref(), 0)
     }
 
     fn validation(&self, entity: &TupleStruct, gen: &Generator) -> Cow<'static, str> {
@@ -1,5 +1,12 @@
+use std::collections::HashMap;
+
 use roxmltree::Namespace;
 
+use crate::parser::{
+    types::Facet,
+    xsd_elements::{FacetType, WhiteSpace},
+};
+
 pub fn split_comment_line(s: &str, max_len: usize, indent: usize) -> String {
     let indent_str = " ".repeat(indent);
 
@@ -25,8 +32,8 @@ pub fn match_built_in_type(type_name: &str, xsd_ns: &Option<Namespace>) -> Optio
         return None;
     }
     let res = match name {
-        "hexBinary" => "std::string::String",
-        "base64Binary" => "std::string::String",
+        "hexBinary" => "xs::HexBinary",
+        "base64Binary" => "xs::Base64Binary",
 
         "boolean" => "bool",
 
@@ -48,8 +55,8 @@ pub fn match_built_in_type(type_name: &str, xsd_ns: &Option<Namespace>) -> Optio
 
         "decimal" => "xs::Decimal",
 
-        "double" => "f64",
-        "float" => "f64",
+        "double" => "xs::Double",
+        "float" => "xs::Float",
 
         "date" => "xs::Date",
         "time" => "xs::Time",
@@ -65,26 +72,26 @@ pub fn match_built_in_type(type_name: &str, xsd_ns: &Option<Namespace>) -> Optio
         "gYearMonth" => "xs::GYearMonth",
 
         "string" => "std::string::String",
-        "normalizedString" => "std::string::String",
-        "token" => "std::string::String",
-        "language" => "std::string::String",
-        "Name" => "std::string::String",
-        "NCName" => "std::string::String",
+        "normalizedString" => "xs::NormalizedString",
+        "token" => "xs::Token",
+        "language" => "xs::Language",
+        "Name" => "xs::Name",
+        "NCName" => "xs::NcName",
         "ENTITY" => "std::string::String",
-        "ID" => "std::string::String",
-        "IDREF" => "std::string::String",
-        "NMTOKEN" => "std::string::String",
-        "anyURI" => "std::string::String",
-        "QName" => "std::string::String",
+        "ID" => "xs::Id",
+        "IDREF" => "xs::IdRef",
+        "NMTOKEN" => "xs::NmToken",
+        "anyURI" => "xs::AnyUri",
+        "QName" => "xs::QName",
 
-        "NOTATION" => "std::string::String",
+        "NOTATION" => "xs::QName",
 
         "anySimpleType" => "std::string::String",
 
         // Built-in list types:
-        "ENTITIES" => "Vec<std::string::String>",
-        "IDREFS" => "Vec<std::string::String>",
-        "NMTOKENS" => "Vec<std::string::String>",
+        "ENTITIES" => "xs::XsdList<std::string::String>",
+        "IDREFS" => "xs::XsdList<xs::IdRef>",
+        "NMTOKENS" => "xs::XsdList<xs::NmToken>",
         _ => "",
     };
 
@@ -95,6 +102,111 @@ pub fn match_built_in_type(type_name: &str, xsd_ns: &Option<Namespace>) -> Optio
     }
 }
 
+// Maps integer types to i64 or u64 when the facets, together with the bounds implied by the
+// type itself, keep every value in range.
+pub fn match_bounded_integer_type(
+    type_name: &str,
+    facets: &[Facet],
+    xsd_ns: &Option<Namespace>,
+) -> Option<&'static str> {
+    let (prefix, name) = split_name(type_name);
+    if xsd_ns.as_ref().and_then(|ns| ns.name()) != prefix {
+        return None;
+    }
+
+    let (mut lower, mut upper) = match name {
+        "integer" => (None, None),
+        "nonNegativeInteger" => (Some(0), None),
+        "positiveInteger" => (Some(1), None),
+        "nonPositiveInteger" => (None, Some(0)),
+        "negativeInteger" => (None, Some(-1)),
+        _ => return None,
+    };
+
+    for facet in facets {
+        // A value out of the range of i128 can't be represented by either type anyway.
+        let parse = |v: &str| v.trim().parse::<i128>().ok();
+        match &facet.facet_type {
+            FacetType::MinInclusive(v) => lower = tighter(lower, parse(v)?, i128::max),
+            FacetType::MinExclusive(v) => {
+                lower = tighter(lower, parse(v)?.checked_add(1)?, i128::max)
+            }
+            FacetType::MaxInclusive(v) => upper = tighter(upper, parse(v)?, i128::min),
+            FacetType::MaxExclusive(v) => {
+                upper = tighter(upper, parse(v)?.checked_sub(1)?, i128::min)
+            }
+            _ => {}
+        }
+    }
+
+    let (lower, upper) = (lower?, upper?);
+    let is_signed = matches!(name, "integer" | "nonPositiveInteger" | "negativeInteger");
+    if is_signed && lower >= i64::MIN as i128 && upper <= i64::MAX as i128 {
+        Some("i64")
+    } else if lower >= 0 && upper <= u64::MAX as i128 {
+        Some("u64")
+    } else {
+        None
+    }
+}
+
+// A facet can only narrow the range of its base type.
+fn tighter(bound: Option<i128>, value: i128, pick: fn(i128, i128) -> i128) -> Option<i128> {
+    Some(bound.map_or(value, |bound| pick(bound, value)))
+}
+
+// Maps restrictions of types derived from string and normalizedString to the type that
+// applies their whiteSpace facet. Other built-ins already collapse whitespace.
+pub fn match_whitespace_type(
+    type_name: &str,
+    facets: &[Facet],
+    restrictions: &HashMap<String, (String, Vec<Facet>)>,
+    xsd_ns: &Option<Namespace>,
+) -> Option<&'static str> {
+    let base = resolve_whitespace(type_name, restrictions, xsd_ns)?;
+    match (whitespace_facet(facets)?, base) {
+        (WhiteSpace::Replace, WhiteSpace::Preserve) => Some("xs::NormalizedString"),
+        (WhiteSpace::Collapse, WhiteSpace::Preserve | WhiteSpace::Replace) => Some("xs::Token"),
+        _ => None,
+    }
+}
+
+// The whiteSpace values of the type are normalized with: the facet of the closest
+// restriction in the chain of bases that has one, or the one of the built-in it ends at.
+pub fn resolve_whitespace(
+    type_name: &str,
+    restrictions: &HashMap<String, (String, Vec<Facet>)>,
+    xsd_ns: &Option<Namespace>,
+) -> Option<WhiteSpace> {
+    let mut type_name = type_name;
+    // A chain longer than the restrictions has a cycle.
+    for _ in 0..=restrictions.len() {
+        let (prefix, name) = split_name(type_name);
+        if xsd_ns.as_ref().and_then(|ns| ns.name()) == prefix {
+            return match name {
+                "string" => Some(WhiteSpace::Preserve),
+                "normalizedString" => Some(WhiteSpace::Replace),
+                "anyType" | "anySimpleType" => None,
+                _ => Some(WhiteSpace::Collapse),
+            };
+        }
+
+        let (base, facets) = restrictions.get(name)?;
+        if let Some(whitespace) = whitespace_facet(facets) {
+            return Some(whitespace.clone());
+        }
+        type_name = base;
+    }
+    None
+}
+
+pub fn whitespace_facet(facets: &[Facet]) -> Option<&WhiteSpace> {
+    facets.iter().find_map(|f| match &f.facet_type {
+        FacetType::WhiteSpace(whitespace) => Some(whitespace),
+        _ => None,
+    })
+}
+
 pub fn sanitize(s: String) -> String {
     if s.is_empty() {
         s
@@ -182,7 +294,18 @@ const RS_KEYWORDS: &[&str] = &[
 
 #[cfg(test)]
 mod test {
-    use crate::generator::utils::{filter_type_name, match_built_in_type, split_name};
+    use std::collections::HashMap;
+
+    use crate::{
+        generator::utils::{
+            filter_type_name, match_bounded_integer_type, match_built_in_type,
+            match_whitespace_type, resolve_whitespace, split_name,
+        },
+        parser::{
+            types::Facet,
+            xsd_elements::{FacetType, WhiteSpace::*},
+        },
+    };
 
     #[test]
     fn test_filter_type_name() {
@@ -228,4 +351,100 @@ mod test {
         assert_eq!(match_type("xsd:string"), Some("String"));
         assert!(match_type("xs:string").is_none());
     }
+
+    #[test]
+    fn test_match_bounded_integer_types() {
+        let xsd_ns = Some(
+            roxmltree::Document::parse(
+                r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"/>"#,
+            )
+            .unwrap()
+            .root_element()
+            .namespaces()
+            .next()
+            .cloned()
+            .unwrap(),
+        );
+
+        let facet = |facet_type| Facet { facet_type, comment: None };
+        let match_type = |name, facets: &[Facet]| match_bounded_integer_type(name, facets, &xsd_ns);
+
+        let percent = [
+            facet(FacetType::MinInclusive("0".into())),
+            facet(FacetType::MaxInclusive("100".into())),
+        ];
+        assert_eq!(match_type("xs:integer", &percent), Some("i64"));
+        assert_eq!(match_type("xs:nonNegativeInteger", &percent), Some("u64"));
+        assert_eq!(match_type("xs:decimal", &percent), None);
+        assert_eq!(match_type("xsd:integer", &percent), None);
+
+        let max = [facet(FacetType::MaxExclusive("1000".into()))];
+        assert_eq!(match_type("xs:positiveInteger", &max), Some("u64"));
+        assert_eq!(match_type("xs:integer", &max), None);
+
+        let min = [facet(FacetType::MinInclusive("-1000".into()))];
+        assert_eq!(match_type("xs:negativeInteger", &min), Some("i64"));
+        assert_eq!(match_type("xs:nonNegativeInteger", &min), None);
+
+        let unsigned = [facet(FacetType::MaxInclusive("18446744073709551615".into()))];
+        assert_eq!(match_type("xs:nonNegativeInteger", &unsigned), Some("u64"));
+
+        let huge = [facet(FacetType::MaxInclusive("18446744073709551616".into()))];
+        assert_eq!(match_type("xs:nonNegativeInteger", &huge), None);
+
+        // A facet below the bound of the base type doesn't widen it.
+        let wide = [
+            facet(FacetType::MinInclusive("-5".into())),
+            facet(FacetType::MaxInclusive("100".into())),
+        ];
+        assert_eq!(match_type("xs:nonNegativeInteger", &wide), Some("u64"));
+
+        let overflow = [
+            facet(FacetType::MinExclusive(i128::MAX.to_string())),
+            facet(FacetType::MaxExclusive(i128::MIN.to_string())),
+        ];
+        assert_eq!(match_type("xs:integer", &overflow), None);
+    }
+
+    #[test]
+    fn test_match_whitespace_types() {
+        let xsd_ns = Some(
+            roxmltree::Document::parse(
+                r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"/>"#,
+            )
+            .unwrap()
+            .root_element()
+            .namespaces()
+            .next()
+            .cloned()
+            .unwrap(),
+        );
+
+        let whitespace = |ws| vec![Facet { facet_type: FacetType::WhiteSpace(ws), comment: None }];
+        let mut restrictions = HashMap::new();
+        restrictions.insert("Name".to_string(), ("xs:string".to_string(), vec![]));
+        restrictions.insert("Line".to_string(), ("tt:Name".to_string(), whitespace(Replace)));
+        restrictions.insert("Code".to_string(), ("xs:int".to_string(), vec![]));
+        let match_type =
+            |name, facets: &[Facet]| match_whitespace_type(name, facets, &restrictions, &xsd_ns);
+
+        assert_eq!(match_type("xs:string", &whitespace(Replace)), Some("xs::NormalizedString"));
+        assert_eq!(match_type("xs:string", &whitespace(Collapse)), Some("xs::Token"));
+        assert_eq!(match_type("xs:normalizedString", &whitespace(Collapse)), Some("xs::Token"));
+        assert_eq!(match_type("xs:string", &whitespace(Preserve)), None);
+        assert_eq!(match_type("xs:token", &whitespace(Collapse)), None);
+        assert_eq!(match_type("xs:string", &[]), None);
+
+        // Restrictions of user types are resolved down to the built-in.
+        assert_eq!(match_type("tt:Name", &whitespace(Replace)), Some("xs::NormalizedString"));
+        assert_eq!(match_type("tt:Line", &whitespace(Collapse)), Some("xs::Token"));
+        assert_eq!(match_type("tt:Line", &whitespace(Replace)), None);
+        assert_eq!(match_type("tt:Code", &whitespace(Collapse)), None);
+        assert_eq!(match_type("tt:Unknown", &whitespace(Collapse)), None);
+
+        assert_eq!(resolve_whitespace("tt:Line", &restrictions, &xsd_ns), Some(Replace));
+        assert_eq!(resolve_whitespace("tt:Code", &restrictions, &xsd_ns), Some(Collapse));
+        restrictions.insert("Loop".to_string(), ("tt:Loop".to_string(), vec![]));
+        assert_eq!(resolve_whitespace("tt:Loop", &restrictions, &xsd_ns), None);
+    }
 }
@@ -0,0 +1,834 @@
+use std::{
+    cell::RefCell,
+    convert::{TryFrom, TryInto},
+    fmt,
+};
+
+use roxmltree::{Document, Namespace};
+use serde::{Deserialize, Serialize};
+
+use crate::parser::{
+    types::{
+        Alias, Enum, EnumCase, EnumSource, Facet, Import, RsEntity, RsFile, Struct, StructField,
+        StructFieldSource, TupleStruct, TypeModifier,
+    },
+    xsd_elements::{FacetType, WhiteSpace},
+};
+
+// Version of the JSON layout, bumped when a change breaks existing documents.
+pub const VERSION: u32 = 1;
+
+// Serializable form of an RsFile, as the generator sees it after parse_schemas has merged
+// bases and groups into the types that use them. The structs of this module define the
+// layout, so that it only changes with them and not with the parser types:
+//
+//     {
+//       "version": 1,
+//       "name": "",
+//       "namespace": null,
+//       "target_ns": { "prefix": "tt", "uri": "http://www.onvif.org/ver10/schema" },
+//       "xsd_ns": { "prefix": "xs", "uri": "http://www.w3.org/2001/XMLSchema" },
+//       "namespaces": [ ... ],
+//       "elements": [0],
+//       "types": [{ "kind": "struct", "name": "Capabilities", "fields": [ ... ], ... }],
+//       "attribute_groups": [ ... ],
+//       "groups": [ ... ]
+//     }
+//
+// Entities carry their kind in "kind". Type names are written as in the schema, with the
+// prefixes of `namespaces`. Optional values are null when absent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IrFile {
+    pub version: u32,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub target_ns: Option<IrNamespace>,
+    pub xsd_ns: Option<IrNamespace>,
+    pub namespaces: Vec<IrNamespace>,
+    // Indices of the global elements in `types`.
+    pub elements: Vec<usize>,
+    pub types: Vec<IrEntity>,
+    pub attribute_groups: Vec<IrEntity>,
+    pub groups: Vec<IrEntity>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IrNamespace {
+    pub prefix: Option<String>,
+    pub uri: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IrEntity {
+    Struct(IrStruct),
+    Field(IrField),
+    TupleStruct(IrTupleStruct),
+    Enum(IrEnum),
+    EnumCase(IrEnumCase),
+    Alias(IrAlias),
+    Import(IrImport),
+}
+
+// A complex type. `subtypes` are the anonymous types of its fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IrStruct {
+    pub name: String,
+    pub comment: Option<String>,
+    pub fields: Vec<IrField>,
+    pub attribute_groups: Vec<IrAlias>,
+    pub groups: Vec<IrAlias>,
+    pub subtypes: Vec<IrEntity>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IrField {
+    pub name: String,
+    pub type_name: String,
+    pub comment: Option<String>,
+    pub source: IrFieldSource,
+    pub modifiers: Vec<IrModifier>,
+    pub subtypes: Vec<IrEntity>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IrFieldSource {
+    Attribute,
+    Element,
+    Base,
+    Choice,
+    Sequence,
+    Other,
+}
+
+// How the type of a field, case or alias wraps its type_name, from the inside out.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IrModifier {
+    None,
+    Array,
+    Option,
+    Recursive,
+    Empty,
+    Flatten,
+    List,
+}
+
+// A simple type wrapping type_name, restricted by its facets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IrTupleStruct {
+    pub name: String,
+    pub type_name: String,
+    pub comment: Option<String>,
+    pub modifiers: Vec<IrModifier>,
+    pub facets: Vec<IrFacet>,
+    pub subtypes: Vec<IrEntity>,
+}
+
+// A facet by its name in XSD, e.g. { "facet": "minInclusive", "value": "1" }. The value of
+// whiteSpace is "preserve", "replace" or "collapse".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IrFacet {
+    pub facet: IrFacetKind,
+    pub value: String,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IrFacetKind {
+    Enumeration,
+    FractionDigits,
+    Length,
+    MaxExclusive,
+    MaxInclusive,
+    MaxLength,
+    MinExclusive,
+    MinInclusive,
+    MinLength,
+    Pattern,
+    TotalDigits,
+    WhiteSpace,
+}
+
+// An enumeration, union or choice, told apart by `source`. `facets` are the facets of an
+// enumeration other than its values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IrEnum {
+    pub name: String,
+    pub type_name: String,
+    pub comment: Option<String>,
+    pub source: IrEnumSource,
+    pub cases: Vec<IrEnumCase>,
+    pub modifiers: Vec<IrModifier>,
+    pub facets: Vec<IrFacet>,
+    pub subtypes: Vec<IrEntity>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IrEnumSource {
+    Restriction,
+    Choice,
+    Union,
+    Other,
+}
+
+// A case holds a value of type_name, or none for the values of an enumeration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IrEnumCase {
+    pub name: String,
+    pub value: String,
+    pub type_name: Option<String>,
+    pub comment: Option<String>,
+    pub source: IrEnumSource,
+    pub modifiers: Vec<IrModifier>,
+    pub subtypes: Vec<IrEntity>,
+}
+
+// A type named after another one, or a reference to a group.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IrAlias {
+    pub name: String,
+    pub original: String,
+    pub comment: Option<String>,
+    pub modifiers: Vec<IrModifier>,
+    pub subtypes: Vec<IrEntity>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IrImport {
+    pub name: String,
+    pub location: String,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum IrError {
+    Json(serde_json::Error),
+    Version(u32),
+    WhiteSpace(String),
+}
+
+impl fmt::Display for IrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IrError::Json(e) => write!(f, "{}", e),
+            IrError::Version(v) => write!(f, "unsupported IR version {}, expected {}", v, VERSION),
+            IrError::WhiteSpace(v) => write!(f, "invalid whiteSpace value {}", v),
+        }
+    }
+}
+
+impl std::error::Error for IrError {}
+
+impl From<&Namespace<'_>> for IrNamespace {
+    fn from(ns: &Namespace<'_>) -> Self {
+        IrNamespace { prefix: ns.name().map(str::to_string), uri: ns.uri().to_string() }
+    }
+}
+
+impl From<&RsFile<'_>> for IrFile {
+    fn from(file: &RsFile<'_>) -> Self {
+        IrFile {
+            version: VERSION,
+            name: file.name.clone(),
+            namespace: file.namespace.clone(),
+            target_ns: file.target_ns.as_ref().map(IrNamespace::from),
+            xsd_ns: file.xsd_ns.as_ref().map(IrNamespace::from),
+            namespaces: file.namespaces.iter().map(IrNamespace::from).collect(),
+            elements: file.elements.clone(),
+            types: ir_entities(&file.types),
+            attribute_groups: ir_entities(&file.attribute_groups),
+            groups: ir_entities(&file.groups),
+        }
+    }
+}
+
+impl IrFile {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("IR is always serializable")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, IrError> {
+        let file: IrFile = serde_json::from_str(json).map_err(IrError::Json)?;
+        if file.version != VERSION {
+            return Err(IrError::Version(file.version));
+        }
+        Ok(file)
+    }
+
+    // roxmltree namespaces can only be created by parsing, so the namespaces of the file
+    // are declared on an empty element that to_rs_file reads them from:
+    //
+    //     let xml = ir.namespace_document();
+    //     let doc = roxmltree::Document::parse(&xml)?;
+    //     let rs_file = ir.to_rs_file(&doc);
+    pub fn namespace_document(&self) -> String {
+        let mut xml = String::from("<ir");
+        let declared = self.namespaces.iter().chain(&self.target_ns).chain(&self.xsd_ns);
+        let mut prefixes = vec![];
+        for ns in declared {
+            if ns.prefix.as_deref() == Some("xml") || prefixes.contains(&&ns.prefix) {
+                continue;
+            }
+            prefixes.push(&ns.prefix);
+            match &ns.prefix {
+                Some(prefix) => xml.push_str(&format!(" xmlns:{}=\"{}\"", prefix, escape(&ns.uri))),
+                None => xml.push_str(&format!(" xmlns=\"{}\"", escape(&ns.uri))),
+            }
+        }
+        xml.push_str("/>");
+        xml
+    }
+
+    pub fn to_rs_file<'input>(
+        &self,
+        namespace_document: &Document<'input>,
+    ) -> Result<RsFile<'input>, IrError> {
+        let in_scope = namespace_document.root_element().namespaces().cloned().collect::<Vec<_>>();
+        let find = |ns: &IrNamespace| {
+            in_scope.iter().find(|n| n.name() == ns.prefix.as_deref() && n.uri() == ns.uri).cloned()
+        };
+
+        Ok(RsFile {
+            name: self.name.clone(),
+            namespace: self.namespace.clone(),
+            types: rs_entities(&self.types)?,
+            attribute_groups: rs_entities(&self.attribute_groups)?,
+            groups: rs_entities(&self.groups)?,
+            target_ns: self.target_ns.as_ref().and_then(find),
+            xsd_ns: self.xsd_ns.as_ref().and_then(find),
+            namespaces: self.namespaces.iter().filter_map(find).collect(),
+            elements: self.elements.clone(),
+        })
+    }
+}
+
+fn ir_entities(entities: &[RsEntity]) -> Vec<IrEntity> {
+    entities.iter().map(IrEntity::from).collect()
+}
+
+fn rs_entities(entities: &[IrEntity]) -> Result<Vec<RsEntity>, IrError> {
+    entities.iter().map(RsEntity::try_from).collect()
+}
+
+fn ir_modifiers(modifiers: &[TypeModifier]) -> Vec<IrModifier> {
+    modifiers.iter().map(IrModifier::from).collect()
+}
+
+fn rs_modifiers(modifiers: &[IrModifier]) -> Vec<TypeModifier> {
+    modifiers.iter().map(TypeModifier::from).collect()
+}
+
+impl From<&RsEntity> for IrEntity {
+    fn from(entity: &RsEntity) -> Self {
+        match entity {
+            RsEntity::Struct(st) => IrEntity::Struct(st.into()),
+            RsEntity::StructField(field) => IrEntity::Field(field.into()),
+            RsEntity::TupleStruct(ts) => IrEntity::TupleStruct(ts.into()),
+            RsEntity::Enum(en) => IrEntity::Enum(en.into()),
+            RsEntity::EnumCase(case) => IrEntity::EnumCase(case.into()),
+            RsEntity::Alias(alias) => IrEntity::Alias(alias.into()),
+            RsEntity::Import(import) => IrEntity::Import(import.into()),
+        }
+    }
+}
+
+impl TryFrom<&IrEntity> for RsEntity {
+    type Error = IrError;
+
+    fn try_from(entity: &IrEntity) -> Result<Self, Self::Error> {
+        Ok(match entity {
+            IrEntity::Struct(st) => RsEntity::Struct(st.try_into()?),
+            IrEntity::Field(field) => RsEntity::StructField(field.try_into()?),
+            IrEntity::TupleStruct(ts) => RsEntity::TupleStruct(ts.try_into()?),
+            IrEntity::Enum(en) => RsEntity::Enum(en.try_into()?),
+            IrEntity::EnumCase(case) => RsEntity::EnumCase(case.try_into()?),
+            IrEntity::Alias(alias) => RsEntity::Alias(alias.try_into()?),
+            IrEntity::Import(import) => RsEntity::Import(import.into()),
+        })
+    }
+}
+
+impl From<&Struct> for IrStruct {
+    fn from(st: &Struct) -> Self {
+        IrStruct {
+            name: st.name.clone(),
+            comment: st.comment.clone(),
+            fields: st.fields.borrow().iter().map(IrField::from).collect(),
+            attribute_groups: st.attribute_groups.borrow().iter().map(IrAlias::from).collect(),
+            groups: st.groups.borrow().iter().map(IrAlias::from).collect(),
+            subtypes: ir_entities(&st.subtypes),
+        }
+    }
+}
+
+impl TryFrom<&IrStruct> for Struct {
+    type Error = IrError;
+
+    fn try_from(st: &IrStruct) -> Result<Self, Self::Error> {
+        let fields = st.fields.iter().map(StructField::try_from).collect::<Result<_, _>>()?;
+        let aliases = |aliases: &[IrAlias]| {
+            aliases.iter().map(Alias::try_from).collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Struct {
+            name: st.name.clone(),
+            comment: st.comment.clone(),
+            fields: RefCell::new(fields),
+            attribute_groups: RefCell::new(aliases(&st.attribute_groups)?),
+            groups: RefCell::new(aliases(&st.groups)?),
+            subtypes: rs_entities(&st.subtypes)?,
+        })
+    }
+}
+
+impl From<&StructField> for IrField {
+    fn from(field: &StructField) -> Self {
+        IrField {
+            name: field.name.clone(),
+            type_name: field.type_name.clone(),
+            comment: field.comment.clone(),
+            source: (&field.source).into(),
+            modifiers: ir_modifiers(&field.type_modifiers),
+            subtypes: ir_entities(&field.subtypes),
+        }
+    }
+}
+
+impl TryFrom<&IrField> for StructField {
+    type Error = IrError;
+
+    fn try_from(field: &IrField) -> Result<Self, Self::Error> {
+        Ok(StructField {
+            name: field.name.clone(),
+            type_name: field.type_name.clone(),
+            comment: field.comment.clone(),
+            subtypes: rs_entities(&field.subtypes)?,
+            source: field.source.into(),
+            type_modifiers: rs_modifiers(&field.modifiers),
+        })
+    }
+}
+
+impl From<&StructFieldSource> for IrFieldSource {
+    fn from(source: &StructFieldSource) -> Self {
+        match source {
+            StructFieldSource::Attribute => IrFieldSource::Attribute,
+            StructFieldSource::Element => IrFieldSource::Element,
+            StructFieldSource::Base => IrFieldSource::Base,
+            StructFieldSource::Choice => IrFieldSource::Choice,
+            StructFieldSource::Sequence => IrFieldSource::Sequence,
+            StructFieldSource::NA => IrFieldSource::Other,
+        }
+    }
+}
+
+impl From<IrFieldSource> for StructFieldSource {
+    fn from(source: IrFieldSource) -> Self {
+        match source {
+            IrFieldSource::Attribute => StructFieldSource::Attribute,
+            IrFieldSource::Element => StructFieldSource::Element,
+            IrFieldSource::Base => StructFieldSource::Base,
+            IrFieldSource::Choice => StructFieldSource::Choice,
+            IrFieldSource::Sequence => StructFieldSource::Sequence,
+            IrFieldSource::Other => StructFieldSource::NA,
+        }
+    }
+}
+
+impl From<&TypeModifier> for IrModifier {
+    fn from(modifier: &TypeModifier) -> Self {
+        match modifier {
+            TypeModifier::None => IrModifier::None,
+            TypeModifier::Array => IrModifier::Array,
+            TypeModifier::Option => IrModifier::Option,
+            TypeModifier::Recursive => IrModifier::Recursive,
+            TypeModifier::Empty => IrModifier::Empty,
+            TypeModifier::Flatten => IrModifier::Flatten,
+            TypeModifier::List => IrModifier::List,
+        }
+    }
+}
+
+impl From<&IrModifier> for TypeModifier {
+    fn from(modifier: &IrModifier) -> Self {
+        match modifier {
+            IrModifier::None => TypeModifier::None,
+            IrModifier::Array => TypeModifier::Array,
+            IrModifier::Option => TypeModifier::Option,
+            IrModifier::Recursive => TypeModifier::Recursive,
+            IrModifier::Empty => TypeModifier::Empty,
+            IrModifier::Flatten => TypeModifier::Flatten,
+            IrModifier::List => TypeModifier::List,
+        }
+    }
+}
+
+impl From<&TupleStruct> for IrTupleStruct {
+    fn from(ts: &TupleStruct) -> Self {
+        IrTupleStruct {
+            name: ts.name.clone(),
+            type_name: ts.type_name.clone(),
+            comment: ts.comment.clone(),
+            modifiers: ir_modifiers(&ts.type_modifiers),
+            facets: ts.facets.iter().map(IrFacet::from).collect(),
+            subtypes: ir_entities(&ts.subtypes),
+        }
+    }
+}
+
+impl TryFrom<&IrTupleStruct> for TupleStruct {
+    type Error = IrError;
+
+    fn try_from(ts: &IrTupleStruct) -> Result<Self, Self::Error> {
+        Ok(TupleStruct {
+            name: ts.name.clone(),
+            comment: ts.comment.clone(),
+            type_name: ts.type_name.clone(),
+            subtypes: rs_entities(&ts.subtypes)?,
+            type_modifiers: rs_modifiers(&ts.modifiers),
+            facets: ts.facets.iter().map(Facet::try_from).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Facet> for IrFacet {
+    fn from(facet: &Facet) -> Self {
+        let (kind, value) = match &facet.facet_type {
+            FacetType::Enumeration(v) => (IrFacetKind::Enumeration, v.as_str()),
+            FacetType::FractionDigits(v) => (IrFacetKind::FractionDigits, v.as_str()),
+            FacetType::Length(v) => (IrFacetKind::Length, v.as_str()),
+            FacetType::MaxExclusive(v) => (IrFacetKind::MaxExclusive, v.as_str()),
+            FacetType::MaxInclusive(v) => (IrFacetKind::MaxInclusive, v.as_str()),
+            FacetType::MaxLength(v) => (IrFacetKind::MaxLength, v.as_str()),
+            FacetType::MinExclusive(v) => (IrFacetKind::MinExclusive, v.as_str()),
+            FacetType::MinInclusive(v) => (IrFacetKind::MinInclusive, v.as_str()),
+            FacetType::MinLength(v) => (IrFacetKind::MinLength, v.as_str()),
+            FacetType::Pattern(v) => (IrFacetKind::Pattern, v.as_str()),
+            FacetType::TotalDigits(v) => (IrFacetKind::TotalDigits, v.as_str()),
+            FacetType::WhiteSpace(ws) => (
+                IrFacetKind::WhiteSpace,
+                match ws {
+                    WhiteSpace::Preserve => "preserve",
+                    WhiteSpace::Replace => "replace",
+                    WhiteSpace::Collapse => "collapse",
+                },
+            ),
+        };
+        IrFacet { facet: kind, value: value.to_string(), comment: facet.comment.clone() }
+    }
+}
+
+impl TryFrom<&IrFacet> for Facet {
+    type Error = IrError;
+
+    fn try_from(facet: &IrFacet) -> Result<Self, Self::Error> {
+        let value = facet.value.clone();
+        let facet_type = match facet.facet {
+            IrFacetKind::Enumeration => FacetType::Enumeration(value),
+            IrFacetKind::FractionDigits => FacetType::FractionDigits(value),
+            IrFacetKind::Length => FacetType::Length(value),
+            IrFacetKind::MaxExclusive => FacetType::MaxExclusive(value),
+            IrFacetKind::MaxInclusive => FacetType::MaxInclusive(value),
+            IrFacetKind::MaxLength => FacetType::MaxLength(value),
+            IrFacetKind::MinExclusive => FacetType::MinExclusive(value),
+            IrFacetKind::MinInclusive => FacetType::MinInclusive(value),
+            IrFacetKind::MinLength => FacetType::MinLength(value),
+            IrFacetKind::Pattern => FacetType::Pattern(value),
+            IrFacetKind::TotalDigits => FacetType::TotalDigits(value),
+            IrFacetKind::WhiteSpace => FacetType::WhiteSpace(match value.as_str() {
+                "preserve" => WhiteSpace::Preserve,
+                "replace" => WhiteSpace::Replace,
+                "collapse" => WhiteSpace::Collapse,
+                _ => return Err(IrError::WhiteSpace(value)),
+            }),
+        };
+        Ok(Facet { facet_type, comment: facet.comment.clone() })
+    }
+}
+
+impl From<&Enum> for IrEnum {
+    fn from(en: &Enum) -> Self {
+        IrEnum {
+            name: en.name.clone(),
+            type_name: en.type_name.clone(),
+            comment: en.comment.clone(),
+            source: (&en.source).into(),
+            cases: en.cases.iter().map(IrEnumCase::from).collect(),
+            modifiers: ir_modifiers(&en.type_modifiers),
+            facets: en.facets.iter().map(IrFacet::from).collect(),
+            subtypes: ir_entities(&en.subtypes),
+        }
+    }
+}
+
+impl TryFrom<&IrEnum> for Enum {
+    type Error = IrError;
+
+    fn try_from(en: &IrEnum) -> Result<Self, Self::Error> {
+        Ok(Enum {
+            name: en.name.clone(),
+            cases: en.cases.iter().map(EnumCase::try_from).collect::<Result<_, _>>()?,
+            comment: en.comment.clone(),
+            type_name: en.type_name.clone(),
+            subtypes: rs_entities(&en.subtypes)?,
+            type_modifiers: rs_modifiers(&en.modifiers),
+            source: en.source.into(),
+            facets: en.facets.iter().map(Facet::try_from).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&EnumSource> for IrEnumSource {
+    fn from(source: &EnumSource) -> Self {
+        match source {
+            EnumSource::Restriction => IrEnumSource::Restriction,
+            EnumSource::Choice => IrEnumSource::Choice,
+            EnumSource::Union => IrEnumSource::Union,
+            EnumSource::NA => IrEnumSource::Other,
+        }
+    }
+}
+
+impl From<IrEnumSource> for EnumSource {
+    fn from(source: IrEnumSource) -> Self {
+        match source {
+            IrEnumSource::Restriction => EnumSource::Restriction,
+            IrEnumSource::Choice => EnumSource::Choice,
+            IrEnumSource::Union => EnumSource::Union,
+            IrEnumSource::Other => EnumSource::NA,
+        }
+    }
+}
+
+impl From<&EnumCase> for IrEnumCase {
+    fn from(case: &EnumCase) -> Self {
+        IrEnumCase {
+            name: case.name.clone(),
+            value: case.value.clone(),
+            type_name: case.type_name.clone(),
+            comment: case.comment.clone(),
+            source: (&case.source).into(),
+            modifiers: ir_modifiers(&case.type_modifiers),
+            subtypes: ir_entities(&case.subtypes),
+        }
+    }
+}
+
+impl TryFrom<&IrEnumCase> for EnumCase {
+    type Error = IrError;
+
+    fn try_from(case: &IrEnumCase) -> Result<Self, Self::Error> {
+        Ok(EnumCase {
+            name: case.name.clone(),
+            comment: case.comment.clone(),
+            value: case.value.clone(),
+            type_name: case.type_name.clone(),
+            type_modifiers: rs_modifiers(&case.modifiers),
+            source: case.source.into(),
+            subtypes: rs_entities(&case.subtypes)?,
+        })
+    }
+}
+
+impl From<&Alias> for IrAlias {
+    fn from(alias: &Alias) -> Self {
+        IrAlias {
+            name: alias.name.clone(),
+            original: alias.original.clone(),
+            comment: alias.comment.clone(),
+            modifiers: ir_modifiers(&alias.type_modifiers),
+            subtypes: ir_entities(&alias.subtypes),
+        }
+    }
+}
+
+impl TryFrom<&IrAlias> for Alias {
+    type Error = IrError;
+
+    fn try_from(alias: &IrAlias) -> Result<Self, Self::Error> {
+        Ok(Alias {
+            name: alias.name.clone(),
+            original: alias.original.clone(),
+            comment: alias.comment.clone(),
+            subtypes: rs_entities(&alias.subtypes)?,
+            type_modifiers: rs_modifiers(&alias.modifiers),
+        })
+    }
+}
+
+impl From<&Import> for IrImport {
+    fn from(import: &Import) -> Self {
+        IrImport {
+            name: import.name.clone(),
+            location: import.location.clone(),
+            comment: import.comment.clone(),
+        }
+    }
+}
+
+impl From<&IrImport> for Import {
+    fn from(import: &IrImport) -> Self {
+        Import {
+            name: import.name.clone(),
+            location: import.location.clone(),
+            comment: import.comment.clone(),
+        }
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{generator::builder::GeneratorBuilder, parser::parse_schemas};
+
+    #[test]
+    fn test_ir_round_trip() {
+        let doc = Document::parse(
+            r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tt="http://example.com/tt" targetNamespace="http://example.com/tt">
+    <xs:simpleType name="Port">
+        <xs:restriction base="xs:int">
+            <xs:minInclusive value="1"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:complexType name="Base">
+        <xs:attribute name="token" type="xs:string" use="required"/>
+    </xs:complexType>
+    <xs:complexType name="Endpoint">
+        <xs:complexContent>
+            <xs:extension base="tt:Base">
+                <xs:sequence>
+                    <xs:element name="Port" type="tt:Port" minOccurs="0"/>
+                </xs:sequence>
+            </xs:extension>
+        </xs:complexContent>
+    </xs:complexType>
+</xs:schema>"#,
+        )
+        .unwrap();
+        let files = parse_schemas(&[doc.root_element()]);
+        let gen = GeneratorBuilder::default().build();
+        let expected = gen.generate_rs_file(&files[0]);
+
+        let json = IrFile::from(&files[0]).to_json();
+        assert!(json.contains(r#""name": "token""#));
+
+        let ir = IrFile::from_json(&json).unwrap();
+        assert_eq!(
+            ir.target_ns,
+            Some(IrNamespace { prefix: Some("tt".into()), uri: "http://example.com/tt".into() })
+        );
+        let xml = ir.namespace_document();
+        let namespaces = Document::parse(&xml).unwrap();
+        let gen = GeneratorBuilder::default().build();
+        assert_eq!(gen.generate_rs_file(&ir.to_rs_file(&namespaces).unwrap()), expected);
+
+        assert!(IrFile::from_json(&json.replacen("\"version\": 1", "\"version\": 2", 1)).is_err());
+    }
+
+    // The layout of the IR is what tools rely on, a change of it has to bump VERSION.
+    #[test]
+    fn test_ir_layout() {
+        let doc = Document::parse(
+            r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tt="http://example.com/tt" targetNamespace="http://example.com/tt">
+    <xs:simpleType name="Mode">
+        <xs:restriction base="xs:string">
+            <xs:enumeration value="On"/>
+            <xs:whiteSpace value="collapse"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:complexType name="Port">
+        <xs:sequence>
+            <xs:element name="Number" type="xs:int" minOccurs="0"/>
+        </xs:sequence>
+        <xs:attribute name="mode" type="tt:Mode"/>
+    </xs:complexType>
+    <xs:element name="Port" type="tt:Port"/>
+</xs:schema>"#,
+        )
+        .unwrap();
+        let files = parse_schemas(&[doc.root_element()]);
+        let json = IrFile::from(&files[0]).to_json();
+
+        let xs = json!({ "prefix": "xs", "uri": "http://www.w3.org/2001/XMLSchema" });
+        let tt = json!({ "prefix": "tt", "uri": "http://example.com/tt" });
+        let field = |name: &str, type_name: &str, source: &str| {
+            json!({
+                "name": name,
+                "type_name": type_name,
+                "comment": null,
+                "source": source,
+                "modifiers": ["option"],
+                "subtypes": []
+            })
+        };
+        let expected = json!({
+            "version": 1,
+            "name": "",
+            "namespace": null,
+            "target_ns": tt,
+            "xsd_ns": xs,
+            "namespaces": [xs, tt],
+            "elements": [2],
+            "types": [
+                {
+                    "kind": "enum",
+                    "name": "Mode",
+                    "type_name": "xs:string",
+                    "comment": null,
+                    "source": "restriction",
+                    "cases": [{
+                        "name": "On",
+                        "value": "On",
+                        "type_name": null,
+                        "comment": null,
+                        "source": "restriction",
+                        "modifiers": [],
+                        "subtypes": []
+                    }],
+                    "modifiers": [],
+                    "facets": [{ "facet": "whiteSpace", "value": "collapse", "comment": null }],
+                    "subtypes": []
+                },
+                {
+                    "kind": "struct",
+                    "name": "Port",
+                    "comment": null,
+                    "fields": [
+                        field("Number", "xs:int", "element"),
+                        field("mode", "tt:Mode", "attribute")
+                    ],
+                    "attribute_groups": [],
+                    "groups": [],
+                    "subtypes": []
+                },
+                {
+                    "kind": "alias",
+                    "name": "Port",
+                    "original": "tt:Port",
+                    "comment": null,
+                    "modifiers": [],
+                    "subtypes": []
+                }
+            ],
+            "attribute_groups": [],
+            "groups": []
+        });
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&json).unwrap(), expected);
+
+        let ir = IrFile::from_json(&json.replace("\"collapse\"", "\"none\"")).unwrap();
+        let xml = ir.namespace_document();
+        let namespaces = Document::parse(&xml).unwrap();
+        assert!(matches!(ir.to_rs_file(&namespaces), Err(IrError::WhiteSpace(_))));
+    }
+}
@@ -1,2 +1,11 @@
+pub mod check;
+pub mod config;
+pub mod diff;
+pub mod doc;
 pub mod generator;
+pub mod ir;
+pub mod loader;
+pub mod model;
 pub mod parser;
+pub mod sample;
+pub mod validate;
@@ -0,0 +1,207 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use roxmltree::{Document, Node};
+
+use crate::parser::{
+    constants::attribute,
+    xsd_elements::{ElementType, XsdNode},
+};
+
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(PathBuf, io::Error),
+    Xml(PathBuf, roxmltree::Error),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            LoadError::Xml(path, e) => write!(f, "{}: {}", path.display(), e),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+// Reads a document and everything it references into memory. Parsing happens later,
+// once all texts are loaded, so that the roxmltree documents can borrow from them.
+#[derive(Debug, Default)]
+pub struct Loader {
+    files: Vec<SourceFile>,
+}
+
+impl Loader {
+    // Loads an xsd file and follows xs:import, xs:include and xs:redefine.
+    pub fn load(&mut self, path: &Path) -> Result<(), LoadError> {
+        self.load_with(path, &|doc| schema_locations(&doc.root_element()))
+    }
+
+    // Loads a file and everything `locations` returns for it, recursively. Relative
+    // locations are resolved against the directory of the referencing file, remote
+    // ones are skipped: their namespaces must be provided by other loaded documents.
+    pub fn load_with(
+        &mut self,
+        path: &Path,
+        locations: &dyn Fn(&Document) -> Vec<String>,
+    ) -> Result<(), LoadError> {
+        let path = path.canonicalize().map_err(|e| LoadError::Io(path.to_path_buf(), e))?;
+        if self.files.iter().any(|f| f.path == path) {
+            return Ok(());
+        }
+
+        let text = fs::read_to_string(&path).map_err(|e| LoadError::Io(path.clone(), e))?;
+        let references = {
+            let doc = Document::parse(&text).map_err(|e| LoadError::Xml(path.clone(), e))?;
+            locations(&doc)
+        };
+        self.files.push(SourceFile { path: path.clone(), text });
+
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        for location in references.iter().filter(|l| !is_remote(l)) {
+            self.load_with(&dir.join(location), locations)?;
+        }
+        Ok(())
+    }
+
+    // Paths of all loaded files, the first one is the root document.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|f| f.path.as_path())
+    }
+
+    pub fn files(&self) -> &[SourceFile] {
+        self.files.as_ref()
+    }
+
+    pub fn into_files(self) -> Vec<SourceFile> {
+        self.files
+    }
+}
+
+// schemaLocation of the xs:import, xs:include and xs:redefine children of a schema.
+// Imports without a location are expected to be resolved by namespace.
+pub fn schema_locations(schema: &Node) -> Vec<String> {
+    schema
+        .children()
+        .filter(|n| {
+            n.is_element()
+                && (n.xsd_type() == ElementType::Import
+                    || matches!(n.tag_name().name(), "include" | "redefine"))
+        })
+        .filter_map(|n| n.attribute(attribute::SCHEMA_LOCATION))
+        .map(str::to_string)
+        .collect()
+}
+
+// Files of a directory tree, each with the path of the file generated for it: the tree is
+// mirrored under `output` with the extensions replaced by `.rs`.
+pub fn walk_dir(input: &Path, output: &Path) -> io::Result<Vec<(PathBuf, PathBuf)>> {
+    let mut paths =
+        fs::read_dir(input)?.map(|e| e.map(|e| e.path())).collect::<io::Result<Vec<_>>>()?;
+    paths.sort();
+
+    let mut files = vec![];
+    for path in paths {
+        let output = output.join(path.file_name().unwrap_or_default());
+        if path.is_dir() {
+            files.extend(walk_dir(&path, &output)?);
+        } else {
+            files.push((path, output.with_extension("rs")));
+        }
+    }
+    Ok(files)
+}
+
+fn is_remote(location: &str) -> bool {
+    location.contains("://")
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn test_schema_locations() {
+        let text = r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+    <xs:import namespace="http://www.w3.org/2005/05/xmlmime" schemaLocation="http://www.w3.org/2005/05/xmlmime"/>
+    <xs:import namespace="http://www.onvif.org/ver10/schema"/>
+    <xs:include schemaLocation="common.xsd"/>
+    <xs:element name="Foo" type="xs:string"/>
+</xs:schema>"#;
+        let doc = Document::parse(text).unwrap();
+
+        assert_eq!(
+            schema_locations(&doc.root_element()),
+            vec!["http://www.w3.org/2005/05/xmlmime", "common.xsd"]
+        );
+    }
+
+    #[test]
+    fn test_load_follows_imports_once() {
+        let dir = env::temp_dir().join(format!("xsd-parser-loader-{}", std::process::id()));
+        fs::create_dir_all(dir.join("common")).unwrap();
+        let schema = |imports: &str| {
+            format!(
+                r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}</xs:schema>"#,
+                imports
+            )
+        };
+        fs::write(
+            dir.join("root.xsd"),
+            schema(r#"<xs:import schemaLocation="common/a.xsd"/><xs:include schemaLocation="common/b.xsd"/>"#),
+        )
+        .unwrap();
+        fs::write(dir.join("common/a.xsd"), schema(r#"<xs:include schemaLocation="b.xsd"/>"#))
+            .unwrap();
+        fs::write(dir.join("common/b.xsd"), schema(r#"<xs:import schemaLocation="../root.xsd"/>"#))
+            .unwrap();
+
+        let mut loader = Loader::default();
+        loader.load(&dir.join("root.xsd")).unwrap();
+        let names = loader
+            .paths()
+            .map(|p| p.strip_prefix(dir.canonicalize().unwrap()).unwrap().to_path_buf())
+            .collect::<Vec<_>>();
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            names,
+            vec![
+                PathBuf::from("root.xsd"),
+                PathBuf::from("common/a.xsd"),
+                PathBuf::from("common/b.xsd")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_dir() {
+        let dir = env::temp_dir().join(format!("xsd-parser-walk-{}", std::process::id()));
+        fs::create_dir_all(dir.join("ver10")).unwrap();
+        fs::write(dir.join("b.xsd"), "").unwrap();
+        fs::write(dir.join("ver10/a.wsdl"), "").unwrap();
+
+        let files = walk_dir(&dir, Path::new("out"));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            files.unwrap(),
+            vec![
+                (dir.join("b.xsd"), PathBuf::from("out/b.rs")),
+                (dir.join("ver10/a.wsdl"), PathBuf::from("out/ver10/a.rs"))
+            ]
+        );
+    }
+}
@@ -0,0 +1,496 @@
+use std::{collections::BTreeMap, fmt};
+
+use crate::parser::xsd_elements::WhiteSpace;
+
+mod parse;
+
+pub const XSD_NS: &str = "http://www.w3.org/2001/XMLSchema";
+pub const XSI_NS: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+// Schema components as the schemas declare them, with names resolved to namespaces. Unlike
+// parser::types nothing is merged or renamed for Rust, so occurrences, the order and nesting
+// of particles, element and attribute forms and identity constraints are kept. It is used
+// by the tools that work with instance documents and schemas rather than generating code.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaSet {
+    pub elements: BTreeMap<QName, Element>,
+    pub types: BTreeMap<QName, Type>,
+    pub groups: BTreeMap<QName, Particle>,
+    pub attribute_groups: BTreeMap<QName, Vec<AttributeItem>>,
+    pub attributes: BTreeMap<QName, AttributeUse>,
+    // The prefix each schema declares for its target namespace, by namespace URI.
+    pub prefixes: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QName {
+    pub namespace: Option<String>,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Element {
+    pub name: QName,
+    pub ty: TypeRef,
+    pub nillable: bool,
+    pub is_abstract: bool,
+    pub default: Option<String>,
+    pub fixed: Option<String>,
+    pub substitution_group: Option<QName>,
+    pub identity_constraints: Vec<IdentityConstraint>,
+    pub documentation: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeRef {
+    Named(QName),
+    Anonymous(Box<Type>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Simple(SimpleType),
+    Complex(ComplexType),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimpleType {
+    pub name: Option<QName>,
+    pub variety: Variety,
+    pub documentation: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum Variety {
+    Restriction { base: TypeRef, facets: Facets },
+    List { item: TypeRef },
+    Union { members: Vec<TypeRef> },
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Facets {
+    pub enumeration: Vec<String>,
+    pub patterns: Vec<String>,
+    pub min_inclusive: Option<String>,
+    pub max_inclusive: Option<String>,
+    pub min_exclusive: Option<String>,
+    pub max_exclusive: Option<String>,
+    pub length: Option<usize>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub total_digits: Option<usize>,
+    pub fraction_digits: Option<usize>,
+    pub white_space: Option<WhiteSpace>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComplexType {
+    pub name: Option<QName>,
+    // The base type and whether it is extended or restricted.
+    pub base: Option<(QName, Derivation)>,
+    // Content declared by the type itself, extensions add it after the content of the base.
+    pub content: Content,
+    pub attributes: Vec<AttributeItem>,
+    pub any_attribute: bool,
+    pub mixed: bool,
+    pub is_abstract: bool,
+    pub documentation: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Derivation {
+    Extension,
+    Restriction,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Content {
+    Empty,
+    // Text of the given simple type.
+    Simple(TypeRef),
+    Elements(Particle),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Particle {
+    pub min_occurs: usize,
+    // None if unbounded.
+    pub max_occurs: Option<usize>,
+    pub term: Term,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    Element(Box<Element>),
+    ElementRef(QName),
+    Group(QName),
+    Sequence(Vec<Particle>),
+    Choice(Vec<Particle>),
+    All(Vec<Particle>),
+    // xs:any, with the value of its namespace attribute and the target namespace of its
+    // schema, which ##other and ##targetNamespace refer to.
+    Any { namespace: String, target_ns: Option<String> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeItem {
+    Attribute(AttributeUse),
+    // Use of a global attribute, its declaration is looked up by name.
+    Ref { name: QName, use_: Use, default: Option<String>, fixed: Option<String> },
+    Group(QName),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeUse {
+    pub name: QName,
+    pub ty: TypeRef,
+    pub use_: Use,
+    pub default: Option<String>,
+    pub fixed: Option<String>,
+    pub documentation: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Use {
+    Optional,
+    Required,
+    Prohibited,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdentityConstraint {
+    pub name: QName,
+    pub kind: IdentityKind,
+    pub selector: String,
+    pub fields: Vec<String>,
+    // Namespaces in scope of the constraint, to resolve the prefixes of its paths.
+    pub namespaces: Vec<(Option<String>, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdentityKind {
+    Unique,
+    Key,
+    KeyRef(QName),
+}
+
+impl QName {
+    pub fn new(namespace: Option<&str>, name: &str) -> Self {
+        QName { namespace: namespace.map(str::to_string), name: name.to_string() }
+    }
+
+    pub fn xsd(name: &str) -> Self {
+        QName::new(Some(XSD_NS), name)
+    }
+
+    pub fn is_xsd(&self) -> bool {
+        self.namespace.as_deref() == Some(XSD_NS)
+    }
+}
+
+impl fmt::Display for QName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.namespace {
+            Some(ns) => write!(f, "{{{}}}{}", ns, self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+impl Particle {
+    pub fn once(term: Term) -> Self {
+        Particle { min_occurs: 1, max_occurs: Some(1), term }
+    }
+}
+
+impl SchemaSet {
+    // Name as the user writes it: `prefix:name` with the prefix a schema declares for its
+    // target namespace, or a local name that is unique among the given names.
+    pub fn find<'a, T>(&self, map: &'a BTreeMap<QName, T>, name: &str) -> Option<&'a T> {
+        match name.split_once(':') {
+            Some((prefix, local)) => {
+                let ns = self.prefixes.iter().find(|(_, p)| p.as_str() == prefix)?.0;
+                map.get(&QName::new(Some(ns), local))
+            }
+            None => {
+                let mut found = map.iter().filter(|(q, _)| q.name == name);
+                match (found.next(), found.next()) {
+                    (Some((_, value)), None) => Some(value),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    pub fn find_element(&self, name: &str) -> Option<&Element> {
+        self.find(&self.elements, name)
+    }
+
+    // `prefix:name` of a component, `{namespace}name` if no schema declares a prefix for
+    // its namespace.
+    pub fn display_name(&self, name: &QName) -> String {
+        match name.namespace.as_ref().and_then(|ns| self.prefixes.get(ns)) {
+            Some(prefix) => format!("{}:{}", prefix, name.name),
+            None if name.is_xsd() => format!("xs:{}", name.name),
+            None => name.to_string(),
+        }
+    }
+
+    pub fn type_def<'a>(&'a self, ty: &'a TypeRef) -> Option<&'a Type> {
+        match ty {
+            TypeRef::Named(name) => self.types.get(name),
+            TypeRef::Anonymous(ty) => Some(ty),
+        }
+    }
+
+    // The built-in type a simple type is derived from, and the facets of the derivation
+    // from the most derived type to the built-in one. List and union types have no built-in
+    // base.
+    pub fn simple_base<'a>(&'a self, ty: &'a TypeRef) -> (Option<&'a QName>, Vec<&'a Facets>) {
+        let mut facets = vec![];
+        let mut current = ty;
+        // Bounded, a schema may be invalid and derive a type from itself.
+        for _ in 0..64 {
+            match current {
+                TypeRef::Named(name) if name.is_xsd() => return (Some(name), facets),
+                _ => {}
+            }
+            match self.type_def(current) {
+                Some(Type::Simple(SimpleType {
+                    variety: Variety::Restriction { base, facets: own },
+                    ..
+                })) => {
+                    facets.push(own);
+                    current = base;
+                }
+                Some(Type::Complex(ct)) => match self.simple_content(ct) {
+                    Some(content) => current = content,
+                    None => break,
+                },
+                _ => break,
+            }
+        }
+        (None, facets)
+    }
+
+    // Item type of a list type, with the facets that restrict the list.
+    pub fn list_item<'a>(&'a self, ty: &'a TypeRef) -> Option<(&'a TypeRef, Vec<&'a Facets>)> {
+        let mut facets = vec![];
+        let mut current = ty;
+        for _ in 0..64 {
+            match self.type_def(current)? {
+                Type::Simple(SimpleType { variety: Variety::List { item }, .. }) => {
+                    return Some((item, facets))
+                }
+                Type::Simple(SimpleType {
+                    variety: Variety::Restriction { base, facets: own },
+                    ..
+                }) => {
+                    facets.push(own);
+                    current = base;
+                }
+                Type::Simple(SimpleType { variety: Variety::Union { .. }, .. }) => return None,
+                Type::Complex(ct) => current = self.simple_content(ct)?,
+            }
+        }
+        None
+    }
+
+    pub fn union_members<'a>(&'a self, ty: &'a TypeRef) -> Option<&'a [TypeRef]> {
+        let mut current = ty;
+        for _ in 0..64 {
+            match self.type_def(current)? {
+                Type::Simple(SimpleType { variety: Variety::Union { members }, .. }) => {
+                    return Some(members)
+                }
+                Type::Simple(SimpleType { variety: Variety::Restriction { base, .. }, .. }) => {
+                    current = base
+                }
+                Type::Simple(SimpleType { variety: Variety::List { .. }, .. }) => return None,
+                Type::Complex(ct) => current = self.simple_content(ct)?,
+            }
+        }
+        None
+    }
+
+    // Type of the text of a complex type with simple content, following its bases.
+    pub fn simple_content<'a>(&'a self, ct: &'a ComplexType) -> Option<&'a TypeRef> {
+        let mut current = ct;
+        for _ in 0..64 {
+            match &current.content {
+                Content::Simple(ty) => return Some(ty),
+                Content::Elements(_) => return None,
+                Content::Empty => {}
+            }
+            match &current.base {
+                Some((base, _)) => match self.types.get(base) {
+                    Some(Type::Complex(base)) => current = base,
+                    _ => return None,
+                },
+                None => return None,
+            }
+        }
+        None
+    }
+
+    // Base types of a named type, the direct base first.
+    pub fn base_chain(&self, name: &QName) -> Vec<QName> {
+        let mut chain = vec![];
+        let mut current = name.clone();
+        while let Some(base) = self.types.get(&current).and_then(|ty| self.base_of(ty)) {
+            if chain.contains(&base) || &base == name {
+                break;
+            }
+            chain.push(base.clone());
+            current = base;
+        }
+        chain
+    }
+
+    pub fn base_of(&self, ty: &Type) -> Option<QName> {
+        match ty {
+            Type::Complex(ct) => ct.base.as_ref().map(|(base, _)| base.clone()),
+            Type::Simple(SimpleType { variety: Variety::Restriction { base, .. }, .. }) => {
+                match base {
+                    TypeRef::Named(base) => Some(base.clone()),
+                    TypeRef::Anonymous(_) => None,
+                }
+            }
+            Type::Simple(_) => None,
+        }
+    }
+
+    // Particles of the content of a complex type in document order: those of the bases it
+    // extends, then its own. A restriction replaces the content of its base.
+    pub fn particles<'a>(&'a self, ct: &'a ComplexType) -> Vec<&'a Particle> {
+        let mut chain = vec![ct];
+        let mut current = ct;
+        while let Some((base, Derivation::Extension)) = &current.base {
+            match self.types.get(base) {
+                Some(Type::Complex(base)) if chain.len() < 64 => {
+                    chain.push(base);
+                    current = base;
+                }
+                _ => break,
+            }
+        }
+        chain
+            .iter()
+            .rev()
+            .filter_map(|ct| match &ct.content {
+                Content::Elements(particle) => Some(particle),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Attributes of a complex type including those of its bases, attribute groups and
+    // references. Later declarations of a name replace earlier ones, prohibited uses are
+    // kept so they can be reported.
+    pub fn attribute_uses(&self, ct: &ComplexType) -> Vec<AttributeUse> {
+        let mut chain = vec![ct];
+        let mut current = ct;
+        while let Some((base, _)) = &current.base {
+            match self.types.get(base) {
+                Some(Type::Complex(base)) if chain.len() < 64 => {
+                    chain.push(base);
+                    current = base;
+                }
+                _ => break,
+            }
+        }
+
+        let mut uses: Vec<AttributeUse> = vec![];
+        for ct in chain.iter().rev() {
+            for attribute in self.flatten_attributes(&ct.attributes, 0) {
+                match uses.iter_mut().find(|u| u.name == attribute.name) {
+                    Some(existing) => *existing = attribute,
+                    None => uses.push(attribute),
+                }
+            }
+        }
+        uses
+    }
+
+    pub fn any_attribute(&self, ct: &ComplexType) -> bool {
+        let mut current = ct;
+        for _ in 0..64 {
+            if current.any_attribute {
+                return true;
+            }
+            match &current.base {
+                Some((base, _)) => match self.types.get(base) {
+                    Some(Type::Complex(base)) => current = base,
+                    _ => return false,
+                },
+                None => return false,
+            }
+        }
+        false
+    }
+
+    // Attributes of an attribute group, with references and nested groups resolved.
+    pub fn attribute_group_uses(&self, items: &[AttributeItem]) -> Vec<AttributeUse> {
+        self.flatten_attributes(items, 0)
+    }
+
+    fn flatten_attributes(&self, items: &[AttributeItem], depth: usize) -> Vec<AttributeUse> {
+        let mut res = vec![];
+        for item in items {
+            match item {
+                AttributeItem::Attribute(attribute) => res.push(attribute.clone()),
+                AttributeItem::Ref { name, use_, default, fixed } => {
+                    let global = self.attributes.get(name);
+                    res.push(AttributeUse {
+                        name: name.clone(),
+                        ty: global
+                            .map(|a| a.ty.clone())
+                            .unwrap_or_else(|| TypeRef::Named(QName::xsd("anySimpleType"))),
+                        use_: *use_,
+                        default: default.clone().or_else(|| global.and_then(|a| a.default.clone())),
+                        fixed: fixed.clone().or_else(|| global.and_then(|a| a.fixed.clone())),
+                        documentation: global.and_then(|a| a.documentation.clone()),
+                    });
+                }
+                AttributeItem::Group(name) if depth < 64 => {
+                    if let Some(group) = self.attribute_groups.get(name) {
+                        res.extend(self.flatten_attributes(group, depth + 1));
+                    }
+                }
+                AttributeItem::Group(_) => {}
+            }
+        }
+        res
+    }
+
+    // Global elements that may appear in place of the given one: itself unless it is
+    // abstract, and the members of its substitution group.
+    pub fn substitutes<'a>(&'a self, head: &'a Element) -> Vec<&'a Element> {
+        let mut res = vec![];
+        if !head.is_abstract {
+            res.push(head);
+        }
+        let mut heads = vec![&head.name];
+        let mut seen = vec![&head.name];
+        while let Some(current) = heads.pop() {
+            for element in self.elements.values() {
+                if element.substitution_group.as_ref() == Some(current)
+                    && !seen.contains(&&element.name)
+                {
+                    seen.push(&element.name);
+                    if !element.is_abstract {
+                        res.push(element);
+                    }
+                    heads.push(&element.name);
+                }
+            }
+        }
+        res
+    }
+
+    // Named types derived from the given one, directly or not.
+    pub fn derived_types(&self, name: &QName) -> Vec<&QName> {
+        self.types.keys().filter(|ty| self.base_chain(ty).contains(name)).collect()
+    }
+}
@@ -0,0 +1,518 @@
+use std::collections::HashSet;
+
+use roxmltree::Node;
+
+use crate::{
+    model::{
+        AttributeItem, AttributeUse, ComplexType, Content, Derivation, Element, Facets,
+        IdentityConstraint, IdentityKind, Particle, QName, SchemaSet, SimpleType, Term, Type,
+        TypeRef, Use, Variety, XSD_NS,
+    },
+    parser::{
+        constants::attribute,
+        utils::get_documentation,
+        xsd_elements::{max_occurs, min_occurs, MaxOccurs, WhiteSpace},
+    },
+};
+
+// Defaults of the schema element that the declarations in it depend on.
+struct Context<'a> {
+    target_ns: Option<&'a str>,
+    elements_qualified: bool,
+    attributes_qualified: bool,
+}
+
+impl SchemaSet {
+    // Components of schemas that reference each other, e.g. those a Loader has read.
+    pub fn parse(schemas: &[Node]) -> Self {
+        let mut set = SchemaSet::default();
+        for schema in schemas {
+            set.add_schema(schema);
+        }
+
+        // Members of a substitution group without a type of their own have the type of
+        // the head, which may itself have the type of its own head. Members of a cyclic
+        // group without a typed head keep anyType.
+        let any_type = TypeRef::Named(QName::xsd("anyType"));
+        let inherited = set
+            .elements
+            .values()
+            .filter(|e| e.ty == any_type)
+            .filter_map(|e| {
+                let mut visited = HashSet::new();
+                let mut head = e;
+                while head.ty == any_type {
+                    let name = head.substitution_group.as_ref()?;
+                    if !visited.insert(name) {
+                        return None;
+                    }
+                    head = set.elements.get(name)?;
+                }
+                Some((e.name.clone(), head.ty.clone()))
+            })
+            .collect::<Vec<_>>();
+        for (name, ty) in inherited {
+            if let Some(element) = set.elements.get_mut(&name) {
+                element.ty = ty;
+            }
+        }
+        set
+    }
+
+    fn add_schema(&mut self, schema: &Node) {
+        let target_ns = schema.attribute(attribute::TARGET_NAMESPACE);
+        let ctx = Context {
+            target_ns,
+            elements_qualified: schema.attribute("elementFormDefault") == Some("qualified"),
+            attributes_qualified: schema.attribute("attributeFormDefault") == Some("qualified"),
+        };
+        if let Some(ns) = target_ns {
+            if let Some(prefix) = schema.namespaces().find(|n| n.uri() == ns).and_then(|n| n.name())
+            {
+                self.prefixes.entry(ns.to_string()).or_insert_with(|| prefix.to_string());
+            }
+        }
+
+        for node in schema.children().filter(Node::is_element) {
+            let name = match node.attribute(attribute::NAME) {
+                Some(name) => QName::new(target_ns, name),
+                None => continue,
+            };
+            match node.tag_name().name() {
+                "element" => {
+                    let element = parse_element(&node, &ctx, true);
+                    self.elements.insert(name, element);
+                }
+                "complexType" => {
+                    let ty = parse_complex_type(&node, &ctx, Some(name.clone()));
+                    self.types.insert(name, Type::Complex(ty));
+                }
+                "simpleType" => {
+                    let ty = parse_simple_type(&node, Some(name.clone()));
+                    self.types.insert(name, Type::Simple(ty));
+                }
+                "group" => {
+                    let particle = model_group(&node)
+                        .and_then(|group| parse_particle(&group, &ctx))
+                        .unwrap_or_else(|| Particle::once(Term::Sequence(vec![])));
+                    self.groups.insert(name, particle);
+                }
+                "attributeGroup" => {
+                    self.attribute_groups.insert(name, parse_attributes(&node, &ctx));
+                }
+                "attribute" => {
+                    self.attributes.insert(name, parse_attribute(&node, &ctx, true));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn children<'a, 'input>(node: &Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(|n| n.is_element() && n.tag_name().namespace() == Some(XSD_NS))
+}
+
+fn child<'a, 'input>(node: &Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    children(node).find(|n| n.tag_name().name() == name)
+}
+
+fn model_group<'a, 'input>(node: &Node<'a, 'input>) -> Option<Node<'a, 'input>> {
+    children(node).find(|n| matches!(n.tag_name().name(), "sequence" | "choice" | "all" | "group"))
+}
+
+// A QName attribute value resolved with the namespaces in scope of its element.
+fn resolve(node: &Node, value: &str) -> QName {
+    let (prefix, name) = match value.split_once(':') {
+        Some((prefix, name)) => (Some(prefix), name),
+        None => (None, value),
+    };
+    QName::new(node.lookup_namespace_uri(prefix), name)
+}
+
+fn is_qualified(node: &Node, default: bool) -> bool {
+    match node.attribute("form") {
+        Some(form) => form == "qualified",
+        None => default,
+    }
+}
+
+fn parse_element(node: &Node, ctx: &Context, global: bool) -> Element {
+    let local = node.attribute(attribute::NAME).unwrap_or_default();
+    let namespace =
+        if global || is_qualified(node, ctx.elements_qualified) { ctx.target_ns } else { None };
+
+    Element {
+        name: QName::new(namespace, local),
+        ty: type_ref(node, ctx, "anyType"),
+        nillable: node.attribute("nillable") == Some("true"),
+        is_abstract: node.attribute("abstract") == Some("true"),
+        default: node.attribute("default").map(str::to_string),
+        fixed: node.attribute("fixed").map(str::to_string),
+        substitution_group: node.attribute("substitutionGroup").map(|v| resolve(node, v)),
+        identity_constraints: children(node).filter_map(|n| parse_identity(&n, ctx)).collect(),
+        documentation: get_documentation(node),
+    }
+}
+
+// The type of an element or attribute: named by its type attribute, declared inline or
+// the given built-in type if neither.
+fn type_ref(node: &Node, ctx: &Context, default: &str) -> TypeRef {
+    if let Some(ty) = node.attribute(attribute::TYPE) {
+        return TypeRef::Named(resolve(node, ty));
+    }
+    if let Some(simple) = child(node, "simpleType") {
+        return TypeRef::Anonymous(Box::new(Type::Simple(parse_simple_type(&simple, None))));
+    }
+    if let Some(complex) = child(node, "complexType") {
+        let ty = parse_complex_type(&complex, ctx, None);
+        return TypeRef::Anonymous(Box::new(Type::Complex(ty)));
+    }
+    TypeRef::Named(QName::xsd(default))
+}
+
+fn parse_identity(node: &Node, ctx: &Context) -> Option<IdentityConstraint> {
+    let kind = match node.tag_name().name() {
+        "unique" => IdentityKind::Unique,
+        "key" => IdentityKind::Key,
+        "keyref" => IdentityKind::KeyRef(resolve(node, node.attribute("refer")?)),
+        _ => return None,
+    };
+    let xpath = |n: &Node| n.attribute("xpath").map(str::to_string);
+    Some(IdentityConstraint {
+        name: QName::new(ctx.target_ns, node.attribute(attribute::NAME)?),
+        kind,
+        selector: child(node, "selector").and_then(|n| xpath(&n))?,
+        fields: children(node)
+            .filter(|n| n.tag_name().name() == "field")
+            .filter_map(|n| xpath(&n))
+            .collect(),
+        namespaces: node
+            .namespaces()
+            .map(|ns| (ns.name().map(str::to_string), ns.uri().to_string()))
+            .collect(),
+    })
+}
+
+fn parse_complex_type(node: &Node, ctx: &Context, name: Option<QName>) -> ComplexType {
+    let mut ty = ComplexType {
+        name,
+        base: None,
+        content: Content::Empty,
+        attributes: vec![],
+        any_attribute: false,
+        mixed: node.attribute("mixed") == Some("true"),
+        is_abstract: node.attribute("abstract") == Some("true"),
+        documentation: get_documentation(node),
+    };
+
+    if let Some(content) = child(node, "simpleContent") {
+        if let Some(derivation) =
+            children(&content).find(|n| n.attribute(attribute::BASE).is_some())
+        {
+            let base = resolve(&derivation, derivation.attribute(attribute::BASE).unwrap());
+            ty.content = if derivation.tag_name().name() == "extension" {
+                ty.base = Some((base.clone(), Derivation::Extension));
+                Content::Simple(TypeRef::Named(base))
+            } else {
+                ty.base = Some((base.clone(), Derivation::Restriction));
+                let restricted = SimpleType {
+                    name: None,
+                    variety: Variety::Restriction {
+                        base: TypeRef::Named(base),
+                        facets: parse_facets(&derivation),
+                    },
+                    documentation: None,
+                };
+                Content::Simple(TypeRef::Anonymous(Box::new(Type::Simple(restricted))))
+            };
+            ty.attributes = parse_attributes(&derivation, ctx);
+            ty.any_attribute = child(&derivation, "anyAttribute").is_some();
+        }
+    } else if let Some(content) = child(node, "complexContent") {
+        if content.attribute("mixed") == Some("true") {
+            ty.mixed = true;
+        }
+        if let Some(derivation) =
+            children(&content).find(|n| n.attribute(attribute::BASE).is_some())
+        {
+            let base = resolve(&derivation, derivation.attribute(attribute::BASE).unwrap());
+            let kind = if derivation.tag_name().name() == "extension" {
+                Derivation::Extension
+            } else {
+                Derivation::Restriction
+            };
+            ty.base = Some((base, kind));
+            if let Some(particle) = model_group(&derivation).and_then(|g| parse_particle(&g, ctx)) {
+                ty.content = Content::Elements(particle);
+            }
+            ty.attributes = parse_attributes(&derivation, ctx);
+            ty.any_attribute = child(&derivation, "anyAttribute").is_some();
+        }
+    } else {
+        if let Some(particle) = model_group(node).and_then(|g| parse_particle(&g, ctx)) {
+            ty.content = Content::Elements(particle);
+        }
+        ty.attributes = parse_attributes(node, ctx);
+        ty.any_attribute = child(node, "anyAttribute").is_some();
+    }
+    ty
+}
+
+fn parse_particle(node: &Node, ctx: &Context) -> Option<Particle> {
+    let term = match node.tag_name().name() {
+        "element" => match node.attribute(attribute::REF) {
+            Some(name) => Term::ElementRef(resolve(node, name)),
+            None => Term::Element(Box::new(parse_element(node, ctx, false))),
+        },
+        "group" => Term::Group(resolve(node, node.attribute(attribute::REF)?)),
+        "sequence" => Term::Sequence(parse_particles(node, ctx)),
+        "choice" => Term::Choice(parse_particles(node, ctx)),
+        "all" => Term::All(parse_particles(node, ctx)),
+        "any" => Term::Any {
+            namespace: node.attribute(attribute::NAMESPACE).unwrap_or("##any").to_string(),
+            target_ns: ctx.target_ns.map(str::to_string),
+        },
+        _ => return None,
+    };
+    Some(Particle {
+        min_occurs: min_occurs(node),
+        max_occurs: match max_occurs(node) {
+            MaxOccurs::Bounded(max) => Some(max),
+            MaxOccurs::Unbounded => None,
+            MaxOccurs::None => Some(1),
+        },
+        term,
+    })
+}
+
+fn parse_particles(node: &Node, ctx: &Context) -> Vec<Particle> {
+    children(node).filter_map(|n| parse_particle(&n, ctx)).collect()
+}
+
+fn parse_attributes(node: &Node, ctx: &Context) -> Vec<AttributeItem> {
+    children(node)
+        .filter_map(|n| match n.tag_name().name() {
+            "attribute" => Some(match n.attribute(attribute::REF) {
+                Some(name) => AttributeItem::Ref {
+                    name: resolve(&n, name),
+                    use_: parse_use(&n),
+                    default: n.attribute("default").map(str::to_string),
+                    fixed: n.attribute("fixed").map(str::to_string),
+                },
+                None => AttributeItem::Attribute(parse_attribute(&n, ctx, false)),
+            }),
+            "attributeGroup" => {
+                n.attribute(attribute::REF).map(|name| AttributeItem::Group(resolve(&n, name)))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn parse_attribute(node: &Node, ctx: &Context, global: bool) -> AttributeUse {
+    let local = node.attribute(attribute::NAME).unwrap_or_default();
+    let namespace =
+        if global || is_qualified(node, ctx.attributes_qualified) { ctx.target_ns } else { None };
+    AttributeUse {
+        name: QName::new(namespace, local),
+        ty: type_ref(node, ctx, "anySimpleType"),
+        use_: parse_use(node),
+        default: node.attribute("default").map(str::to_string),
+        fixed: node.attribute("fixed").map(str::to_string),
+        documentation: get_documentation(node),
+    }
+}
+
+fn parse_use(node: &Node) -> Use {
+    match node.attribute(attribute::USE) {
+        Some("required") => Use::Required,
+        Some("prohibited") => Use::Prohibited,
+        _ => Use::Optional,
+    }
+}
+
+fn parse_simple_type(node: &Node, name: Option<QName>) -> SimpleType {
+    let inline = |n: &Node| {
+        child(n, "simpleType")
+            .map(|s| TypeRef::Anonymous(Box::new(Type::Simple(parse_simple_type(&s, None)))))
+    };
+    let any = || TypeRef::Named(QName::xsd("anySimpleType"));
+
+    let variety = if let Some(list) = child(node, "list") {
+        let item = match list.attribute(attribute::ITEM_TYPE) {
+            Some(item) => TypeRef::Named(resolve(&list, item)),
+            None => inline(&list).unwrap_or_else(any),
+        };
+        Variety::List { item }
+    } else if let Some(union) = child(node, "union") {
+        let mut members = union
+            .attribute(attribute::MEMBER_TYPES)
+            .map(|m| {
+                m.split_whitespace().map(|name| TypeRef::Named(resolve(&union, name))).collect()
+            })
+            .unwrap_or_else(Vec::new);
+        members.extend(
+            children(&union)
+                .filter(|n| n.tag_name().name() == "simpleType")
+                .map(|s| TypeRef::Anonymous(Box::new(Type::Simple(parse_simple_type(&s, None))))),
+        );
+        Variety::Union { members }
+    } else if let Some(restriction) = child(node, "restriction") {
+        let base = match restriction.attribute(attribute::BASE) {
+            Some(base) => TypeRef::Named(resolve(&restriction, base)),
+            None => inline(&restriction).unwrap_or_else(any),
+        };
+        Variety::Restriction { base, facets: parse_facets(&restriction) }
+    } else {
+        Variety::Restriction { base: any(), facets: Facets::default() }
+    };
+
+    SimpleType { name, variety, documentation: get_documentation(node) }
+}
+
+fn parse_facets(node: &Node) -> Facets {
+    let mut facets = Facets::default();
+    for facet in children(node) {
+        let value = match facet.attribute(attribute::VALUE) {
+            Some(value) => value.to_string(),
+            None => continue,
+        };
+        let number = value.trim().parse::<usize>().ok();
+        match facet.tag_name().name() {
+            "enumeration" => facets.enumeration.push(value),
+            "pattern" => facets.patterns.push(value),
+            "minInclusive" => facets.min_inclusive = Some(value),
+            "maxInclusive" => facets.max_inclusive = Some(value),
+            "minExclusive" => facets.min_exclusive = Some(value),
+            "maxExclusive" => facets.max_exclusive = Some(value),
+            "length" => facets.length = number,
+            "minLength" => facets.min_length = number,
+            "maxLength" => facets.max_length = number,
+            "totalDigits" => facets.total_digits = number,
+            "fractionDigits" => facets.fraction_digits = number,
+            "whiteSpace" => {
+                facets.white_space = match value.as_str() {
+                    "preserve" => Some(WhiteSpace::Preserve),
+                    "replace" => Some(WhiteSpace::Replace),
+                    "collapse" => Some(WhiteSpace::Collapse),
+                    _ => None,
+                }
+            }
+            _ => {}
+        }
+    }
+    facets
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_schema_set() {
+        let doc = roxmltree::Document::parse(
+            r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tt="http://example.com/tt" targetNamespace="http://example.com/tt" elementFormDefault="qualified">
+    <xs:simpleType name="Port">
+        <xs:restriction base="xs:int">
+            <xs:minInclusive value="1"/>
+            <xs:maxInclusive value="65535"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:complexType name="Base">
+        <xs:sequence>
+            <xs:element name="Name" type="xs:string"/>
+        </xs:sequence>
+        <xs:attribute name="token" type="xs:string" use="required"/>
+    </xs:complexType>
+    <xs:complexType name="Endpoint">
+        <xs:complexContent>
+            <xs:extension base="tt:Base">
+                <xs:choice minOccurs="0" maxOccurs="unbounded">
+                    <xs:element name="Port" type="tt:Port"/>
+                    <xs:element name="Local" form="unqualified" type="xs:string"/>
+                </xs:choice>
+            </xs:extension>
+        </xs:complexContent>
+    </xs:complexType>
+    <xs:element name="Endpoints">
+        <xs:complexType>
+            <xs:sequence>
+                <xs:element name="Endpoint" type="tt:Endpoint" maxOccurs="unbounded"/>
+            </xs:sequence>
+        </xs:complexType>
+        <xs:key name="EndpointKey">
+            <xs:selector xpath="tt:Endpoint"/>
+            <xs:field xpath="@token"/>
+        </xs:key>
+    </xs:element>
+</xs:schema>"#,
+        )
+        .unwrap();
+        let set = SchemaSet::parse(&[doc.root_element()]);
+        let tt = |name: &str| QName::new(Some("http://example.com/tt"), name);
+
+        assert_eq!(set.prefixes["http://example.com/tt"], "tt");
+        let port = TypeRef::Named(tt("Port"));
+        let (base, facets) = set.simple_base(&port);
+        assert_eq!(base, Some(&QName::xsd("int")));
+        assert_eq!(facets[0].max_inclusive.as_deref(), Some("65535"));
+
+        let endpoint = match &set.types[&tt("Endpoint")] {
+            Type::Complex(ct) => ct,
+            ty => panic!("unexpected type {:?}", ty),
+        };
+        assert_eq!(set.base_chain(&tt("Endpoint")), vec![tt("Base")]);
+        assert_eq!(set.attribute_uses(endpoint)[0].use_, Use::Required);
+
+        let particles = set.particles(endpoint);
+        assert_eq!(particles.len(), 2);
+        match &particles[1].term {
+            Term::Choice(branches) => {
+                assert_eq!(particles[1].max_occurs, None);
+                match (&branches[0].term, &branches[1].term) {
+                    (Term::Element(port), Term::Element(local)) => {
+                        assert_eq!(port.name, tt("Port"));
+                        assert_eq!(local.name, QName::new(None, "Local"));
+                    }
+                    terms => panic!("unexpected terms {:?}", terms),
+                }
+            }
+            term => panic!("unexpected term {:?}", term),
+        }
+
+        let endpoints = set.find_element("tt:Endpoints").unwrap();
+        assert_eq!(set.find_element("Endpoints"), Some(endpoints));
+        let key = &endpoints.identity_constraints[0];
+        assert_eq!(key.kind, IdentityKind::Key);
+        assert_eq!(
+            (key.selector.as_str(), key.fields.clone()),
+            ("tt:Endpoint", vec!["@token".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_substitution_group_types() {
+        // A chain of ten members, each in the group of the next, and a cycle.
+        let members = (0..10)
+            .map(|i| format!(r#"<xs:element name="E{}" substitutionGroup="tt:E{}"/>"#, i, i + 1))
+            .collect::<String>();
+        let text = format!(
+            r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tt="http://example.com/tt" targetNamespace="http://example.com/tt">
+    {}
+    <xs:element name="E10" type="xs:int"/>
+    <xs:element name="A" substitutionGroup="tt:B"/>
+    <xs:element name="B" substitutionGroup="tt:A"/>
+</xs:schema>"#,
+            members
+        );
+        let doc = roxmltree::Document::parse(&text).unwrap();
+        let set = SchemaSet::parse(&[doc.root_element()]);
+        let tt = |name: &str| QName::new(Some("http://example.com/tt"), name);
+
+        assert_eq!(set.elements[&tt("E0")].ty, TypeRef::Named(QName::xsd("int")));
+        assert_eq!(set.elements[&tt("A")].ty, TypeRef::Named(QName::xsd("anyType")));
+    }
+}
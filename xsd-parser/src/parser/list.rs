@@ -26,6 +26,6 @@ pub fn parse_list(list: &Node) -> RsEntity {
             }
         }
     };
-    result.type_modifiers.push(TypeModifier::Array);
+    result.type_modifiers.push(TypeModifier::List);
     RsEntity::TupleStruct(result)
 }
@@ -13,6 +13,7 @@ mod group;
 mod import;
 mod list;
 mod node_parser;
+pub mod reachable;
 mod restriction;
 pub mod schema;
 mod sequence;
@@ -21,7 +22,7 @@ mod simple_type;
 mod tests;
 pub mod types;
 mod union;
-mod utils;
+pub(crate) mod utils;
 pub mod xsd_elements;
 
 use std::collections::HashMap;
@@ -37,49 +38,51 @@ pub fn parse(text: &str) -> Result<RsFile, ()> {
     let doc = roxmltree::Document::parse(text).expect("Parse document error");
     let root = doc.root();
 
-    let mut map = HashMap::new();
-
     let schema =
         root.children().filter(|e| e.is_element()).last().expect("Schema element is required");
 
-    let mut schema_rs = parse_schema(&schema);
-    for ty in &schema_rs.types {
-        if let RsEntity::Struct(st) = ty {
-            map.extend(st.get_types_map());
-        }
-    }
-    for ag in &schema_rs.attribute_groups {
-        if let RsEntity::Struct(st) = ag {
-            map.extend(st.get_types_map());
-        }
-    }
-    for ag in &schema_rs.groups {
-        if let RsEntity::Struct(st) = ag {
-            map.extend(st.get_types_map());
+    Ok(parse_schemas(&[schema]).pop().unwrap())
+}
+
+// Parses schemas that reference each other (imports, inline schemas of a wsdl) and
+// resolves bases, attribute groups and groups across all of them.
+pub fn parse_schemas<'input>(schemas: &[roxmltree::Node<'_, 'input>]) -> Vec<RsFile<'input>> {
+    let mut files = schemas.iter().map(parse_schema).collect::<Vec<RsFile>>();
+    let mut extended_types = vec![vec![]; files.len()];
+
+    let mut map = HashMap::new();
+    for file in &files {
+        for ty in file.types.iter().chain(&file.attribute_groups).chain(&file.groups) {
+            if let RsEntity::Struct(st) = ty {
+                map.extend(st.get_types_map());
+            }
         }
     }
 
-    let mut extended_types = Vec::new();
-    for ty in &schema_rs.types {
-        if let RsEntity::Struct(st) = ty {
-            extended_types.extend(st.extend_base(&mut map));
-            st.extend_attribute_group(&map);
-            st.extend_group(&map);
+    for (file, extended) in files.iter().zip(extended_types.iter_mut()) {
+        for ty in &file.types {
+            if let RsEntity::Struct(st) = ty {
+                extended.extend(st.extend_base(&map));
+                st.extend_attribute_group(&map);
+                st.extend_group(&map);
+            }
         }
     }
 
-    for ty in extended_types {
-        if schema_rs.types.iter().any(|field| {
-            if let RsEntity::Struct(st) = field {
-                st.name == ty.name
-            } else {
-                false
+    for (file, extended) in files.iter_mut().zip(extended_types) {
+        for ty in extended {
+            if file.types.iter().any(|field| {
+                if let RsEntity::Struct(st) = field {
+                    st.name == ty.name
+                } else {
+                    false
+                }
+            }) {
+                continue;
             }
-        }) {
-            continue;
+            file.types.push(RsEntity::Struct(ty));
         }
-        schema_rs.types.push(RsEntity::Struct(ty));
     }
 
-    Ok(schema_rs)
+    files
 }
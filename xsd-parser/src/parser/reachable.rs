@@ -0,0 +1,298 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::parser::types::{RsEntity, RsFile};
+
+// Positions of the top level entities by target namespace and name.
+type Index = HashMap<(Option<String>, String), Vec<(usize, usize)>>;
+
+// Groups and attribute groups by target namespace and name, with the file they are in.
+type Groups<'a> = HashMap<(Option<String>, String), (usize, &'a RsEntity)>;
+
+// Removes the top level entities that the roots don't depend on. Roots are names of
+// elements or types as written in the config, with the prefix their schema declares for
+// its target namespace, or as {namespace}name. Field types, groups, attribute groups and
+// union members are followed across all files. Extended bases are not kept: parse_schemas
+// copies their fields into the types that extend them, so the types of those fields are
+// followed.
+pub fn retain_reachable(files: &mut [RsFile], roots: &[String]) {
+    let mut index = Index::new();
+    for (i, file) in files.iter().enumerate() {
+        let uri = file.target_ns.as_ref().map(|ns| ns.uri().to_string());
+        for (j, entity) in file.types.iter().enumerate() {
+            index.entry((uri.clone(), entity.name().to_string())).or_default().push((i, j));
+        }
+    }
+
+    let mut groups = Groups::new();
+    for (i, file) in files.iter().enumerate() {
+        let uri = file.target_ns.as_ref().map(|ns| ns.uri().to_string());
+        for group in file.groups.iter().chain(&file.attribute_groups) {
+            groups.insert((uri.clone(), group.name().to_string()), (i, group));
+        }
+    }
+
+    let mut queue = vec![];
+    for (i, file) in files.iter().enumerate() {
+        let prefix = file.target_ns.as_ref().and_then(|ns| ns.name());
+        for (j, entity) in file.types.iter().enumerate() {
+            let name = match prefix {
+                Some(prefix) => format!("{}:{}", prefix, entity.name()),
+                None => entity.name().to_string(),
+            };
+            if roots.contains(&name) {
+                queue.push((i, j));
+            }
+        }
+    }
+    for root in roots {
+        if let Some((uri, name)) = root.strip_prefix('{').and_then(|r| r.split_once('}')) {
+            queue.extend(
+                index.get(&(Some(uri.to_string()), name.to_string())).into_iter().flatten(),
+            );
+        }
+    }
+
+    let mut reachable = HashSet::new();
+    while let Some((i, j)) = queue.pop() {
+        if !reachable.insert((i, j)) {
+            continue;
+        }
+
+        let mut references = vec![];
+        let mut visited_groups = HashSet::new();
+        collect_references(
+            i,
+            &files[i].types[j],
+            files,
+            &groups,
+            &mut visited_groups,
+            &mut references,
+        );
+        for (file, reference) in references {
+            let keys = keys(&files[file], &reference);
+            queue.extend(keys.iter().filter_map(|key| index.get(key)).flatten());
+        }
+    }
+
+    for (i, file) in files.iter_mut().enumerate() {
+        let mut j = 0;
+        file.types.retain(|_| {
+            j += 1;
+            reachable.contains(&(i, j - 1))
+        });
+        // Indices of the elements move down by the types removed before them.
+        let removed_before = |j: usize| (0..j).filter(|&k| !reachable.contains(&(i, k))).count();
+        file.elements = file
+            .elements
+            .iter()
+            .filter(|&&j| reachable.contains(&(i, j)))
+            .map(|&j| j - removed_before(j))
+            .collect();
+    }
+}
+
+// Namespace and name a type name in the file may refer to: the prefix is resolved with the
+// namespaces of the file. Unprefixed names are looked up in the default namespace, then in
+// the target namespace of the file.
+fn keys(file: &RsFile, type_name: &str) -> Vec<(Option<String>, String)> {
+    let (prefix, name) = match type_name.split_once(':') {
+        Some((prefix, name)) => (Some(prefix), name),
+        None => (None, type_name),
+    };
+    let uri = file.namespaces.iter().find(|ns| ns.name() == prefix).map(|ns| ns.uri().to_string());
+    let target_uri = file.target_ns.as_ref().map(|ns| ns.uri().to_string());
+
+    let mut keys = vec![(uri, name.to_string())];
+    if prefix.is_none() {
+        keys.push((target_uri, name.to_string()));
+    }
+    keys
+}
+
+// Type names the entity of the file refers to, with the file that resolves their prefixes:
+// references of a group are resolved in the file of the group.
+fn collect_references(
+    file: usize,
+    entity: &RsEntity,
+    files: &[RsFile],
+    groups: &Groups,
+    visited_groups: &mut HashSet<(Option<String>, String)>,
+    references: &mut Vec<(usize, String)>,
+) {
+    let mut nested = vec![];
+    match entity {
+        RsEntity::Struct(st) => {
+            for field in st.fields.borrow().iter() {
+                references.push((file, field.type_name.clone()));
+                nested.extend(field.subtypes.iter().cloned());
+            }
+            nested.extend(st.subtypes.iter().cloned());
+
+            // Groups are merged into the structs that use them, their own references are
+            // followed in case they were not.
+            let group_names = st
+                .groups
+                .borrow()
+                .iter()
+                .chain(st.attribute_groups.borrow().iter())
+                .map(|g| g.original.clone())
+                .collect::<Vec<_>>();
+            for group_name in group_names {
+                let key = keys(&files[file], &group_name)
+                    .into_iter()
+                    .find(|key| groups.contains_key(key));
+                if let Some(key) = key {
+                    let (group_file, group) = groups[&key];
+                    if visited_groups.insert(key) {
+                        collect_references(
+                            group_file,
+                            group,
+                            files,
+                            groups,
+                            visited_groups,
+                            references,
+                        );
+                    }
+                }
+            }
+        }
+        RsEntity::StructField(field) => {
+            references.push((file, field.type_name.clone()));
+            nested.extend(field.subtypes.iter().cloned());
+        }
+        RsEntity::TupleStruct(ts) => {
+            references.push((file, ts.type_name.clone()));
+            nested.extend(ts.subtypes.iter().cloned());
+        }
+        RsEntity::Enum(en) => {
+            references.push((file, en.type_name.clone()));
+            for case in &en.cases {
+                references.extend(case.type_name.clone().map(|t| (file, t)));
+                nested.extend(case.subtypes.iter().cloned());
+            }
+            nested.extend(en.subtypes.iter().cloned());
+        }
+        RsEntity::EnumCase(case) => {
+            references.extend(case.type_name.clone().map(|t| (file, t)));
+            nested.extend(case.subtypes.iter().cloned());
+        }
+        RsEntity::Alias(alias) => {
+            references.push((file, alias.original.clone()));
+            nested.extend(alias.subtypes.iter().cloned());
+        }
+        RsEntity::Import(_) => {}
+    }
+    for entity in &nested {
+        collect_references(file, entity, files, groups, visited_groups, references);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::{parse_schemas, schema::parse_schema};
+
+    #[test]
+    fn test_retain_reachable() {
+        let common = roxmltree::Document::parse(
+            r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tt="http://example.com/tt" targetNamespace="http://example.com/tt">
+    <xs:simpleType name="ReferenceToken">
+        <xs:restriction base="xs:string"/>
+    </xs:simpleType>
+    <xs:simpleType name="Name">
+        <xs:restriction base="xs:string"/>
+    </xs:simpleType>
+    <xs:simpleType name="Port">
+        <xs:union memberTypes="xs:int tt:Name"/>
+    </xs:simpleType>
+    <xs:complexType name="DeviceEntity">
+        <xs:attribute name="token" type="tt:ReferenceToken"/>
+    </xs:complexType>
+    <xs:complexType name="Profile">
+        <xs:complexContent>
+            <xs:extension base="tt:DeviceEntity">
+                <xs:sequence>
+                    <xs:element name="Port" type="tt:Port"/>
+                </xs:sequence>
+            </xs:extension>
+        </xs:complexContent>
+    </xs:complexType>
+    <xs:complexType name="Unused">
+        <xs:attribute name="name" type="tt:Name"/>
+    </xs:complexType>
+</xs:schema>"#,
+        )
+        .unwrap();
+        let service = roxmltree::Document::parse(
+            r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tt="http://example.com/tt" xmlns:trt="http://example.com/trt" targetNamespace="http://example.com/trt">
+    <xs:element name="GetStreamUri" type="xs:string"/>
+    <xs:element name="GetProfiles">
+        <xs:complexType>
+            <xs:sequence>
+                <xs:element name="Profiles" type="tt:Profile" maxOccurs="unbounded"/>
+            </xs:sequence>
+        </xs:complexType>
+    </xs:element>
+</xs:schema>"#,
+        )
+        .unwrap();
+
+        let mut files = parse_schemas(&[common.root_element(), service.root_element()]);
+        retain_reachable(&mut files, &["trt:GetProfiles".to_string()]);
+
+        let names = |file: &RsFile| file.types.iter().map(|t| t.name().to_string()).collect();
+        let mut common_names: Vec<String> = names(&files[0]);
+        common_names.sort();
+        assert_eq!(common_names, vec!["Name", "Port", "Profile", "ReferenceToken"]);
+        assert_eq!(names(&files[1]), vec!["GetProfiles".to_string()]);
+        assert_eq!(files[1].elements, vec![0]);
+    }
+
+    #[test]
+    fn test_retain_reachable_by_namespace() {
+        // Both schemas declare a group Info, and the roots use other prefixes than the
+        // schemas.
+        let a = roxmltree::Document::parse(
+            r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:a="http://example.com/a" targetNamespace="http://example.com/a">
+    <xs:group name="Info">
+        <xs:sequence>
+            <xs:element name="A" type="a:AType"/>
+        </xs:sequence>
+    </xs:group>
+    <xs:simpleType name="AType">
+        <xs:restriction base="xs:string"/>
+    </xs:simpleType>
+</xs:schema>"#,
+        )
+        .unwrap();
+        let b = roxmltree::Document::parse(
+            r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:b="http://example.com/b" targetNamespace="http://example.com/b">
+    <xs:group name="Info">
+        <xs:sequence>
+            <xs:element name="B" type="b:BType"/>
+        </xs:sequence>
+    </xs:group>
+    <xs:simpleType name="BType">
+        <xs:restriction base="xs:string"/>
+    </xs:simpleType>
+    <xs:complexType name="Device">
+        <xs:group ref="b:Info"/>
+    </xs:complexType>
+</xs:schema>"#,
+        )
+        .unwrap();
+
+        // Groups are not merged into the types here, so they are followed by reference.
+        let mut files = vec![parse_schema(&b.root_element()), parse_schema(&a.root_element())];
+        retain_reachable(&mut files, &["{http://example.com/b}Device".to_string()]);
+
+        let names =
+            |file: &RsFile| file.types.iter().map(|t| t.name().to_string()).collect::<Vec<_>>();
+        assert_eq!(names(&files[0]), vec!["BType".to_string(), "Device".to_string()]);
+        assert!(names(&files[1]).is_empty());
+    }
+}
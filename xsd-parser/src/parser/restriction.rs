@@ -48,6 +48,11 @@ fn simple_type_restriction(node: &Node) -> RsEntity {
                 cases,
                 type_name: base.to_string(),
                 source: EnumSource::Restriction,
+                facets: facets
+                    .iter()
+                    .filter(|f| !matches!(f.facet_type, FacetType::Enumeration(_)))
+                    .cloned()
+                    .collect(),
                 ..Default::default()
             });
         }
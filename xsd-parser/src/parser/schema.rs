@@ -21,16 +21,13 @@ pub fn parse_schema<'input>(schema: &Node<'_, 'input>) -> RsFile<'input> {
             .find(|namespace| namespace.name().is_some())
             .or_else(|| xsd_namespaces.next())
             .cloned(),
-        types: schema
-            .children()
-            .filter(|n| {
-                n.is_element()
-                    && n.xsd_type() != ElementType::Annotation
-                    && n.xsd_type() != ElementType::AttributeGroup
-                    && n.xsd_type() != ElementType::Group
-            })
-            .map(|node| parse_node(&node, schema))
+        namespaces: schema.namespaces().cloned().collect(),
+        elements: type_nodes(schema)
+            .enumerate()
+            .filter(|(_, n)| n.xsd_type() == ElementType::Element)
+            .map(|(i, _)| i)
             .collect(),
+        types: type_nodes(schema).map(|node| parse_node(&node, schema)).collect(),
         attribute_groups: schema
             .children()
             .filter(|n| n.is_element() && n.xsd_type() == ElementType::AttributeGroup)
@@ -44,6 +41,18 @@ pub fn parse_schema<'input>(schema: &Node<'_, 'input>) -> RsFile<'input> {
     }
 }
 
+// Children of the schema parsed into `types`.
+fn type_nodes<'a, 'input>(
+    schema: &Node<'a, 'input>,
+) -> impl Iterator<Item = Node<'a, 'input>> + Clone {
+    schema.children().filter(|n| {
+        n.is_element()
+            && n.xsd_type() != ElementType::Annotation
+            && n.xsd_type() != ElementType::AttributeGroup
+            && n.xsd_type() != ElementType::Group
+    })
+}
+
 #[cfg(test)]
 mod test {
     use crate::parser::schema::parse_schema;
@@ -66,7 +66,7 @@ mod test {
             RsEntity::TupleStruct(ts) => {
                 assert_eq!(ts.name, "SomeType");
                 assert_eq!(ts.type_name, "xs:SSD");
-                assert_eq!(ts.type_modifiers, vec![TypeModifier::Array, TypeModifier::Array]);
+                assert_eq!(ts.type_modifiers, vec![TypeModifier::List, TypeModifier::List]);
                 assert_eq!(ts.comment.unwrap().trim(), "Some text");
                 assert!(ts.subtypes.is_empty());
             }
@@ -105,7 +105,7 @@ mod test {
             RsEntity::TupleStruct(ts) => {
                 assert_eq!(ts.name, "SomeType");
                 assert_eq!(ts.type_name, "SomeTypeEnum");
-                assert_eq!(ts.type_modifiers, vec![TypeModifier::Array]);
+                assert_eq!(ts.type_modifiers, vec![TypeModifier::List]);
                 assert_eq!(ts.comment.unwrap().trim(), "Some text");
                 assert_eq!(ts.subtypes.len(), 1);
                 match &ts.subtypes[0] {
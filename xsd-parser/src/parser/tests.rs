@@ -122,4 +122,52 @@ mod test {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_extension_base_across_schemas() {
+        use crate::parser::{parse_schemas, types::RsEntity};
+
+        let common = roxmltree::Document::parse(
+            r#"
+<xs:schema xmlns:tt="http://www.onvif.org/ver10/schema"
+    xmlns:xs="http://www.w3.org/2001/XMLSchema"
+    targetNamespace="http://www.onvif.org/ver10/schema">
+    <xs:complexType name="DeviceEntity">
+        <xs:attribute name="token" type="tt:ReferenceToken" use="required"/>
+    </xs:complexType>
+</xs:schema>"#,
+        )
+        .unwrap();
+        let media = roxmltree::Document::parse(
+            r#"
+<xs:schema xmlns:tt="http://www.onvif.org/ver10/schema"
+    xmlns:xs="http://www.w3.org/2001/XMLSchema"
+    targetNamespace="http://www.onvif.org/ver10/media/wsdl">
+    <xs:import namespace="http://www.onvif.org/ver10/schema" schemaLocation="common.xsd"/>
+    <xs:complexType name="VideoSource">
+        <xs:complexContent>
+            <xs:extension base="tt:DeviceEntity">
+                <xs:sequence>
+                    <xs:element name="Framerate" type="xs:float"/>
+                </xs:sequence>
+            </xs:extension>
+        </xs:complexContent>
+    </xs:complexType>
+</xs:schema>"#,
+        )
+        .unwrap();
+
+        let result = parse_schemas(&[common.root_element(), media.root_element()]);
+        assert_eq!(result.len(), 2);
+
+        match result[1].types.iter().find(|t| t.name() == "VideoSource") {
+            Some(RsEntity::Struct(s)) => {
+                assert_eq!(s.fields.borrow().len(), 2);
+                assert_eq!(s.fields.borrow()[0].name, "Framerate");
+                assert_eq!(s.fields.borrow()[1].name, "token");
+                assert_eq!(s.fields.borrow()[1].type_name, "tt:ReferenceToken");
+            }
+            _ => unreachable!(),
+        }
+    }
 }
@@ -13,6 +13,10 @@ pub struct RsFile<'input> {
     pub groups: Vec<RsEntity>,
     pub target_ns: Option<Namespace<'input>>,
     pub xsd_ns: Option<Namespace<'input>>,
+    // Namespaces in scope of the schema element, to resolve the prefixes of type names.
+    pub namespaces: Vec<Namespace<'input>>,
+    // Indices of the global elements in `types`, a type may have the name of an element.
+    pub elements: Vec<usize>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -301,6 +305,8 @@ pub struct Enum {
     pub subtypes: Vec<RsEntity>,
     pub type_modifiers: Vec<TypeModifier>,
     pub source: EnumSource,
+    // Facets of a restriction other than its enumerations.
+    pub facets: Vec<Facet>,
 }
 
 #[allow(clippy::upper_case_acronyms)]
@@ -321,6 +327,7 @@ pub enum TypeModifier {
     Recursive,
     Empty,
     Flatten,
+    List,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -0,0 +1,721 @@
+use std::{cell::Cell, collections::BTreeMap};
+
+use regex_syntax::hir::{Class, Hir, HirKind};
+
+use crate::model::{
+    ComplexType, Element, Facets, Particle, QName, SchemaSet, Term, Type, TypeRef, Use, XSD_NS,
+    XSI_NS,
+};
+
+// Content below this depth is left out so recursive types end, optional content already
+// below OPTIONAL_DEPTH.
+const MAX_DEPTH: usize = 32;
+const OPTIONAL_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, Default)]
+pub struct SampleOptions {
+    // Write optional elements and attributes once instead of leaving them out.
+    pub optional: bool,
+    // Names of the branches to take in choices, `prefix:Name` or `Name` of an element or
+    // group. The first branch is taken in choices with none of them.
+    pub choices: Vec<String>,
+}
+
+// Writes an instance document with the given element as the root that is valid against
+// the schemas as far as it is possible: occurrences and attribute uses are kept, values
+// satisfy the enumeration, range, length and (for simple patterns) pattern facets, abstract
+// elements and types are replaced with the first substitute and derived type.
+pub fn sample(set: &SchemaSet, element: &Element, options: &SampleOptions) -> String {
+    let sampler = Sampler { set, options, ids: Cell::new(0) };
+    let root = sampler.element(element, 0);
+
+    let mut prefixes = BTreeMap::new();
+    root.collect_namespaces(set, &mut prefixes);
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    root.write(&prefixes, 0, &mut xml);
+    xml
+}
+
+struct Sampler<'a> {
+    set: &'a SchemaSet,
+    options: &'a SampleOptions,
+    // IDs written so far, each xs:ID value gets the next number.
+    ids: Cell<usize>,
+}
+
+impl<'a> Sampler<'a> {
+    fn element(&self, element: &Element, depth: usize) -> XmlElement {
+        let mut res = XmlElement::new(element.name.clone());
+        let mut ty = self.set.type_def(&element.ty);
+
+        // An abstract type can only be used through xsi:type.
+        if let (Some(Type::Complex(ct)), TypeRef::Named(name)) = (ty, &element.ty) {
+            if ct.is_abstract {
+                let derived = self.set.derived_types(name).into_iter().find(
+                    |d| !matches!(self.set.types.get(d), Some(Type::Complex(ct)) if ct.is_abstract),
+                );
+                if let Some(derived) = derived {
+                    res.attributes
+                        .push((QName::new(Some(XSI_NS), "type"), Value::QName(derived.clone())));
+                    ty = self.set.types.get(derived);
+                }
+            }
+        }
+
+        if let Some(value) = element.fixed.as_ref().or(element.default.as_ref()) {
+            res.text = Some(value.clone());
+            if let Some(Type::Complex(ct)) = ty {
+                self.attributes(ct, &mut res);
+            }
+            return res;
+        }
+
+        match ty {
+            Some(Type::Complex(ct)) => self.complex(ct, &mut res, depth),
+            Some(Type::Simple(_)) => res.text = Some(self.simple_value(&element.ty)),
+            // Built-in types, the ur-type has no content.
+            None => match &element.ty {
+                TypeRef::Named(name) if name.is_xsd() && name.name != "anyType" => {
+                    res.text = Some(self.simple_value(&element.ty))
+                }
+                _ => {}
+            },
+        }
+        res
+    }
+
+    fn complex(&self, ct: &ComplexType, res: &mut XmlElement, depth: usize) {
+        self.attributes(ct, res);
+        if let Some(content) = self.set.simple_content(ct) {
+            res.text = Some(self.simple_value(content));
+            return;
+        }
+        if depth < MAX_DEPTH {
+            for particle in self.set.particles(ct) {
+                self.particle(particle, &mut res.children, depth + 1);
+            }
+        }
+    }
+
+    fn attributes(&self, ct: &ComplexType, res: &mut XmlElement) {
+        for attribute in self.set.attribute_uses(ct) {
+            let value = match (attribute.use_, &attribute.fixed) {
+                (Use::Prohibited, _) => continue,
+                (_, Some(fixed)) => fixed.clone(),
+                (Use::Optional, _) if !self.options.optional => continue,
+                _ => match &attribute.default {
+                    Some(default) => default.clone(),
+                    None => self.simple_value(&attribute.ty),
+                },
+            };
+            res.attributes.push((attribute.name, Value::Text(value)));
+        }
+    }
+
+    fn particle(&self, particle: &Particle, res: &mut Vec<XmlElement>, depth: usize) {
+        let count = match particle.min_occurs {
+            0 if self.options.optional
+                && depth < OPTIONAL_DEPTH
+                && particle.max_occurs != Some(0) =>
+            {
+                1
+            }
+            min => min,
+        };
+        for _ in 0..count {
+            match &particle.term {
+                Term::Element(element) => res.push(self.element(element, depth)),
+                Term::ElementRef(name) => {
+                    if let Some(element) = self.set.elements.get(name) {
+                        let element = self.substitute(element);
+                        res.push(self.element(element, depth));
+                    }
+                }
+                Term::Group(name) => {
+                    if let Some(group) = self.set.groups.get(name) {
+                        if depth < MAX_DEPTH {
+                            self.particle(group, res, depth + 1);
+                        }
+                    }
+                }
+                Term::Sequence(particles) | Term::All(particles) => {
+                    for particle in particles {
+                        self.particle(particle, res, depth);
+                    }
+                }
+                Term::Choice(branches) => {
+                    let chosen = branches
+                        .iter()
+                        .find(|branch| self.is_chosen(branch))
+                        .or_else(|| branches.first());
+                    if let Some(branch) = chosen {
+                        self.particle(branch, res, depth);
+                    }
+                }
+                Term::Any { .. } => {}
+            }
+        }
+    }
+
+    fn substitute(&'a self, head: &'a Element) -> &'a Element {
+        let substitutes = self.set.substitutes(head);
+        substitutes
+            .iter()
+            .find(|e| self.is_named(&e.name))
+            .or_else(|| substitutes.first())
+            .copied()
+            .unwrap_or(head)
+    }
+
+    fn is_chosen(&self, branch: &Particle) -> bool {
+        match &branch.term {
+            Term::Element(element) => self.is_named(&element.name),
+            Term::ElementRef(name) => {
+                self.is_named(name)
+                    || self.set.elements.get(name).is_some_and(|head| {
+                        self.set.substitutes(head).iter().any(|e| self.is_named(&e.name))
+                    })
+            }
+            Term::Group(name) => self.is_named(name),
+            _ => false,
+        }
+    }
+
+    fn is_named(&self, name: &QName) -> bool {
+        let display = self.set.display_name(name);
+        self.options.choices.iter().any(|choice| choice == &name.name || choice == &display)
+    }
+
+    fn simple_value(&self, ty: &TypeRef) -> String {
+        if let Some((item, facets)) = self.set.list_item(ty) {
+            let facets = merge(&facets);
+            let count = facets.length.or(facets.min_length).unwrap_or(1).max(1);
+            return vec![self.simple_value(item); count].join(" ");
+        }
+        if let Some(members) = self.set.union_members(ty) {
+            return members.first().map(|m| self.simple_value(m)).unwrap_or_default();
+        }
+
+        let (builtin, facets) = self.set.simple_base(ty);
+        if let Some(value) = facets.iter().find_map(|f| f.enumeration.first()) {
+            return value.clone();
+        }
+        let merged = merge(&facets);
+        if let Some(value) =
+            facets.iter().find_map(|f| f.patterns.first()).and_then(|p| pattern_value(p, &merged))
+        {
+            return value;
+        }
+        match builtin.map(|b| b.name.as_str()).unwrap_or("string") {
+            "ID" => {
+                self.ids.set(self.ids.get() + 1);
+                sized(&format!("id{}", self.ids.get()), &merged)
+            }
+            builtin => builtin_value(builtin, &merged),
+        }
+    }
+}
+
+// The facets that apply to a value, from those of a derivation chain with the most derived
+// type first.
+fn merge(facets: &[&Facets]) -> Facets {
+    let mut res = Facets::default();
+    for f in facets {
+        res.min_inclusive = res.min_inclusive.or_else(|| f.min_inclusive.clone());
+        res.max_inclusive = res.max_inclusive.or_else(|| f.max_inclusive.clone());
+        res.min_exclusive = res.min_exclusive.or_else(|| f.min_exclusive.clone());
+        res.max_exclusive = res.max_exclusive.or_else(|| f.max_exclusive.clone());
+        res.length = res.length.or(f.length);
+        res.min_length = res.min_length.or(f.min_length);
+        res.max_length = res.max_length.or(f.max_length);
+        res.total_digits = res.total_digits.or(f.total_digits);
+        res.fraction_digits = res.fraction_digits.or(f.fraction_digits);
+    }
+    res
+}
+
+fn builtin_value(name: &str, facets: &Facets) -> String {
+    match name {
+        "byte" | "short" | "int" | "long" | "integer" | "nonNegativeInteger"
+        | "nonPositiveInteger" | "unsignedByte" | "unsignedShort" | "unsignedInt"
+        | "unsignedLong" => integer_value(0, facets),
+        "positiveInteger" => integer_value(1, facets),
+        "negativeInteger" => integer_value(-1, facets),
+        "decimal" | "float" | "double" => decimal_value(facets),
+        "boolean" => "true".to_string(),
+        "date" => "2000-01-01".to_string(),
+        "dateTime" => "2000-01-01T00:00:00Z".to_string(),
+        "time" => "00:00:00".to_string(),
+        "duration" => "PT0S".to_string(),
+        "gYear" => "2000".to_string(),
+        "gYearMonth" => "2000-01".to_string(),
+        "gMonth" => "--01".to_string(),
+        "gMonthDay" => "--01-01".to_string(),
+        "gDay" => "---01".to_string(),
+        "hexBinary" => "00".repeat(octets(facets)),
+        "base64Binary" => base64_zeros(octets(facets)),
+        "anyURI" => "http://example.com".to_string(),
+        "language" => "en".to_string(),
+        "QName" | "Name" | "NCName" | "ID" | "IDREF" | "IDREFS" | "ENTITY" | "ENTITIES"
+        | "NMTOKEN" | "NMTOKENS" => sized("name", facets),
+        _ => sized("string", facets),
+    }
+}
+
+fn integer_value(default: i128, facets: &Facets) -> String {
+    let bound = |value: &Option<String>| value.as_ref().and_then(|v| v.trim().parse::<i128>().ok());
+    let mut value = default;
+    if let Some(min) = bound(&facets.min_inclusive) {
+        value = value.max(min);
+    }
+    if let Some(min) = bound(&facets.min_exclusive) {
+        value = value.max(min + 1);
+    }
+    if let Some(max) = bound(&facets.max_inclusive) {
+        value = value.min(max);
+    }
+    if let Some(max) = bound(&facets.max_exclusive) {
+        value = value.min(max - 1);
+    }
+    value.to_string()
+}
+
+// The value closest to 0 within the range facets, with at most fractionDigits and
+// totalDigits digits. Bounds are compared as decimals scaled to integers, not as floats,
+// so that large and precise values are kept.
+fn decimal_value(facets: &Facets) -> String {
+    let bounds = [
+        &facets.min_inclusive,
+        &facets.max_inclusive,
+        &facets.min_exclusive,
+        &facets.max_exclusive,
+    ];
+    // One digit more than the bounds have, so that there is a value between exclusive ones.
+    let precision = bounds
+        .iter()
+        .filter_map(|bound| bound.as_ref())
+        .map(|bound| plain_decimal(bound).map_or(0, |(_, _, fraction)| fraction.len()) + 1)
+        .max()
+        .unwrap_or(0);
+    let max_scale = facets.fraction_digits.unwrap_or(precision).min(precision) as u32;
+
+    for scale in (0..=max_scale).rev() {
+        let bound = |value: &Option<String>, ceil| match value {
+            Some(value) => scaled(value, scale, ceil).map(Some),
+            None => Some(None),
+        };
+        let lower = [
+            bound(&facets.min_inclusive, true),
+            bound(&facets.min_exclusive, false).map(|b| b.map(|b| b + 1)),
+        ];
+        let upper = [
+            bound(&facets.max_inclusive, false),
+            bound(&facets.max_exclusive, true).map(|b| b.map(|b| b - 1)),
+        ];
+        let (lower, upper) = match (lower, upper) {
+            ([Some(a), Some(b)], [Some(c), Some(d)]) => (a.max(b), c.min(d)),
+            _ => return "0".to_string(),
+        };
+
+        let value = lower.map_or(0, |lower| lower.max(0));
+        let value = upper.map_or(value, |upper| upper.min(value));
+        if lower.is_none_or(|lower| lower <= value) {
+            let value = format_scaled(value, scale);
+            if facets.total_digits.is_none_or(|total| total_digits(&value) <= total) {
+                return value;
+            }
+        }
+    }
+    "0".to_string()
+}
+
+// A decimal or float literal in units of 10^-scale, rounded up or down. None if it is
+// neither or doesn't fit.
+fn scaled(value: &str, scale: u32, ceil: bool) -> Option<i128> {
+    let (negative, int, fraction) = plain_decimal(value)?;
+    let scale = scale as usize;
+    let kept = format!("{}{:0<width$}", int, &fraction[..fraction.len().min(scale)], width = scale);
+    let mut res = if kept.is_empty() { 0 } else { kept.parse::<i128>().ok()? };
+    let inexact = fraction.chars().skip(scale).any(|c| c != '0');
+    if inexact && ceil != negative {
+        res += 1;
+    }
+    Some(if negative { -res } else { res })
+}
+
+// The sign and the digits before and after the point of a literal, with its exponent applied.
+fn plain_decimal(value: &str) -> Option<(bool, String, String)> {
+    let value = value.trim();
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (mantissa, exponent) = match value.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i32>().ok()?),
+        None => (value, 0),
+    };
+    let (int, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = format!("{}{}", int, fraction);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) || exponent.abs() > 64 {
+        return None;
+    }
+
+    let point = int.len() as i32 + exponent;
+    Some(if point <= 0 {
+        (negative, String::new(), format!("{}{}", "0".repeat(-point as usize), digits))
+    } else if point as usize >= digits.len() {
+        (negative, format!("{:0<width$}", digits, width = point as usize), String::new())
+    } else {
+        let (int, fraction) = digits.split_at(point as usize);
+        (negative, int.to_string(), fraction.to_string())
+    })
+}
+
+fn format_scaled(value: i128, scale: u32) -> String {
+    let sign = if value < 0 { "-" } else { "" };
+    let digits = format!("{:0>width$}", value.unsigned_abs(), width = scale as usize + 1);
+    let (int, fraction) = digits.split_at(digits.len() - scale as usize);
+    match fraction.trim_end_matches('0') {
+        "" => format!("{}{}", sign, int),
+        fraction => format!("{}{}.{}", sign, int, fraction),
+    }
+}
+
+// Significant digits of a literal of format_scaled, leading and trailing zeros aside.
+fn total_digits(value: &str) -> usize {
+    let value = value.trim_start_matches('-');
+    let (int, fraction) = value.split_once('.').unwrap_or((value, ""));
+    (int.trim_start_matches('0').len() + fraction.len()).max(1)
+}
+
+// The number of octets of a binary value, one if the length facets allow it.
+fn octets(facets: &Facets) -> usize {
+    facets.length.unwrap_or_else(|| {
+        facets.min_length.unwrap_or(1).max(1).min(facets.max_length.unwrap_or(usize::MAX))
+    })
+}
+
+fn base64_zeros(octets: usize) -> String {
+    let padding = match octets % 3 {
+        0 => "",
+        1 => "AA==",
+        _ => "AAA=",
+    };
+    format!("{}{}", "AAAA".repeat(octets / 3), padding)
+}
+
+// A string padded or cut to the length facets.
+fn sized(value: &str, facets: &Facets) -> String {
+    let mut value = value.to_string();
+    let min = facets.length.or(facets.min_length).unwrap_or(0);
+    let max = facets.length.or(facets.max_length).unwrap_or(usize::MAX);
+    while value.len() < min {
+        value.push('x');
+    }
+    value.truncate(max);
+    value
+}
+
+// A string matching an XSD pattern, if regex-syntax can parse it, of a length the length
+// facets allow. Alternations take their first branch, classes a letter or digit in them and
+// repetitions their minimal count, or more until the value reaches the minimal length.
+fn pattern_value(pattern: &str, facets: &Facets) -> Option<String> {
+    let hir = regex_syntax::Parser::new().parse(pattern).ok()?;
+    let min = facets.length.or(facets.min_length).unwrap_or(0);
+    let max = facets.length.or(facets.max_length).unwrap_or(usize::MAX);
+
+    let mut res = String::new();
+    write_hir(&hir, &mut 0, &mut res)?;
+    let shortest = res.chars().count();
+    if shortest < min {
+        res.clear();
+        write_hir(&hir, &mut (min - shortest), &mut res)?;
+    }
+
+    let length = res.chars().count();
+    if length < min || length > max {
+        return None;
+    }
+    Some(res)
+}
+
+// `extra` is the number of characters the repetitions may add beyond their minimal count.
+fn write_hir(hir: &Hir, extra: &mut usize, res: &mut String) -> Option<()> {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => {}
+        HirKind::Literal(literal) => res.push_str(std::str::from_utf8(&literal.0).ok()?),
+        HirKind::Class(Class::Unicode(class)) => {
+            let ranges = class.ranges();
+            let contains = |c: char| ranges.iter().any(|r| r.start() <= c && c <= r.end());
+            let c = ['a', 'A', '0', '1', 'x']
+                .iter()
+                .copied()
+                .find(|c| contains(*c))
+                .or_else(|| ranges.iter().map(|r| r.start().max('!')).find(|c| contains(*c)))?;
+            res.push(c);
+        }
+        HirKind::Class(Class::Bytes(class)) => {
+            let range = class.ranges().first()?;
+            res.push(char::from(range.start().max(b'!').min(range.end())));
+        }
+        HirKind::Repetition(repetition) => {
+            for _ in 0..repetition.min {
+                write_hir(&repetition.sub, extra, res)?;
+            }
+            let mut count = repetition.min;
+            while *extra > 0 && repetition.max.is_none_or(|max| count < max) {
+                let length = res.len();
+                write_hir(&repetition.sub, &mut 0, res)?;
+                let added = res[length..].chars().count();
+                if added == 0 {
+                    break;
+                }
+                *extra = extra.saturating_sub(added);
+                count += 1;
+            }
+        }
+        HirKind::Capture(capture) => write_hir(&capture.sub, extra, res)?,
+        HirKind::Concat(hirs) => {
+            for hir in hirs {
+                write_hir(hir, extra, res)?;
+            }
+        }
+        HirKind::Alternation(hirs) => write_hir(hirs.first()?, extra, res)?,
+    }
+    Some(())
+}
+
+enum Value {
+    Text(String),
+    // A QName value, e.g. of xsi:type, written with the prefix of its namespace.
+    QName(QName),
+}
+
+struct XmlElement {
+    name: QName,
+    attributes: Vec<(QName, Value)>,
+    children: Vec<XmlElement>,
+    text: Option<String>,
+}
+
+impl XmlElement {
+    fn new(name: QName) -> Self {
+        XmlElement { name, attributes: vec![], children: vec![], text: None }
+    }
+
+    // Prefixes of the namespaces used in the document, as the schemas declare them if they
+    // do, otherwise ns1, ns2...
+    fn collect_namespaces(&self, set: &SchemaSet, prefixes: &mut BTreeMap<String, String>) {
+        let names = std::iter::once(&self.name)
+            .chain(self.attributes.iter().map(|(name, _)| name))
+            .chain(self.attributes.iter().filter_map(|(_, value)| match value {
+                Value::QName(name) => Some(name),
+                Value::Text(_) => None,
+            }));
+        for ns in names.filter_map(|name| name.namespace.as_ref()) {
+            if prefixes.contains_key(ns) {
+                continue;
+            }
+            let prefix = match (set.prefixes.get(ns), ns.as_str()) {
+                (_, XSI_NS) => "xsi".to_string(),
+                (Some(prefix), _) if !prefixes.values().any(|p| p == prefix) => prefix.clone(),
+                (_, XSD_NS) => "xs".to_string(),
+                _ => (1..)
+                    .map(|i| format!("ns{}", i))
+                    .find(|p| !prefixes.values().any(|used| used == p))
+                    .unwrap(),
+            };
+            prefixes.insert(ns.clone(), prefix);
+        }
+        for child in &self.children {
+            child.collect_namespaces(set, prefixes);
+        }
+    }
+
+    fn write(&self, prefixes: &BTreeMap<String, String>, depth: usize, xml: &mut String) {
+        let indent = "  ".repeat(depth);
+        let name = qualified(&self.name, prefixes);
+        xml.push_str(&format!("{}<{}", indent, name));
+        if depth == 0 {
+            let mut declared = prefixes.iter().collect::<Vec<_>>();
+            declared.sort_by(|a, b| a.1.cmp(b.1));
+            for (ns, prefix) in declared {
+                xml.push_str(&format!(" xmlns:{}=\"{}\"", prefix, escape(ns)));
+            }
+        }
+        for (attribute, value) in &self.attributes {
+            let value = match value {
+                Value::Text(text) => text.clone(),
+                Value::QName(name) => qualified(name, prefixes),
+            };
+            xml.push_str(&format!(" {}=\"{}\"", qualified(attribute, prefixes), escape(&value)));
+        }
+
+        match (&self.text, self.children.is_empty()) {
+            (Some(text), true) => xml.push_str(&format!(">{}</{}>\n", escape(text), name)),
+            (None, true) => xml.push_str("/>\n"),
+            _ => {
+                xml.push_str(">\n");
+                for child in &self.children {
+                    child.write(prefixes, depth + 1, xml);
+                }
+                xml.push_str(&format!("{}</{}>\n", indent, name));
+            }
+        }
+    }
+}
+
+fn qualified(name: &QName, prefixes: &BTreeMap<String, String>) -> String {
+    match name.namespace.as_ref().and_then(|ns| prefixes.get(ns)) {
+        Some(prefix) => format!("{}:{}", prefix, name.name),
+        None => name.name.clone(),
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SCHEMA: &str = r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tt="http://example.com/tt" targetNamespace="http://example.com/tt" elementFormDefault="qualified">
+    <xs:simpleType name="Port">
+        <xs:restriction base="xs:int">
+            <xs:minInclusive value="1"/>
+            <xs:maxInclusive value="65535"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:simpleType name="Mode">
+        <xs:restriction base="xs:string">
+            <xs:enumeration value="Auto"/>
+            <xs:enumeration value="Manual"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:complexType name="Endpoint">
+        <xs:sequence>
+            <xs:element name="Address" type="tt:Address"/>
+            <xs:choice>
+                <xs:element name="Port" type="tt:Port"/>
+                <xs:element name="Mode" type="tt:Mode"/>
+            </xs:choice>
+            <xs:element name="Comment" type="xs:string" minOccurs="0"/>
+        </xs:sequence>
+        <xs:attribute name="token" type="xs:string" use="required"/>
+        <xs:attribute name="weight" type="xs:float"/>
+    </xs:complexType>
+    <xs:simpleType name="Address">
+        <xs:restriction base="xs:string">
+            <xs:pattern value="\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:element name="Endpoint" type="tt:Endpoint"/>
+</xs:schema>"#;
+
+    #[test]
+    fn test_sample() {
+        let doc = roxmltree::Document::parse(SCHEMA).unwrap();
+        let set = SchemaSet::parse(&[doc.root_element()]);
+        let element = set.find_element("tt:Endpoint").unwrap();
+
+        assert_eq!(
+            sample(&set, element, &SampleOptions::default()),
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<tt:Endpoint xmlns:tt="http://example.com/tt" token="string">
+  <tt:Address>0.0.0.0</tt:Address>
+  <tt:Port>1</tt:Port>
+</tt:Endpoint>
+"#
+        );
+
+        let options = SampleOptions { optional: true, choices: vec!["tt:Mode".to_string()] };
+        assert_eq!(
+            sample(&set, element, &options),
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<tt:Endpoint xmlns:tt="http://example.com/tt" token="string" weight="0">
+  <tt:Address>0.0.0.0</tt:Address>
+  <tt:Mode>Auto</tt:Mode>
+  <tt:Comment>string</tt:Comment>
+</tt:Endpoint>
+"#
+        );
+    }
+
+    #[test]
+    fn test_sample_is_valid() {
+        let doc = roxmltree::Document::parse(
+            r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tt="http://example.com/tt" targetNamespace="http://example.com/tt" elementFormDefault="qualified">
+    <xs:complexType name="Item">
+        <xs:sequence>
+            <xs:element name="Hash">
+                <xs:simpleType>
+                    <xs:restriction base="xs:hexBinary">
+                        <xs:length value="4"/>
+                    </xs:restriction>
+                </xs:simpleType>
+            </xs:element>
+            <xs:element name="Data">
+                <xs:simpleType>
+                    <xs:restriction base="xs:base64Binary">
+                        <xs:minLength value="5"/>
+                    </xs:restriction>
+                </xs:simpleType>
+            </xs:element>
+            <xs:element name="Code">
+                <xs:simpleType>
+                    <xs:restriction base="xs:string">
+                        <xs:pattern value="[A-Z]+(-[0-9])*"/>
+                        <xs:minLength value="5"/>
+                    </xs:restriction>
+                </xs:simpleType>
+            </xs:element>
+            <xs:element name="Ratio">
+                <xs:simpleType>
+                    <xs:restriction base="xs:decimal">
+                        <xs:minExclusive value="0.5"/>
+                        <xs:maxInclusive value="0.6"/>
+                        <xs:fractionDigits value="1"/>
+                    </xs:restriction>
+                </xs:simpleType>
+            </xs:element>
+            <xs:element name="Total">
+                <xs:simpleType>
+                    <xs:restriction base="xs:decimal">
+                        <xs:minInclusive value="12345678901234567890.25"/>
+                        <xs:totalDigits value="21"/>
+                    </xs:restriction>
+                </xs:simpleType>
+            </xs:element>
+        </xs:sequence>
+        <xs:attribute name="id" type="xs:ID" use="required"/>
+    </xs:complexType>
+    <xs:element name="Items">
+        <xs:complexType>
+            <xs:sequence>
+                <xs:element name="Item" type="tt:Item" minOccurs="2" maxOccurs="2"/>
+            </xs:sequence>
+        </xs:complexType>
+    </xs:element>
+</xs:schema>"#,
+        )
+        .unwrap();
+        let set = SchemaSet::parse(&[doc.root_element()]);
+        let element = set.find_element("tt:Items").unwrap();
+
+        let xml = sample(&set, element, &SampleOptions::default());
+        assert!(xml.contains(r#"<tt:Item id="id1">"#));
+        assert!(xml.contains(r#"<tt:Item id="id2">"#));
+        assert!(xml.contains("<tt:Hash>00000000</tt:Hash>"));
+        assert!(xml.contains("<tt:Data>AAAAAAA=</tt:Data>"));
+        assert!(xml.contains("<tt:Code>AAAAA</tt:Code>"));
+        assert!(xml.contains("<tt:Ratio>0.6</tt:Ratio>"));
+        assert!(xml.contains("<tt:Total>12345678901234567890.3</tt:Total>"));
+
+        let instance = roxmltree::Document::parse(&xml).unwrap();
+        assert_eq!(crate::validate::validate(&set, &instance), vec![]);
+    }
+}
@@ -0,0 +1,1179 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+};
+
+use regex::Regex;
+use roxmltree::{Document, Node};
+
+use crate::model::{
+    ComplexType, Element, Facets, IdentityConstraint, IdentityKind, Particle, QName, SchemaSet,
+    Term, Type, TypeRef, Use, XSI_NS,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub line: u32,
+    pub column: u32,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+// Validates an instance document against the schemas: the order and occurrences of child
+// elements, attribute uses, the facets of simple values and identity constraints. Content
+// of xs:any and xs:anyType is accepted without checking it. Violations are returned in
+// document order.
+pub fn validate(set: &SchemaSet, doc: &Document) -> Vec<Violation> {
+    let mut validator =
+        Validator { set, doc, violations: vec![], patterns: HashMap::new(), ids: HashSet::new() };
+    let root = doc.root_element();
+    match set.elements.get(&node_name(&root)) {
+        Some(element) => {
+            validator.element(&root, element);
+        }
+        None => validator.report(
+            &root,
+            format!("no global declaration for element {}", set.display_name(&node_name(&root))),
+        ),
+    }
+    validator.violations.sort_by_key(|v| (v.line, v.column));
+    validator.violations
+}
+
+// Values of the identity constraints evaluated in a subtree, by constraint name.
+type KeyTables = BTreeMap<QName, Vec<Vec<String>>>;
+
+struct Validator<'a, 'input> {
+    set: &'a SchemaSet,
+    doc: &'a Document<'input>,
+    violations: Vec<Violation>,
+    // Compiled patterns, None for those the regex crate can't compile.
+    patterns: HashMap<String, Option<Regex>>,
+    // xs:ID values seen so far, which must be unique in the document.
+    ids: HashSet<String>,
+}
+
+impl<'a, 'input> Validator<'a, 'input> {
+    fn report(&mut self, node: &Node, message: String) {
+        let pos = self.doc.text_pos_at(node.range().start);
+        self.violations.push(Violation { line: pos.row, column: pos.col, message });
+    }
+
+    fn element(&mut self, node: &Node, element: &'a Element) -> KeyTables {
+        let mut tables = KeyTables::new();
+        let name = self.set.display_name(&element.name);
+        if element.is_abstract {
+            self.report(node, format!("element {} is abstract", name));
+        }
+
+        let mut ty = self.set.type_def(&element.ty);
+        let xsi_type;
+        let mut ty_ref = &element.ty;
+        if let Some(value) = node.attribute((XSI_NS, "type")) {
+            match self.xsi_type(node, value, &element.ty) {
+                Ok(derived) => {
+                    ty = self.set.types.get(derived);
+                    xsi_type = TypeRef::Named(derived.clone());
+                    ty_ref = &xsi_type;
+                }
+                Err(message) => self.report(node, message),
+            }
+        }
+
+        if node.attribute((XSI_NS, "nil")).is_some_and(|nil| nil == "true" || nil == "1") {
+            if !element.nillable {
+                self.report(node, format!("element {} is not nillable", name));
+            } else if node.children().any(|n| n.is_element() || !is_blank(&n)) {
+                self.report(node, format!("nil element {} must be empty", name));
+            }
+            if let Some(Type::Complex(ct)) = ty {
+                self.attributes(node, ct);
+            }
+            return tables;
+        }
+
+        match ty {
+            Some(Type::Complex(ct)) => tables = self.complex(node, ct),
+            Some(Type::Simple(_)) => self.simple(node, ty_ref),
+            // Built-in types, the content of the ur-type is not checked.
+            None => match ty_ref {
+                TypeRef::Named(ty) if ty.is_xsd() && ty.name != "anyType" => {
+                    self.simple(node, ty_ref)
+                }
+                TypeRef::Named(ty) if !ty.is_xsd() => {
+                    let ty = self.set.display_name(ty);
+                    self.report(node, format!("type {} of element {} is not declared", ty, name))
+                }
+                _ => {}
+            },
+        }
+
+        if let Some(fixed) = &element.fixed {
+            let text = node.text().unwrap_or_default();
+            if node.has_children() && collapse(text) != collapse(fixed) {
+                self.report(node, format!("element {} must have the fixed value {}", name, fixed));
+            }
+        }
+
+        // Keyrefs are checked once the keys of the element are filled, whatever the
+        // declaration order.
+        let (keyrefs, keys): (Vec<_>, Vec<_>) = element
+            .identity_constraints
+            .iter()
+            .partition(|c| matches!(c.kind, IdentityKind::KeyRef(_)));
+        for constraint in keys.into_iter().chain(keyrefs) {
+            self.identity(node, constraint, &mut tables);
+        }
+        tables
+    }
+
+    // The type named by xsi:type, which must be derived from the declared type.
+    fn xsi_type(&self, node: &Node, value: &str, declared: &TypeRef) -> Result<&'a QName, String> {
+        let (prefix, local) = match value.split_once(':') {
+            Some((prefix, local)) => (Some(prefix), local),
+            None => (None, value),
+        };
+        let name = QName::new(node.lookup_namespace_uri(prefix), local);
+        let (derived, _) = match self.set.types.get_key_value(&name) {
+            Some(found) => found,
+            None => return Err(format!("xsi:type {} is not declared", value)),
+        };
+        match declared {
+            TypeRef::Named(base) if base.is_xsd() && base.name == "anyType" => Ok(derived),
+            TypeRef::Named(base)
+                if base == derived || self.set.base_chain(derived).contains(base) =>
+            {
+                Ok(derived)
+            }
+            _ => Err(format!("xsi:type {} is not derived from the declared type", value)),
+        }
+    }
+
+    fn complex(&mut self, node: &Node, ct: &'a ComplexType) -> KeyTables {
+        self.attributes(node, ct);
+        let name = self.set.display_name(&node_name(node));
+
+        if let Some(content) = self.set.simple_content(ct) {
+            if let Some(child) = node.children().find(Node::is_element) {
+                self.report(&child, format!("element {} can only contain text", name));
+            } else {
+                self.simple(node, content);
+            }
+            return KeyTables::new();
+        }
+
+        if !ct.mixed {
+            if let Some(text) = node.children().find(|n| n.is_text() && !is_blank(n)) {
+                self.report(&text, format!("element {} cannot contain text", name));
+            }
+        }
+
+        let children = node.children().filter(Node::is_element).collect::<Vec<_>>();
+        let particles = self.set.particles(ct);
+        let mut matcher = Matcher::new(self.set, &children);
+        let mut end = Some(0);
+        for particle in particles.iter().copied() {
+            end = end.and_then(|pos| matcher.particle(particle, pos));
+        }
+
+        match end {
+            Some(end) if end == children.len() => {}
+            _ => {
+                // Children left after a complete match are unexpected whatever failed to
+                // match before them.
+                let (pos, expected) = match end {
+                    Some(end) if end > matcher.furthest => (end, "no more elements".to_string()),
+                    _ if matcher.expected.is_empty() => {
+                        (matcher.furthest, "no more elements".to_string())
+                    }
+                    _ => (matcher.furthest, matcher.expected.join(", ")),
+                };
+                match children.get(pos) {
+                    Some(child) => {
+                        let child_name = self.set.display_name(&node_name(child));
+                        let message = format!(
+                            "unexpected element {} in {}, expected {}",
+                            child_name, name, expected
+                        );
+                        self.report(child, message);
+                    }
+                    None => self.report(
+                        node,
+                        format!("element {} is incomplete, expected {}", name, expected),
+                    ),
+                }
+            }
+        }
+
+        let mut tables = KeyTables::new();
+        for (index, element) in matcher.assigned.iter().copied() {
+            for (key, mut rows) in self.element(&children[index], element) {
+                tables.entry(key).or_default().append(&mut rows);
+            }
+        }
+        for (index, element) in &matcher.lax {
+            if let Some(element) = element {
+                self.element(&children[*index], element);
+            }
+        }
+
+        // Children the content model didn't match are still checked against a declaration
+        // of their name, so a misplaced element doesn't hide the errors in the others.
+        for (index, child) in children.iter().enumerate() {
+            if matcher.assigned.iter().any(|(i, _)| *i == index)
+                || matcher.lax.iter().any(|(i, _)| *i == index)
+            {
+                continue;
+            }
+            let name = node_name(child);
+            if let Some(element) = particles.iter().find_map(|p| self.declaration(p, &name, 0)) {
+                self.element(child, element);
+            }
+        }
+        tables
+    }
+
+    fn declaration(
+        &self,
+        particle: &'a Particle,
+        name: &QName,
+        depth: usize,
+    ) -> Option<&'a Element> {
+        if depth > 64 {
+            return None;
+        }
+        match &particle.term {
+            Term::Element(element) => Some(element.as_ref()).filter(|e| &e.name == name),
+            Term::ElementRef(head) => {
+                let head = self.set.elements.get(head)?;
+                self.set.substitutes(head).into_iter().find(|e| &e.name == name)
+            }
+            Term::Group(group) => self.declaration(self.set.groups.get(group)?, name, depth + 1),
+            Term::Sequence(particles) | Term::Choice(particles) | Term::All(particles) => {
+                particles.iter().find_map(|p| self.declaration(p, name, depth + 1))
+            }
+            Term::Any { .. } => None,
+        }
+    }
+
+    fn attributes(&mut self, node: &Node, ct: &ComplexType) {
+        let uses = self.set.attribute_uses(ct);
+        let element = self.set.display_name(&node_name(node));
+
+        for attribute in node.attributes() {
+            if attribute.namespace() == Some(XSI_NS) {
+                continue;
+            }
+            let name = QName::new(attribute.namespace(), attribute.name());
+            let display = self.set.display_name(&name);
+            match uses.iter().find(|u| u.name == name) {
+                Some(u) if u.use_ == Use::Prohibited => self
+                    .report(node, format!("attribute {} is not allowed on {}", display, element)),
+                Some(u) => {
+                    if let Some(fixed) = &u.fixed {
+                        if collapse(attribute.value()) != collapse(fixed) {
+                            let message = format!(
+                                "attribute {} must have the fixed value {}",
+                                display, fixed
+                            );
+                            self.report(node, message);
+                        }
+                    }
+                    if let Err(message) = self.check_value(&u.ty, attribute.value()) {
+                        self.report(node, format!("attribute {}: {}", display, message));
+                    }
+                }
+                None if self.set.any_attribute(ct) => {}
+                None => self
+                    .report(node, format!("attribute {} is not allowed on {}", display, element)),
+            }
+        }
+
+        for u in uses.iter().filter(|u| u.use_ == Use::Required) {
+            let present = node
+                .attributes()
+                .any(|a| a.namespace() == u.name.namespace.as_deref() && a.name() == u.name.name);
+            if !present {
+                let display = self.set.display_name(&u.name);
+                self.report(node, format!("attribute {} is required on {}", display, element));
+            }
+        }
+    }
+
+    fn simple(&mut self, node: &Node, ty: &TypeRef) {
+        let name = self.set.display_name(&node_name(node));
+        if let Some(child) = node.children().find(Node::is_element) {
+            self.report(&child, format!("element {} can only contain text", name));
+            return;
+        }
+        let text = node.children().filter_map(|n| n.text()).collect::<String>();
+        if let Err(message) = self.check_value(ty, &text) {
+            self.report(node, format!("element {}: {}", name, message));
+        }
+    }
+
+    fn check_value(&mut self, ty: &TypeRef, value: &str) -> Result<(), String> {
+        if let Some((item, facets)) = self.set.list_item(ty) {
+            let items = value.split_whitespace().collect::<Vec<_>>();
+            for facets in facets {
+                check_length(facets, items.len(), "items")?;
+                check_enumeration(facets, &items.join(" "))?;
+            }
+            for item_value in items {
+                self.check_value(item, item_value)?;
+            }
+            return Ok(());
+        }
+        if let Some(members) = self.set.union_members(ty) {
+            if members.iter().any(|m| self.check_value(m, value).is_ok()) {
+                return Ok(());
+            }
+            return Err(format!("{:?} matches none of the member types", value));
+        }
+
+        let (builtin, facets) = self.set.simple_base(ty);
+        let builtin = builtin.map(|b| b.name.as_str()).unwrap_or("anySimpleType");
+        let value = match builtin {
+            "string" | "anySimpleType" => value.to_string(),
+            "normalizedString" => value.replace(['\t', '\n', '\r'], " "),
+            _ => collapse(value),
+        };
+        check_builtin(builtin, &value)?;
+        for facets in facets {
+            check_enumeration(facets, &value)?;
+            if !facets.patterns.is_empty()
+                && !facets.patterns.iter().any(|p| self.matches_pattern(p, &value))
+            {
+                return Err(format!(
+                    "{:?} does not match the pattern {}",
+                    value,
+                    facets.patterns.join(" | ")
+                ));
+            }
+            let length = match builtin {
+                "hexBinary" => value.len() / 2,
+                "base64Binary" => value.trim_end_matches('=').len() * 3 / 4,
+                _ => value.chars().count(),
+            };
+            check_length(facets, length, "characters")?;
+            check_range(builtin, facets, &value)?;
+            check_digits(facets, &value)?;
+        }
+        if builtin == "ID" && !self.ids.insert(value.clone()) {
+            return Err(format!("duplicate xs:ID {:?}", value));
+        }
+        Ok(())
+    }
+
+    // Patterns that can't be compiled are treated as matching, e.g. those with character
+    // class subtraction which the regex crate has no syntax for.
+    fn matches_pattern(&mut self, pattern: &str, value: &str) -> bool {
+        let regex = self
+            .patterns
+            .entry(pattern.to_string())
+            .or_insert_with(|| Regex::new(&format!("^(?:{})$", xsd_regex(pattern))).ok());
+        regex.as_ref().is_none_or(|r| r.is_match(value))
+    }
+
+    fn identity(&mut self, node: &Node, constraint: &IdentityConstraint, tables: &mut KeyTables) {
+        let name = self.set.display_name(&constraint.name);
+        let mut rows = vec![];
+        for selected in select(node, &constraint.selector, constraint) {
+            let mut row = vec![];
+            for field in &constraint.fields {
+                let values = field_values(&selected, field, constraint);
+                match values.len() {
+                    1 => row.push(collapse(&values[0])),
+                    0 => break,
+                    _ => {
+                        self.report(
+                            &selected,
+                            format!("field {} of {} selects more than one value", field, name),
+                        );
+                        break;
+                    }
+                }
+            }
+            if row.len() < constraint.fields.len() {
+                if constraint.kind == IdentityKind::Key {
+                    self.report(&selected, format!("key {} is missing a field", name));
+                }
+                continue;
+            }
+
+            match &constraint.kind {
+                IdentityKind::Unique | IdentityKind::Key => {
+                    if rows.contains(&row) {
+                        self.report(
+                            &selected,
+                            format!("duplicate value {} for {}", row.join(", "), name),
+                        );
+                    }
+                    rows.push(row);
+                }
+                IdentityKind::KeyRef(refer) => {
+                    if !tables.get(refer).is_some_and(|keys| keys.contains(&row)) {
+                        let refer = self.set.display_name(refer);
+                        let message = format!(
+                            "{} refers to {} which has no value {}",
+                            name,
+                            refer,
+                            row.join(", ")
+                        );
+                        self.report(&selected, message);
+                    }
+                }
+            }
+        }
+        if !matches!(constraint.kind, IdentityKind::KeyRef(_)) {
+            tables.entry(constraint.name.clone()).or_default().append(&mut rows);
+        }
+    }
+}
+
+// Matches child elements against particles. Schemas satisfy the Unique Particle
+// Attribution constraint, so a greedy match without backtracking across particles is
+// enough.
+struct Matcher<'a, 'b, 'input> {
+    set: &'a SchemaSet,
+    children: &'b [Node<'b, 'input>],
+    // Declarations of the matched children by index.
+    assigned: Vec<(usize, &'a Element)>,
+    // Children matched by xs:any, with their global declaration if there is one.
+    lax: Vec<(usize, Option<&'a Element>)>,
+    // The furthest position where a term didn't match, and what was expected there.
+    furthest: usize,
+    expected: Vec<String>,
+}
+
+impl<'a, 'b, 'input> Matcher<'a, 'b, 'input> {
+    fn new(set: &'a SchemaSet, children: &'b [Node<'b, 'input>]) -> Self {
+        Matcher { set, children, assigned: vec![], lax: vec![], furthest: 0, expected: vec![] }
+    }
+
+    fn expect(&mut self, pos: usize, name: String) {
+        if pos > self.furthest {
+            self.furthest = pos;
+            self.expected.clear();
+        }
+        if pos == self.furthest && !self.expected.contains(&name) {
+            self.expected.push(name);
+        }
+    }
+
+    // Position after the particle matched from `pos`, None if it can't match there.
+    fn particle(&mut self, particle: &'a Particle, pos: usize) -> Option<usize> {
+        let mut count = 0;
+        let mut current = pos;
+        while particle.max_occurs.is_none_or(|max| count < max) {
+            let saved = (self.assigned.len(), self.lax.len());
+            match self.term(&particle.term, current) {
+                Some(next) if next > current => {
+                    count += 1;
+                    current = next;
+                }
+                Some(_) => {
+                    count = count.max(particle.min_occurs);
+                    break;
+                }
+                None => {
+                    self.assigned.truncate(saved.0);
+                    self.lax.truncate(saved.1);
+                    break;
+                }
+            }
+        }
+        if count >= particle.min_occurs {
+            Some(current)
+        } else {
+            None
+        }
+    }
+
+    fn term(&mut self, term: &'a Term, pos: usize) -> Option<usize> {
+        match term {
+            Term::Element(element) => self.element(&[element.as_ref()], pos),
+            Term::ElementRef(name) => match self.set.elements.get(name) {
+                Some(head) => {
+                    let substitutes = self.set.substitutes(head);
+                    if substitutes.is_empty() {
+                        self.expect(pos, self.set.display_name(name));
+                        return None;
+                    }
+                    self.element(&substitutes, pos)
+                }
+                None => {
+                    self.expect(pos, self.set.display_name(name));
+                    None
+                }
+            },
+            Term::Group(name) => match self.set.groups.get(name) {
+                Some(group) => self.particle(group, pos),
+                None => None,
+            },
+            Term::Sequence(particles) => {
+                let mut current = pos;
+                for particle in particles {
+                    current = self.particle(particle, current)?;
+                }
+                Some(current)
+            }
+            Term::Choice(branches) => {
+                let mut empty = false;
+                for branch in branches {
+                    let saved = (self.assigned.len(), self.lax.len());
+                    match self.particle(branch, pos) {
+                        Some(next) if next > pos => return Some(next),
+                        Some(_) => empty = true,
+                        None => {}
+                    }
+                    self.assigned.truncate(saved.0);
+                    self.lax.truncate(saved.1);
+                }
+                if empty {
+                    Some(pos)
+                } else {
+                    None
+                }
+            }
+            // Children in any order, each particle up to its maxOccurs times as XML Schema
+            // 1.1 allows.
+            Term::All(particles) => {
+                let mut counts = vec![0; particles.len()];
+                let mut current = pos;
+                'children: while current < self.children.len() {
+                    for (i, particle) in particles.iter().enumerate() {
+                        if particle.max_occurs.is_some_and(|max| counts[i] >= max) {
+                            continue;
+                        }
+                        if let Some(next) = self.term(&particle.term, current) {
+                            if next > current {
+                                counts[i] += 1;
+                                current = next;
+                                continue 'children;
+                            }
+                        }
+                    }
+                    break;
+                }
+                let mut complete = true;
+                for (particle, count) in particles.iter().zip(counts) {
+                    if count < particle.min_occurs {
+                        if let Term::Element(element) = &particle.term {
+                            self.expect(current, self.set.display_name(&element.name));
+                        }
+                        complete = false;
+                    }
+                }
+                if complete {
+                    Some(current)
+                } else {
+                    None
+                }
+            }
+            Term::Any { namespace, target_ns } => match self.children.get(pos).map(node_name) {
+                Some(name)
+                    if matches_wildcard(
+                        namespace,
+                        target_ns.as_deref(),
+                        name.namespace.as_deref(),
+                    ) =>
+                {
+                    self.lax.push((pos, self.set.elements.get(&name)));
+                    Some(pos + 1)
+                }
+                _ => {
+                    self.expect(pos, format!("an element from {}", namespace));
+                    None
+                }
+            },
+        }
+    }
+
+    fn element(&mut self, candidates: &[&'a Element], pos: usize) -> Option<usize> {
+        if let Some(child) = self.children.get(pos) {
+            let name = node_name(child);
+            if let Some(element) = candidates.iter().find(|e| e.name == name) {
+                self.assigned.push((pos, element));
+                return Some(pos + 1);
+            }
+        }
+        for element in candidates {
+            self.expect(pos, self.set.display_name(&element.name));
+        }
+        None
+    }
+}
+
+fn node_name(node: &Node) -> QName {
+    QName::new(node.tag_name().namespace(), node.tag_name().name())
+}
+
+fn is_blank(node: &Node) -> bool {
+    node.text().is_none_or(|t| t.trim().is_empty())
+}
+
+fn collapse(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn matches_wildcard(constraint: &str, target_ns: Option<&str>, namespace: Option<&str>) -> bool {
+    constraint.split_whitespace().any(|ns| match ns {
+        "##any" => true,
+        // Any namespace but the target namespace, unqualified names excluded.
+        "##other" => namespace.is_some() && namespace != target_ns,
+        "##targetNamespace" => namespace == target_ns,
+        "##local" => namespace.is_none(),
+        ns => namespace == Some(ns),
+    })
+}
+
+fn check_enumeration(facets: &Facets, value: &str) -> Result<(), String> {
+    if facets.enumeration.is_empty() || facets.enumeration.iter().any(|e| collapse(e) == value) {
+        Ok(())
+    } else {
+        Err(format!("{:?} is not one of {}", value, facets.enumeration.join(", ")))
+    }
+}
+
+fn check_length(facets: &Facets, length: usize, unit: &str) -> Result<(), String> {
+    match (facets.length, facets.min_length, facets.max_length) {
+        (Some(expected), _, _) if length != expected => {
+            Err(format!("{} {} instead of {}", length, unit, expected))
+        }
+        (_, Some(min), _) if length < min => {
+            Err(format!("{} {}, at least {} expected", length, unit, min))
+        }
+        (_, _, Some(max)) if length > max => {
+            Err(format!("{} {}, at most {} expected", length, unit, max))
+        }
+        _ => Ok(()),
+    }
+}
+
+// Range facets of numeric types, those of other types (e.g. dates) are not checked.
+fn check_range(builtin: &str, facets: &Facets, value: &str) -> Result<(), String> {
+    let compare = |bound| compare_bound(builtin, value, bound);
+    if let Some((min, Ordering::Less)) = compare(&facets.min_inclusive) {
+        return Err(format!("{} is less than {}", value, min));
+    }
+    if let Some((max, Ordering::Greater)) = compare(&facets.max_inclusive) {
+        return Err(format!("{} is greater than {}", value, max));
+    }
+    if let Some((min, Ordering::Less | Ordering::Equal)) = compare(&facets.min_exclusive) {
+        return Err(format!("{} is not greater than {}", value, min));
+    }
+    if let Some((max, Ordering::Greater | Ordering::Equal)) = compare(&facets.max_exclusive) {
+        return Err(format!("{} is not less than {}", value, max));
+    }
+    Ok(())
+}
+
+// The bound with the order of the value relative to it, None without a comparable bound.
+fn compare_bound(builtin: &str, value: &str, bound: &Option<String>) -> Option<(String, Ordering)> {
+    let bound = bound.as_deref()?.trim();
+    let ordering = match builtin {
+        "float" | "double" => value.parse::<f64>().ok()?.partial_cmp(&bound.parse().ok()?),
+        // Decimals and integers are compared exactly, f64 would round large values.
+        _ => compare_decimals(value, bound),
+    };
+    Some((bound.to_string(), ordering?))
+}
+
+// Sign, integer digits without leading zeros and fraction digits without trailing zeros
+// of a decimal, None if it isn't one. Zero is never negative.
+fn decimal_digits(value: &str) -> Option<(bool, &str, &str)> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (int, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if int.is_empty() && fraction.is_empty()
+        || !int.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let int = int.trim_start_matches('0');
+    let fraction = fraction.trim_end_matches('0');
+    Some((negative && !(int.is_empty() && fraction.is_empty()), int, fraction))
+}
+
+fn compare_decimals(a: &str, b: &str) -> Option<Ordering> {
+    let (a_negative, a_int, a_fraction) = decimal_digits(a)?;
+    let (b_negative, b_int, b_fraction) = decimal_digits(b)?;
+    let magnitude = a_int
+        .len()
+        .cmp(&b_int.len())
+        .then_with(|| a_int.cmp(b_int))
+        .then_with(|| a_fraction.cmp(b_fraction));
+    Some(match (a_negative, b_negative) {
+        (false, false) => magnitude,
+        (true, true) => magnitude.reverse(),
+        (negative, _) => {
+            if negative {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        }
+    })
+}
+
+fn check_digits(facets: &Facets, value: &str) -> Result<(), String> {
+    if facets.total_digits.is_none() && facets.fraction_digits.is_none() {
+        return Ok(());
+    }
+    let digits = value.trim_start_matches(['+', '-']);
+    let (int, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let int = int.trim_start_matches('0');
+    let fraction = fraction.trim_end_matches('0');
+    if let Some(total) = facets.total_digits {
+        if int.len() + fraction.len() > total {
+            return Err(format!("{} has more than {} digits", value, total));
+        }
+    }
+    if let Some(max) = facets.fraction_digits {
+        if fraction.len() > max {
+            return Err(format!("{} has more than {} fraction digits", value, max));
+        }
+    }
+    Ok(())
+}
+
+// Lexical space of the built-in types that are commonly restricted, others accept any value.
+fn check_builtin(name: &str, value: &str) -> Result<(), String> {
+    let range = match name {
+        "byte" => Some((i8::MIN as i128, i8::MAX as i128)),
+        "short" => Some((i16::MIN as i128, i16::MAX as i128)),
+        "int" => Some((i32::MIN as i128, i32::MAX as i128)),
+        "long" => Some((i64::MIN as i128, i64::MAX as i128)),
+        "unsignedByte" => Some((0, u8::MAX as i128)),
+        "unsignedShort" => Some((0, u16::MAX as i128)),
+        "unsignedInt" => Some((0, u32::MAX as i128)),
+        "unsignedLong" => Some((0, u64::MAX as i128)),
+        "integer" => Some((i128::MIN, i128::MAX)),
+        "nonNegativeInteger" => Some((0, i128::MAX)),
+        "positiveInteger" => Some((1, i128::MAX)),
+        "nonPositiveInteger" => Some((i128::MIN, 0)),
+        "negativeInteger" => Some((i128::MIN, -1)),
+        _ => None,
+    };
+    if let Some((min, max)) = range {
+        return match value.parse::<i128>() {
+            Ok(n) if n >= min && n <= max => Ok(()),
+            Ok(_) => Err(format!("{} is out of the range of xs:{}", value, name)),
+            Err(_) => Err(format!("{:?} is not a valid xs:{}", value, name)),
+        };
+    }
+
+    let valid = match name {
+        "decimal" => {
+            let digits = value.trim_start_matches(['+', '-']);
+            !digits.is_empty()
+                && digits != "."
+                && digits.chars().all(|c| c.is_ascii_digit() || c == '.')
+                && digits.matches('.').count() <= 1
+        }
+        "float" | "double" => {
+            matches!(value, "INF" | "-INF" | "NaN")
+                || (value.parse::<f64>().is_ok() && !value.to_lowercase().contains("inf"))
+        }
+        "boolean" => matches!(value, "true" | "false" | "1" | "0"),
+        "hexBinary" => {
+            value.len().is_multiple_of(2) && value.chars().all(|c| c.is_ascii_hexdigit())
+        }
+        "base64Binary" => {
+            value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '=' | ' '))
+        }
+        "dateTime" | "date" | "time" | "gYearMonth" | "gYear" | "gMonthDay" | "gDay" | "gMonth" => {
+            is_date_time(name, value)
+        }
+        "duration" => {
+            let regex =
+                Regex::new(r"^-?P(\d+Y)?(\d+M)?(\d+D)?(T(\d+H)?(\d+M)?(\d+(\.\d+)?S)?)?$").unwrap();
+            regex.is_match(value) && !value.ends_with('P') && !value.ends_with('T')
+        }
+        _ => true,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("{:?} is not a valid xs:{}", value, name))
+    }
+}
+
+// Lexical space of the date and time types of XSD 1.1 Appendix D.3, with the ranges of
+// their fields. 24:00:00 is allowed as the end of the day.
+fn is_date_time(name: &str, value: &str) -> bool {
+    let year = r"(?P<year>-?(?:[1-9]\d{3,}|0\d{3}))";
+    let month = r"(?P<month>\d{2})";
+    let day = r"(?P<day>\d{2})";
+    let time = r"(?P<hour>\d{2}):(?P<minute>\d{2}):(?P<second>\d{2})(?P<fraction>\.\d+)?";
+    let pattern = match name {
+        "dateTime" => format!("{}-{}-{}T{}", year, month, day, time),
+        "date" => format!("{}-{}-{}", year, month, day),
+        "time" => time.to_string(),
+        "gYearMonth" => format!("{}-{}", year, month),
+        "gYear" => year.to_string(),
+        "gMonthDay" => format!("--{}-{}", month, day),
+        "gDay" => format!("---{}", day),
+        _ => format!("--{}", month),
+    };
+    let regex =
+        Regex::new(&format!(r"^{}(?:Z|(?P<tz>[+-]\d{{2}}):(?P<tzm>\d{{2}}))?$", pattern)).unwrap();
+    let captures = match regex.captures(value) {
+        Some(captures) => captures,
+        None => return false,
+    };
+    let field = |name: &str| captures.name(name).map(|m| m.as_str().parse::<i64>().ok());
+
+    // Without a year, February has 29 days as in a leap year.
+    let leap = match field("year") {
+        Some(Some(year)) => year % 4 == 0 && (year % 100 != 0 || year % 400 == 0),
+        Some(None) => return false,
+        None => true,
+    };
+    let month = field("month").flatten();
+    if month.is_some_and(|month| !(1..=12).contains(&month)) {
+        return false;
+    }
+    if let Some(day) = field("day").flatten() {
+        let days = match month {
+            Some(2) if leap => 29,
+            Some(2) => 28,
+            Some(4 | 6 | 9 | 11) => 30,
+            _ => 31,
+        };
+        if !(1..=days).contains(&day) {
+            return false;
+        }
+    }
+    if let (Some(hour), Some(minute), Some(second)) =
+        (field("hour").flatten(), field("minute").flatten(), field("second").flatten())
+    {
+        let zero_fraction = captures
+            .name("fraction")
+            .is_none_or(|f| f.as_str().trim_start_matches('.').chars().all(|c| c == '0'));
+        let end_of_day = hour == 24 && minute == 0 && second == 0 && zero_fraction;
+        if !end_of_day && (hour > 23 || minute > 59 || second > 59) {
+            return false;
+        }
+    }
+    match (field("tz").flatten(), field("tzm").flatten()) {
+        (Some(hours), Some(minutes)) => minutes < 60 && hours.abs() * 60 + minutes <= 14 * 60,
+        _ => true,
+    }
+}
+
+// XML Schema regular expressions in the syntax of the regex crate: the multi-character
+// escapes for XML names have no equivalent there.
+fn xsd_regex(pattern: &str) -> String {
+    pattern
+        .replace(r"\i", "[_:A-Za-z]")
+        .replace(r"\I", "[^_:A-Za-z]")
+        .replace(r"\c", r"[-._:A-Za-z0-9]")
+        .replace(r"\C", r"[^-._:A-Za-z0-9]")
+}
+
+// Nodes an identity constraint selector selects: the restricted XPath of XML Schema, i.e.
+// `|`-separated paths of child steps that may start with `.//`.
+fn select<'a, 'input>(
+    node: &Node<'a, 'input>,
+    path: &str,
+    constraint: &IdentityConstraint,
+) -> Vec<Node<'a, 'input>> {
+    let mut res = vec![];
+    for path in path.split('|').map(str::trim) {
+        let (descendants, path) = match path.strip_prefix(".//") {
+            Some(path) => (true, path),
+            None => (false, path),
+        };
+        let mut current = if descendants {
+            node.descendants().filter(Node::is_element).collect::<Vec<_>>()
+        } else {
+            vec![*node]
+        };
+        for step in path.split('/').map(|s| s.trim().trim_start_matches("child::")) {
+            if step == "." || step.is_empty() {
+                continue;
+            }
+            current = current
+                .iter()
+                .flat_map(|n| {
+                    n.children().filter(|c| c.is_element() && step_matches(c, step, constraint))
+                })
+                .collect();
+        }
+        for n in current {
+            if !res.contains(&n) {
+                res.push(n);
+            }
+        }
+    }
+    res
+}
+
+fn step_matches(node: &Node, step: &str, constraint: &IdentityConstraint) -> bool {
+    let (prefix, local) = match step.split_once(':') {
+        Some((prefix, local)) => (Some(prefix), local),
+        None => (None, step),
+    };
+    // Unprefixed names in XPath have no namespace.
+    let namespace = match prefix {
+        Some(prefix) => constraint
+            .namespaces
+            .iter()
+            .find(|(p, _)| p.as_deref() == Some(prefix))
+            .map(|(_, uri)| uri.as_str()),
+        None => None,
+    };
+    if local != "*" && node.tag_name().name() != local {
+        return false;
+    }
+    prefix.is_none() && local == "*" || node.tag_name().namespace() == namespace
+}
+
+// Values a field selects relative to a selected node, the last step may be an attribute.
+fn field_values(node: &Node, field: &str, constraint: &IdentityConstraint) -> Vec<String> {
+    let mut res = vec![];
+    for path in field.split('|').map(str::trim) {
+        let (path, attribute) = match path.rsplit_once('/') {
+            Some((path, last)) if last.starts_with('@') || last.starts_with("attribute::") => {
+                (path, Some(last))
+            }
+            None if path.starts_with('@') || path.starts_with("attribute::") => (".", Some(path)),
+            _ => (path, None),
+        };
+        for selected in select(node, path, constraint) {
+            match attribute {
+                Some(attribute) => {
+                    let name = attribute.trim_start_matches('@').trim_start_matches("attribute::");
+                    let value = match name.split_once(':') {
+                        Some((prefix, local)) => {
+                            let ns = constraint
+                                .namespaces
+                                .iter()
+                                .find(|(p, _)| p.as_deref() == Some(prefix))
+                                .map(|(_, uri)| uri.as_str())
+                                .unwrap_or_default();
+                            selected.attribute((ns, local))
+                        }
+                        None => selected.attribute(name),
+                    };
+                    res.extend(value.map(str::to_string));
+                }
+                None => res.push(selected.text().unwrap_or_default().to_string()),
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let schema = roxmltree::Document::parse(
+            r#"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tt="http://example.com/tt" targetNamespace="http://example.com/tt" elementFormDefault="qualified">
+    <xs:simpleType name="Port">
+        <xs:restriction base="xs:int">
+            <xs:minInclusive value="1"/>
+            <xs:maxInclusive value="65535"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:complexType name="Endpoint">
+        <xs:sequence>
+            <xs:element name="Address" type="xs:string"/>
+            <xs:choice>
+                <xs:element name="Port" type="tt:Port"/>
+                <xs:element name="Auto" type="xs:boolean"/>
+            </xs:choice>
+        </xs:sequence>
+        <xs:attribute name="token" type="xs:string" use="required"/>
+    </xs:complexType>
+    <xs:element name="Endpoints">
+        <xs:complexType>
+            <xs:sequence>
+                <xs:element name="Endpoint" type="tt:Endpoint" maxOccurs="unbounded"/>
+            </xs:sequence>
+        </xs:complexType>
+        <xs:key name="EndpointKey">
+            <xs:selector xpath="tt:Endpoint"/>
+            <xs:field xpath="@token"/>
+        </xs:key>
+    </xs:element>
+</xs:schema>"#,
+        )
+        .unwrap();
+        let set = SchemaSet::parse(&[schema.root_element()]);
+
+        let doc = roxmltree::Document::parse(
+            r#"<tt:Endpoints xmlns:tt="http://example.com/tt">
+    <tt:Endpoint token="a">
+        <tt:Address>10.0.0.1</tt:Address>
+        <tt:Port>80</tt:Port>
+    </tt:Endpoint>
+    <tt:Endpoint token="b">
+        <tt:Address>10.0.0.2</tt:Address>
+        <tt:Auto>true</tt:Auto>
+    </tt:Endpoint>
+</tt:Endpoints>"#,
+        )
+        .unwrap();
+        assert_eq!(validate(&set, &doc), vec![]);
+
+        let doc = roxmltree::Document::parse(
+            r#"<tt:Endpoints xmlns:tt="http://example.com/tt">
+    <tt:Endpoint token="a">
+        <tt:Port>0</tt:Port>
+    </tt:Endpoint>
+    <tt:Endpoint token="a" mode="x">
+        <tt:Address>10.0.0.2</tt:Address>
+    </tt:Endpoint>
+</tt:Endpoints>"#,
+        )
+        .unwrap();
+        let messages = validate(&set, &doc).iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "3:9: unexpected element tt:Port in tt:Endpoint, expected tt:Address",
+                "3:9: element tt:Port: 0 is less than 1",
+                "5:5: attribute mode is not allowed on tt:Endpoint",
+                "5:5: element tt:Endpoint is incomplete, expected tt:Port, tt:Auto",
+                "5:5: duplicate value a for tt:EndpointKey",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_values() {
+        let schema = roxmltree::Document::parse(
+            r###"
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:tt="http://example.com/tt" targetNamespace="http://example.com/tt" elementFormDefault="qualified">
+    <xs:simpleType name="Serial">
+        <xs:restriction base="xs:integer">
+            <xs:maxInclusive value="9007199254740992"/>
+        </xs:restriction>
+    </xs:simpleType>
+    <xs:complexType name="Item">
+        <xs:sequence>
+            <xs:element name="Serial" type="tt:Serial"/>
+            <xs:element name="Date" type="xs:date"/>
+            <xs:any namespace="##other" minOccurs="0"/>
+        </xs:sequence>
+        <xs:attribute name="id" type="xs:ID"/>
+        <xs:attribute name="ref" type="xs:string"/>
+    </xs:complexType>
+    <xs:element name="Items">
+        <xs:complexType>
+            <xs:sequence>
+                <xs:element name="Item" type="tt:Item" maxOccurs="unbounded"/>
+            </xs:sequence>
+        </xs:complexType>
+        <xs:keyref name="ItemRef" refer="tt:ItemKey">
+            <xs:selector xpath="tt:Item"/>
+            <xs:field xpath="@ref"/>
+        </xs:keyref>
+        <xs:key name="ItemKey">
+            <xs:selector xpath="tt:Item"/>
+            <xs:field xpath="@id"/>
+        </xs:key>
+    </xs:element>
+</xs:schema>"###,
+        )
+        .unwrap();
+        let set = SchemaSet::parse(&[schema.root_element()]);
+
+        let doc = roxmltree::Document::parse(
+            r#"<tt:Items xmlns:tt="http://example.com/tt" xmlns:ext="http://example.com/ext">
+    <tt:Item id="a" ref="b">
+        <tt:Serial>9007199254740992</tt:Serial>
+        <tt:Date>2020-02-29</tt:Date>
+        <ext:Note/>
+    </tt:Item>
+    <tt:Item id="b" ref="a">
+        <tt:Serial>1</tt:Serial>
+        <tt:Date>2020-03-01Z</tt:Date>
+    </tt:Item>
+</tt:Items>"#,
+        )
+        .unwrap();
+        assert_eq!(validate(&set, &doc), vec![]);
+
+        let doc = roxmltree::Document::parse(
+            r#"<tt:Items xmlns:tt="http://example.com/tt">
+    <tt:Item id="a">
+        <tt:Serial>9007199254740993</tt:Serial>
+        <tt:Date>2020-13-45</tt:Date>
+        <tt:Note/>
+    </tt:Item>
+    <tt:Item id="a" ref="c">
+        <tt:Serial>1</tt:Serial>
+        <tt:Date>2021-02-29</tt:Date>
+    </tt:Item>
+</tt:Items>"#,
+        )
+        .unwrap();
+        let messages = validate(&set, &doc).iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "3:9: element tt:Serial: 9007199254740993 is greater than 9007199254740992",
+                "4:9: element tt:Date: \"2020-13-45\" is not a valid xs:date",
+                "5:9: unexpected element tt:Note in tt:Item, expected an element from ##other",
+                "7:5: attribute id: duplicate xs:ID \"a\"",
+                "7:5: duplicate value a for tt:ItemKey",
+                "7:5: tt:ItemRef refers to tt:ItemKey which has no value c",
+                "9:9: element tt:Date: \"2021-02-29\" is not a valid xs:date",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexical_values() {
+        for (name, value) in [
+            ("dateTime", "2000-01-01T24:00:00.000-05:00"),
+            ("dateTime", "-0044-03-15T12:00:00"),
+            ("date", "0000-02-29Z"),
+            ("time", "23:59:59.5+14:00"),
+            ("gYearMonth", "10000-12"),
+            ("gMonthDay", "--02-29"),
+            ("gDay", "---31"),
+            ("gMonth", "--12Z"),
+        ] {
+            assert!(is_date_time(name, value), "{} {}", name, value);
+        }
+        for (name, value) in [
+            ("dateTime", "2000-01-01T24:00:01"),
+            ("dateTime", "2000-01-01T00:00"),
+            ("date", "1900-02-29"),
+            ("date", "02000-01-01"),
+            ("time", "12:60:00"),
+            ("time", "12:00:00+15:00"),
+            ("gMonthDay", "--04-31"),
+            ("gMonth", "--13"),
+        ] {
+            assert!(!is_date_time(name, value), "{} {}", name, value);
+        }
+
+        assert_eq!(
+            compare_decimals("9007199254740993", "9007199254740992"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(compare_decimals("-0.10", "-0.1"), Some(Ordering::Equal));
+        assert_eq!(compare_decimals("-0", "+0.0"), Some(Ordering::Equal));
+        assert_eq!(compare_decimals("-2.5", "-2.45"), Some(Ordering::Less));
+        assert_eq!(compare_decimals(".5", "0.45"), Some(Ordering::Greater));
+        assert_eq!(compare_decimals("1e3", "1"), None);
+    }
+}
@@ -14,7 +14,7 @@ impl Validate for BarType {}
 #[yaserde(prefix = "tns", namespace = "tns: http://example.com")]
 pub struct FooType {
     #[yaserde(prefix = "tns", rename = "a")]
-    pub a: f64,
+    pub a: xs::Float,
 
     #[yaserde(prefix = "tns", rename = "b")]
     pub b: i32,
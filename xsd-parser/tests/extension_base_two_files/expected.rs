@@ -6,7 +6,7 @@
 )]
 pub struct FooType {
     #[yaserde(prefix = "tns", rename = "a")]
-    pub a: f64,
+    pub a: xs::Float,
 
     #[yaserde(prefix = "tns2", rename = "b")]
     pub b: i32,
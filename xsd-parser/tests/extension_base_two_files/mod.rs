@@ -1,8 +1,11 @@
+use xsd_types::types as xs;
+
 use super::utils;
 
 #[test]
 fn deserialization_works() {
     mod expected {
+        use xsd_types::types as xs;
         use yaserde_derive::{YaDeserialize, YaSerialize};
         include!("expected.rs");
     }
@@ -11,7 +14,7 @@ fn deserialization_works() {
 
     let de: expected::FooType = yaserde::de::from_str(ser).unwrap();
 
-    assert_eq!(de, expected::FooType { a: 150.0, b: 3, c: "string".to_string() });
+    assert_eq!(de, expected::FooType { a: xs::Float(150.0), b: 3, c: "string".to_string() });
 }
 
 #[test]
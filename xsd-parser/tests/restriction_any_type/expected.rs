@@ -6,7 +6,7 @@ pub struct AppSequenceType {
     pub instance_id: u32,
 
     #[yaserde(attribute, rename = "SequenceId")]
-    pub sequence_id: Option<String>,
+    pub sequence_id: Option<xs::AnyUri>,
 
     #[yaserde(attribute, rename = "MessageNumber")]
     pub message_number: u32,
@@ -1,9 +1,12 @@
+use xsd_types::types as xs;
+
 use super::utils;
 
 #[test]
 fn deserialization_works() {
     mod expected {
         use xsd_parser::generator::validator::Validate;
+        use xsd_types::types as xs;
         use yaserde_derive::{YaDeserialize, YaSerialize};
 
         include!("expected.rs");
@@ -17,7 +20,7 @@ fn deserialization_works() {
         de,
         expected::AppSequenceType {
             instance_id: 7,
-            sequence_id: Some("http://www.company.org/cum/sonoras".to_string()),
+            sequence_id: Some(xs::AnyUri("http://www.company.org/cum/sonoras".to_string())),
             message_number: 7,
         }
     );
@@ -0,0 +1,62 @@
+use crate::types::utils::collapse_whitespace;
+
+// https://www.w3.org/TR/xmlschema-2/#anyURI
+string_type!(AnyUri, collapse_whitespace);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    use super::*;
+    use crate::utils::xml_eq::assert_xml_eq;
+
+    #[test]
+    fn any_uri_parse_test() {
+        assert_eq!(
+            AnyUri::from_str(" http://www.example.com/a b "),
+            Ok(AnyUri("http://www.example.com/a b".to_string()))
+        );
+        assert_eq!(AnyUri::from_str("../a.xsd#b"), Ok(AnyUri("../a.xsd#b".to_string())));
+    }
+
+    #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: test")]
+    pub struct Message {
+        #[yaserde(prefix = "t", rename = "Value")]
+        pub value: AnyUri,
+
+        #[yaserde(prefix = "t", rename = "Text")]
+        pub text: String,
+    }
+
+    #[test]
+    fn any_uri_serialize_test() {
+        let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Value>http://www.example.com</t:Value>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m = Message {
+            value: AnyUri("http://www.example.com".to_string()),
+            text: "Hello world".to_string(),
+        };
+        let actual = yaserde::ser::to_string(&m).unwrap();
+        assert_xml_eq(&actual, expected);
+    }
+
+    #[test]
+    fn any_uri_deserialize_test() {
+        let s = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Value> http://www.example.com </t:Value>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m: Message = yaserde::de::from_str(s).unwrap();
+        assert_eq!(m.value, AnyUri("http://www.example.com".to_string()));
+        assert_eq!(m.text, "Hello world".to_string());
+    }
+}
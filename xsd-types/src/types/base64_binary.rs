@@ -0,0 +1,107 @@
+use std::{fmt, str::FromStr};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use xsd_macro_utils::UtilsDefaultSerde;
+
+// Length facets of base64Binary count octets, which is what len() returns.
+#[derive(Default, Clone, PartialEq, Eq, Debug, UtilsDefaultSerde)]
+pub struct Base64Binary(pub Vec<u8>);
+
+impl Base64Binary {
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Base64Binary(bytes)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for Base64Binary {
+    type Err = String;
+
+    // Whitespace is allowed anywhere in the lexical form, e.g. MIME line breaks every
+    // 76 characters.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        STANDARD.decode(s).map(Base64Binary).map_err(|e| format!("bad base64Binary format: {}", e))
+    }
+}
+
+impl fmt::Display for Base64Binary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", STANDARD.encode(&self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    use super::*;
+    use crate::utils::xml_eq::assert_xml_eq;
+
+    #[test]
+    fn base64_binary_parse_test() {
+        assert_eq!(Base64Binary::from_str("SGVsbG8="), Ok(Base64Binary(b"Hello".to_vec())));
+        assert_eq!(Base64Binary::from_str(" SGVs\n bG8= "), Ok(Base64Binary(b"Hello".to_vec())));
+        assert_eq!(Base64Binary::from_str(""), Ok(Base64Binary(vec![])));
+
+        assert!(Base64Binary::from_str("SGVsbG8").is_err());
+        assert!(Base64Binary::from_str("SGVs*G8=").is_err());
+    }
+
+    #[test]
+    fn base64_binary_display_test() {
+        assert_eq!(Base64Binary(b"Hello".to_vec()).to_string(), "SGVsbG8=");
+        assert_eq!(Base64Binary(vec![]).to_string(), "");
+        assert_eq!(Base64Binary::from_str("SGVsbG8=").unwrap().len(), 5);
+    }
+
+    #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: test")]
+    pub struct Message {
+        #[yaserde(prefix = "t", rename = "Data")]
+        pub data: Base64Binary,
+
+        #[yaserde(prefix = "t", rename = "Text")]
+        pub text: String,
+    }
+
+    #[test]
+    fn base64_binary_serialize_test() {
+        let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Data>SGVsbG8=</t:Data>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m = Message { data: Base64Binary(b"Hello".to_vec()), text: "Hello world".to_string() };
+        let actual = yaserde::ser::to_string(&m).unwrap();
+        assert_xml_eq(&actual, expected);
+    }
+
+    #[test]
+    fn base64_binary_deserialize_test() {
+        let s = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Data>
+                    SGVs
+                    bG8=
+                </t:Data>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m: Message = yaserde::de::from_str(s).unwrap();
+        assert_eq!(m.data, Base64Binary(b"Hello".to_vec()));
+        assert_eq!(m.text, "Hello world".to_string());
+    }
+}
@@ -1,81 +1,123 @@
-use std::{fmt, str::FromStr};
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Sub},
+    str::FromStr,
+};
+
+#[cfg(feature = "chrono")]
+use std::convert::TryFrom;
 
-use chrono::{format::strftime::StrftimeItems, FixedOffset, NaiveDate};
 use xsd_macro_utils::UtilsDefaultSerde;
 
-use crate::types::utils::parse_timezone;
+use crate::types::{
+    duration::Duration,
+    lexical::{format_date, parse_date},
+    naive::{NaiveDate, NaiveTime},
+    timezone::Timezone,
+    utils::compare_timezoned,
+};
 
-#[derive(PartialEq, Debug, UtilsDefaultSerde)]
+#[derive(Clone, Debug, UtilsDefaultSerde)]
 pub struct Date {
     pub value: NaiveDate,
-    pub timezone: Option<FixedOffset>,
+    pub timezone: Option<Timezone>,
 }
 
 impl Date {
-    pub fn from_chrono_naive_date(date: NaiveDate) -> Self {
-        Date { value: date, timezone: None }
+    #[cfg(feature = "chrono")]
+    pub fn from_chrono_naive_date(date: chrono::NaiveDate) -> Self {
+        Date { value: date.into(), timezone: None }
+    }
+
+    // None if the year is out of the range of chrono.
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono_naive_date(&self) -> Option<chrono::NaiveDate> {
+        chrono::NaiveDate::try_from(self.value).ok()
+    }
+
+    // The date is treated as its first moment and the time of day of the result is
+    // dropped, so only whole days of the duration change the date.
+    pub fn checked_add_duration(&self, duration: &Duration) -> Option<Date> {
+        let datetime = duration.add_to(self.value.and_time(NaiveTime::MIDNIGHT))?;
+        Some(Date { value: datetime.date(), timezone: self.timezone })
     }
 
-    pub fn to_chrono_naive_date(&self) -> NaiveDate {
-        self.value
+    pub fn checked_sub_duration(&self, duration: &Duration) -> Option<Date> {
+        self.checked_add_duration(&duration.negated())
     }
 }
 
-impl Default for Date {
-    fn default() -> Date {
-        Self { value: NaiveDate::from_ymd_opt(1, 1, 1).unwrap(), timezone: None }
+impl Add<&Duration> for &Date {
+    type Output = Date;
+
+    fn add(self, rhs: &Duration) -> Date {
+        self.checked_add_duration(rhs).expect("overflow when adding duration to date")
     }
 }
 
-impl FromStr for Date {
-    type Err = String;
+impl Sub<&Duration> for &Date {
+    type Output = Date;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        fn parse_naive_date(s: &str) -> Result<NaiveDate, String> {
-            NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|e| e.to_string())
-        }
+    fn sub(self, rhs: &Duration) -> Date {
+        self.checked_sub_duration(rhs).expect("overflow when subtracting duration from date")
+    }
+}
 
-        if let Some(s) = s.strip_suffix('Z') {
-            return Ok(Date {
-                value: parse_naive_date(s)?,
-                timezone: Some(FixedOffset::east_opt(0).unwrap()),
-            });
-        }
+impl Add<Duration> for Date {
+    type Output = Date;
 
-        if s.contains('+') {
-            if s.matches('+').count() > 1 {
-                return Err("bad date format".to_string());
-            }
+    fn add(self, rhs: Duration) -> Date {
+        &self + &rhs
+    }
+}
 
-            let idx: usize = s.match_indices('+').collect::<Vec<_>>()[0].0;
-            let date_token = &s[..idx];
-            let tz_token = &s[idx..];
-            return Ok(Date {
-                value: parse_naive_date(date_token)?,
-                timezone: Some(parse_timezone(tz_token)?),
-            });
-        }
+impl Sub<Duration> for Date {
+    type Output = Date;
 
-        if s.matches('-').count() == 3 {
-            let idx: usize = s.match_indices('-').collect::<Vec<_>>()[2].0;
-            let date_token = &s[..idx];
-            let tz_token = &s[idx..];
-            return Ok(Date {
-                value: parse_naive_date(date_token)?,
-                timezone: Some(parse_timezone(tz_token)?),
-            });
-        }
+    fn sub(self, rhs: Duration) -> Date {
+        &self - &rhs
+    }
+}
+
+impl PartialEq for Date {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Date {
+    // A date is compared as its first moment.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare_timezoned(
+            self.value.and_time(NaiveTime::MIDNIGHT),
+            self.timezone,
+            other.value.and_time(NaiveTime::MIDNIGHT),
+            other.timezone,
+        )
+    }
+}
+
+impl Default for Date {
+    fn default() -> Date {
+        Self { value: NaiveDate::from_ymd(1, 1, 1).unwrap(), timezone: None }
+    }
+}
 
-        Ok(Date { value: parse_naive_date(s)?, timezone: None })
+impl FromStr for Date {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (value, timezone) = parse_date(s)?;
+        Ok(Date { value, timezone })
     }
 }
 
 impl fmt::Display for Date {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let fmt = StrftimeItems::new("%Y-%m-%d");
         match self.timezone {
-            Some(tz) => write!(f, "{}{}", self.value.format_with_items(fmt.clone()), tz),
-            None => write!(f, "{}", self.value.format_with_items(fmt.clone())),
+            Some(tz) => write!(f, "{}{}", format_date(self.value), tz),
+            None => write!(f, "{}", format_date(self.value)),
         }
     }
 }
@@ -92,15 +134,15 @@ mod tests {
         // No timezone.
         assert_eq!(
             Date::from_str("2020-02-02"),
-            Ok(Date { value: NaiveDate::from_ymd_opt(2020, 2, 2).unwrap(), timezone: None })
+            Ok(Date { value: NaiveDate::from_ymd(2020, 2, 2).unwrap(), timezone: None })
         );
 
         // Timezone "Z".
         assert_eq!(
             Date::from_str("2020-02-02Z"),
             Ok(Date {
-                value: NaiveDate::from_ymd_opt(2020, 2, 2).unwrap(),
-                timezone: Some(FixedOffset::east_opt(0).unwrap())
+                value: NaiveDate::from_ymd(2020, 2, 2).unwrap(),
+                timezone: Some(Timezone::UTC)
             })
         );
 
@@ -108,8 +150,8 @@ mod tests {
         assert_eq!(
             Date::from_str("2020-02-02+06:30"),
             Ok(Date {
-                value: NaiveDate::from_ymd_opt(2020, 2, 2).unwrap(),
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap())
+                value: NaiveDate::from_ymd(2020, 2, 2).unwrap(),
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap())
             })
         );
 
@@ -117,8 +159,8 @@ mod tests {
         assert_eq!(
             Date::from_str("2020-02-02-06:30"),
             Ok(Date {
-                value: NaiveDate::from_ymd_opt(2020, 2, 2).unwrap(),
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                value: NaiveDate::from_ymd(2020, 2, 2).unwrap(),
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             })
         );
     }
@@ -127,26 +169,22 @@ mod tests {
     fn date_display_test() {
         // No timezone.
         assert_eq!(
-            Date { value: NaiveDate::from_ymd_opt(2020, 2, 2).unwrap(), timezone: None }
-                .to_string(),
+            Date { value: NaiveDate::from_ymd(2020, 2, 2).unwrap(), timezone: None }.to_string(),
             "2020-02-02"
         );
 
-        // Timezone +00:00.
+        // Zero offset.
         assert_eq!(
-            Date {
-                value: NaiveDate::from_ymd_opt(2020, 2, 2).unwrap(),
-                timezone: Some(FixedOffset::east_opt(0).unwrap())
-            }
-            .to_string(),
-            "2020-02-02+00:00"
+            Date { value: NaiveDate::from_ymd(2020, 2, 2).unwrap(), timezone: Some(Timezone::UTC) }
+                .to_string(),
+            "2020-02-02Z"
         );
 
         // Positive offset.
         assert_eq!(
             Date {
-                value: NaiveDate::from_ymd_opt(2020, 2, 2).unwrap(),
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap())
+                value: NaiveDate::from_ymd(2020, 2, 2).unwrap(),
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap())
             }
             .to_string(),
             "2020-02-02+06:30"
@@ -155,14 +193,57 @@ mod tests {
         // Negative offset.
         assert_eq!(
             Date {
-                value: NaiveDate::from_ymd_opt(2020, 2, 2).unwrap(),
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                value: NaiveDate::from_ymd(2020, 2, 2).unwrap(),
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             }
             .to_string(),
             "2020-02-02-06:30"
         );
     }
 
+    #[test]
+    fn date_years_test() {
+        assert_eq!(Date::from_str("-0044-03-15").unwrap().value.year(), -44);
+        assert_eq!(Date::from_str("-0044-03-15").unwrap().to_string(), "-0044-03-15");
+        assert_eq!(Date::from_str("10000-01-01Z").unwrap().to_string(), "10000-01-01Z");
+        assert_eq!(Date::from_str("0000-01-01").unwrap().value.year(), 0);
+        assert_eq!(Date::from_str("0999-01-01").unwrap().to_string(), "0999-01-01");
+        assert!(Date::from_str("999-01-01").is_err());
+        assert!(Date::from_str("01000-01-01").is_err());
+        assert!(Date::from_str("2001-02-29").is_err());
+    }
+
+    #[test]
+    fn date_order_test() {
+        let date = |s| Date::from_str(s).unwrap();
+
+        assert_eq!(date("2000-01-02+12:00"), date("2000-01-01-12:00"));
+        assert!(date("2000-01-01") < date("2000-01-02"));
+        assert!(date("2000-01-01") < date("2000-01-03Z"));
+        assert_eq!(date("2000-01-01").partial_cmp(&date("2000-01-01Z")), None);
+        assert_eq!(date("2000-01-01").to_string(), "2000-01-01");
+    }
+
+    #[test]
+    fn date_add_duration_test() {
+        let date = |s| Date::from_str(s).unwrap();
+        let dur = |s| Duration::from_str(s).unwrap();
+
+        assert_eq!(date("2000-01-31") + dur("P1M"), date("2000-02-29"));
+        assert_eq!(date("2000-01-31+03:00") + dur("P1Y1MT23H"), date("2001-02-28+03:00"));
+        assert_eq!(date("2000-03-01Z") - dur("PT1H"), date("2000-02-29Z"));
+        assert_eq!(&date("2000-03-01") - &dur("P1D"), date("2000-02-29"));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn date_chrono_test() {
+        let date = chrono::NaiveDate::from_ymd_opt(-44, 3, 15).unwrap();
+        assert_eq!(Date::from_chrono_naive_date(date).to_string(), "-0044-03-15");
+        assert_eq!(Date::from_str("-0044-03-15").unwrap().to_chrono_naive_date(), Some(date));
+        assert_eq!(Date::from_str("300000-01-01").unwrap().to_chrono_naive_date(), None);
+    }
+
     #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
     #[yaserde(prefix = "t", namespace = "t: test")]
     pub struct Message {
@@ -183,8 +264,8 @@ mod tests {
             "#;
         let m = Message {
             created_at: Date {
-                value: NaiveDate::from_ymd_opt(2020, 2, 2).unwrap(),
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap()),
+                value: NaiveDate::from_ymd(2020, 2, 2).unwrap(),
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap()),
             },
             text: "Hello world".to_string(),
         };
@@ -201,8 +282,11 @@ mod tests {
             </t:Message>
             "#;
         let m: Message = yaserde::de::from_str(s).unwrap();
-        assert_eq!(m.created_at.value, NaiveDate::from_ymd_opt(2020, 2, 2).unwrap());
-        assert_eq!(m.created_at.timezone, Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap()),);
+        assert_eq!(m.created_at.value, NaiveDate::from_ymd(2020, 2, 2).unwrap());
+        assert_eq!(
+            m.created_at.timezone,
+            Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap()),
+        );
         assert_eq!(m.text, "Hello world".to_string());
     }
 }
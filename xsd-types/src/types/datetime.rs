@@ -1,109 +1,302 @@
-use std::{fmt, str::FromStr};
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Sub},
+    str::FromStr,
+};
 
-use chrono::{format::ParseError, DateTime as CDateTime, FixedOffset};
+#[cfg(feature = "chrono")]
+use std::convert::TryFrom;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime as CDateTime, FixedOffset};
 use xsd_macro_utils::UtilsDefaultSerde;
 
-#[derive(PartialEq, PartialOrd, Debug, UtilsDefaultSerde)]
+use crate::types::{
+    duration::Duration,
+    lexical::{format_datetime, parse_datetime},
+    naive::{NaiveDate, NaiveDateTime},
+    timezone::Timezone,
+    utils::compare_timezoned,
+};
+
+// The value is the local date and time as written; a missing time zone is kept as None
+// rather than assumed to be UTC.
+#[derive(Clone, Debug, UtilsDefaultSerde)]
 pub struct DateTime {
-    pub value: CDateTime<FixedOffset>,
+    pub value: NaiveDateTime,
+    pub timezone: Option<Timezone>,
 }
 
 impl DateTime {
-    pub fn from_chrono_datetime(datetime: CDateTime<FixedOffset>) -> Self {
-        DateTime { value: datetime }
+    // None if the offset is not a whole number of minutes within +-14:00.
+    #[cfg(feature = "chrono")]
+    pub fn from_chrono_datetime(datetime: CDateTime<FixedOffset>) -> Option<Self> {
+        let timezone = Timezone::try_from(*datetime.offset()).ok()?;
+        Some(DateTime { value: datetime.naive_local().into(), timezone: Some(timezone) })
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn from_chrono_naive_datetime(datetime: chrono::NaiveDateTime) -> Self {
+        DateTime { value: datetime.into(), timezone: None }
+    }
+
+    // None if the value has no time zone or the year is out of the range of chrono.
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono_datetime(&self) -> Option<CDateTime<FixedOffset>> {
+        self.to_chrono_datetime_or(self.timezone?.into())
+    }
+
+    // Uses `timezone` as the implicit time zone if the value has none.
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono_datetime_or(&self, timezone: FixedOffset) -> Option<CDateTime<FixedOffset>> {
+        let tz = self.timezone.map(FixedOffset::from).unwrap_or(timezone);
+        self.to_chrono_naive_datetime()?.and_local_timezone(tz).single()
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono_naive_datetime(&self) -> Option<chrono::NaiveDateTime> {
+        chrono::NaiveDateTime::try_from(self.value).ok()
     }
 
-    pub fn to_chrono_datetime(&self) -> CDateTime<FixedOffset> {
-        self.value
+    // None if the result does not fit into the supported range of years.
+    pub fn checked_add_duration(&self, duration: &Duration) -> Option<DateTime> {
+        let value = duration.add_to(self.value)?;
+        Some(DateTime { value, timezone: self.timezone })
+    }
+
+    pub fn checked_sub_duration(&self, duration: &Duration) -> Option<DateTime> {
+        self.checked_add_duration(&duration.negated())
+    }
+}
+
+impl Add<&Duration> for &DateTime {
+    type Output = DateTime;
+
+    fn add(self, rhs: &Duration) -> DateTime {
+        self.checked_add_duration(rhs).expect("overflow when adding duration to datetime")
+    }
+}
+
+impl Sub<&Duration> for &DateTime {
+    type Output = DateTime;
+
+    fn sub(self, rhs: &Duration) -> DateTime {
+        self.checked_sub_duration(rhs).expect("overflow when subtracting duration from datetime")
+    }
+}
+
+impl Add<Duration> for DateTime {
+    type Output = DateTime;
+
+    fn add(self, rhs: Duration) -> DateTime {
+        &self + &rhs
+    }
+}
+
+impl Sub<Duration> for DateTime {
+    type Output = DateTime;
+
+    fn sub(self, rhs: Duration) -> DateTime {
+        &self - &rhs
+    }
+}
+
+impl PartialEq for DateTime {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for DateTime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare_timezoned(self.value, self.timezone, other.value, other.timezone)
     }
 }
 
 impl Default for DateTime {
     fn default() -> DateTime {
-        Self { value: CDateTime::parse_from_rfc3339("0001-01-01T00:00:00Z").unwrap() }
+        Self {
+            value: NaiveDate::from_ymd(1, 1, 1).unwrap().and_hms(0, 0, 0).unwrap(),
+            timezone: Some(Timezone::UTC),
+        }
     }
 }
 
 impl FromStr for DateTime {
-    type Err = ParseError;
+    type Err = String;
 
-    // Note:
-    // `parse_from_rfc3339` parses an RFC 3339 and ISO 8601 date and time string.
-    // XSD follows ISO 8601, which allows no time zone at the end of literal.
-    // Since RFC 3339 does not allow such behavior, the function tries to add
-    // 'Z' (which equals "+00:00") in case there is no timezone provided.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let tz_provided = s.ends_with('Z') || s.contains('+') || s.matches('-').count() == 3;
-        let s_with_timezone = if tz_provided { s.to_string() } else { format!("{}Z", s) };
-        match CDateTime::parse_from_rfc3339(&s_with_timezone) {
-            Ok(cdt) => Ok(DateTime { value: cdt }),
-            Err(err) => Err(err),
-        }
+        let (value, timezone) = parse_datetime(s)?;
+        Ok(DateTime { value, timezone })
     }
 }
 
 impl fmt::Display for DateTime {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.value.to_rfc3339())
+        match self.timezone {
+            Some(tz) => write!(f, "{}{}", format_datetime(self.value), tz),
+            None => write!(f, "{}", format_datetime(self.value)),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use chrono::NaiveDate;
     use yaserde_derive::{YaDeserialize, YaSerialize};
 
     use super::*;
     use crate::utils::xml_eq::assert_xml_eq;
 
+    fn naive(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd(year, month, day).unwrap().and_hms(hour, minute, 0).unwrap()
+    }
+
     #[test]
     fn datetime_parse_test() {
         // No timezone.
-        let offset = FixedOffset::east_opt(0).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
-        assert_eq!(DateTime::from_str("2020-03-07T04:40:00"), Ok(DateTime { value: dt }));
+        let parsed = DateTime::from_str("2020-03-07T04:40:00").unwrap();
+        assert_eq!(parsed.value, naive(2020, 3, 7, 4, 40));
+        assert_eq!(parsed.timezone, None);
+
         // Timezone "Z".
-        assert_eq!(DateTime::from_str("2020-03-07T04:40:00Z"), Ok(DateTime { value: dt }));
+        assert_eq!(
+            DateTime::from_str("2020-03-07T04:40:00Z"),
+            Ok(DateTime { value: naive(2020, 3, 7, 4, 40), timezone: Some(Timezone::UTC) })
+        );
 
         // Positive offset.
-        let offset = FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
-        assert_eq!(DateTime::from_str("2020-03-07T04:40:00+06:30"), Ok(DateTime { value: dt }));
+        assert_eq!(
+            DateTime::from_str("2020-03-07T04:40:00+06:30"),
+            Ok(DateTime {
+                value: naive(2020, 3, 7, 4, 40),
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap())
+            })
+        );
 
         // Negative offset.
-        let offset = FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
-        assert_eq!(DateTime::from_str("2020-03-07T04:40:00-06:30"), Ok(DateTime { value: dt }));
+        assert_eq!(
+            DateTime::from_str("2020-03-07T04:40:00-06:30"),
+            Ok(DateTime {
+                value: naive(2020, 3, 7, 4, 40),
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
+            })
+        );
     }
 
     #[test]
     fn datetime_display_test() {
-        // Timezone +00:00.
-        let offset = FixedOffset::east_opt(0).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
-        assert_eq!(DateTime { value: dt }.to_string(), "2020-03-07T04:40:00+00:00");
+        let dt = |offset_minutes| DateTime {
+            value: naive(2020, 3, 7, 4, 40),
+            timezone: Timezone::from_offset_minutes(offset_minutes),
+        };
+
+        // Zero offset.
+        assert_eq!(dt(0).to_string(), "2020-03-07T04:40:00Z");
 
         // Positive offset.
-        let offset = FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
-        assert_eq!(DateTime { value: dt }.to_string(), "2020-03-07T04:40:00+06:30");
+        assert_eq!(dt(6 * 60 + 30).to_string(), "2020-03-07T04:40:00+06:30");
 
         // Negative offset.
-        let offset = FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
-        assert_eq!(DateTime { value: dt }.to_string(), "2020-03-07T04:40:00-06:30");
+        assert_eq!(dt(-(6 * 60 + 30)).to_string(), "2020-03-07T04:40:00-06:30");
+    }
+
+    #[test]
+    fn datetime_no_timezone_test() {
+        let dt = |s| DateTime::from_str(s).unwrap();
+
+        assert_eq!(dt("2020-01-01T10:00:00").to_string(), "2020-01-01T10:00:00");
+        assert_eq!(
+            (dt("2020-01-01T10:00:00") + Duration::from_str("PT1H").unwrap()).to_string(),
+            "2020-01-01T11:00:00"
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn datetime_chrono_test() {
+        let dt = |s| DateTime::from_str(s).unwrap();
+
+        assert_eq!(dt("2020-01-01T10:00:00").to_chrono_datetime(), None);
+        assert_eq!(
+            dt("2020-01-01T10:00:00").to_chrono_datetime_or(FixedOffset::east_opt(3600).unwrap()),
+            dt("2020-01-01T09:00:00Z").to_chrono_datetime()
+        );
+
+        let value = CDateTime::parse_from_rfc3339("2020-03-07T04:40:00-06:30").unwrap();
+        assert_eq!(DateTime::from_chrono_datetime(value), Some(dt("2020-03-07T04:40:00-06:30")));
+        assert_eq!(dt("2020-03-07T04:40:00-06:30").to_chrono_datetime(), Some(value));
+        assert_eq!(dt("300000-01-01T00:00:00Z").to_chrono_datetime(), None);
+    }
+
+    #[test]
+    fn datetime_order_test() {
+        let dt = |s| DateTime::from_str(s).unwrap();
+
+        // Timezoned values are compared as instants.
+        assert_eq!(dt("2020-01-01T10:00:00+01:00"), dt("2020-01-01T09:00:00Z"));
+        assert!(dt("2020-01-01T10:00:00+01:00") < dt("2020-01-01T09:30:00Z"));
+
+        // Values without a time zone are compared with each other as they are.
+        assert!(dt("2020-01-01T10:00:00") < dt("2020-01-01T11:00:00"));
+
+        // Otherwise the values must be more than 14 hours apart.
+        assert_eq!(dt("2020-01-01T10:00:00").partial_cmp(&dt("2020-01-01T10:00:00Z")), None);
+        assert_eq!(dt("2020-01-01T10:00:00").partial_cmp(&dt("2020-01-01T23:00:00Z")), None);
+        assert!(dt("2020-01-01T10:00:00") < dt("2020-01-02T00:00:01Z"));
+        assert!(dt("2020-01-01T10:00:00") > dt("2019-12-31T19:59:59Z"));
+        assert_ne!(dt("2020-01-01T10:00:00"), dt("2020-01-01T10:00:00Z"));
+    }
+
+    #[test]
+    fn datetime_years_test() {
+        let dt = |s| DateTime::from_str(s).unwrap();
+
+        assert_eq!(dt("-0044-03-15T12:00:00").to_string(), "-0044-03-15T12:00:00");
+        assert_eq!(dt("10000-01-01T00:00:00Z").to_string(), "10000-01-01T00:00:00Z");
+        assert_eq!(dt("0000-12-31T24:00:00").to_string(), "0001-01-01T00:00:00");
+        assert!(dt("-0044-03-15T12:00:00Z") < dt("0044-03-15T12:00:00Z"));
+    }
+
+    #[test]
+    fn datetime_end_of_day_test() {
+        let dt = |s| DateTime::from_str(s).unwrap();
+
+        assert_eq!(dt("2000-01-01T24:00:00Z"), dt("2000-01-02T00:00:00Z"));
+        assert_eq!(dt("2000-12-31T24:00:00").to_string(), "2001-01-01T00:00:00");
+        assert!(DateTime::from_str("2000-01-01T24:00:00.1Z").is_err());
+    }
+
+    #[test]
+    fn datetime_fraction_test() {
+        let dt = DateTime::from_str("2020-03-07T04:40:00.1+06:30").unwrap();
+        assert_eq!(dt.value.time().nanosecond(), 100_000_000);
+        assert_eq!(dt.to_string(), "2020-03-07T04:40:00.1+06:30");
+        assert_eq!(
+            DateTime::from_str("2020-03-07T04:40:00.000000001Z").unwrap().to_string(),
+            "2020-03-07T04:40:00.000000001Z"
+        );
+    }
+
+    #[test]
+    fn datetime_add_duration_test() {
+        let dt = |s| DateTime::from_str(s).unwrap();
+        let dur = |s| Duration::from_str(s).unwrap();
+
+        // Examples from XSD 1.1 Appendix E.3.3.
+        assert_eq!(
+            dt("2000-01-12T12:13:14Z") + dur("P1Y3M5DT7H10M3.3S"),
+            dt("2001-04-17T19:23:17.3Z")
+        );
+        assert_eq!(dt("2000-01-01T00:00:00Z") - dur("P3M"), dt("1999-10-01T00:00:00Z"));
+        assert_eq!(&dt("2000-01-31T00:00:00Z") + &dur("P1M"), dt("2000-02-29T00:00:00Z"));
+        assert_eq!(
+            &dt("2000-03-30T10:00:00+05:00") - &dur("P1MT1H"),
+            dt("2000-02-29T09:00:00+05:00")
+        );
+
+        // The day is pinned before seconds are added.
+        assert_eq!(dt("2000-01-31T00:00:00Z") + dur("P1M1D"), dt("2000-03-01T00:00:00Z"));
     }
 
     #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
@@ -124,12 +317,13 @@ mod tests {
                 <t:Text>Hello world</t:Text>
             </t:Message>
             "#;
-
-        let offset = FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
-        let m = Message { created_at: DateTime { value: dt }, text: "Hello world".to_string() };
+        let m = Message {
+            created_at: DateTime {
+                value: naive(2020, 3, 7, 4, 40),
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap()),
+            },
+            text: "Hello world".to_string(),
+        };
         let actual = yaserde::ser::to_string(&m).unwrap();
         assert_xml_eq(&actual, expected);
     }
@@ -144,12 +338,8 @@ mod tests {
             "#;
         let m: Message = yaserde::de::from_str(s).unwrap();
 
-        let offset = FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
-
-        assert_eq!(m.created_at.value, dt);
+        assert_eq!(m.created_at.value, naive(2020, 3, 7, 4, 40));
+        assert_eq!(m.created_at.timezone, Timezone::from_offset_minutes(-(6 * 60 + 30)));
         assert_eq!(m.text, "Hello world".to_string());
     }
 }
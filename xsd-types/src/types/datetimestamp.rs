@@ -1,9 +1,10 @@
 use std::{fmt, str::FromStr};
 
-use chrono::{format::ParseError, DateTime as CDateTime, FixedOffset};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime as CDateTime, FixedOffset};
 use xsd_macro_utils::UtilsDefaultSerde;
 
-use crate::types::datetime::DateTime;
+use crate::types::{datetime::DateTime, lexical::parse_datetime};
 
 // The only difference from DateTime is that the time zone expression is required at the end of the value.
 #[derive(Default, PartialEq, PartialOrd, Debug, UtilsDefaultSerde)]
@@ -12,22 +13,29 @@ pub struct DateTimeStamp {
 }
 
 impl DateTimeStamp {
-    pub fn from_chrono_datetime(datetime: CDateTime<FixedOffset>) -> Self {
-        DateTimeStamp { value: DateTime::from_chrono_datetime(datetime) }
+    // None if the offset is not a whole number of minutes within +-14:00.
+    #[cfg(feature = "chrono")]
+    pub fn from_chrono_datetime(datetime: CDateTime<FixedOffset>) -> Option<Self> {
+        Some(DateTimeStamp { value: DateTime::from_chrono_datetime(datetime)? })
     }
 
-    pub fn to_chrono_datetime(&self) -> CDateTime<FixedOffset> {
-        self.value.to_chrono_datetime()
+    // The time zone is required by the lexical space, UTC is only a fallback for values built
+    // by hand without one. None if the year is out of the range of chrono.
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono_datetime(&self) -> Option<CDateTime<FixedOffset>> {
+        self.value.to_chrono_datetime_or(FixedOffset::east_opt(0).unwrap())
     }
 }
 
 impl FromStr for DateTimeStamp {
-    type Err = ParseError;
+    type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match CDateTime::parse_from_rfc3339(s) {
-            Ok(cdt) => Ok(DateTimeStamp::from_chrono_datetime(cdt)),
-            Err(err) => Err(err),
+        match parse_datetime(s)? {
+            (value, Some(timezone)) => {
+                Ok(DateTimeStamp { value: DateTime { value, timezone: Some(timezone) } })
+            }
+            (_, None) => Err("bad dateTimeStamp format: time zone is required".to_string()),
         }
     }
 }
@@ -40,78 +48,56 @@ impl fmt::Display for DateTimeStamp {
 
 #[cfg(test)]
 mod tests {
-    use chrono::NaiveDate;
     use yaserde_derive::{YaDeserialize, YaSerialize};
 
     use super::*;
-    use crate::utils::xml_eq::assert_xml_eq;
+    use crate::{
+        types::{naive::NaiveDate, timezone::Timezone},
+        utils::xml_eq::assert_xml_eq,
+    };
+
+    fn stamp(offset_minutes: i32) -> DateTimeStamp {
+        DateTimeStamp {
+            value: DateTime {
+                value: NaiveDate::from_ymd(2020, 3, 7).unwrap().and_hms(4, 40, 0).unwrap(),
+                timezone: Timezone::from_offset_minutes(offset_minutes),
+            },
+        }
+    }
 
     #[test]
     fn datetime_parse_test() {
         // No timezone.
-        let offset = FixedOffset::east_opt(0).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
         assert!(DateTimeStamp::from_str("2020-03-07T04:40:00").is_err());
+
         // Timezone "Z".
-        assert_eq!(
-            DateTimeStamp::from_str("2020-03-07T04:40:00Z"),
-            Ok(DateTimeStamp::from_chrono_datetime(dt))
-        );
+        assert_eq!(DateTimeStamp::from_str("2020-03-07T04:40:00Z"), Ok(stamp(0)));
 
         // Positive offset.
-        let offset = FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
-        assert_eq!(
-            DateTimeStamp::from_str("2020-03-07T04:40:00+06:30"),
-            Ok(DateTimeStamp::from_chrono_datetime(dt))
-        );
+        assert_eq!(DateTimeStamp::from_str("2020-03-07T04:40:00+06:30"), Ok(stamp(6 * 60 + 30)));
 
         // Negative offset.
-        let offset = FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
-        assert_eq!(
-            DateTimeStamp::from_str("2020-03-07T04:40:00-06:30"),
-            Ok(DateTimeStamp::from_chrono_datetime(dt))
-        );
+        assert_eq!(DateTimeStamp::from_str("2020-03-07T04:40:00-06:30"), Ok(stamp(-(6 * 60 + 30))));
     }
 
     #[test]
     fn datetime_display_test() {
-        // Timezone +00:00.
-        let offset = FixedOffset::east_opt(0).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
-        assert_eq!(
-            DateTimeStamp::from_chrono_datetime(dt).to_string(),
-            "2020-03-07T04:40:00+00:00"
-        );
+        // Zero offset.
+        assert_eq!(stamp(0).to_string(), "2020-03-07T04:40:00Z");
 
         // Positive offset.
-        let offset = FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
-        assert_eq!(
-            DateTimeStamp::from_chrono_datetime(dt).to_string(),
-            "2020-03-07T04:40:00+06:30"
-        );
+        assert_eq!(stamp(6 * 60 + 30).to_string(), "2020-03-07T04:40:00+06:30");
 
         // Negative offset.
-        let offset = FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
-        assert_eq!(
-            DateTimeStamp::from_chrono_datetime(dt).to_string(),
-            "2020-03-07T04:40:00-06:30"
-        );
+        assert_eq!(stamp(-(6 * 60 + 30)).to_string(), "2020-03-07T04:40:00-06:30");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn datetime_chrono_test() {
+        let value = CDateTime::parse_from_rfc3339("2020-03-07T04:40:00+06:30").unwrap();
+        assert_eq!(DateTimeStamp::from_chrono_datetime(value), Some(stamp(6 * 60 + 30)));
+        assert_eq!(stamp(6 * 60 + 30).to_chrono_datetime(), Some(value));
     }
 
     #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
@@ -133,14 +119,7 @@ mod tests {
             </t:Message>
             "#;
 
-        let offset = FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
-        let m = Message {
-            created_at: DateTimeStamp::from_chrono_datetime(dt),
-            text: "Hello world".to_string(),
-        };
+        let m = Message { created_at: stamp(6 * 60 + 30), text: "Hello world".to_string() };
         let actual = yaserde::ser::to_string(&m).unwrap();
         assert_xml_eq(&actual, expected);
     }
@@ -155,12 +134,7 @@ mod tests {
             "#;
         let m: Message = yaserde::de::from_str(s).unwrap();
 
-        let offset = FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap();
-        let dt_utc =
-            NaiveDate::from_ymd_opt(2020, 3, 7).unwrap().and_hms_opt(4, 40, 0).unwrap() - offset;
-        let dt = CDateTime::<FixedOffset>::from_naive_utc_and_offset(dt_utc, offset);
-
-        assert_eq!(m.created_at.value, DateTime::from_chrono_datetime(dt));
+        assert_eq!(m.created_at, stamp(-(6 * 60 + 30)));
         assert_eq!(m.text, "Hello world".to_string());
     }
 }
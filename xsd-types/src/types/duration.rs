@@ -1,8 +1,21 @@
-use std::{fmt, fmt::Write, str::FromStr};
+use std::{cmp::Ordering, convert::TryFrom, fmt, fmt::Write, str::FromStr};
 
+use bigdecimal::BigDecimal;
+use num_bigint::BigInt;
 use xsd_macro_utils::UtilsDefaultSerde;
 
-#[derive(Default, PartialEq, PartialOrd, Debug, UtilsDefaultSerde)]
+use crate::types::{
+    datetime::DateTime,
+    naive::{days_in_month, NaiveDate, NaiveDateTime, NaiveTime},
+    utils::format_fraction,
+};
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+// Equality and order follow XSD 1.1: a duration is a number of months and a number of
+// seconds, so P1Y equals P12M and PT36H equals P1DT12H. Durations with both months and
+// days (or smaller units) are only partially ordered: P1M and P30D are incomparable.
+#[derive(Default, Clone, Debug, UtilsDefaultSerde)]
 pub struct Duration {
     pub is_negative: bool,
 
@@ -12,7 +25,9 @@ pub struct Duration {
 
     pub hours: u64,
     pub minutes: u64,
-    pub seconds: f64,
+    pub seconds: u64,
+    // Fractional part of the seconds, kept exact so that PT0.1S round-trips.
+    pub nanoseconds: u32,
 }
 
 impl Duration {
@@ -20,21 +35,148 @@ impl Duration {
         if self.years > 0 || self.months > 0 {
             Err("Duration with months or years require a starting date to be converted".into())
         } else {
-            let secs = self.seconds as u64;
+            let secs = self.seconds + 60 * (self.minutes + 60 * (self.hours + 24 * self.days));
+
+            Ok(std::time::Duration::new(secs, self.nanoseconds))
+        }
+    }
+
+    // The result is in canonical form: days, hours, minutes and seconds, no months.
+    pub fn from_std_duration(duration: std::time::Duration) -> Self {
+        let secs = duration.as_secs();
+        Duration {
+            days: secs / 86400,
+            hours: secs / 3600 % 24,
+            minutes: secs / 60 % 60,
+            seconds: secs % 60,
+            nanoseconds: duration.subsec_nanos(),
+            ..Default::default()
+        }
+    }
 
-            let nanos = ((self.seconds - secs as f64) * 1e9) as u32;
-            let secs = secs + 60 * (self.minutes + 60 * (self.hours + 24 * self.days));
+    // Length of the duration when it starts at `start`: P1M lasts 31 days from
+    // 2020-01-01 and 29 days from 2020-02-01.
+    pub fn to_std_duration_from(&self, start: &DateTime) -> Result<std::time::Duration, String> {
+        let start = start.value;
+        let end = self.add_to(start).ok_or("Overflow adding duration")?;
+        let nanos = (end.timestamp_nanos() - start.timestamp_nanos()).unsigned_abs();
+        let secs = u64::try_from(nanos / NANOS_PER_SECOND as u128).map_err(|e| e.to_string())?;
+        Ok(std::time::Duration::new(secs, (nanos % NANOS_PER_SECOND as u128) as u32))
+    }
+
+    // Replaces years and months with the number of days they last when the duration
+    // starts at `start`.
+    pub fn to_days_from(&self, start: &DateTime) -> Result<Duration, String> {
+        let start = start.value;
+        let months = Duration { years: self.years, months: self.months, ..Default::default() };
+        let months = if self.is_negative { months.negated() } else { months };
+        let end = months.add_to(start).ok_or("Overflow adding duration")?;
+
+        Ok(Duration {
+            years: 0,
+            months: 0,
+            days: self.days + (end.date().days() - start.date().days()).unsigned_abs(),
+            ..self.clone()
+        })
+    }
 
-            Ok(std::time::Duration::new(secs, nanos))
+    // Canonical form: months carry into years, seconds into minutes, hours and days.
+    // Days never carry into months since their ratio is not fixed.
+    pub fn normalize(&self) -> Duration {
+        let months = self.years * 12 + self.months;
+        let nanos = self.total_nanoseconds().unsigned_abs();
+        let whole = (nanos / NANOS_PER_SECOND as u128) as u64;
+
+        Duration {
+            is_negative: self.is_negative && (months > 0 || nanos > 0),
+            years: months / 12,
+            months: months % 12,
+            days: whole / 86400,
+            hours: whole / 3600 % 24,
+            minutes: whole / 60 % 60,
+            seconds: whole % 60,
+            nanoseconds: (nanos % NANOS_PER_SECOND as u128) as u32,
         }
     }
 
-    // TODO: Add from_std_duration
+    // The months property of XSD 1.1: years and months, signed.
+    pub fn total_months(&self) -> i64 {
+        let months = (self.years * 12 + self.months) as i64;
+        if self.is_negative {
+            -months
+        } else {
+            months
+        }
+    }
 
-    // TODO: Add a version of to_std_duration that takes a moment at time to start from.
+    // The seconds property of XSD 1.1: days, hours, minutes and seconds, signed.
+    pub fn total_seconds(&self) -> BigDecimal {
+        BigDecimal::new(BigInt::from(self.total_nanoseconds()), 9).normalized()
+    }
 
-    // TODO: Implement normalization function that takes a moment at time to start from and
-    // converts months & years to days.
+    // Same as total_seconds, as an integer number of nanoseconds.
+    pub fn total_nanoseconds(&self) -> i128 {
+        let seconds = self.seconds as i128
+            + 60 * (self.minutes as i128 + 60 * (self.hours as i128 + 24 * self.days as i128));
+        let nanos = seconds * NANOS_PER_SECOND + self.nanoseconds as i128;
+        if self.is_negative {
+            -nanos
+        } else {
+            nanos
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.total_months() == 0 && self.total_nanoseconds() == 0
+    }
+
+    // dateTimePlusDuration of XSD 1.1 Appendix E.3.3: months are added first and the day
+    // is pinned to the end of the resulting month, then seconds are added with carry.
+    pub(crate) fn add_to(&self, datetime: NaiveDateTime) -> Option<NaiveDateTime> {
+        let date = datetime.date();
+        let months = date.year() as i64 * 12 + date.month() as i64 - 1 + self.total_months();
+        let year = i32::try_from(months.div_euclid(12)).ok()?;
+        let month = months.rem_euclid(12) as u32 + 1;
+        let day = date.day().min(days_in_month(year, month));
+        let datetime = NaiveDate::from_ymd(year, month, day)?.and_time(datetime.time());
+
+        datetime.checked_add_nanos(self.total_nanoseconds())
+    }
+
+    pub(crate) fn negated(&self) -> Duration {
+        Duration { is_negative: !self.is_negative, ..self.clone() }
+    }
+}
+
+impl PartialEq for Duration {
+    fn eq(&self, other: &Self) -> bool {
+        self.total_months() == other.total_months()
+            && self.total_nanoseconds() == other.total_nanoseconds()
+    }
+}
+
+impl PartialOrd for Duration {
+    // XSD 1.1 section 3.3.6.2: durations are compared by adding them to four reference
+    // dateTimes. They are ordered only if all four results agree.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.total_months() == other.total_months() {
+            return Some(self.total_nanoseconds().cmp(&other.total_nanoseconds()));
+        }
+
+        const REFERENCES: [(i32, u32, u32); 4] =
+            [(1696, 9, 1), (1697, 2, 1), (1903, 3, 1), (1903, 7, 1)];
+
+        let mut res = None;
+        for (year, month, day) in REFERENCES.iter() {
+            let start = NaiveDate::from_ymd(*year, *month, *day)?.and_time(NaiveTime::MIDNIGHT);
+            let ord = self.add_to(start)?.cmp(&other.add_to(start)?);
+            match res {
+                Some(prev) if prev != ord => return None,
+                _ => res = Some(ord),
+            }
+        }
+        res
+    }
 }
 
 impl FromStr for Duration {
@@ -73,13 +215,13 @@ impl FromStr for Duration {
 
         fn fill_seconds(
             context: &mut ParsingContext,
-            seconds: &mut f64,
+            dur: &mut Duration,
         ) -> Result<(), &'static str> {
             if context.is_number_empty {
                 return Err("No value is specified for seconds, so 'S' must not be present");
             }
 
-            if context.is_dot_found && context.denom == 1 {
+            if context.is_dot_found && context.fraction_digits == 0 {
                 return Err("At least one digit must follow the decimal point if it appears");
             }
 
@@ -87,7 +229,9 @@ impl FromStr for Duration {
                 return Err("Bad order of duration components");
             }
 
-            *seconds = context.number as f64 + context.numer as f64 / context.denom as f64;
+            dur.seconds = context.number;
+            dur.nanoseconds =
+                context.nanoseconds * 10u32.pow(9 - context.fraction_digits.min(9) as u32);
             context.last_filled_component = 6;
             context.number = 0;
             context.is_number_empty = true;
@@ -150,7 +294,7 @@ impl FromStr for Duration {
                     if !context.is_t_found {
                         return Err("No symbol 'T' found before seconds components".into());
                     }
-                    fill_seconds(&mut context, &mut dur.seconds)?;
+                    fill_seconds(&mut context, &mut dur)?;
                 }
 
                 // Number:
@@ -171,10 +315,13 @@ impl FromStr for Duration {
                     }
 
                     if context.is_dot_found {
-                        context.numer *= 10;
-                        context.numer +=
-                            digit.to_digit(10).expect("error converting a digit") as u64;
-                        context.denom *= 10;
+                        let digit = digit.to_digit(10).expect("error converting a digit");
+                        if context.fraction_digits < 9 {
+                            context.nanoseconds = context.nanoseconds * 10 + digit;
+                        } else if digit != 0 {
+                            return Err("Seconds are supported up to nanosecond precision".into());
+                        }
+                        context.fraction_digits += 1;
                     } else {
                         context.number *= 10;
                         context.number +=
@@ -207,7 +354,8 @@ impl FromStr for Duration {
 
 impl fmt::Display for Duration {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut s = if self.is_negative { "-P".to_string() } else { "P".to_string() };
+        let mut s =
+            if self.is_negative && !self.is_zero() { "-P".to_string() } else { "P".to_string() };
 
         let mut date_str = String::new();
         if self.years > 0 {
@@ -227,8 +375,8 @@ impl fmt::Display for Duration {
         if self.minutes > 0 {
             write!(&mut time_str, "{}M", self.minutes)?;
         }
-        if self.seconds > 0.0 {
-            write!(&mut time_str, "{}S", self.seconds)?;
+        if self.seconds > 0 || self.nanoseconds > 0 {
+            write!(&mut time_str, "{}{}S", self.seconds, format_fraction(self.nanoseconds))?;
         }
 
         if time_str.is_empty() {
@@ -256,9 +404,9 @@ struct ParsingContext {
     is_number_empty: bool,
 
     is_dot_found: bool,
-    // Numerator and denominator of seconds fraction part.
-    numer: u64,
-    denom: u64,
+    // Seconds fraction part: the first nine digits and the total number of digits.
+    nanoseconds: u32,
+    fraction_digits: usize,
 }
 
 impl ParsingContext {
@@ -272,8 +420,8 @@ impl ParsingContext {
             is_number_empty: true,
 
             is_dot_found: false,
-            numer: 0,
-            denom: 1,
+            nanoseconds: 0,
+            fraction_digits: 0,
         }
     }
 }
@@ -315,4 +463,98 @@ mod tests {
         check_invalid("P");
         check_invalid("PT15.S");
     }
+
+    fn dur(s: &str) -> Duration {
+        Duration::from_str(s).unwrap()
+    }
+
+    fn datetime(s: &str) -> DateTime {
+        DateTime::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn duration_fraction_test() {
+        let d = dur("PT0.1S");
+        assert_eq!((d.seconds, d.nanoseconds), (0, 100_000_000));
+        assert_eq!(d.to_string(), "PT0.1S");
+        assert_eq!(dur("PT1.000000001S").to_string(), "PT1.000000001S");
+        assert_eq!(dur("PT2.5000000000S").to_string(), "PT2.5S");
+        assert_eq!(dur("PT0.1S").total_seconds(), "0.1".parse::<BigDecimal>().unwrap());
+        assert!(dur("PT0.3S") > dur("PT0.299999999S"));
+        check_invalid("PT0.0000000001S");
+    }
+
+    #[test]
+    fn duration_negative_zero_test() {
+        assert_eq!(dur("-PT0S").to_string(), "P0Y");
+        assert_eq!(dur("-P0Y").to_string(), "P0Y");
+        assert_eq!(dur("-PT0.5S").to_string(), "-PT0.5S");
+    }
+
+    #[test]
+    fn duration_from_std_test() {
+        assert_eq!(
+            Duration::from_std_duration(std::time::Duration::from_millis(93_784_500)).to_string(),
+            "P1DT2H3M4.5S"
+        );
+        assert_eq!(
+            Duration::from_std_duration(std::time::Duration::from_secs(90))
+                .to_std_duration()
+                .unwrap(),
+            std::time::Duration::from_secs(90)
+        );
+    }
+
+    #[test]
+    fn duration_relative_test() {
+        let day = std::time::Duration::from_secs(86400);
+        assert_eq!(
+            dur("P1M").to_std_duration_from(&datetime("2020-01-01T00:00:00Z")),
+            Ok(day * 31)
+        );
+        assert_eq!(
+            dur("P1M").to_std_duration_from(&datetime("2020-02-01T00:00:00Z")),
+            Ok(day * 29)
+        );
+        assert_eq!(
+            dur("P1Y").to_std_duration_from(&datetime("2021-02-01T00:00:00Z")),
+            Ok(day * 365)
+        );
+
+        let days = dur("P1Y2M3DT4H").to_days_from(&datetime("2020-01-01T00:00:00Z")).unwrap();
+        assert_eq!((days.years, days.months, days.days, days.hours), (0, 0, 428, 4));
+        let days = dur("-P1M").to_days_from(&datetime("2020-03-31T00:00:00Z")).unwrap();
+        assert!(days.is_negative);
+        assert_eq!(days.days, 31);
+    }
+
+    #[test]
+    fn duration_normalize_test() {
+        assert_eq!(dur("P14M").normalize().to_string(), "P1Y2M");
+        assert_eq!(dur("PT36H").normalize().to_string(), "P1DT12H");
+        assert_eq!(dur("PT3661.5S").normalize().to_string(), "PT1H1M1.5S");
+        assert_eq!(dur("P1M40D").normalize().to_string(), "P1M40D");
+        assert!(!dur("-PT0S").normalize().is_negative);
+    }
+
+    #[test]
+    fn duration_equality_test() {
+        assert_eq!(dur("P1Y"), dur("P12M"));
+        assert_eq!(dur("PT24H"), dur("P1D"));
+        assert_eq!(dur("-PT0S"), dur("PT0S"));
+        assert_ne!(dur("P1M"), dur("P30D"));
+    }
+
+    #[test]
+    fn duration_order_test() {
+        assert!(dur("P1Y") > dur("P364D"));
+        assert!(dur("P1Y") < dur("P367D"));
+        assert_eq!(dur("P1Y").partial_cmp(&dur("P365D")), None);
+        assert_eq!(dur("P1M").partial_cmp(&dur("P30D")), None);
+        assert!(dur("P1M") > dur("P27D"));
+        assert!(dur("P1M") < dur("P32D"));
+        assert!(dur("PT1H") > dur("PT59M"));
+        assert!(dur("-P1D") < dur("PT1S"));
+        assert!(dur("P5M") > dur("P4M"));
+    }
 }
@@ -0,0 +1,103 @@
+use std::{fmt, str::FromStr};
+
+use xsd_macro_utils::UtilsDefaultSerde;
+
+use crate::types::utils::{format_float_literal, parse_float_literal};
+
+// Equality and order are those of IEEE 754, as in XSD: NaN is not equal to itself
+// and 0 equals -0.
+#[derive(Default, Clone, Copy, PartialEq, PartialOrd, Debug, UtilsDefaultSerde)]
+pub struct Float(pub f32);
+
+impl Float {
+    pub fn from_f32(value: f32) -> Self {
+        Float(value)
+    }
+
+    pub fn to_f32(&self) -> f32 {
+        self.0
+    }
+}
+
+impl FromStr for Float {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_float_literal(s).map(Float)
+    }
+}
+
+impl fmt::Display for Float {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", format_float_literal(&format!("{:e}", self.0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    use super::*;
+    use crate::utils::xml_eq::assert_xml_eq;
+
+    #[test]
+    fn float_parse_test() {
+        assert_eq!(Float::from_str("1.0E3"), Ok(Float(1000.0)));
+        assert_eq!(Float::from_str("-12.5"), Ok(Float(-12.5)));
+        assert_eq!(Float::from_str(" 7 "), Ok(Float(7.0)));
+        assert_eq!(Float::from_str("INF"), Ok(Float(f32::INFINITY)));
+        assert_eq!(Float::from_str("-INF"), Ok(Float(f32::NEG_INFINITY)));
+        assert!(Float::from_str("NaN").unwrap().0.is_nan());
+        assert!(Float::from_str("-0").unwrap().0.is_sign_negative());
+
+        assert!(Float::from_str("inf").is_err());
+        assert!(Float::from_str("1,5").is_err());
+    }
+
+    #[test]
+    fn float_display_test() {
+        assert_eq!(Float(1000.0).to_string(), "1.0E3");
+        assert_eq!(Float(0.1).to_string(), "1.0E-1");
+        assert_eq!(Float(-1.5).to_string(), "-1.5E0");
+        assert_eq!(Float(0.0).to_string(), "0.0E0");
+        assert_eq!(Float(-0.0).to_string(), "-0.0E0");
+        assert_eq!(Float(f32::INFINITY).to_string(), "INF");
+        assert_eq!(Float(f32::NAN).to_string(), "NaN");
+    }
+
+    #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: test")]
+    pub struct Message {
+        #[yaserde(prefix = "t", rename = "Value")]
+        pub value: Float,
+
+        #[yaserde(prefix = "t", rename = "Text")]
+        pub text: String,
+    }
+
+    #[test]
+    fn float_serialize_test() {
+        let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Value>-INF</t:Value>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m = Message { value: Float(f32::NEG_INFINITY), text: "Hello world".to_string() };
+        let actual = yaserde::ser::to_string(&m).unwrap();
+        assert_xml_eq(&actual, expected);
+    }
+
+    #[test]
+    fn float_deserialize_test() {
+        let s = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Value>1.5E2</t:Value>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m: Message = yaserde::de::from_str(s).unwrap();
+        assert_eq!(m.value, Float(150.0));
+        assert_eq!(m.text, "Hello world".to_string());
+    }
+}
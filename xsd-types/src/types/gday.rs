@@ -1,18 +1,21 @@
-use std::{fmt, str::FromStr};
+use std::{cmp::Ordering, fmt, str::FromStr};
 
-use chrono::FixedOffset;
 use xsd_macro_utils::UtilsDefaultSerde;
 
-use crate::types::utils::parse_timezone;
+use crate::types::{
+    lexical::{parse_two_digits, split_timezone},
+    timezone::Timezone,
+    utils::{compare_partial_dates, REFERENCE_YEAR},
+};
 
-#[derive(PartialEq, Debug, UtilsDefaultSerde)]
+#[derive(Debug, UtilsDefaultSerde)]
 pub struct GDay {
     pub value: i32,
-    pub timezone: Option<FixedOffset>,
+    pub timezone: Option<Timezone>,
 }
 
 impl GDay {
-    pub fn new(day: i32, timezone: Option<FixedOffset>) -> Result<Self, String> {
+    pub fn new(day: i32, timezone: Option<Timezone>) -> Result<Self, String> {
         if !(1..=31).contains(&day) {
             return Err("gDay value should lie between 1 and 31".to_string());
         }
@@ -20,6 +23,23 @@ impl GDay {
     }
 }
 
+impl PartialEq for GDay {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for GDay {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare_partial_dates(
+            (REFERENCE_YEAR, 1, self.value),
+            self.timezone,
+            (REFERENCE_YEAR, 1, other.value),
+            other.timezone,
+        )
+    }
+}
+
 impl Default for GDay {
     fn default() -> GDay {
         Self { value: 1, timezone: None }
@@ -30,40 +50,11 @@ impl FromStr for GDay {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        fn parse_value(s: &str) -> Result<i32, String> {
-            if s.len() != 5 || &s[0..3] != "---" {
-                return Err("bad gDay format".to_string());
-            }
-            let token = &s[3..5];
-            if !token.chars().all(|c| c.is_ascii_digit()) {
-                return Err("bad gDay format".to_string());
-            }
-            token.parse::<i32>().map_err(|e| e.to_string())
+        let (s, timezone) = split_timezone(s)?;
+        match s.strip_prefix("---").map(|s| parse_two_digits(s, "gDay")) {
+            Some(Ok((day, ""))) => GDay::new(day as i32, timezone),
+            _ => Err("bad gDay format".to_string()),
         }
-
-        if let Some(s) = s.strip_suffix('Z') {
-            return GDay::new(parse_value(s)?, Some(FixedOffset::east_opt(0).unwrap()));
-        }
-
-        if s.contains('+') {
-            if s.matches('+').count() > 1 {
-                return Err("bad gDay format".to_string());
-            }
-
-            let idx: usize = s.match_indices('+').collect::<Vec<_>>()[0].0;
-            let value_token = &s[..idx];
-            let tz_token = &s[idx..];
-            return GDay::new(parse_value(value_token)?, Some(parse_timezone(tz_token)?));
-        }
-
-        if s.matches('-').count() == 4 {
-            let idx: usize = s.match_indices('-').collect::<Vec<_>>()[3].0;
-            let value_token = &s[..idx];
-            let tz_token = &s[idx..];
-            return GDay::new(parse_value(value_token)?, Some(parse_timezone(tz_token)?));
-        }
-
-        GDay::new(parse_value(s)?, None)
     }
 }
 
@@ -89,17 +80,14 @@ mod tests {
         assert_eq!(GDay::from_str("---25"), Ok(GDay { value: 25, timezone: None }));
 
         // Timezone "Z".
-        assert_eq!(
-            GDay::from_str("---25Z"),
-            Ok(GDay { value: 25, timezone: Some(FixedOffset::east_opt(0).unwrap()) })
-        );
+        assert_eq!(GDay::from_str("---25Z"), Ok(GDay { value: 25, timezone: Some(Timezone::UTC) }));
 
         // Positive offset.
         assert_eq!(
             GDay::from_str("---25+06:30"),
             Ok(GDay {
                 value: 25,
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap())
             })
         );
 
@@ -108,7 +96,7 @@ mod tests {
             GDay::from_str("---25-06:30"),
             Ok(GDay {
                 value: 25,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             })
         );
 
@@ -127,27 +115,37 @@ mod tests {
         // No timezone.
         assert_eq!(GDay { value: 3, timezone: None }.to_string(), "---03");
 
-        // Timezone +00:00.
-        assert_eq!(
-            GDay { value: 3, timezone: Some(FixedOffset::east_opt(0).unwrap()) }.to_string(),
-            "---03+00:00"
-        );
+        // Zero offset.
+        assert_eq!(GDay { value: 3, timezone: Some(Timezone::UTC) }.to_string(), "---03Z");
 
         // Positive offset.
         assert_eq!(
-            GDay { value: 3, timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap()) }
+            GDay { value: 3, timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap()) }
                 .to_string(),
             "---03+06:30"
         );
 
         // Negative offset.
         assert_eq!(
-            GDay { value: 3, timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap()) }
-                .to_string(),
+            GDay {
+                value: 3,
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
+            }
+            .to_string(),
             "---03-06:30"
         );
     }
 
+    #[test]
+    fn gday_order_test() {
+        let v = |s| GDay::from_str(s).unwrap();
+
+        assert_eq!(v("---02+14:00"), v("---01-10:00"));
+        assert!(v("---01") < v("---02"));
+        assert!(v("---01") < v("---03Z"));
+        assert_eq!(v("---01").partial_cmp(&v("---01Z")), None);
+    }
+
     #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
     #[yaserde(prefix = "t", namespace = "t: test")]
     pub struct Message {
@@ -169,7 +167,7 @@ mod tests {
         let m = Message {
             created_at: GDay {
                 value: 7,
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap()),
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap()),
             },
             text: "Hello world".to_string(),
         };
@@ -187,7 +185,10 @@ mod tests {
             "#;
         let m: Message = yaserde::de::from_str(s).unwrap();
         assert_eq!(m.created_at.value, 29);
-        assert_eq!(m.created_at.timezone, Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap()),);
+        assert_eq!(
+            m.created_at.timezone,
+            Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap()),
+        );
         assert_eq!(m.text, "Hello world".to_string());
     }
 }
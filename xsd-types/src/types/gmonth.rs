@@ -1,18 +1,21 @@
-use std::{fmt, str::FromStr};
+use std::{cmp::Ordering, fmt, str::FromStr};
 
-use chrono::FixedOffset;
 use xsd_macro_utils::UtilsDefaultSerde;
 
-use crate::types::utils::parse_timezone;
+use crate::types::{
+    lexical::{parse_two_digits, split_timezone},
+    timezone::Timezone,
+    utils::{compare_partial_dates, REFERENCE_YEAR},
+};
 
-#[derive(PartialEq, Debug, UtilsDefaultSerde)]
+#[derive(Debug, UtilsDefaultSerde)]
 pub struct GMonth {
     pub value: i32,
-    pub timezone: Option<FixedOffset>,
+    pub timezone: Option<Timezone>,
 }
 
 impl GMonth {
-    pub fn new(month: i32, timezone: Option<FixedOffset>) -> Result<Self, String> {
+    pub fn new(month: i32, timezone: Option<Timezone>) -> Result<Self, String> {
         if !(1..=12).contains(&month) {
             return Err("GMonth value should lie between 1 and 12".to_string());
         }
@@ -20,6 +23,23 @@ impl GMonth {
     }
 }
 
+impl PartialEq for GMonth {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for GMonth {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare_partial_dates(
+            (REFERENCE_YEAR, self.value, 1),
+            self.timezone,
+            (REFERENCE_YEAR, other.value, 1),
+            other.timezone,
+        )
+    }
+}
+
 impl Default for GMonth {
     fn default() -> GMonth {
         Self { value: 1, timezone: None }
@@ -30,40 +50,11 @@ impl FromStr for GMonth {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        fn parse_value(s: &str) -> Result<i32, String> {
-            if s.len() != 4 || &s[0..2] != "--" {
-                return Err("bad gMonth format".to_string());
-            }
-            let token = &s[2..4];
-            if !token.chars().all(|c| c.is_ascii_digit()) {
-                return Err("bad gMonth format".to_string());
-            }
-            token.parse::<i32>().map_err(|e| e.to_string())
-        }
-
-        if let Some(s) = s.strip_suffix('Z') {
-            return GMonth::new(parse_value(s)?, Some(FixedOffset::east_opt(0).unwrap()));
-        }
-
-        if s.contains('+') {
-            if s.matches('+').count() > 1 {
-                return Err("bad gMonth format".to_string());
-            }
-
-            let idx: usize = s.match_indices('+').collect::<Vec<_>>()[0].0;
-            let value_token = &s[..idx];
-            let tz_token = &s[idx..];
-            return GMonth::new(parse_value(value_token)?, Some(parse_timezone(tz_token)?));
+        let (s, timezone) = split_timezone(s)?;
+        match s.strip_prefix("--").map(|s| parse_two_digits(s, "gMonth")) {
+            Some(Ok((month, ""))) => GMonth::new(month as i32, timezone),
+            _ => Err("bad gMonth format".to_string()),
         }
-
-        if s.matches('-').count() == 3 {
-            let idx: usize = s.match_indices('-').collect::<Vec<_>>()[2].0;
-            let value_token = &s[..idx];
-            let tz_token = &s[idx..];
-            return GMonth::new(parse_value(value_token)?, Some(parse_timezone(tz_token)?));
-        }
-
-        GMonth::new(parse_value(s)?, None)
     }
 }
 
@@ -91,7 +82,7 @@ mod tests {
         // Timezone "Z".
         assert_eq!(
             GMonth::from_str("--12Z"),
-            Ok(GMonth { value: 12, timezone: Some(FixedOffset::east_opt(0).unwrap()) })
+            Ok(GMonth { value: 12, timezone: Some(Timezone::UTC) })
         );
 
         // Positive offset.
@@ -99,7 +90,7 @@ mod tests {
             GMonth::from_str("--12+06:30"),
             Ok(GMonth {
                 value: 12,
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap())
             })
         );
 
@@ -108,7 +99,7 @@ mod tests {
             GMonth::from_str("--12-06:30"),
             Ok(GMonth {
                 value: 12,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             })
         );
 
@@ -127,27 +118,40 @@ mod tests {
         // No timezone.
         assert_eq!(GMonth { value: 3, timezone: None }.to_string(), "--03");
 
-        // Timezone +00:00.
-        assert_eq!(
-            GMonth { value: 3, timezone: Some(FixedOffset::east_opt(0).unwrap()) }.to_string(),
-            "--03+00:00"
-        );
+        // Zero offset.
+        assert_eq!(GMonth { value: 3, timezone: Some(Timezone::UTC) }.to_string(), "--03Z");
 
         // Positive offset.
         assert_eq!(
-            GMonth { value: 3, timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap()) }
-                .to_string(),
+            GMonth {
+                value: 3,
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap())
+            }
+            .to_string(),
             "--03+06:30"
         );
 
         // Negative offset.
         assert_eq!(
-            GMonth { value: 3, timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap()) }
-                .to_string(),
+            GMonth {
+                value: 3,
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
+            }
+            .to_string(),
             "--03-06:30"
         );
     }
 
+    #[test]
+    fn gmonth_order_test() {
+        let v = |s| GMonth::from_str(s).unwrap();
+
+        assert_eq!(v("--01Z"), v("--01+00:00"));
+        assert!(v("--01") < v("--02"));
+        assert!(v("--01-14:00") < v("--02Z"));
+        assert_eq!(v("--01").partial_cmp(&v("--01Z")), None);
+    }
+
     #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
     #[yaserde(prefix = "t", namespace = "t: test")]
     pub struct Message {
@@ -169,7 +173,7 @@ mod tests {
         let m = Message {
             created_at: GMonth {
                 value: 7,
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap()),
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap()),
             },
             text: "Hello world".to_string(),
         };
@@ -187,7 +191,10 @@ mod tests {
             "#;
         let m: Message = yaserde::de::from_str(s).unwrap();
         assert_eq!(m.created_at.value, 9);
-        assert_eq!(m.created_at.timezone, Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap()),);
+        assert_eq!(
+            m.created_at.timezone,
+            Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap()),
+        );
         assert_eq!(m.text, "Hello world".to_string());
     }
 }
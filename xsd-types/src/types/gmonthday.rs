@@ -1,19 +1,24 @@
-use std::{fmt, str::FromStr};
+use std::{cmp::Ordering, fmt, str::FromStr};
 
-use chrono::FixedOffset;
 use xsd_macro_utils::UtilsDefaultSerde;
 
-use crate::types::{gday::GDay, gmonth::GMonth, utils::parse_timezone};
+use crate::types::{
+    gday::GDay,
+    gmonth::GMonth,
+    lexical::{parse_two_digits, split_timezone},
+    timezone::Timezone,
+    utils::{compare_partial_dates, REFERENCE_YEAR},
+};
 
-#[derive(PartialEq, Debug, UtilsDefaultSerde)]
+#[derive(Debug, UtilsDefaultSerde)]
 pub struct GMonthDay {
     pub month: i32,
     pub day: i32,
-    pub timezone: Option<FixedOffset>,
+    pub timezone: Option<Timezone>,
 }
 
 impl GMonthDay {
-    pub fn new(month: i32, day: i32, timezone: Option<FixedOffset>) -> Result<Self, String> {
+    pub fn new(month: i32, day: i32, timezone: Option<Timezone>) -> Result<Self, String> {
         if !(1..=12).contains(&month) {
             return Err("Month value within GMonthDay should lie between 1 and 12".to_string());
         }
@@ -39,6 +44,23 @@ impl GMonthDay {
     }
 }
 
+impl PartialEq for GMonthDay {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for GMonthDay {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare_partial_dates(
+            (REFERENCE_YEAR, self.month, self.day),
+            self.timezone,
+            (REFERENCE_YEAR, other.month, other.day),
+            other.timezone,
+        )
+    }
+}
+
 impl Default for GMonthDay {
     fn default() -> GMonthDay {
         Self { month: 1, day: 1, timezone: None }
@@ -49,53 +71,14 @@ impl FromStr for GMonthDay {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        fn parse_value(s: &str) -> Result<(i32, i32), String> {
-            if s.len() != 7 || &s[0..2] != "--" || &s[4..5] != "-" {
-                return Err("bad gMonthDay format".to_string());
-            }
-
-            let month_token = &s[2..4];
-            if !month_token.chars().all(|c| c.is_ascii_digit()) {
-                return Err("bad month format within gMonthDay".to_string());
-            }
-            let month = month_token.parse::<i32>().map_err(|e| e.to_string())?;
-
-            let day_token = &s[5..7];
-            if !day_token.chars().all(|c| c.is_ascii_digit()) {
-                return Err("bad day format within gMonthDay".to_string());
-            }
-            let day = day_token.parse::<i32>().map_err(|e| e.to_string())?;
-
-            Ok((month, day))
-        }
-
-        if let Some(s) = s.strip_suffix('Z') {
-            let (month, day) = parse_value(s)?;
-            return GMonthDay::new(month, day, Some(FixedOffset::east_opt(0).unwrap()));
+        let (s, timezone) = split_timezone(s)?;
+        let s = s.strip_prefix("--").ok_or_else(|| "bad gMonthDay format".to_string())?;
+        let (month, s) = parse_two_digits(s, "month within gMonthDay")?;
+        let s = s.strip_prefix('-').ok_or_else(|| "bad gMonthDay format".to_string())?;
+        match parse_two_digits(s, "day within gMonthDay")? {
+            (day, "") => GMonthDay::new(month as i32, day as i32, timezone),
+            _ => Err("bad gMonthDay format".to_string()),
         }
-
-        if s.contains('+') {
-            if s.matches('+').count() > 1 {
-                return Err("bad gMonthDay format".to_string());
-            }
-
-            let idx: usize = s.match_indices('+').collect::<Vec<_>>()[0].0;
-            let value_token = &s[..idx];
-            let tz_token = &s[idx..];
-            let (month, day) = parse_value(value_token)?;
-            return GMonthDay::new(month, day, Some(parse_timezone(tz_token)?));
-        }
-
-        if s.matches('-').count() == 4 {
-            let idx: usize = s.match_indices('-').collect::<Vec<_>>()[3].0;
-            let value_token = &s[..idx];
-            let tz_token = &s[idx..];
-            let (month, day) = parse_value(value_token)?;
-            return GMonthDay::new(month, day, Some(parse_timezone(tz_token)?));
-        }
-
-        let (month, day) = parse_value(s)?;
-        GMonthDay::new(month, day, None)
     }
 }
 
@@ -126,7 +109,7 @@ mod tests {
         // Timezone "Z".
         assert_eq!(
             GMonthDay::from_str("--12-20Z"),
-            Ok(GMonthDay { month: 12, day: 20, timezone: Some(FixedOffset::east_opt(0).unwrap()) })
+            Ok(GMonthDay { month: 12, day: 20, timezone: Some(Timezone::UTC) })
         );
 
         // Positive offset.
@@ -135,7 +118,7 @@ mod tests {
             Ok(GMonthDay {
                 month: 12,
                 day: 20,
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap())
             })
         );
 
@@ -145,7 +128,7 @@ mod tests {
             Ok(GMonthDay {
                 month: 12,
                 day: 20,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             })
         );
 
@@ -169,11 +152,10 @@ mod tests {
         // No timezone.
         assert_eq!(GMonthDay { month: 3, day: 2, timezone: None }.to_string(), "--03-02");
 
-        // Timezone +00:00.
+        // Zero offset.
         assert_eq!(
-            GMonthDay { month: 3, day: 2, timezone: Some(FixedOffset::east_opt(0).unwrap()) }
-                .to_string(),
-            "--03-02+00:00"
+            GMonthDay { month: 3, day: 2, timezone: Some(Timezone::UTC) }.to_string(),
+            "--03-02Z"
         );
 
         // Positive offset.
@@ -181,7 +163,7 @@ mod tests {
             GMonthDay {
                 month: 3,
                 day: 2,
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap())
             }
             .to_string(),
             "--03-02+06:30"
@@ -192,13 +174,23 @@ mod tests {
             GMonthDay {
                 month: 3,
                 day: 2,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             }
             .to_string(),
             "--03-02-06:30"
         );
     }
 
+    #[test]
+    fn gmonthday_order_test() {
+        let v = |s| GMonthDay::from_str(s).unwrap();
+
+        assert_eq!(v("--03-02+12:00"), v("--03-01-12:00"));
+        assert!(v("--02-29") < v("--03-01"));
+        assert!(v("--02-29") < v("--03-02Z"));
+        assert_eq!(v("--02-29").partial_cmp(&v("--02-29Z")), None);
+    }
+
     #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
     #[yaserde(prefix = "t", namespace = "t: test")]
     pub struct Message {
@@ -221,7 +213,7 @@ mod tests {
             created_at: GMonthDay {
                 month: 7,
                 day: 9,
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap()),
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap()),
             },
             text: "Hello world".to_string(),
         };
@@ -240,7 +232,10 @@ mod tests {
         let m: Message = yaserde::de::from_str(s).unwrap();
         assert_eq!(m.created_at.month, 7);
         assert_eq!(m.created_at.day, 9);
-        assert_eq!(m.created_at.timezone, Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap()),);
+        assert_eq!(
+            m.created_at.timezone,
+            Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap()),
+        );
         assert_eq!(m.text, "Hello world".to_string());
     }
 }
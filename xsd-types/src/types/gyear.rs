@@ -1,25 +1,43 @@
-use std::{fmt, str::FromStr};
+use std::{cmp::Ordering, fmt, str::FromStr};
 
-use chrono::FixedOffset;
 use xsd_macro_utils::UtilsDefaultSerde;
 
-use crate::types::utils::parse_timezone;
+use crate::types::{
+    lexical::{format_year, parse_year, split_timezone},
+    timezone::Timezone,
+    utils::compare_partial_dates,
+};
 
-#[derive(PartialEq, Debug, UtilsDefaultSerde)]
+#[derive(Debug, UtilsDefaultSerde)]
 pub struct GYear {
     pub value: i32,
-    pub timezone: Option<FixedOffset>,
+    pub timezone: Option<Timezone>,
 }
 
 impl GYear {
-    pub fn new(year: i32, timezone: Option<FixedOffset>) -> Result<Self, String> {
-        if year == 0 {
-            return Err("bad gYear format: year 0 occurred".to_string());
-        }
+    // Any year is valid, 0000 is 1 BCE.
+    pub fn new(year: i32, timezone: Option<Timezone>) -> Result<Self, String> {
         Ok(GYear { value: year, timezone })
     }
 }
 
+impl PartialEq for GYear {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for GYear {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare_partial_dates(
+            (self.value, 1, 1),
+            self.timezone,
+            (other.value, 1, 1),
+            other.timezone,
+        )
+    }
+}
+
 impl Default for GYear {
     fn default() -> GYear {
         Self { value: 1, timezone: None }
@@ -30,67 +48,19 @@ impl FromStr for GYear {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some(s) = s.strip_prefix('-') {
-            let mut gyear = parse_str_positive(s)?;
-            gyear.value *= -1;
-            return Ok(gyear);
-        }
-        parse_str_positive(s)
-    }
-}
-
-fn parse_str_positive(s: &str) -> Result<GYear, String> {
-    fn parse_value(s: &str) -> Result<i32, String> {
-        if s.len() < 4 {
-            return Err("bad gYear format: to short".to_string());
-        }
-        if !s.chars().all(|c| c.is_ascii_digit()) {
-            return Err("bad gYear format".to_string());
-        }
-        s.parse::<i32>().map_err(|e| e.to_string())
-    }
-
-    if let Some(s) = s.strip_suffix('Z') {
-        return GYear::new(parse_value(s)?, Some(FixedOffset::east_opt(0).unwrap()));
-    }
-
-    if s.contains('+') {
-        if s.matches('+').count() > 1 {
-            return Err("bad gYear format".to_string());
+        let (s, timezone) = split_timezone(s)?;
+        match parse_year(s)? {
+            (year, "") => GYear::new(year, timezone),
+            _ => Err("bad gYear format".to_string()),
         }
-
-        let idx: usize = s.match_indices('+').collect::<Vec<_>>()[0].0;
-        let value_token = &s[..idx];
-        let tz_token = &s[idx..];
-        return GYear::new(parse_value(value_token)?, Some(parse_timezone(tz_token)?));
-    }
-
-    if s.contains('-') {
-        if s.matches('-').count() > 1 {
-            return Err("bad gYear format".to_string());
-        }
-
-        let idx: usize = s.match_indices('-').collect::<Vec<_>>()[0].0;
-        let value_token = &s[..idx];
-        let tz_token = &s[idx..];
-        return GYear::new(parse_value(value_token)?, Some(parse_timezone(tz_token)?));
     }
-
-    GYear::new(parse_value(s)?, None)
 }
 
 impl fmt::Display for GYear {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if self.value > 0 {
-            match self.timezone {
-                Some(tz) => write!(f, "{:04}{}", self.value, tz),
-                None => write!(f, "{:04}", self.value),
-            }
-        } else {
-            match self.timezone {
-                Some(tz) => write!(f, "-{:04}{}", -self.value, tz),
-                None => write!(f, "-{:04}", -self.value),
-            }
+        match self.timezone {
+            Some(tz) => write!(f, "{}{}", format_year(self.value), tz),
+            None => write!(f, "{}", format_year(self.value)),
         }
     }
 }
@@ -110,7 +80,7 @@ mod tests {
         // Timezone "Z".
         assert_eq!(
             GYear::from_str("2020Z"),
-            Ok(GYear { value: 2020, timezone: Some(FixedOffset::east_opt(0).unwrap()) })
+            Ok(GYear { value: 2020, timezone: Some(Timezone::UTC) })
         );
 
         // Positive offset.
@@ -118,7 +88,7 @@ mod tests {
             GYear::from_str("2020+06:30"),
             Ok(GYear {
                 value: 2020,
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap())
             })
         );
 
@@ -127,7 +97,7 @@ mod tests {
             GYear::from_str("2020-06:30"),
             Ok(GYear {
                 value: 2020,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             })
         );
 
@@ -136,7 +106,7 @@ mod tests {
             GYear::from_str("-0020-06:30"),
             Ok(GYear {
                 value: -20,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             })
         );
 
@@ -145,14 +115,17 @@ mod tests {
             GYear::from_str("-20000-06:30"),
             Ok(GYear {
                 value: -20000,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             })
         );
 
+        // Year 0000.
+        assert_eq!(GYear::from_str("0000"), Ok(GYear { value: 0, timezone: None }));
+
         // Invalid values.
         assert!(GYear::from_str("01").is_err());
         assert!(GYear::from_str("2001-12").is_err());
-        assert!(GYear::from_str("0000").is_err());
+        assert!(GYear::from_str("00000").is_err());
         assert!(GYear::from_str("+123").is_err());
     }
 
@@ -161,17 +134,14 @@ mod tests {
         // No timezone.
         assert_eq!(GYear { value: 987, timezone: None }.to_string(), "0987");
 
-        // Timezone +00:00.
-        assert_eq!(
-            GYear { value: 987, timezone: Some(FixedOffset::east_opt(0).unwrap()) }.to_string(),
-            "0987+00:00"
-        );
+        // Zero offset.
+        assert_eq!(GYear { value: 987, timezone: Some(Timezone::UTC) }.to_string(), "0987Z");
 
         // Positive offset.
         assert_eq!(
             GYear {
                 value: 987,
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap())
             }
             .to_string(),
             "0987+06:30"
@@ -181,7 +151,7 @@ mod tests {
         assert_eq!(
             GYear {
                 value: 987,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             }
             .to_string(),
             "0987-06:30"
@@ -191,7 +161,7 @@ mod tests {
         assert_eq!(
             GYear {
                 value: -987,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             }
             .to_string(),
             "-0987-06:30"
@@ -201,13 +171,23 @@ mod tests {
         assert_eq!(
             GYear {
                 value: -98765,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             }
             .to_string(),
             "-98765-06:30"
         );
     }
 
+    #[test]
+    fn gyear_order_test() {
+        let v = |s| GYear::from_str(s).unwrap();
+
+        assert_eq!(v("2000Z"), v("2000+00:00"));
+        assert!(v("-0001") < v("0001"));
+        assert!(v("2000") < v("2001Z"));
+        assert_eq!(v("2000").partial_cmp(&v("2000Z")), None);
+    }
+
     #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
     #[yaserde(prefix = "t", namespace = "t: test")]
     pub struct Message {
@@ -229,7 +209,7 @@ mod tests {
         let m = Message {
             created_at: GYear {
                 value: 2007,
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap()),
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap()),
             },
             text: "Hello world".to_string(),
         };
@@ -247,7 +227,10 @@ mod tests {
             "#;
         let m: Message = yaserde::de::from_str(s).unwrap();
         assert_eq!(m.created_at.value, 2007);
-        assert_eq!(m.created_at.timezone, Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap()),);
+        assert_eq!(
+            m.created_at.timezone,
+            Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap()),
+        );
         assert_eq!(m.text, "Hello world".to_string());
     }
 }
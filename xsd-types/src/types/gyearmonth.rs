@@ -1,23 +1,24 @@
-use std::{fmt, str::FromStr};
+use std::{cmp::Ordering, fmt, str::FromStr};
 
-use chrono::FixedOffset;
 use xsd_macro_utils::UtilsDefaultSerde;
 
-use crate::types::{gmonth::GMonth, gyear::GYear, utils::parse_timezone};
+use crate::types::{
+    gmonth::GMonth,
+    gyear::GYear,
+    lexical::{format_year, parse_two_digits, parse_year, split_timezone},
+    timezone::Timezone,
+    utils::compare_partial_dates,
+};
 
-#[derive(PartialEq, Debug, UtilsDefaultSerde)]
+#[derive(Debug, UtilsDefaultSerde)]
 pub struct GYearMonth {
     pub year: i32,
     pub month: i32,
-    pub timezone: Option<FixedOffset>,
+    pub timezone: Option<Timezone>,
 }
 
 impl GYearMonth {
-    pub fn new(year: i32, month: i32, timezone: Option<FixedOffset>) -> Result<Self, String> {
-        if year == 0 {
-            return Err("bad gYear format: year 0 occurred".to_string());
-        }
-
+    pub fn new(year: i32, month: i32, timezone: Option<Timezone>) -> Result<Self, String> {
         if !(1..=12).contains(&month) {
             return Err("Month value within GYearMonth should lie between 1 and 12".to_string());
         }
@@ -34,6 +35,23 @@ impl GYearMonth {
     }
 }
 
+impl PartialEq for GYearMonth {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for GYearMonth {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare_partial_dates(
+            (self.year, self.month, 1),
+            self.timezone,
+            (other.year, other.month, 1),
+            other.timezone,
+        )
+    }
+}
+
 impl Default for GYearMonth {
     fn default() -> GYearMonth {
         Self { year: 1, month: 1, timezone: None }
@@ -44,82 +62,21 @@ impl FromStr for GYearMonth {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some(s) = s.strip_prefix('-') {
-            let mut gyearmonth = parse_str_positive(s)?;
-            gyearmonth.year *= -1;
-            return Ok(gyearmonth);
+        let (s, timezone) = split_timezone(s)?;
+        let (year, s) = parse_year(s)?;
+        let s = s.strip_prefix('-').ok_or_else(|| "bad gYearMonth format".to_string())?;
+        match parse_two_digits(s, "month within gYearMonth")? {
+            (month, "") => GYearMonth::new(year, month as i32, timezone),
+            _ => Err("bad gYearMonth format".to_string()),
         }
-        parse_str_positive(s)
     }
 }
 
-fn parse_str_positive(s: &str) -> Result<GYearMonth, String> {
-    fn parse_value(s: &str) -> Result<(i32, i32), String> {
-        if s.matches('-').count() != 1 {
-            return Err("bad gYearMonth format".to_string());
-        }
-
-        let idx: usize = s.match_indices('-').collect::<Vec<_>>()[0].0;
-        let year_token = &s[..idx];
-        let month_token = &s[idx + 1..];
-        if year_token.len() < 4 || month_token.len() != 2 {
-            return Err("bad gYearMonth format".to_string());
-        }
-
-        if !year_token.chars().all(|c| c.is_ascii_digit()) {
-            return Err("bad year format within gYearMonth".to_string());
-        }
-        let year = year_token.parse::<i32>().map_err(|e| e.to_string())?;
-
-        if !month_token.chars().all(|c| c.is_ascii_digit()) {
-            return Err("bad month format within gYearMonth".to_string());
-        }
-        let month = month_token.parse::<i32>().map_err(|e| e.to_string())?;
-
-        Ok((year, month))
-    }
-
-    if let Some(s) = s.strip_suffix('Z') {
-        let (year, month) = parse_value(s)?;
-        return GYearMonth::new(year, month, Some(FixedOffset::east_opt(0).unwrap()));
-    }
-
-    if s.contains('+') {
-        if s.matches('+').count() > 1 {
-            return Err("bad gMonthDay format".to_string());
-        }
-
-        let idx: usize = s.match_indices('+').collect::<Vec<_>>()[0].0;
-        let value_token = &s[..idx];
-        let tz_token = &s[idx..];
-        let (year, month) = parse_value(value_token)?;
-        return GYearMonth::new(year, month, Some(parse_timezone(tz_token)?));
-    }
-
-    if s.matches('-').count() == 2 {
-        let idx: usize = s.match_indices('-').collect::<Vec<_>>()[1].0;
-        let value_token = &s[..idx];
-        let tz_token = &s[idx..];
-        let (year, month) = parse_value(value_token)?;
-        return GYearMonth::new(year, month, Some(parse_timezone(tz_token)?));
-    }
-
-    let (year, month) = parse_value(s)?;
-    GYearMonth::new(year, month, None)
-}
-
 impl fmt::Display for GYearMonth {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if self.year > 0 {
-            match self.timezone {
-                Some(tz) => write!(f, "{:04}-{:02}{}", self.year, self.month, tz),
-                None => write!(f, "{:04}-{:02}", self.year, self.month),
-            }
-        } else {
-            match self.timezone {
-                Some(tz) => write!(f, "-{:04}-{:02}{}", -self.year, self.month, tz),
-                None => write!(f, "-{:04}-{:02}", -self.year, self.month),
-            }
+        match self.timezone {
+            Some(tz) => write!(f, "{}-{:02}{}", format_year(self.year), self.month, tz),
+            None => write!(f, "{}-{:02}", format_year(self.year), self.month),
         }
     }
 }
@@ -142,11 +99,7 @@ mod tests {
         // Timezone "Z".
         assert_eq!(
             GYearMonth::from_str("2020-03Z"),
-            Ok(GYearMonth {
-                year: 2020,
-                month: 3,
-                timezone: Some(FixedOffset::east_opt(0).unwrap())
-            })
+            Ok(GYearMonth { year: 2020, month: 3, timezone: Some(Timezone::UTC) })
         );
 
         // Positive offset.
@@ -155,7 +108,7 @@ mod tests {
             Ok(GYearMonth {
                 year: 2020,
                 month: 3,
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap())
             })
         );
 
@@ -165,7 +118,7 @@ mod tests {
             Ok(GYearMonth {
                 year: 2020,
                 month: 3,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             })
         );
 
@@ -175,7 +128,7 @@ mod tests {
             Ok(GYearMonth {
                 year: -20,
                 month: 3,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             })
         );
 
@@ -185,16 +138,22 @@ mod tests {
             Ok(GYearMonth {
                 year: -20000,
                 month: 3,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             })
         );
 
+        // Year 0000.
+        assert_eq!(
+            GYearMonth::from_str("0000-03"),
+            Ok(GYearMonth { year: 0, month: 3, timezone: None })
+        );
+
         // Invalid values.
         assert!(GYearMonth::from_str("01-03").is_err());
         assert!(GYearMonth::from_str("2000-1").is_err());
         assert!(GYearMonth::from_str("2000-13").is_err());
         assert!(GYearMonth::from_str("2000-00").is_err());
-        assert!(GYearMonth::from_str("0000-03").is_err());
+        assert!(GYearMonth::from_str("00000-03").is_err());
         assert!(GYearMonth::from_str("2000-+3").is_err());
         assert!(GYearMonth::from_str("-200-03").is_err());
         assert!(GYearMonth::from_str("+200-03").is_err());
@@ -206,11 +165,10 @@ mod tests {
         // No timezone.
         assert_eq!(GYearMonth { year: 987, month: 6, timezone: None }.to_string(), "0987-06");
 
-        // Timezone +00:00.
+        // Zero offset.
         assert_eq!(
-            GYearMonth { year: 987, month: 6, timezone: Some(FixedOffset::east_opt(0).unwrap()) }
-                .to_string(),
-            "0987-06+00:00"
+            GYearMonth { year: 987, month: 6, timezone: Some(Timezone::UTC) }.to_string(),
+            "0987-06Z"
         );
 
         // Positive offset.
@@ -218,7 +176,7 @@ mod tests {
             GYearMonth {
                 year: 987,
                 month: 6,
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap())
             }
             .to_string(),
             "0987-06+06:30"
@@ -229,7 +187,7 @@ mod tests {
             GYearMonth {
                 year: 987,
                 month: 6,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             }
             .to_string(),
             "0987-06-06:30"
@@ -240,7 +198,7 @@ mod tests {
             GYearMonth {
                 year: -987,
                 month: 6,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             }
             .to_string(),
             "-0987-06-06:30"
@@ -251,13 +209,23 @@ mod tests {
             GYearMonth {
                 year: -98765,
                 month: 6,
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             }
             .to_string(),
             "-98765-06-06:30"
         );
     }
 
+    #[test]
+    fn gyearmonth_order_test() {
+        let v = |s| GYearMonth::from_str(s).unwrap();
+
+        assert_eq!(v("2000-03Z"), v("2000-03+00:00"));
+        assert!(v("2000-02") < v("2000-03"));
+        assert!(v("1999-12") < v("2000-01-14:00"));
+        assert_eq!(v("2000-02").partial_cmp(&v("2000-02Z")), None);
+    }
+
     #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
     #[yaserde(prefix = "t", namespace = "t: test")]
     pub struct Message {
@@ -280,7 +248,7 @@ mod tests {
             created_at: GYearMonth {
                 year: 2007,
                 month: 2,
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap()),
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap()),
             },
             text: "Hello world".to_string(),
         };
@@ -299,7 +267,10 @@ mod tests {
         let m: Message = yaserde::de::from_str(s).unwrap();
         assert_eq!(m.created_at.year, 2007);
         assert_eq!(m.created_at.month, 2);
-        assert_eq!(m.created_at.timezone, Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap()),);
+        assert_eq!(
+            m.created_at.timezone,
+            Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap()),
+        );
         assert_eq!(m.text, "Hello world".to_string());
     }
 }
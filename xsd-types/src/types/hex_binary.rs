@@ -0,0 +1,115 @@
+use std::{fmt, str::FromStr};
+
+use xsd_macro_utils::UtilsDefaultSerde;
+
+// Length facets of hexBinary count octets, which is what len() returns.
+#[derive(Default, Clone, PartialEq, Eq, Debug, UtilsDefaultSerde)]
+pub struct HexBinary(pub Vec<u8>);
+
+impl HexBinary {
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        HexBinary(bytes)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for HexBinary {
+    type Err = String;
+
+    // Whitespace is ignored, since values are often wrapped over several lines.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_digit(16).ok_or_else(|| format!("bad hexBinary format: '{}'", c)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if digits.len() % 2 != 0 {
+            return Err("bad hexBinary format: odd number of digits".to_string());
+        }
+
+        Ok(HexBinary(digits.chunks(2).map(|pair| (pair[0] * 16 + pair[1]) as u8).collect()))
+    }
+}
+
+impl fmt::Display for HexBinary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02X}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    use super::*;
+    use crate::utils::xml_eq::assert_xml_eq;
+
+    #[test]
+    fn hex_binary_parse_test() {
+        assert_eq!(HexBinary::from_str("0FB7"), Ok(HexBinary(vec![0x0f, 0xb7])));
+        assert_eq!(HexBinary::from_str("0fb7"), Ok(HexBinary(vec![0x0f, 0xb7])));
+        assert_eq!(HexBinary::from_str(" 0F\n B7 "), Ok(HexBinary(vec![0x0f, 0xb7])));
+        assert_eq!(HexBinary::from_str(""), Ok(HexBinary(vec![])));
+
+        assert!(HexBinary::from_str("0FB").is_err());
+        assert!(HexBinary::from_str("0G").is_err());
+    }
+
+    #[test]
+    fn hex_binary_display_test() {
+        assert_eq!(HexBinary(vec![0x0f, 0xb7]).to_string(), "0FB7");
+        assert_eq!(HexBinary(vec![]).to_string(), "");
+        assert_eq!(HexBinary(vec![0x0f, 0xb7]).len(), 2);
+    }
+
+    #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: test")]
+    pub struct Message {
+        #[yaserde(prefix = "t", rename = "Digest")]
+        pub digest: HexBinary,
+
+        #[yaserde(prefix = "t", rename = "Text")]
+        pub text: String,
+    }
+
+    #[test]
+    fn hex_binary_serialize_test() {
+        let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Digest>0FB7</t:Digest>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m = Message { digest: HexBinary(vec![0x0f, 0xb7]), text: "Hello world".to_string() };
+        let actual = yaserde::ser::to_string(&m).unwrap();
+        assert_xml_eq(&actual, expected);
+    }
+
+    #[test]
+    fn hex_binary_deserialize_test() {
+        let s = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Digest>0fb7</t:Digest>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m: Message = yaserde::de::from_str(s).unwrap();
+        assert_eq!(m.digest, HexBinary(vec![0x0f, 0xb7]));
+        assert_eq!(m.text, "Hello world".to_string());
+    }
+}
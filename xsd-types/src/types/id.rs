@@ -0,0 +1,21 @@
+use crate::types::utils::{collapse_whitespace, is_ncname};
+
+// https://www.w3.org/TR/xmlschema-2/#ID
+string_type!(Id, collapse_whitespace, is_ncname, "ID");
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn id_parse_test() {
+        assert_eq!(Id::from_str(" id1 "), Ok(Id("id1".to_string())));
+
+        // Invalid values.
+        assert!(Id::from_str("").is_err());
+        assert!(Id::from_str("1").is_err());
+        assert!(Id::from_str("a:b").is_err());
+    }
+}
@@ -0,0 +1,20 @@
+use crate::types::utils::{collapse_whitespace, is_ncname};
+
+// https://www.w3.org/TR/xmlschema-2/#IDREF
+string_type!(IdRef, collapse_whitespace, is_ncname, "IDREF");
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn idref_parse_test() {
+        assert_eq!(IdRef::from_str(" id1 "), Ok(IdRef("id1".to_string())));
+
+        // Invalid values.
+        assert!(IdRef::from_str("").is_err());
+        assert!(IdRef::from_str("a b").is_err());
+    }
+}
@@ -1,10 +1,15 @@
-use std::{fmt, str::FromStr};
+use std::{
+    convert::TryFrom,
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+    str::FromStr,
+};
 
 use num_bigint::{BigInt, ParseBigIntError, ToBigInt};
 use xsd_macro_utils::UtilsDefaultSerde;
 
 // https://www.w3.org/TR/xmlschema-2/#integer
-#[derive(Default, PartialEq, PartialOrd, Debug, UtilsDefaultSerde)]
+#[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, UtilsDefaultSerde)]
 pub struct Integer(pub BigInt);
 
 impl Integer {
@@ -19,6 +24,66 @@ impl ToBigInt for Integer {
     }
 }
 
+macro_rules! impl_from_primitive {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Integer {
+            fn from(value: $t) -> Self {
+                Integer(BigInt::from(value))
+            }
+        })*
+    };
+}
+
+impl_from_primitive!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl TryFrom<&Integer> for i64 {
+    type Error = String;
+
+    fn try_from(value: &Integer) -> Result<Self, Self::Error> {
+        i64::try_from(&value.0).map_err(|_| format!("{} is out of range for i64", value))
+    }
+}
+
+impl TryFrom<&Integer> for u64 {
+    type Error = String;
+
+    fn try_from(value: &Integer) -> Result<Self, Self::Error> {
+        u64::try_from(&value.0).map_err(|_| format!("{} is out of range for u64", value))
+    }
+}
+
+impl Add for Integer {
+    type Output = Integer;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Integer(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Integer {
+    type Output = Integer;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Integer(self.0 - rhs.0)
+    }
+}
+
+impl Mul for Integer {
+    type Output = Integer;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Integer(self.0 * rhs.0)
+    }
+}
+
+impl Neg for Integer {
+    type Output = Integer;
+
+    fn neg(self) -> Self::Output {
+        Integer(-self.0)
+    }
+}
+
 impl FromStr for Integer {
     type Err = ParseBigIntError;
 
@@ -74,6 +139,27 @@ mod tests {
         assert_eq!(Integer((-1).to_bigint().unwrap()).to_string(), "-1");
     }
 
+    #[test]
+    fn integer_conversion_test() {
+        assert_eq!(Integer::from(-5i64), Integer((-5).to_bigint().unwrap()));
+        assert_eq!(Integer::from(u64::MAX).to_string(), "18446744073709551615");
+
+        assert_eq!(i64::try_from(&Integer::from(-5)), Ok(-5));
+        assert!(i64::try_from(&Integer::from(u64::MAX)).is_err());
+        assert_eq!(u64::try_from(&Integer::from(u64::MAX)), Ok(u64::MAX));
+        assert!(u64::try_from(&Integer::from(-1)).is_err());
+    }
+
+    #[test]
+    fn integer_arithmetic_test() {
+        let big = Integer::from(i64::MAX);
+        assert_eq!((big.clone() + big.clone()).to_string(), "18446744073709551614");
+        assert_eq!(Integer::from(2) - Integer::from(5), Integer::from(-3));
+        assert_eq!(big.clone() * Integer::from(-1), -big);
+        assert!(Integer::from(-3) < Integer::from(2));
+        assert_eq!(Integer::from(7).max(Integer::from(3)), Integer::from(7));
+    }
+
     #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
     #[yaserde(prefix = "t", namespace = "t: test")]
     pub struct IntegerPair {
@@ -0,0 +1,39 @@
+use crate::types::utils::collapse_whitespace;
+
+// https://www.w3.org/TR/xmlschema-2/#language
+string_type!(Language, collapse_whitespace, is_language, "language");
+
+// [a-zA-Z]{1,8}(-[a-zA-Z0-9]{1,8})*
+fn is_language(s: &str) -> bool {
+    s.split('-').enumerate().all(|(i, part)| {
+        (1..=8).contains(&part.len())
+            && part.chars().all(|c| {
+                if i == 0 {
+                    c.is_ascii_alphabetic()
+                } else {
+                    c.is_ascii_alphanumeric()
+                }
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn language_parse_test() {
+        assert_eq!(Language::from_str(" en-US "), Ok(Language("en-US".to_string())));
+        assert_eq!(Language::from_str("i-klingon"), Ok(Language("i-klingon".to_string())));
+        assert_eq!(Language::from_str("de-1996"), Ok(Language("de-1996".to_string())));
+
+        // Invalid values.
+        assert!(Language::from_str("").is_err());
+        assert!(Language::from_str("en_US").is_err());
+        assert!(Language::from_str("1996-de").is_err());
+        assert!(Language::from_str("toolongtag").is_err());
+        assert!(Language::from_str("en-").is_err());
+    }
+}
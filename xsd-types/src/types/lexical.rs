@@ -0,0 +1,191 @@
+use crate::types::{
+    naive::{NaiveDate, NaiveDateTime, NaiveTime},
+    timezone::Timezone,
+    utils::{format_fraction, parse_fraction, parse_timezone},
+};
+
+// Lexical and canonical mappings of XSD 1.1 Appendix D.3 for the date and time types.
+
+// Splits the optional time zone off the end of the value.
+pub(crate) fn split_timezone(s: &str) -> Result<(&str, Option<Timezone>), String> {
+    if let Some(value) = s.strip_suffix('Z') {
+        return Ok((value, Some(Timezone::UTC)));
+    }
+
+    let bytes = s.as_bytes();
+    let len = bytes.len();
+    if len > 6 && matches!(bytes[len - 6], b'+' | b'-') && bytes[len - 3] == b':' {
+        return Ok((&s[..len - 6], Some(parse_timezone(&s[len - 6..])?)));
+    }
+
+    Ok((s, None))
+}
+
+// Parses the year at the start of the value: at least four digits, without a leading zero
+// when there are more, optionally preceded by a minus sign. Year 0000 is 1 BCE.
+pub(crate) fn parse_year(s: &str) -> Result<(i32, &str), String> {
+    let (is_negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+
+    let len = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (digits, rest) = s.split_at(len);
+    if digits.len() < 4 || (digits.len() > 4 && digits.starts_with('0')) {
+        return Err("bad year format".to_string());
+    }
+
+    let year = digits.parse::<i32>().map_err(|e| e.to_string())?;
+    Ok((if is_negative { -year } else { year }, rest))
+}
+
+pub(crate) fn format_year(year: i32) -> String {
+    if year < 0 {
+        format!("-{:04}", -(year as i64))
+    } else {
+        format!("{:04}", year)
+    }
+}
+
+// Parses the two digits at the start of the value, e.g. the month of --12-20.
+pub(crate) fn parse_two_digits<'a>(s: &'a str, name: &str) -> Result<(u32, &'a str), String> {
+    match s.get(..2) {
+        Some(digits) if digits.chars().all(|c| c.is_ascii_digit()) => {
+            Ok((digits.parse().unwrap(), &s[2..]))
+        }
+        _ => Err(format!("bad {} format", name)),
+    }
+}
+
+pub(crate) fn parse_date(s: &str) -> Result<(NaiveDate, Option<Timezone>), String> {
+    let (s, timezone) = split_timezone(s)?;
+    let (date, rest) = parse_date_fragments(s)?;
+    if !rest.is_empty() {
+        return Err("bad date format".to_string());
+    }
+
+    Ok((date, timezone))
+}
+
+pub(crate) fn format_date(date: NaiveDate) -> String {
+    format!("{}-{:02}-{:02}", format_year(date.year()), date.month(), date.day())
+}
+
+// 24:00:00 is the same value as 00:00:00.
+pub(crate) fn parse_time(s: &str) -> Result<(NaiveTime, Option<Timezone>), String> {
+    let (s, timezone) = split_timezone(s)?;
+    let (time, _) = parse_time_fragments(s)?;
+    Ok((time, timezone))
+}
+
+pub(crate) fn format_time(time: NaiveTime) -> String {
+    format!(
+        "{:02}:{:02}:{:02}{}",
+        time.hour(),
+        time.minute(),
+        time.second(),
+        format_fraction(time.nanosecond())
+    )
+}
+
+// 24:00:00 is the first moment of the next day.
+pub(crate) fn parse_datetime(s: &str) -> Result<(NaiveDateTime, Option<Timezone>), String> {
+    let (s, timezone) = split_timezone(s)?;
+    let (date, s) = parse_date_fragments(s)?;
+    let s = s.strip_prefix('T').ok_or_else(|| "bad dateTime format".to_string())?;
+    let (time, is_end_of_day) = parse_time_fragments(s)?;
+    let date = if is_end_of_day {
+        NaiveDate::from_days(date.days() + 1).ok_or_else(|| "date out of range".to_string())?
+    } else {
+        date
+    };
+
+    Ok((date.and_time(time), timezone))
+}
+
+pub(crate) fn format_datetime(datetime: NaiveDateTime) -> String {
+    format!("{}T{}", format_date(datetime.date()), format_time(datetime.time()))
+}
+
+fn parse_date_fragments(s: &str) -> Result<(NaiveDate, &str), String> {
+    let (year, s) = parse_year(s)?;
+    let s = s.strip_prefix('-').ok_or_else(|| "bad date format".to_string())?;
+    let (month, s) = parse_two_digits(s, "month")?;
+    let s = s.strip_prefix('-').ok_or_else(|| "bad date format".to_string())?;
+    let (day, s) = parse_two_digits(s, "day")?;
+
+    let date = NaiveDate::from_ymd(year, month, day)
+        .ok_or_else(|| "bad date format: out of range".to_string())?;
+    Ok((date, s))
+}
+
+// Also returns whether the time is the end of the day, 24:00:00.
+fn parse_time_fragments(s: &str) -> Result<(NaiveTime, bool), String> {
+    let (hour, s) = parse_two_digits(s, "hour")?;
+    let s = s.strip_prefix(':').ok_or_else(|| "bad time format".to_string())?;
+    let (minute, s) = parse_two_digits(s, "minute")?;
+    let s = s.strip_prefix(':').ok_or_else(|| "bad time format".to_string())?;
+    let (second, s) = parse_two_digits(s, "second")?;
+    let nanosecond = match s.strip_prefix('.') {
+        Some(fraction) => parse_fraction(fraction)?,
+        None if s.is_empty() => 0,
+        None => return Err("bad time format".to_string()),
+    };
+
+    if hour == 24 && minute == 0 && second == 0 && nanosecond == 0 {
+        return Ok((NaiveTime::MIDNIGHT, true));
+    }
+
+    let time = NaiveTime::from_hms_nano(hour, minute, second, nanosecond)
+        .ok_or_else(|| "bad time format: out of range".to_string())?;
+    Ok((time, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn year_test() {
+        assert_eq!(parse_year("2020"), Ok((2020, "")));
+        assert_eq!(parse_year("-0044-03"), Ok((-44, "-03")));
+        assert_eq!(parse_year("10000"), Ok((10000, "")));
+        assert!(parse_year("020").is_err());
+        assert!(parse_year("02020").is_err());
+        assert_eq!(parse_year("0000"), Ok((0, "")));
+        assert_eq!(parse_year("-0000"), Ok((0, "")));
+        assert!(parse_year("+2020").is_err());
+
+        assert_eq!(format_year(44), "0044");
+        assert_eq!(format_year(-44), "-0044");
+        assert_eq!(format_year(10000), "10000");
+        assert_eq!(format_year(0), "0000");
+    }
+
+    #[test]
+    fn datetime_lexical_test() {
+        let parse = |s| {
+            let (value, timezone) = parse_datetime(s).unwrap();
+            format!(
+                "{}{}",
+                format_datetime(value),
+                timezone.map(|tz| tz.to_string()).unwrap_or_default()
+            )
+        };
+
+        assert_eq!(parse("-0044-03-15T12:00:00"), "-0044-03-15T12:00:00");
+        assert_eq!(parse("10000-01-01T00:00:00Z"), "10000-01-01T00:00:00Z");
+        assert_eq!(parse("2000-01-01T24:00:00"), "2000-01-02T00:00:00");
+        assert_eq!(parse("1999-12-31T24:00:00.000-05:00"), "2000-01-01T00:00:00-05:00");
+        assert_eq!(parse("2000-01-01T12:00:00.50+14:00"), "2000-01-01T12:00:00.5+14:00");
+        assert_eq!(parse("0000-12-31T24:00:00-00:00"), "0001-01-01T00:00:00Z");
+
+        assert!(parse_datetime("2000-01-01T24:00:01").is_err());
+        assert!(parse_datetime("2000-01-01T24:30:00").is_err());
+        assert!(parse_datetime("2000-02-30T00:00:00").is_err());
+        assert!(parse_datetime("2000-01-01 00:00:00").is_err());
+        assert!(parse_datetime("2000-01-01T00:00").is_err());
+        assert!(parse_datetime("2000-01-01T00:00:00+15:00").is_err());
+        assert!(parse_datetime("02000-01-01T00:00:00").is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    fmt,
+    io::{Read, Write},
+    iter::FromIterator,
+    str::FromStr,
+};
+
+use xml::{attribute::OwnedAttribute, namespace::Namespace};
+use yaserde::{de, ser, YaDeserialize, YaSerialize};
+
+// https://www.w3.org/TR/xmlschema-2/#atomic-vs-list
+// Items are separated by whitespace, so length facets of a list type count items.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct XsdList<T>(pub Vec<T>);
+
+impl<T> XsdList<T> {
+    pub fn from_vec(items: Vec<T>) -> Self {
+        XsdList(items)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.0.iter()
+    }
+}
+
+impl<T> Default for XsdList<T> {
+    fn default() -> Self {
+        XsdList(Vec::new())
+    }
+}
+
+impl<T> From<Vec<T>> for XsdList<T> {
+    fn from(items: Vec<T>) -> Self {
+        XsdList(items)
+    }
+}
+
+impl<T> FromIterator<T> for XsdList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        XsdList(iter.into_iter().collect())
+    }
+}
+
+impl<T> IntoIterator for XsdList<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<T: FromStr> FromStr for XsdList<T>
+where
+    T::Err: fmt::Display,
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split([' ', '\t', '\n', '\r'])
+            .filter(|item| !item.is_empty())
+            .map(|item| T::from_str(item).map_err(|e| format!("bad list item '{}': {}", item, e)))
+            .collect()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for XsdList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut items = self.0.iter();
+        if let Some(item) = items.next() {
+            write!(f, "{}", item)?;
+        }
+        for item in items {
+            write!(f, " {}", item)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: fmt::Display> YaSerialize for XsdList<T> {
+    fn serialize<W: Write>(&self, writer: &mut ser::Serializer<W>) -> Result<(), String> {
+        crate::utils::yaserde::serialize(self, "XsdList", writer, |s| s.to_string())
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<OwnedAttribute>,
+        namespace: Namespace,
+    ) -> Result<(Vec<OwnedAttribute>, Namespace), String> {
+        Ok((attributes, namespace))
+    }
+}
+
+impl<T: FromStr> YaDeserialize for XsdList<T>
+where
+    T::Err: fmt::Display,
+{
+    fn deserialize<R: Read>(reader: &mut de::Deserializer<R>) -> Result<Self, String> {
+        crate::utils::yaserde::deserialize(reader, XsdList::from_str)
+    }
+}
+
+impl<T: FromStr> popper::XmlDeserialize for XsdList<T>
+where
+    T::Err: fmt::Display,
+{
+    fn xml_deserialize(popper: &mut popper::XmlPopper) -> Result<Self, popper::DeError> {
+        Ok(XsdList::from_str(popper.pop_value()?.as_str())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    use super::*;
+    use crate::{types::NmToken, utils::xml_eq::assert_xml_eq};
+
+    #[test]
+    fn list_parse_test() {
+        assert_eq!(XsdList::<i32>::from_str("1 2 3"), Ok(XsdList(vec![1, 2, 3])));
+        assert_eq!(XsdList::<i32>::from_str("\n  1\t2  \r\n3 "), Ok(XsdList(vec![1, 2, 3])));
+        assert_eq!(XsdList::<i32>::from_str(" "), Ok(XsdList(vec![])));
+        assert_eq!(XsdList::<NmToken>::from_str("a b").map(|list| list.len()), Ok(2));
+
+        // Invalid values.
+        assert!(XsdList::<i32>::from_str("1 a 3").is_err());
+        assert!(XsdList::<i32>::from_str("1,2").is_err());
+    }
+
+    #[test]
+    fn list_display_test() {
+        assert_eq!(XsdList(vec![1, 2, 3]).to_string(), "1 2 3");
+        assert_eq!(XsdList::<i32>(vec![]).to_string(), "");
+    }
+
+    #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: test")]
+    pub struct Message {
+        #[yaserde(prefix = "t", rename = "Values")]
+        pub values: XsdList<i32>,
+
+        #[yaserde(prefix = "t", rename = "Text")]
+        pub text: String,
+    }
+
+    #[test]
+    fn list_serialize_test() {
+        let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Values>1 2 3</t:Values>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m = Message { values: XsdList(vec![1, 2, 3]), text: "Hello world".to_string() };
+        let actual = yaserde::ser::to_string(&m).unwrap();
+        assert_xml_eq(&actual, expected);
+    }
+
+    #[test]
+    fn list_deserialize_test() {
+        let s = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Values>
+                    1 2
+                    3
+                </t:Values>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m: Message = yaserde::de::from_str(s).unwrap();
+        assert_eq!(m.values, XsdList(vec![1, 2, 3]));
+        assert_eq!(m.text, "Hello world".to_string());
+    }
+}
@@ -1,3 +1,10 @@
+// Declared first, the string types below are generated with its macro.
+#[macro_use]
+mod string_type;
+pub mod any_uri;
+pub use any_uri::*;
+pub mod base64_binary;
+pub use base64_binary::*;
 pub mod date;
 pub use date::*;
 pub mod datetime;
@@ -6,8 +13,12 @@ pub mod datetimestamp;
 pub use datetimestamp::*;
 pub mod decimal;
 pub use decimal::*;
+pub mod double;
+pub use double::*;
 pub mod duration;
 pub use duration::*;
+pub mod float;
+pub use float::*;
 pub mod gday;
 pub use gday::*;
 pub mod gmonth;
@@ -18,17 +29,44 @@ pub mod gyear;
 pub use gyearmonth::*;
 pub mod gyearmonth;
 pub use gyear::*;
+pub mod hex_binary;
+pub use hex_binary::*;
+pub mod id;
+pub use id::*;
+pub mod idref;
+pub use idref::*;
 pub mod integer;
 pub use integer::*;
+pub mod language;
+pub use language::*;
+mod lexical;
+pub mod list;
+pub use list::*;
+pub mod naive;
+pub use naive::*;
+pub mod name;
+pub use name::*;
+pub mod ncname;
+pub use ncname::*;
 pub mod negative_integer;
 pub use negative_integer::*;
+pub mod nmtoken;
+pub use nmtoken::*;
 pub mod non_negative_integer;
 pub use non_negative_integer::*;
 pub mod non_positive_integer;
 pub use non_positive_integer::*;
+pub mod normalized_string;
+pub use normalized_string::*;
 pub mod positive_integer;
 pub use positive_integer::*;
+pub mod qname;
+pub use qname::*;
 pub mod time;
 pub use time::*;
+pub mod timezone;
+pub use timezone::*;
+pub mod token;
+pub use token::*;
 pub mod utils;
 pub use utils::*;
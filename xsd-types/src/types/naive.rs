@@ -0,0 +1,289 @@
+use std::convert::TryFrom;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const NANOS_PER_DAY: i128 = 86400 * NANOS_PER_SECOND;
+
+// Local date and time values of Date, Time and DateTime, without a time zone.
+// Dates follow the proleptic Gregorian calendar with the year numbering of XSD 1.1,
+// so year 0 is 1 BCE.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NaiveDate {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl NaiveDate {
+    // None if the month or the day does not exist.
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+
+        Some(NaiveDate { year, month, day })
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    pub fn and_time(self, time: NaiveTime) -> NaiveDateTime {
+        NaiveDateTime { date: self, time }
+    }
+
+    pub fn and_hms(self, hour: u32, minute: u32, second: u32) -> Option<NaiveDateTime> {
+        Some(self.and_time(NaiveTime::from_hms(hour, minute, second)?))
+    }
+
+    // Days since 1970-01-01, after days_from_civil of
+    // https://howardhinnant.github.io/date_algorithms.html
+    pub(crate) fn days(&self) -> i64 {
+        let year = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let day_of_year = (153 * ((self.month as i64 + 9) % 12) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146097 + day_of_era - 719468
+    }
+
+    // Inverse of days, None if the year does not fit.
+    pub(crate) fn from_days(days: i64) -> Option<NaiveDate> {
+        let days = days.checked_add(719468)?;
+        let era = days.div_euclid(146097);
+        let day_of_era = days.rem_euclid(146097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Some(NaiveDate { year: i32::try_from(year).ok()?, month, day })
+    }
+}
+
+pub(crate) fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+pub(crate) fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// 24:00:00 is not a time of its own, the parsers turn it into 00:00:00 of the next day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NaiveTime {
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanosecond: u32,
+}
+
+impl NaiveTime {
+    pub const MIDNIGHT: NaiveTime = NaiveTime { hour: 0, minute: 0, second: 0, nanosecond: 0 };
+
+    pub fn from_hms(hour: u32, minute: u32, second: u32) -> Option<NaiveTime> {
+        NaiveTime::from_hms_nano(hour, minute, second, 0)
+    }
+
+    pub fn from_hms_nano(
+        hour: u32,
+        minute: u32,
+        second: u32,
+        nanosecond: u32,
+    ) -> Option<NaiveTime> {
+        if hour >= 24 || minute >= 60 || second >= 60 || nanosecond >= 1_000_000_000 {
+            return None;
+        }
+
+        Some(NaiveTime { hour, minute, second, nanosecond })
+    }
+
+    pub fn hour(&self) -> u32 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u32 {
+        self.minute
+    }
+
+    pub fn second(&self) -> u32 {
+        self.second
+    }
+
+    pub fn nanosecond(&self) -> u32 {
+        self.nanosecond
+    }
+
+    fn nanos_of_day(&self) -> i128 {
+        let seconds = (self.hour * 3600 + self.minute * 60 + self.second) as i128;
+        seconds * NANOS_PER_SECOND + self.nanosecond as i128
+    }
+
+    fn from_nanos_of_day(nanos: i128) -> NaiveTime {
+        let seconds = (nanos / NANOS_PER_SECOND) as u32;
+        NaiveTime {
+            hour: seconds / 3600,
+            minute: seconds / 60 % 60,
+            second: seconds % 60,
+            nanosecond: (nanos % NANOS_PER_SECOND) as u32,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NaiveDateTime {
+    date: NaiveDate,
+    time: NaiveTime,
+}
+
+impl NaiveDateTime {
+    pub fn new(date: NaiveDate, time: NaiveTime) -> NaiveDateTime {
+        NaiveDateTime { date, time }
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn time(&self) -> NaiveTime {
+        self.time
+    }
+
+    // Nanoseconds since 1970-01-01T00:00:00.
+    pub(crate) fn timestamp_nanos(&self) -> i128 {
+        self.date.days() as i128 * NANOS_PER_DAY + self.time.nanos_of_day()
+    }
+
+    pub(crate) fn from_timestamp_nanos(nanos: i128) -> Option<NaiveDateTime> {
+        let days = i64::try_from(nanos.div_euclid(NANOS_PER_DAY)).ok()?;
+        Some(NaiveDateTime {
+            date: NaiveDate::from_days(days)?,
+            time: NaiveTime::from_nanos_of_day(nanos.rem_euclid(NANOS_PER_DAY)),
+        })
+    }
+
+    pub(crate) fn checked_add_nanos(&self, nanos: i128) -> Option<NaiveDateTime> {
+        NaiveDateTime::from_timestamp_nanos(self.timestamp_nanos().checked_add(nanos)?)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDate> for NaiveDate {
+    fn from(date: chrono::NaiveDate) -> Self {
+        use chrono::Datelike;
+
+        NaiveDate { year: date.year(), month: date.month(), day: date.day() }
+    }
+}
+
+// chrono covers years -262143 to 262142 only.
+#[cfg(feature = "chrono")]
+impl TryFrom<NaiveDate> for chrono::NaiveDate {
+    type Error = String;
+
+    fn try_from(date: NaiveDate) -> Result<Self, Self::Error> {
+        chrono::NaiveDate::from_ymd_opt(date.year, date.month, date.day)
+            .ok_or_else(|| format!("year {} is out of the range of chrono", date.year))
+    }
+}
+
+// XSD has no leap seconds, chrono's are kept as the last nanosecond of the minute.
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveTime> for NaiveTime {
+    fn from(time: chrono::NaiveTime) -> Self {
+        use chrono::Timelike;
+
+        NaiveTime {
+            hour: time.hour(),
+            minute: time.minute(),
+            second: time.second(),
+            nanosecond: time.nanosecond().min(999_999_999),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<NaiveTime> for chrono::NaiveTime {
+    fn from(time: NaiveTime) -> Self {
+        chrono::NaiveTime::from_hms_nano_opt(time.hour, time.minute, time.second, time.nanosecond)
+            .unwrap()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDateTime> for NaiveDateTime {
+    fn from(datetime: chrono::NaiveDateTime) -> Self {
+        NaiveDateTime { date: datetime.date().into(), time: datetime.time().into() }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<NaiveDateTime> for chrono::NaiveDateTime {
+    type Error = String;
+
+    fn try_from(datetime: NaiveDateTime) -> Result<Self, Self::Error> {
+        Ok(chrono::NaiveDate::try_from(datetime.date)?.and_time(datetime.time.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_test() {
+        assert!(NaiveDate::from_ymd(2000, 2, 29).is_some());
+        assert!(NaiveDate::from_ymd(1900, 2, 29).is_none());
+        assert!(NaiveDate::from_ymd(0, 2, 29).is_some());
+        assert!(NaiveDate::from_ymd(2000, 13, 1).is_none());
+        assert!(NaiveDate::from_ymd(2000, 4, 31).is_none());
+
+        for &(year, month, day) in &[(1970, 1, 1), (2000, 2, 29), (0, 1, 1), (-44, 3, 15)] {
+            let date = NaiveDate::from_ymd(year, month, day).unwrap();
+            assert_eq!(NaiveDate::from_days(date.days()), Some(date));
+        }
+        assert_eq!(NaiveDate::from_ymd(1970, 1, 1).unwrap().days(), 0);
+        assert_eq!(NaiveDate::from_ymd(2000, 3, 1).unwrap().days(), 11017);
+        assert_eq!(NaiveDate::from_ymd(1, 1, 1).unwrap().days(), -719162);
+    }
+
+    #[test]
+    fn datetime_test() {
+        let dt = |y, m, d, h| NaiveDate::from_ymd(y, m, d).unwrap().and_hms(h, 0, 0).unwrap();
+
+        assert_eq!(
+            dt(1999, 12, 31, 23).checked_add_nanos(3600 * NANOS_PER_SECOND),
+            Some(dt(2000, 1, 1, 0))
+        );
+        assert_eq!(dt(0, 1, 1, 0).checked_add_nanos(-NANOS_PER_DAY), Some(dt(-1, 12, 31, 0)));
+        assert!(dt(2000, 1, 1, 0) < dt(2000, 1, 1, 1));
+        assert!(dt(-1, 12, 31, 23) < dt(0, 1, 1, 0));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_test() {
+        let date = NaiveDate::from_ymd(-44, 3, 15).unwrap();
+        assert_eq!(NaiveDate::from(chrono::NaiveDate::try_from(date).unwrap()), date);
+        assert!(chrono::NaiveDate::try_from(NaiveDate::from_ymd(300000, 1, 1).unwrap()).is_err());
+
+        let time = NaiveTime::from_hms_nano(23, 59, 59, 1).unwrap();
+        assert_eq!(NaiveTime::from(chrono::NaiveTime::from(time)), time);
+    }
+}
@@ -0,0 +1,22 @@
+use crate::types::utils::{collapse_whitespace, is_name};
+
+// https://www.w3.org/TR/xmlschema-2/#Name
+string_type!(Name, collapse_whitespace, is_name, "Name");
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn name_parse_test() {
+        assert_eq!(Name::from_str(" xs:element "), Ok(Name("xs:element".to_string())));
+        assert_eq!(Name::from_str("_a-b.c1"), Ok(Name("_a-b.c1".to_string())));
+
+        // Invalid values.
+        assert!(Name::from_str("").is_err());
+        assert!(Name::from_str("1a").is_err());
+        assert!(Name::from_str("a b").is_err());
+    }
+}
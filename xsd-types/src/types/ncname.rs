@@ -0,0 +1,60 @@
+use crate::types::utils::{collapse_whitespace, is_ncname};
+
+// https://www.w3.org/TR/xmlschema-2/#NCName
+string_type!(NcName, collapse_whitespace, is_ncname, "NCName");
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    use super::*;
+    use crate::utils::xml_eq::assert_xml_eq;
+
+    #[test]
+    fn ncname_parse_test() {
+        assert_eq!(NcName::from_str(" element "), Ok(NcName("element".to_string())));
+
+        // Invalid values.
+        assert!(NcName::from_str("").is_err());
+        assert!(NcName::from_str("xs:element").is_err());
+        assert!(NcName::from_str("1a").is_err());
+    }
+
+    #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: test")]
+    pub struct Message {
+        #[yaserde(prefix = "t", rename = "Value")]
+        pub value: NcName,
+
+        #[yaserde(prefix = "t", rename = "Text")]
+        pub text: String,
+    }
+
+    #[test]
+    fn ncname_serialize_test() {
+        let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Value>element</t:Value>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m = Message { value: NcName("element".to_string()), text: "Hello world".to_string() };
+        let actual = yaserde::ser::to_string(&m).unwrap();
+        assert_xml_eq(&actual, expected);
+    }
+
+    #[test]
+    fn ncname_deserialize_test() {
+        let s = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Value> element </t:Value>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m: Message = yaserde::de::from_str(s).unwrap();
+        assert_eq!(m.value, NcName("element".to_string()));
+        assert_eq!(m.text, "Hello world".to_string());
+    }
+}
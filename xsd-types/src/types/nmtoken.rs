@@ -0,0 +1,21 @@
+use crate::types::utils::{collapse_whitespace, is_nmtoken};
+
+// https://www.w3.org/TR/xmlschema-2/#NMTOKEN
+string_type!(NmToken, collapse_whitespace, is_nmtoken, "NMTOKEN");
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn nmtoken_parse_test() {
+        assert_eq!(NmToken::from_str(" 1a "), Ok(NmToken("1a".to_string())));
+        assert_eq!(NmToken::from_str("-a:b"), Ok(NmToken("-a:b".to_string())));
+
+        // Invalid values.
+        assert!(NmToken::from_str("").is_err());
+        assert!(NmToken::from_str("a b").is_err());
+    }
+}
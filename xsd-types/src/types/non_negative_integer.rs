@@ -1,16 +1,35 @@
-use std::{fmt, str::FromStr};
+use std::{convert::TryFrom, fmt, str::FromStr};
 
-use num_bigint::{BigUint, ToBigUint};
+use num_bigint::{BigInt, BigUint, ToBigUint};
 use xsd_macro_utils::UtilsDefaultSerde;
 
+use crate::types::Integer;
+
 // https://www.w3.org/TR/xmlschema-2/#nonNegativeInteger
-#[derive(Default, PartialEq, PartialOrd, Debug, UtilsDefaultSerde)]
+#[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, UtilsDefaultSerde)]
 pub struct NonNegativeInteger(pub BigUint);
 
 impl NonNegativeInteger {
     pub fn from_biguint(bigint: BigUint) -> Self {
         NonNegativeInteger(bigint)
     }
+
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(NonNegativeInteger(&self.0 + &rhs.0))
+    }
+
+    // None when rhs is greater than self.
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        if rhs.0 > self.0 {
+            None
+        } else {
+            Some(NonNegativeInteger(&self.0 - &rhs.0))
+        }
+    }
+
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        Some(NonNegativeInteger(&self.0 * &rhs.0))
+    }
 }
 
 impl ToBigUint for NonNegativeInteger {
@@ -19,6 +38,54 @@ impl ToBigUint for NonNegativeInteger {
     }
 }
 
+impl From<u64> for NonNegativeInteger {
+    fn from(value: u64) -> Self {
+        NonNegativeInteger(BigUint::from(value))
+    }
+}
+
+impl TryFrom<i64> for NonNegativeInteger {
+    type Error = String;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        NonNegativeInteger::try_from(Integer::from(value))
+    }
+}
+
+impl TryFrom<Integer> for NonNegativeInteger {
+    type Error = String;
+
+    fn try_from(value: Integer) -> Result<Self, Self::Error> {
+        value
+            .0
+            .to_biguint()
+            .map(NonNegativeInteger)
+            .ok_or_else(|| "Bad value for NonNegativeInteger".to_string())
+    }
+}
+
+impl From<NonNegativeInteger> for Integer {
+    fn from(value: NonNegativeInteger) -> Self {
+        Integer(BigInt::from(value.0))
+    }
+}
+
+impl TryFrom<&NonNegativeInteger> for i64 {
+    type Error = String;
+
+    fn try_from(value: &NonNegativeInteger) -> Result<Self, Self::Error> {
+        i64::try_from(&value.0).map_err(|_| format!("{} is out of range for i64", value))
+    }
+}
+
+impl TryFrom<&NonNegativeInteger> for u64 {
+    type Error = String;
+
+    fn try_from(value: &NonNegativeInteger) -> Result<Self, Self::Error> {
+        u64::try_from(&value.0).map_err(|_| format!("{} is out of range for u64", value))
+    }
+}
+
 impl FromStr for NonNegativeInteger {
     type Err = String;
 
@@ -83,6 +150,29 @@ mod tests {
         assert_eq!(NonNegativeInteger(0.to_biguint().unwrap()).to_string(), "0");
     }
 
+    #[test]
+    fn non_negative_integer_conversion_test() {
+        assert_eq!(NonNegativeInteger::from(5u64), NonNegativeInteger(5.to_biguint().unwrap()));
+        assert_eq!(NonNegativeInteger::try_from(0i64), Ok(NonNegativeInteger::from(0)));
+        assert!(NonNegativeInteger::try_from(-1i64).is_err());
+        assert!(NonNegativeInteger::try_from(Integer::from(-1)).is_err());
+
+        assert_eq!(Integer::from(NonNegativeInteger::from(7)), Integer::from(7));
+        assert_eq!(u64::try_from(&NonNegativeInteger::from(u64::MAX)), Ok(u64::MAX));
+        assert!(i64::try_from(&NonNegativeInteger::from(u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn non_negative_integer_arithmetic_test() {
+        let (two, five) = (NonNegativeInteger::from(2), NonNegativeInteger::from(5));
+        assert_eq!(two.checked_add(&five), Some(NonNegativeInteger::from(7)));
+        assert_eq!(five.checked_sub(&two), Some(NonNegativeInteger::from(3)));
+        assert_eq!(five.checked_sub(&five), Some(NonNegativeInteger::from(0)));
+        assert_eq!(two.checked_sub(&five), None);
+        assert_eq!(two.checked_mul(&five), Some(NonNegativeInteger::from(10)));
+        assert!(two < five);
+    }
+
     #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
     #[yaserde(prefix = "t", namespace = "t: test")]
     pub struct NonNegativeIntegerPair {
@@ -1,16 +1,30 @@
-use std::{fmt, str::FromStr};
+use std::{convert::TryFrom, fmt, str::FromStr};
 
 use num_bigint::{BigInt, ToBigInt};
 use xsd_macro_utils::UtilsDefaultSerde;
 
+use crate::types::Integer;
+
 // https://www.w3.org/TR/xmlschema-2/#nonPositiveInteger
-#[derive(Default, PartialEq, PartialOrd, Debug, UtilsDefaultSerde)]
+#[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, UtilsDefaultSerde)]
 pub struct NonPositiveInteger(pub BigInt);
 
 impl NonPositiveInteger {
     pub fn from_bigint(bigint: BigInt) -> Self {
         NonPositiveInteger(bigint)
     }
+
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(NonPositiveInteger(&self.0 + &rhs.0))
+    }
+
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        NonPositiveInteger::try_from(&self.0 - &rhs.0).ok()
+    }
+
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        NonPositiveInteger::try_from(&self.0 * &rhs.0).ok()
+    }
 }
 
 impl ToBigInt for NonPositiveInteger {
@@ -19,11 +33,10 @@ impl ToBigInt for NonPositiveInteger {
     }
 }
 
-impl FromStr for NonPositiveInteger {
-    type Err = String;
+impl TryFrom<BigInt> for NonPositiveInteger {
+    type Error = String;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let value = BigInt::from_str(s).map_err(|e| e.to_string())?;
+    fn try_from(value: BigInt) -> Result<Self, Self::Error> {
         if value > 0.to_bigint().unwrap() {
             Err("Bad value for NonPositiveInteger".to_string())
         } else {
@@ -32,6 +45,52 @@ impl FromStr for NonPositiveInteger {
     }
 }
 
+impl TryFrom<i64> for NonPositiveInteger {
+    type Error = String;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        NonPositiveInteger::try_from(BigInt::from(value))
+    }
+}
+
+impl TryFrom<Integer> for NonPositiveInteger {
+    type Error = String;
+
+    fn try_from(value: Integer) -> Result<Self, Self::Error> {
+        NonPositiveInteger::try_from(value.0)
+    }
+}
+
+impl From<NonPositiveInteger> for Integer {
+    fn from(value: NonPositiveInteger) -> Self {
+        Integer(value.0)
+    }
+}
+
+impl TryFrom<&NonPositiveInteger> for i64 {
+    type Error = String;
+
+    fn try_from(value: &NonPositiveInteger) -> Result<Self, Self::Error> {
+        i64::try_from(&value.0).map_err(|_| format!("{} is out of range for i64", value))
+    }
+}
+
+impl TryFrom<&NonPositiveInteger> for u64 {
+    type Error = String;
+
+    fn try_from(value: &NonPositiveInteger) -> Result<Self, Self::Error> {
+        u64::try_from(&value.0).map_err(|_| format!("{} is out of range for u64", value))
+    }
+}
+
+impl FromStr for NonPositiveInteger {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NonPositiveInteger::try_from(BigInt::from_str(s).map_err(|e| e.to_string())?)
+    }
+}
+
 impl fmt::Display for NonPositiveInteger {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.0.to_str_radix(10))
@@ -90,6 +149,35 @@ mod tests {
         assert_eq!(NonPositiveInteger((-1).to_bigint().unwrap()).to_string(), "-1");
     }
 
+    #[test]
+    fn non_positive_integer_conversion_test() {
+        assert_eq!(
+            NonPositiveInteger::try_from(-5i64),
+            Ok(NonPositiveInteger((-5).to_bigint().unwrap()))
+        );
+        assert!(NonPositiveInteger::try_from(0i64).is_ok());
+        assert!(NonPositiveInteger::try_from(1i64).is_err());
+        assert!(NonPositiveInteger::try_from(Integer::from(1)).is_err());
+
+        let min = NonPositiveInteger::try_from(i64::MIN).unwrap();
+        assert_eq!(Integer::from(min.clone()), Integer::from(i64::MIN));
+        assert_eq!(i64::try_from(&min), Ok(i64::MIN));
+        assert!(u64::try_from(&min).is_err());
+    }
+
+    #[test]
+    fn non_positive_integer_arithmetic_test() {
+        let zero = NonPositiveInteger::try_from(0i64).unwrap();
+        let minus_two = NonPositiveInteger::try_from(-2i64).unwrap();
+        let minus_five = NonPositiveInteger::try_from(-5i64).unwrap();
+        assert_eq!(minus_two.checked_add(&minus_five), NonPositiveInteger::try_from(-7i64).ok());
+        assert_eq!(minus_five.checked_sub(&minus_two), NonPositiveInteger::try_from(-3i64).ok());
+        assert_eq!(minus_two.checked_sub(&minus_five), None);
+        assert_eq!(minus_two.checked_mul(&zero), Some(zero.clone()));
+        assert_eq!(minus_two.checked_mul(&minus_five), None);
+        assert!(minus_five < minus_two);
+    }
+
     #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
     #[yaserde(prefix = "t", namespace = "t: test")]
     pub struct NonPositiveIntegerPair {
@@ -0,0 +1,60 @@
+use crate::types::utils::replace_whitespace;
+
+// https://www.w3.org/TR/xmlschema-2/#normalizedString
+string_type!(NormalizedString, replace_whitespace);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    use super::*;
+    use crate::utils::xml_eq::assert_xml_eq;
+
+    #[test]
+    fn normalized_string_parse_test() {
+        assert_eq!(
+            NormalizedString::from_str(" a\tb\n"),
+            Ok(NormalizedString(" a b ".to_string()))
+        );
+        assert_eq!(NormalizedString::from_str(""), Ok(NormalizedString(String::new())));
+    }
+
+    #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: test")]
+    pub struct Message {
+        #[yaserde(prefix = "t", rename = "Value")]
+        pub value: NormalizedString,
+
+        #[yaserde(prefix = "t", rename = "Text")]
+        pub text: String,
+    }
+
+    #[test]
+    fn normalized_string_serialize_test() {
+        let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Value>a b</t:Value>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m =
+            Message { value: NormalizedString("a b".to_string()), text: "Hello world".to_string() };
+        let actual = yaserde::ser::to_string(&m).unwrap();
+        assert_xml_eq(&actual, expected);
+    }
+
+    #[test]
+    fn normalized_string_deserialize_test() {
+        let s = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Value>a&#9;b</t:Value>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m: Message = yaserde::de::from_str(s).unwrap();
+        assert_eq!(m.value, NormalizedString("a b".to_string()));
+        assert_eq!(m.text, "Hello world".to_string());
+    }
+}
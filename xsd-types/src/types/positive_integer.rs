@@ -1,16 +1,35 @@
-use std::{fmt, str::FromStr};
+use std::{convert::TryFrom, fmt, str::FromStr};
 
-use num_bigint::{BigUint, ToBigUint};
+use num_bigint::{BigInt, BigUint, ToBigUint};
 use xsd_macro_utils::UtilsDefaultSerde;
 
+use crate::types::Integer;
+
 // https://www.w3.org/TR/xmlschema-2/#positiveInteger
-#[derive(Default, PartialEq, PartialOrd, Debug, UtilsDefaultSerde)]
+#[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, UtilsDefaultSerde)]
 pub struct PositiveInteger(pub BigUint);
 
 impl PositiveInteger {
     pub fn from_biguint(bigint: BigUint) -> Self {
         PositiveInteger(bigint)
     }
+
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(PositiveInteger(&self.0 + &rhs.0))
+    }
+
+    // None unless rhs is less than self.
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        if rhs.0 >= self.0 {
+            None
+        } else {
+            Some(PositiveInteger(&self.0 - &rhs.0))
+        }
+    }
+
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        Some(PositiveInteger(&self.0 * &rhs.0))
+    }
 }
 
 impl ToBigUint for PositiveInteger {
@@ -19,11 +38,10 @@ impl ToBigUint for PositiveInteger {
     }
 }
 
-impl FromStr for PositiveInteger {
-    type Err = String;
+impl TryFrom<BigUint> for PositiveInteger {
+    type Error = String;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let value = BigUint::from_str(s).map_err(|e| e.to_string())?;
+    fn try_from(value: BigUint) -> Result<Self, Self::Error> {
         if value <= 0.to_biguint().unwrap() {
             Err("Bad value for PositiveInteger".to_string())
         } else {
@@ -32,6 +50,62 @@ impl FromStr for PositiveInteger {
     }
 }
 
+impl TryFrom<u64> for PositiveInteger {
+    type Error = String;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        PositiveInteger::try_from(BigUint::from(value))
+    }
+}
+
+impl TryFrom<i64> for PositiveInteger {
+    type Error = String;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        PositiveInteger::try_from(Integer::from(value))
+    }
+}
+
+impl TryFrom<Integer> for PositiveInteger {
+    type Error = String;
+
+    fn try_from(value: Integer) -> Result<Self, Self::Error> {
+        let value =
+            value.0.to_biguint().ok_or_else(|| "Bad value for PositiveInteger".to_string())?;
+        PositiveInteger::try_from(value)
+    }
+}
+
+impl From<PositiveInteger> for Integer {
+    fn from(value: PositiveInteger) -> Self {
+        Integer(BigInt::from(value.0))
+    }
+}
+
+impl TryFrom<&PositiveInteger> for i64 {
+    type Error = String;
+
+    fn try_from(value: &PositiveInteger) -> Result<Self, Self::Error> {
+        i64::try_from(&value.0).map_err(|_| format!("{} is out of range for i64", value))
+    }
+}
+
+impl TryFrom<&PositiveInteger> for u64 {
+    type Error = String;
+
+    fn try_from(value: &PositiveInteger) -> Result<Self, Self::Error> {
+        u64::try_from(&value.0).map_err(|_| format!("{} is out of range for u64", value))
+    }
+}
+
+impl FromStr for PositiveInteger {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PositiveInteger::try_from(BigUint::from_str(s).map_err(|e| e.to_string())?)
+    }
+}
+
 impl fmt::Display for PositiveInteger {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.0.to_str_radix(10))
@@ -79,6 +153,32 @@ mod tests {
         assert_eq!(PositiveInteger(100000.to_biguint().unwrap()).to_string(), "100000");
     }
 
+    #[test]
+    fn positive_integer_conversion_test() {
+        assert_eq!(PositiveInteger::try_from(5u64), Ok(PositiveInteger(5.to_biguint().unwrap())));
+        assert_eq!(PositiveInteger::try_from(5i64), PositiveInteger::try_from(5u64));
+        assert!(PositiveInteger::try_from(0u64).is_err());
+        assert!(PositiveInteger::try_from(-1i64).is_err());
+        assert!(PositiveInteger::try_from(Integer::from(0)).is_err());
+
+        let one = PositiveInteger::try_from(1u64).unwrap();
+        assert_eq!(Integer::from(one.clone()), Integer::from(1));
+        assert_eq!(i64::try_from(&one), Ok(1));
+        assert_eq!(u64::try_from(&one), Ok(1));
+    }
+
+    #[test]
+    fn positive_integer_arithmetic_test() {
+        let two = PositiveInteger::try_from(2u64).unwrap();
+        let five = PositiveInteger::try_from(5u64).unwrap();
+        assert_eq!(two.checked_add(&five), PositiveInteger::try_from(7u64).ok());
+        assert_eq!(five.checked_sub(&two), PositiveInteger::try_from(3u64).ok());
+        assert_eq!(five.checked_sub(&five), None);
+        assert_eq!(two.checked_sub(&five), None);
+        assert_eq!(two.checked_mul(&five), PositiveInteger::try_from(10u64).ok());
+        assert!(two < five);
+    }
+
     #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
     #[yaserde(prefix = "t", namespace = "t: test")]
     pub struct PositiveIntegerPair {
@@ -0,0 +1,280 @@
+use std::{
+    fmt,
+    io::{Read, Write},
+    str::FromStr,
+};
+
+use xml::{attribute::OwnedAttribute, namespace::Namespace, reader::XmlEvent};
+use yaserde::{de, ser, YaDeserialize, YaSerialize};
+
+use crate::types::utils::is_ncname;
+
+// Prefix declared for a name that has a namespace but no prefix of its own.
+const GENERATED_PREFIX: &str = "ns0";
+
+// A qualified name such as tt:InvalidArgVal. Values are equal when their namespaces and
+// local names are, the prefix only matters for serialization.
+#[derive(Default, Clone, Debug)]
+pub struct QName {
+    pub namespace: Option<String>,
+    pub local: String,
+    pub prefix: Option<String>,
+}
+
+impl QName {
+    pub fn new(namespace: Option<&str>, local: &str) -> Self {
+        QName { namespace: namespace.map(str::to_string), local: local.to_string(), prefix: None }
+    }
+
+    // Sets the namespace bound to the prefix, or to the default namespace for an unprefixed
+    // name. `lookup` gets "" for the default namespace.
+    pub fn resolve(&mut self, lookup: impl Fn(&str) -> Option<String>) -> Result<(), String> {
+        match self.prefix.as_deref() {
+            Some(prefix) => {
+                let namespace = lookup(prefix)
+                    .ok_or_else(|| format!("bad QName: prefix '{}' is not declared", prefix))?;
+                self.namespace = Some(namespace);
+            }
+            None => self.namespace = lookup("").filter(|ns| !ns.is_empty()),
+        }
+        Ok(())
+    }
+
+    // The declaration the written value relies on, as (prefix, namespace). A name with a
+    // namespace but no prefix gets a generated one: declaring the default namespace instead
+    // would move the element holding the value into that namespace too.
+    pub fn namespace_declaration(&self) -> Option<(&str, &str)> {
+        let namespace = self.namespace.as_deref()?;
+        Some((self.prefix.as_deref().unwrap_or(GENERATED_PREFIX), namespace))
+    }
+
+    // The value as written next to `namespace_declaration`.
+    pub fn to_xml_value(&self) -> String {
+        match self.namespace_declaration() {
+            Some((prefix, _)) => format!("{}:{}", prefix, self.local),
+            None => self.to_string(),
+        }
+    }
+}
+
+impl PartialEq for QName {
+    fn eq(&self, other: &Self) -> bool {
+        self.namespace == other.namespace && self.local == other.local
+    }
+}
+
+impl Eq for QName {}
+
+// Parses the lexical form only: the namespace stays unresolved until `resolve` is called.
+impl FromStr for QName {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (prefix, local) = match s.split_once(':') {
+            Some((prefix, local)) => (Some(prefix), local),
+            None => (None, s),
+        };
+
+        if !is_ncname(local) || prefix.is_some_and(|p| !is_ncname(p)) {
+            return Err(format!("bad QName format: '{}'", s));
+        }
+
+        Ok(QName { namespace: None, local: local.to_string(), prefix: prefix.map(str::to_string) })
+    }
+}
+
+impl fmt::Display for QName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.prefix {
+            Some(prefix) => write!(f, "{}:{}", prefix, self.local),
+            None => write!(f, "{}", self.local),
+        }
+    }
+}
+
+// The namespace of the value is declared on the element holding it, so the value stays
+// resolvable wherever the document is read.
+impl YaSerialize for QName {
+    fn serialize<W: Write>(&self, writer: &mut ser::Serializer<W>) -> Result<(), String> {
+        let name = writer.get_start_event_name().unwrap_or_else(|| "QName".to_string());
+
+        if !writer.skip_start_end() {
+            let start = xml::writer::XmlEvent::start_element(name.as_str());
+            let start = match self.namespace_declaration() {
+                Some((prefix, namespace)) => start.ns(prefix, namespace),
+                None => start,
+            };
+            writer.write(start).map_err(|_e| "Start element write failed".to_string())?;
+        }
+
+        writer
+            .write(xml::writer::XmlEvent::characters(self.to_xml_value().as_str()))
+            .map_err(|_e| "Element value write failed".to_string())?;
+
+        if !writer.skip_start_end() {
+            writer
+                .write(xml::writer::XmlEvent::end_element())
+                .map_err(|_e| "End element write failed".to_string())?;
+        }
+
+        Ok(())
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<OwnedAttribute>,
+        mut namespace: Namespace,
+    ) -> Result<(Vec<OwnedAttribute>, Namespace), String> {
+        if let Some((prefix, uri)) = self.namespace_declaration() {
+            namespace.put(prefix, uri);
+        }
+        Ok((attributes, namespace))
+    }
+}
+
+impl YaDeserialize for QName {
+    fn deserialize<R: Read>(reader: &mut de::Deserializer<R>) -> Result<Self, String> {
+        let namespace = match reader.peek()? {
+            XmlEvent::StartElement { namespace, .. } => namespace.clone(),
+            _ => return Err("Start element not found".to_string()),
+        };
+
+        crate::utils::yaserde::deserialize(reader, |s| {
+            let mut qname = QName::from_str(s)?;
+            qname.resolve(|prefix| namespace.get(prefix).map(str::to_string))?;
+            Ok(qname)
+        })
+    }
+}
+
+impl popper::XmlDeserialize for QName {
+    fn xml_deserialize(popper: &mut popper::XmlPopper) -> Result<Self, popper::DeError> {
+        let mut qname = QName::from_str(popper.pop_value()?.as_str())?;
+        qname.resolve(|prefix| popper.lookup_namespace(prefix))?;
+        Ok(qname)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    use super::*;
+    use crate::utils::xml_eq::assert_xml_eq;
+
+    const SCHEMA: &str = "http://www.onvif.org/ver10/schema";
+
+    #[test]
+    fn qname_parse_test() {
+        let qname = QName::from_str("tt:InvalidArgVal").unwrap();
+        assert_eq!(qname.prefix.as_deref(), Some("tt"));
+        assert_eq!(qname.local, "InvalidArgVal");
+        assert_eq!(qname.namespace, None);
+
+        assert_eq!(QName::from_str(" Sender ").unwrap().local, "Sender");
+
+        assert!(QName::from_str("").is_err());
+        assert!(QName::from_str("tt:").is_err());
+        assert!(QName::from_str(":Name").is_err());
+        assert!(QName::from_str("a:b:c").is_err());
+        assert!(QName::from_str("1Name").is_err());
+    }
+
+    #[test]
+    fn qname_resolve_test() {
+        let lookup = |prefix: &str| match prefix {
+            "tt" => Some(SCHEMA.to_string()),
+            "" => Some("default".to_string()),
+            _ => None,
+        };
+
+        let mut qname = QName::from_str("tt:InvalidArgVal").unwrap();
+        qname.resolve(lookup).unwrap();
+        assert_eq!(qname, QName::new(Some(SCHEMA), "InvalidArgVal"));
+
+        let mut qname = QName::from_str("Sender").unwrap();
+        qname.resolve(lookup).unwrap();
+        assert_eq!(qname.namespace.as_deref(), Some("default"));
+
+        assert!(QName::from_str("ter:Sender").unwrap().resolve(lookup).is_err());
+    }
+
+    #[test]
+    fn qname_display_test() {
+        assert_eq!(QName::from_str("tt:InvalidArgVal").unwrap().to_string(), "tt:InvalidArgVal");
+        assert_eq!(QName::new(None, "Sender").to_string(), "Sender");
+        assert_eq!(QName::new(Some(SCHEMA), "Sender").to_string(), "Sender");
+    }
+
+    #[test]
+    fn qname_namespace_declaration_test() {
+        let mut qname = QName::from_str("tt:InvalidArgVal").unwrap();
+        assert_eq!(qname.namespace_declaration(), None);
+        qname.namespace = Some(SCHEMA.to_string());
+        assert_eq!(qname.namespace_declaration(), Some(("tt", SCHEMA)));
+        assert_eq!(
+            QName::new(Some(SCHEMA), "Sender").namespace_declaration(),
+            Some(("ns0", SCHEMA))
+        );
+        assert_eq!(QName::new(None, "Sender").namespace_declaration(), None);
+    }
+
+    #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: test")]
+    pub struct Message {
+        #[yaserde(prefix = "t", rename = "Code")]
+        pub code: QName,
+
+        #[yaserde(prefix = "t", rename = "Text")]
+        pub text: String,
+    }
+
+    #[test]
+    fn qname_serialize_test() {
+        let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Code xmlns:tt="http://www.onvif.org/ver10/schema">tt:InvalidArgVal</t:Code>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m = Message {
+            code: QName {
+                namespace: Some(SCHEMA.to_string()),
+                local: "InvalidArgVal".to_string(),
+                prefix: Some("tt".to_string()),
+            },
+            text: "Hello world".to_string(),
+        };
+        let actual = yaserde::ser::to_string(&m).unwrap();
+        assert_xml_eq(&actual, expected);
+    }
+
+    #[test]
+    fn qname_serialize_generated_prefix_test() {
+        let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Code xmlns:ns0="http://www.onvif.org/ver10/schema">ns0:Sender</t:Code>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m =
+            Message { code: QName::new(Some(SCHEMA), "Sender"), text: "Hello world".to_string() };
+        let actual = yaserde::ser::to_string(&m).unwrap();
+        assert_xml_eq(&actual, expected);
+    }
+
+    #[test]
+    fn qname_deserialize_test() {
+        let s = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test" xmlns:tt="http://www.onvif.org/ver10/schema">
+                <t:Code>tt:InvalidArgVal</t:Code>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m: Message = yaserde::de::from_str(s).unwrap();
+        assert_eq!(m.code, QName::new(Some(SCHEMA), "InvalidArgVal"));
+        assert_eq!(m.code.prefix.as_deref(), Some("tt"));
+        assert_eq!(m.text, "Hello world".to_string());
+    }
+}
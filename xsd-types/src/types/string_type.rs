@@ -0,0 +1,59 @@
+// A string type of XML Schema. The text is normalized as the whiteSpace facet of the type
+// says, then rejected unless the checker accepts it, with the XSD name in the error:
+//
+//     string_type!(Id, collapse_whitespace, is_ncname, "ID");
+//
+// Without a checker any normalized text is a value and parsing cannot fail.
+macro_rules! string_type {
+    (@common $name:ident) => {
+        #[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+        #[derive(xsd_macro_utils::UtilsDefaultSerde)]
+        pub struct $name(pub String);
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn len(&self) -> usize {
+                self.0.chars().count()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+    ($name:ident, $normalize:path) => {
+        string_type!(@common $name);
+
+        impl std::str::FromStr for $name {
+            type Err = std::convert::Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok($name($normalize(s)))
+            }
+        }
+    };
+    ($name:ident, $normalize:path, $check:path, $xsd_name:literal) => {
+        string_type!(@common $name);
+
+        impl std::str::FromStr for $name {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let value = $normalize(s);
+                if !$check(&value) {
+                    return Err(format!("bad {} format: '{}'", $xsd_name, value));
+                }
+                Ok($name(value))
+            }
+        }
+    };
+}
@@ -1,29 +1,55 @@
-use std::{fmt, str::FromStr};
+use std::{cmp::Ordering, fmt, str::FromStr};
 
-use chrono::{format::strftime::StrftimeItems, FixedOffset, NaiveTime};
 use xsd_macro_utils::UtilsDefaultSerde;
 
-use crate::types::utils::parse_timezone;
+use crate::types::{
+    lexical::{format_time, parse_time},
+    naive::{NaiveDate, NaiveTime},
+    timezone::Timezone,
+    utils::compare_timezoned,
+};
 
-#[derive(PartialEq, Debug, UtilsDefaultSerde)]
+#[derive(Debug, UtilsDefaultSerde)]
 pub struct Time {
     pub value: NaiveTime,
-    pub timezone: Option<FixedOffset>,
+    pub timezone: Option<Timezone>,
 }
 
 impl Time {
-    pub fn from_chrono_naive_time(time: NaiveTime) -> Self {
-        Time { value: time, timezone: None }
+    #[cfg(feature = "chrono")]
+    pub fn from_chrono_naive_time(time: chrono::NaiveTime) -> Self {
+        Time { value: time.into(), timezone: None }
     }
 
-    pub fn to_chrono_naive_time(&self) -> NaiveTime {
-        self.value
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono_naive_time(&self) -> chrono::NaiveTime {
+        self.value.into()
+    }
+}
+
+impl PartialEq for Time {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Time {
+    // Times are compared on the reference date of XSD 1.1 section 3.3.8, so 01:00:00+02:00
+    // is earlier than 23:30:00Z.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let date = NaiveDate::from_ymd(1972, 12, 31)?;
+        compare_timezoned(
+            date.and_time(self.value),
+            self.timezone,
+            date.and_time(other.value),
+            other.timezone,
+        )
     }
 }
 
 impl Default for Time {
     fn default() -> Time {
-        Self { value: NaiveTime::from_hms_opt(0, 0, 0).unwrap(), timezone: None }
+        Self { value: NaiveTime::MIDNIGHT, timezone: None }
     }
 }
 
@@ -31,55 +57,16 @@ impl FromStr for Time {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        fn parse_naive_time(s: &str) -> Result<NaiveTime, String> {
-            NaiveTime::parse_from_str(s, "%H:%M:%S").map_err(|e| e.to_string())
-        }
-
-        if let Some(s) = s.strip_suffix('Z') {
-            return Ok(Time {
-                value: parse_naive_time(s)?,
-                timezone: Some(FixedOffset::east_opt(0).unwrap()),
-            });
-        }
-
-        if s.contains('+') {
-            if s.matches('+').count() > 1 {
-                return Err("bad date format".to_string());
-            }
-
-            let idx: usize = s.match_indices('+').collect::<Vec<_>>()[0].0;
-            let time_token = &s[..idx];
-            let tz_token = &s[idx..];
-            return Ok(Time {
-                value: parse_naive_time(time_token)?,
-                timezone: Some(parse_timezone(tz_token)?),
-            });
-        }
-
-        if s.contains('-') {
-            if s.matches('-').count() > 1 {
-                return Err("bad date format".to_string());
-            }
-
-            let idx: usize = s.match_indices('-').collect::<Vec<_>>()[0].0;
-            let time_token = &s[..idx];
-            let tz_token = &s[idx..];
-            return Ok(Time {
-                value: parse_naive_time(time_token)?,
-                timezone: Some(parse_timezone(tz_token)?),
-            });
-        }
-
-        Ok(Time { value: parse_naive_time(s)?, timezone: None })
+        let (value, timezone) = parse_time(s)?;
+        Ok(Time { value, timezone })
     }
 }
 
 impl fmt::Display for Time {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let fmt = StrftimeItems::new("%H:%M:%S");
         match self.timezone {
-            Some(tz) => write!(f, "{}{}", self.value.format_with_items(fmt.clone()), tz),
-            None => write!(f, "{}", self.value.format_with_items(fmt.clone())),
+            Some(tz) => write!(f, "{}{}", format_time(self.value), tz),
+            None => write!(f, "{}", format_time(self.value)),
         }
     }
 }
@@ -96,15 +83,15 @@ mod tests {
         // No timezone.
         assert_eq!(
             Time::from_str("04:40:00"),
-            Ok(Time { value: NaiveTime::from_hms_opt(4, 40, 0).unwrap(), timezone: None })
+            Ok(Time { value: NaiveTime::from_hms(4, 40, 0).unwrap(), timezone: None })
         );
 
         // Timezone "Z".
         assert_eq!(
             Time::from_str("04:40:00Z"),
             Ok(Time {
-                value: NaiveTime::from_hms_opt(4, 40, 0).unwrap(),
-                timezone: Some(FixedOffset::east_opt(0).unwrap())
+                value: NaiveTime::from_hms(4, 40, 0).unwrap(),
+                timezone: Some(Timezone::UTC)
             })
         );
 
@@ -112,8 +99,8 @@ mod tests {
         assert_eq!(
             Time::from_str("04:40:00+06:30"),
             Ok(Time {
-                value: NaiveTime::from_hms_opt(4, 40, 0).unwrap(),
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap())
+                value: NaiveTime::from_hms(4, 40, 0).unwrap(),
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap())
             })
         );
 
@@ -121,35 +108,64 @@ mod tests {
         assert_eq!(
             Time::from_str("04:40:00-06:30"),
             Ok(Time {
-                value: NaiveTime::from_hms_opt(4, 40, 0).unwrap(),
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                value: NaiveTime::from_hms(4, 40, 0).unwrap(),
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             })
         );
     }
 
+    #[test]
+    fn time_order_test() {
+        let time = |s| Time::from_str(s).unwrap();
+
+        assert_eq!(time("12:00:00+02:00"), time("10:00:00Z"));
+        assert!(time("01:00:00+02:00") < time("23:30:00Z"));
+        assert!(time("01:00:00") < time("02:00:00"));
+        assert_eq!(time("12:00:00").partial_cmp(&time("12:00:00Z")), None);
+        assert_ne!(time("12:00:00"), time("12:00:00Z"));
+        assert_eq!(time("12:00:00").to_string(), "12:00:00");
+    }
+
+    #[test]
+    fn time_end_of_day_test() {
+        assert_eq!(Time::from_str("24:00:00").unwrap().to_string(), "00:00:00");
+        assert_eq!(Time::from_str("24:00:00Z").unwrap(), Time::from_str("00:00:00Z").unwrap());
+        assert!(Time::from_str("24:00:01").is_err());
+        assert!(Time::from_str("23:60:00").is_err());
+    }
+
+    #[test]
+    fn time_fraction_test() {
+        let t = Time::from_str("04:40:00.1+06:30").unwrap();
+        assert_eq!(t.value, NaiveTime::from_hms_nano(4, 40, 0, 100_000_000).unwrap());
+        assert_eq!(t.to_string(), "04:40:00.1+06:30");
+        assert_eq!(Time::from_str("04:40:00.1-00:00").unwrap().to_string(), "04:40:00.1Z");
+        assert_eq!(Time::from_str("04:40:00.000000001").unwrap().to_string(), "04:40:00.000000001");
+        assert_eq!(Time::from_str("04:40:00.500").unwrap().to_string(), "04:40:00.5");
+        assert!(Time::from_str("04:40:00.").is_err());
+        assert!(Time::from_str("04:40:00.0000000001").is_err());
+    }
+
     #[test]
     fn time_display_test() {
         // No timezone.
         assert_eq!(
-            Time { value: NaiveTime::from_hms_opt(4, 40, 0).unwrap(), timezone: None }.to_string(),
+            Time { value: NaiveTime::from_hms(4, 40, 0).unwrap(), timezone: None }.to_string(),
             "04:40:00"
         );
 
-        // Timezone +00:00.
+        // Zero offset.
         assert_eq!(
-            Time {
-                value: NaiveTime::from_hms_opt(4, 40, 0).unwrap(),
-                timezone: Some(FixedOffset::east_opt(0).unwrap())
-            }
-            .to_string(),
-            "04:40:00+00:00"
+            Time { value: NaiveTime::from_hms(4, 40, 0).unwrap(), timezone: Some(Timezone::UTC) }
+                .to_string(),
+            "04:40:00Z"
         );
 
         // Positive offset.
         assert_eq!(
             Time {
-                value: NaiveTime::from_hms_opt(4, 40, 0).unwrap(),
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap())
+                value: NaiveTime::from_hms(4, 40, 0).unwrap(),
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap())
             }
             .to_string(),
             "04:40:00+06:30"
@@ -158,8 +174,8 @@ mod tests {
         // Negative offset.
         assert_eq!(
             Time {
-                value: NaiveTime::from_hms_opt(4, 40, 0).unwrap(),
-                timezone: Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+                value: NaiveTime::from_hms(4, 40, 0).unwrap(),
+                timezone: Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
             }
             .to_string(),
             "04:40:00-06:30"
@@ -186,8 +202,8 @@ mod tests {
             "#;
         let m = Message {
             created_at: Time {
-                value: NaiveTime::from_hms_opt(4, 40, 0).unwrap(),
-                timezone: Some(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap()),
+                value: NaiveTime::from_hms(4, 40, 0).unwrap(),
+                timezone: Some(Timezone::from_offset_minutes(6 * 60 + 30).unwrap()),
             },
             text: "Hello world".to_string(),
         };
@@ -204,8 +220,11 @@ mod tests {
             </t:Message>
             "#;
         let m: Message = yaserde::de::from_str(s).unwrap();
-        assert_eq!(m.created_at.value, NaiveTime::from_hms_opt(4, 40, 0).unwrap());
-        assert_eq!(m.created_at.timezone, Some(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap()));
+        assert_eq!(m.created_at.value, NaiveTime::from_hms(4, 40, 0).unwrap());
+        assert_eq!(
+            m.created_at.timezone,
+            Some(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
+        );
         assert_eq!(m.text, "Hello world".to_string());
     }
 }
@@ -0,0 +1,82 @@
+use std::fmt;
+
+#[cfg(feature = "chrono")]
+use std::convert::TryFrom;
+
+// Time zone offset of the date and time types, a whole number of minutes from -14:00 to
+// +14:00. The canonical form of a zero offset is "Z".
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Timezone {
+    offset_minutes: i32,
+}
+
+impl Timezone {
+    pub const UTC: Timezone = Timezone { offset_minutes: 0 };
+
+    pub fn from_offset_minutes(offset_minutes: i32) -> Option<Timezone> {
+        if offset_minutes.abs() > 14 * 60 {
+            return None;
+        }
+
+        Some(Timezone { offset_minutes })
+    }
+
+    pub fn offset_minutes(&self) -> i32 {
+        self.offset_minutes
+    }
+}
+
+impl fmt::Display for Timezone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.offset_minutes == 0 {
+            return write!(f, "Z");
+        }
+
+        let sign = if self.offset_minutes < 0 { '-' } else { '+' };
+        let minutes = self.offset_minutes.abs();
+        write!(f, "{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Timezone> for chrono::FixedOffset {
+    fn from(timezone: Timezone) -> Self {
+        chrono::FixedOffset::east_opt(timezone.offset_minutes * 60).unwrap()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::FixedOffset> for Timezone {
+    type Error = String;
+
+    fn try_from(offset: chrono::FixedOffset) -> Result<Self, Self::Error> {
+        let seconds = offset.local_minus_utc();
+        match Timezone::from_offset_minutes(seconds / 60) {
+            Some(timezone) if seconds % 60 == 0 => Ok(timezone),
+            _ => Err(format!("offset {} is not a time zone of XSD", offset)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timezone_display_test() {
+        assert_eq!(Timezone::UTC.to_string(), "Z");
+        assert_eq!(Timezone::from_offset_minutes(6 * 60 + 30).unwrap().to_string(), "+06:30");
+        assert_eq!(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap().to_string(), "-06:30");
+        assert_eq!(Timezone::from_offset_minutes(-14 * 60).unwrap().to_string(), "-14:00");
+        assert!(Timezone::from_offset_minutes(14 * 60 + 1).is_none());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn timezone_chrono_test() {
+        let offset = chrono::FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap();
+        assert_eq!(chrono::FixedOffset::from(Timezone::try_from(offset).unwrap()), offset);
+        assert!(Timezone::try_from(chrono::FixedOffset::east_opt(15 * 3600).unwrap()).is_err());
+        assert!(Timezone::try_from(chrono::FixedOffset::east_opt(30).unwrap()).is_err());
+    }
+}
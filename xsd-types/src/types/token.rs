@@ -0,0 +1,58 @@
+use crate::types::utils::collapse_whitespace;
+
+// https://www.w3.org/TR/xmlschema-2/#token
+string_type!(Token, collapse_whitespace);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use yaserde_derive::{YaDeserialize, YaSerialize};
+
+    use super::*;
+    use crate::utils::xml_eq::assert_xml_eq;
+
+    #[test]
+    fn token_parse_test() {
+        assert_eq!(Token::from_str("  a\t b\n"), Ok(Token("a b".to_string())));
+        assert_eq!(Token::from_str(" "), Ok(Token(String::new())));
+    }
+
+    #[derive(Default, PartialEq, Debug, YaSerialize, YaDeserialize)]
+    #[yaserde(prefix = "t", namespace = "t: test")]
+    pub struct Message {
+        #[yaserde(prefix = "t", rename = "Value")]
+        pub value: Token,
+
+        #[yaserde(prefix = "t", rename = "Text")]
+        pub text: String,
+    }
+
+    #[test]
+    fn token_serialize_test() {
+        let expected = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Value>a b</t:Value>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m = Message { value: Token("a b".to_string()), text: "Hello world".to_string() };
+        let actual = yaserde::ser::to_string(&m).unwrap();
+        assert_xml_eq(&actual, expected);
+    }
+
+    #[test]
+    fn token_deserialize_test() {
+        let s = r#"<?xml version="1.0" encoding="utf-8"?>
+            <t:Message xmlns:t="test">
+                <t:Value>
+                  a  b
+                </t:Value>
+                <t:Text>Hello world</t:Text>
+            </t:Message>
+            "#;
+        let m: Message = yaserde::de::from_str(s).unwrap();
+        assert_eq!(m.value, Token("a b".to_string()));
+        assert_eq!(m.text, "Hello world".to_string());
+    }
+}
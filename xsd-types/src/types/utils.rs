@@ -1,9 +1,14 @@
-use chrono::FixedOffset;
+use std::{cmp::Ordering, str::FromStr};
+
+use crate::types::{
+    naive::{NaiveDate, NaiveDateTime},
+    timezone::Timezone,
+};
 
 // Parses ISO 8601 timezone.
-pub fn parse_timezone(s: &str) -> Result<FixedOffset, String> {
+pub fn parse_timezone(s: &str) -> Result<Timezone, String> {
     if s == "Z" {
-        return Ok(FixedOffset::east_opt(0).unwrap());
+        return Ok(Timezone::UTC);
     }
 
     let tokens: Vec<&str> = s[1..].split(':').collect();
@@ -21,12 +26,177 @@ pub fn parse_timezone(s: &str) -> Result<FixedOffset, String> {
         return Err("bad timezone format: out of range".to_string());
     }
 
-    let offset_secs = 60 * (60 * hours + minutes);
-    match s.chars().next().unwrap() {
-        '+' => FixedOffset::east_opt(offset_secs).ok_or("Seconds out of bound".to_owned()),
-        '-' => FixedOffset::west_opt(offset_secs).ok_or("Seconds out of bound".to_owned()),
-        _ => Err("bad timezone format: timezone should start with '+' or '-'".to_string()),
+    let offset_minutes = match s.chars().next().unwrap() {
+        '+' => 60 * hours + minutes,
+        '-' => -(60 * hours + minutes),
+        _ => return Err("bad timezone format: timezone should start with '+' or '-'".to_string()),
+    };
+    Timezone::from_offset_minutes(offset_minutes)
+        .ok_or_else(|| "bad timezone format: out of range".to_string())
+}
+
+// Order of XSD 1.1 section 3.3.7: values with a time zone are compared as instants. A value
+// without one may have any time zone from -14:00 to +14:00, so it is ordered relative to a
+// value with a time zone only when they are more than 14 hours apart.
+pub(crate) fn compare_timezoned(
+    lhs: NaiveDateTime,
+    lhs_tz: Option<Timezone>,
+    rhs: NaiveDateTime,
+    rhs_tz: Option<Timezone>,
+) -> Option<Ordering> {
+    const NANOS_PER_MINUTE: i128 = 60_000_000_000;
+
+    fn to_utc(value: NaiveDateTime, tz: Timezone) -> i128 {
+        value.timestamp_nanos() - tz.offset_minutes() as i128 * NANOS_PER_MINUTE
+    }
+
+    match (lhs_tz, rhs_tz) {
+        (None, None) => Some(lhs.cmp(&rhs)),
+        (Some(lhs_tz), Some(rhs_tz)) => Some(to_utc(lhs, lhs_tz).cmp(&to_utc(rhs, rhs_tz))),
+        (Some(lhs_tz), None) => {
+            let lhs = to_utc(lhs, lhs_tz);
+            let rhs = rhs.timestamp_nanos();
+            let range = 14 * 60 * NANOS_PER_MINUTE;
+            if lhs < rhs - range {
+                Some(Ordering::Less)
+            } else if lhs > rhs + range {
+                Some(Ordering::Greater)
+            } else {
+                None
+            }
+        }
+        (None, Some(_)) => compare_timezoned(rhs, rhs_tz, lhs, lhs_tz).map(Ordering::reverse),
+    }
+}
+
+// Year of gMonth, gDay and gMonthDay values when they are compared, a leap year so that
+// --02-29 exists.
+pub(crate) const REFERENCE_YEAR: i32 = 1972;
+
+// Orders gYear, gMonthDay... values given as (year, month, day) by their first moment,
+// like compare_timezoned.
+pub(crate) fn compare_partial_dates(
+    lhs: (i32, i32, i32),
+    lhs_tz: Option<Timezone>,
+    rhs: (i32, i32, i32),
+    rhs_tz: Option<Timezone>,
+) -> Option<Ordering> {
+    fn first_moment((year, month, day): (i32, i32, i32)) -> Option<NaiveDateTime> {
+        NaiveDate::from_ymd(year, month as u32, day as u32)?.and_hms(0, 0, 0)
+    }
+
+    compare_timezoned(first_moment(lhs)?, lhs_tz, first_moment(rhs)?, rhs_tz)
+}
+
+// Parses the digits after the decimal point of the seconds into nanoseconds.
+// Trailing zeros are ignored, any other digit beyond nanosecond precision is an error.
+pub fn parse_fraction(s: &str) -> Result<u32, String> {
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit()) {
+        return Err("bad fractional seconds format".to_string());
+    }
+
+    let digits = s.trim_end_matches('0');
+    if digits.len() > 9 {
+        return Err("fractional seconds beyond nanosecond precision".to_string());
+    }
+
+    Ok(format!("{:0<9}", digits).parse::<u32>().unwrap())
+}
+
+// Formats nanoseconds as the shortest fraction of a second, e.g. ".1" for 100_000_000.
+pub fn format_fraction(nanoseconds: u32) -> String {
+    if nanoseconds == 0 {
+        return String::new();
     }
+
+    format!(".{}", format!("{:09}", nanoseconds).trim_end_matches('0'))
+}
+
+// Parses the lexical form of float and double: a decimal number with an optional exponent,
+// INF, +INF, -INF or NaN. Rust's own spellings such as "inf" or "infinity" are rejected.
+pub fn parse_float_literal<T: FromStr>(s: &str) -> Result<T, String> {
+    let s = s.trim();
+    let value = match s {
+        "INF" | "+INF" => "inf",
+        "-INF" => "-inf",
+        "NaN" => "NaN",
+        _ => {
+            let (mantissa, exponent) = match s.find(['e', 'E']) {
+                Some(idx) => (&s[..idx], Some(&s[idx + 1..])),
+                None => (s, None),
+            };
+            let is_number = |s: &str, allow_dot: bool| {
+                let s = s.strip_prefix(['+', '-']).unwrap_or(s);
+                let digits = if allow_dot { s.replacen('.', "", 1) } else { s.to_string() };
+                !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+            };
+
+            if !is_number(mantissa, true) || exponent.is_some_and(|e| !is_number(e, false)) {
+                return Err(format!("bad float format: '{}'", s));
+            }
+            s
+        }
+    };
+
+    value.parse::<T>().map_err(|_| format!("bad float format: '{}'", s))
+}
+
+// Turns the output of the `{:e}` format into the canonical form of float and double:
+// 1000 is "1.0E3", negative zero is "-0.0E0" and infinities are "INF" and "-INF".
+pub fn format_float_literal(exp: &str) -> String {
+    match exp {
+        "inf" => "INF".to_string(),
+        "-inf" => "-INF".to_string(),
+        "NaN" => "NaN".to_string(),
+        _ => {
+            let (mantissa, exponent) = exp.split_once('e').unwrap_or((exp, "0"));
+            if mantissa.contains('.') {
+                format!("{}E{}", mantissa, exponent)
+            } else {
+                format!("{}.0E{}", mantissa, exponent)
+            }
+        }
+    }
+}
+
+// whiteSpace="replace": tabs, line feeds and carriage returns become spaces.
+pub fn replace_whitespace(s: &str) -> String {
+    s.replace(['\t', '\n', '\r'], " ")
+}
+
+// whiteSpace="collapse": after replacing, runs of spaces become a single space and leading
+// and trailing spaces are removed.
+pub fn collapse_whitespace(s: &str) -> String {
+    s.split([' ', '\t', '\n', '\r']).filter(|part| !part.is_empty()).collect::<Vec<_>>().join(" ")
+}
+
+// NameStartChar and NameChar of https://www.w3.org/TR/xml/#NT-Name
+fn is_name_start_char(c: char) -> bool {
+    matches!(c,
+        ':' | 'A'..='Z' | '_' | 'a'..='z' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}'
+        | '\u{F8}'..='\u{2FF}' | '\u{370}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}'
+        | '\u{200C}'..='\u{200D}' | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}'
+        | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}')
+}
+
+fn is_name_char(c: char) -> bool {
+    is_name_start_char(c)
+        || matches!(c, '-' | '.' | '0'..='9' | '\u{B7}' | '\u{300}'..='\u{36F}' | '\u{203F}'..='\u{2040}')
+}
+
+pub fn is_name(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(is_name_start_char) && chars.all(is_name_char)
+}
+
+// A Name without colons.
+pub fn is_ncname(s: &str) -> bool {
+    !s.contains(':') && is_name(s)
+}
+
+pub fn is_nmtoken(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(is_name_char)
 }
 
 #[cfg(test)]
@@ -36,25 +206,25 @@ mod tests {
     #[test]
     fn timezone_parse_test() {
         // Timezone "Z".
-        assert_eq!(parse_timezone("Z"), Ok(FixedOffset::east_opt(0).unwrap()));
+        assert_eq!(parse_timezone("Z"), Ok(Timezone::UTC));
 
         // Positive offset.
         assert_eq!(
             parse_timezone("+06:30"),
-            Ok(FixedOffset::east_opt(6 * 3600 + 30 * 60).unwrap())
+            Ok(Timezone::from_offset_minutes(6 * 60 + 30).unwrap())
         );
 
         // Negative offset.
         assert_eq!(
             parse_timezone("-06:30"),
-            Ok(FixedOffset::west_opt(6 * 3600 + 30 * 60).unwrap())
+            Ok(Timezone::from_offset_minutes(-(6 * 60 + 30)).unwrap())
         );
 
         // Positive offset max.
-        assert_eq!(parse_timezone("+14:00"), Ok(FixedOffset::east_opt(14 * 3600).unwrap()));
+        assert_eq!(parse_timezone("+14:00"), Ok(Timezone::from_offset_minutes(14 * 60).unwrap()));
 
         // Negative offset max.
-        assert_eq!(parse_timezone("-14:00"), Ok(FixedOffset::west_opt(14 * 3600).unwrap()));
+        assert_eq!(parse_timezone("-14:00"), Ok(Timezone::from_offset_minutes(-14 * 60).unwrap()));
 
         // Invalid values.
         assert!(parse_timezone("06:30").is_err());
@@ -75,4 +245,70 @@ mod tests {
         assert!(parse_timezone("+Z").is_err());
         assert!(parse_timezone("ZZ").is_err());
     }
+
+    #[test]
+    fn fraction_test() {
+        assert_eq!(parse_fraction("1"), Ok(100_000_000));
+        assert_eq!(parse_fraction("000000001"), Ok(1));
+        assert_eq!(parse_fraction("1230000000000"), Ok(123_000_000));
+        assert!(parse_fraction("0000000001").is_err());
+        assert!(parse_fraction("").is_err());
+        assert!(parse_fraction("1a").is_err());
+
+        assert_eq!(format_fraction(0), "");
+        assert_eq!(format_fraction(100_000_000), ".1");
+        assert_eq!(format_fraction(1), ".000000001");
+    }
+
+    #[test]
+    fn float_literal_test() {
+        assert_eq!(parse_float_literal::<f64>("1.5E3"), Ok(1500.0));
+        assert_eq!(parse_float_literal::<f64>("-.5e-1"), Ok(-0.05));
+        assert_eq!(parse_float_literal::<f64>("12."), Ok(12.0));
+        assert_eq!(parse_float_literal::<f64>("+INF"), Ok(f64::INFINITY));
+        assert_eq!(parse_float_literal::<f64>("-INF"), Ok(f64::NEG_INFINITY));
+        assert!(parse_float_literal::<f64>("NaN").unwrap().is_nan());
+        assert!(parse_float_literal::<f64>("-0").unwrap().is_sign_negative());
+
+        assert!(parse_float_literal::<f64>("inf").is_err());
+        assert!(parse_float_literal::<f64>("infinity").is_err());
+        assert!(parse_float_literal::<f64>("nan").is_err());
+        assert!(parse_float_literal::<f64>("-NaN").is_err());
+        assert!(parse_float_literal::<f64>(".").is_err());
+        assert!(parse_float_literal::<f64>("1E").is_err());
+        assert!(parse_float_literal::<f64>("1E2.5").is_err());
+        assert!(parse_float_literal::<f64>("0x10").is_err());
+
+        assert_eq!(format_float_literal(&format!("{:e}", 1000f64)), "1.0E3");
+        assert_eq!(format_float_literal(&format!("{:e}", 0.125f64)), "1.25E-1");
+        assert_eq!(format_float_literal(&format!("{:e}", -0f64)), "-0.0E0");
+        assert_eq!(format_float_literal(&format!("{:e}", f64::NEG_INFINITY)), "-INF");
+        assert_eq!(format_float_literal(&format!("{:e}", f64::NAN)), "NaN");
+    }
+
+    #[test]
+    fn whitespace_test() {
+        assert_eq!(replace_whitespace(" a\tb\r\nc "), " a b  c ");
+        assert_eq!(collapse_whitespace(" a\tb\r\n c  "), "a b c");
+        assert_eq!(collapse_whitespace(" \n "), "");
+    }
+
+    #[test]
+    fn name_test() {
+        assert!(is_name("xs:element"));
+        assert!(is_name("_a-b.c1"));
+        assert!(is_name("Élan"));
+        assert!(!is_name("1a"));
+        assert!(!is_name("-a"));
+        assert!(!is_name("a b"));
+        assert!(!is_name(""));
+
+        assert!(is_ncname("element"));
+        assert!(!is_ncname("xs:element"));
+
+        assert!(is_nmtoken("1a"));
+        assert!(is_nmtoken("-a:b"));
+        assert!(!is_nmtoken("a b"));
+        assert!(!is_nmtoken(""));
+    }
 }
@@ -1,3 +1,5 @@
 #[cfg(test)]
 pub mod xml_eq;
+#[cfg(feature = "soap")]
+pub mod soap;
 pub mod yaserde;
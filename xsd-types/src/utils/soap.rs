@@ -0,0 +1,103 @@
+use std::{fmt::Display, io::Write};
+
+use soap::ser::{Error, EventWriter, XmlSerialize};
+
+use crate::types::*;
+
+// Values are written as text in their lexical form, which is what Display produces.
+macro_rules! impl_xml_serialize {
+    ($($type:ty),*) => {
+        $(impl XmlSerialize for $type {
+            fn xml_serialize<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+                self.to_string().as_str().xml_serialize(writer)
+            }
+        })*
+    };
+}
+
+impl_xml_serialize!(
+    AnyUri,
+    Base64Binary,
+    Date,
+    DateTime,
+    DateTimeStamp,
+    Decimal,
+    Double,
+    Duration,
+    Float,
+    GDay,
+    GMonth,
+    GMonthDay,
+    GYear,
+    GYearMonth,
+    HexBinary,
+    Id,
+    IdRef,
+    Integer,
+    Language,
+    Name,
+    NcName,
+    NegativeInteger,
+    NmToken,
+    NonNegativeInteger,
+    NonPositiveInteger,
+    NormalizedString,
+    PositiveInteger,
+    Time,
+    Token
+);
+
+// The namespace of the name is declared on the element holding it.
+impl XmlSerialize for QName {
+    fn xml_namespaces(&self) -> Vec<(String, String)> {
+        self.namespace_declaration()
+            .map(|(prefix, namespace)| vec![(prefix.to_string(), namespace.to_string())])
+            .unwrap_or_default()
+    }
+
+    fn xml_serialize<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+        self.to_xml_value().as_str().xml_serialize(writer)
+    }
+}
+
+impl<T: Display> XmlSerialize for XsdList<T> {
+    fn xml_serialize<W: Write>(&self, writer: &mut EventWriter<W>) -> Result<(), Error> {
+        self.to_string().as_str().xml_serialize(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use soap::ser::{event_writer, write_child};
+
+    use super::*;
+
+    fn to_xml<T: XmlSerialize>(value: &T) -> String {
+        let mut buffer = Vec::new();
+        write_child(&mut event_writer(&mut buffer), "Value", value).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn xml_serialize_test() {
+        assert!(
+            to_xml(&Date::from_str("2020-02-02").unwrap()).ends_with("<Value>2020-02-02</Value>")
+        );
+        assert!(to_xml(&Double(f64::NEG_INFINITY)).ends_with("<Value>-INF</Value>"));
+        assert!(to_xml(&XsdList(vec![1, 2, 3])).ends_with("<Value>1 2 3</Value>"));
+    }
+
+    #[test]
+    fn qname_xml_serialize_test() {
+        let schema = "http://www.onvif.org/ver10/schema";
+        let mut qname = QName::from_str("tt:InvalidArgVal").unwrap();
+        qname.namespace = Some(schema.to_string());
+        assert!(to_xml(&qname)
+            .ends_with(&format!(r#"<Value xmlns:tt="{}">tt:InvalidArgVal</Value>"#, schema)));
+        assert!(to_xml(&QName::new(Some(schema), "Sender"))
+            .ends_with(&format!(r#"<Value xmlns:ns0="{}">ns0:Sender</Value>"#, schema)));
+        assert!(to_xml(&QName::new(None, "Sender")).ends_with("<Value>Sender</Value>"));
+    }
+}